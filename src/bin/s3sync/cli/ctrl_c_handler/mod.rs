@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use tokio::task::JoinHandle;
 use tokio::{select, signal};
 use tracing::{trace, warn};
@@ -18,6 +20,42 @@ pub fn spawn_ctrl_c_handler(cancellation_token: PipelineCancellationToken) -> Jo
     })
 }
 
+pub fn spawn_graceful_ctrl_c_handler(
+    cancellation_token: PipelineCancellationToken,
+    drain_token: PipelineCancellationToken,
+    graceful_shutdown_timeout: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        select! {
+            _ = cancellation_token.cancelled() => {
+                trace!("cancellation_token canceled.");
+                return;
+            }
+            _ = signal::ctrl_c() => {
+                warn!(
+                    graceful_shutdown_timeout_sec = graceful_shutdown_timeout.as_secs(),
+                    "ctrl-c received, waiting for in-flight objects to finish."
+                );
+                drain_token.cancel();
+            }
+        }
+
+        select! {
+            _ = cancellation_token.cancelled() => {
+                trace!("cancellation_token canceled.")
+            }
+            _ = tokio::time::sleep(graceful_shutdown_timeout) => {
+                warn!("graceful shutdown timed out, shutting down.");
+                cancellation_token.cancel();
+            }
+            _ = signal::ctrl_c() => {
+                warn!("ctrl-c received again, shutting down.");
+                cancellation_token.cancel();
+            }
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
@@ -71,6 +109,60 @@ mod tests {
         assert!(cancellation_token.is_cancelled());
     }
 
+    #[tokio::test]
+    #[cfg(target_family = "unix")]
+    async fn graceful_ctrl_c_handler_drains_and_times_out() {
+        const WAITING_TIME_MILLIS_FOR_ASYNC_CTRL_C_HANDLER_START: u64 = 100;
+        const GRACEFUL_SHUTDOWN_TIMEOUT_MILLIS: u64 = 500;
+
+        init_dummy_tracing_subscriber();
+
+        let _semaphore = SEMAPHORE.clone().acquire_owned().await.unwrap();
+
+        let cancellation_token = token::create_pipeline_cancellation_token();
+        let drain_token = token::create_pipeline_cancellation_token();
+
+        let join_handle = spawn_graceful_ctrl_c_handler(
+            cancellation_token.clone(),
+            drain_token.clone(),
+            Duration::from_millis(GRACEFUL_SHUTDOWN_TIMEOUT_MILLIS),
+        );
+        tokio::time::sleep(Duration::from_millis(
+            WAITING_TIME_MILLIS_FOR_ASYNC_CTRL_C_HANDLER_START,
+        ))
+        .await;
+
+        kill_sigint_to_self();
+
+        drain_token.cancelled().await;
+        assert!(!cancellation_token.is_cancelled());
+
+        join_handle.await.unwrap();
+
+        assert!(cancellation_token.is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn graceful_ctrl_c_handler_handles_cancellation_token() {
+        init_dummy_tracing_subscriber();
+
+        let _semaphore = SEMAPHORE.clone().acquire_owned().await.unwrap();
+
+        let cancellation_token = token::create_pipeline_cancellation_token();
+        let drain_token = token::create_pipeline_cancellation_token();
+
+        let join_handle = spawn_graceful_ctrl_c_handler(
+            cancellation_token.clone(),
+            drain_token.clone(),
+            Duration::from_secs(60),
+        );
+        cancellation_token.cancel();
+
+        join_handle.await.unwrap();
+
+        assert!(!drain_token.is_cancelled());
+    }
+
     #[cfg(target_family = "unix")]
     fn kill_sigint_to_self() {
         nix::sys::signal::kill(nix::unistd::Pid::this(), nix::sys::signal::Signal::SIGINT).unwrap();
@@ -1,22 +1,71 @@
+use std::collections::VecDeque;
 use std::io;
 use std::io::Write;
+use std::time::Duration;
 
 use async_channel::Receiver;
 use indicatif::{HumanBytes, HumanCount, HumanDuration, ProgressBar, ProgressStyle};
-use simple_moving_average::{SumTreeSMA, SMA};
 use tokio::task::JoinHandle;
 use tokio::time::Instant;
+use tracing::info;
 
 use s3sync::types::SyncStatistics;
 
-const MOVING_AVERAGE_PERIOD_SECS: usize = 10;
+use super::object_size_histogram::ObjectSizeHistogram;
+
 const REFRESH_INTERVAL: f32 = 1.0;
 
+// moving average of the samples(one per refresh interval) over the last `window` samples.
+struct RollingRate {
+    window: usize,
+    samples: VecDeque<u64>,
+    sum: u64,
+}
+
+impl RollingRate {
+    fn new(window: usize) -> Self {
+        Self {
+            window,
+            samples: VecDeque::with_capacity(window),
+            sum: 0,
+        }
+    }
+
+    fn add_sample(&mut self, sample: u64) {
+        if self.samples.len() == self.window {
+            self.sum -= self.samples.pop_front().unwrap();
+        }
+        self.samples.push_back(sample);
+        self.sum += sample;
+    }
+
+    fn get_average(&self) -> f64 {
+        if self.samples.is_empty() {
+            return 0.0;
+        }
+
+        self.sum as f64 / self.samples.len() as f64
+    }
+}
+
+fn estimate_remaining_time(remaining_count: u64, processed_count_per_sec: f64) -> Option<Duration> {
+    if processed_count_per_sec <= 0.0 {
+        return None;
+    }
+
+    Some(Duration::from_secs_f64(
+        remaining_count as f64 / processed_count_per_sec,
+    ))
+}
+
 pub fn show_indicator(
     stats_receiver: Receiver<SyncStatistics>,
     show_progress: bool,
     show_result: bool,
     dry_run: bool,
+    rate_window: usize,
+    stats_interval: Option<Duration>,
+    show_object_size_histogram: bool,
 ) -> JoinHandle<()> {
     let progress_style = ProgressStyle::with_template("{wide_msg}").unwrap();
     let progress_text = ProgressBar::new(0);
@@ -24,9 +73,11 @@ pub fn show_indicator(
 
     tokio::spawn(async move {
         let start_time = Instant::now();
+        let mut last_stats_time = start_time;
 
-        let mut ma_synced_bytes = SumTreeSMA::<_, u64, MOVING_AVERAGE_PERIOD_SECS>::new();
-        let mut ma_synced_count = SumTreeSMA::<_, u64, MOVING_AVERAGE_PERIOD_SECS>::new();
+        let mut ma_synced_bytes = RollingRate::new(rate_window);
+        let mut ma_synced_count = RollingRate::new(rate_window);
+        let mut ma_processed_count = RollingRate::new(rate_window);
 
         let mut total_sync_count: u64 = 0;
         let mut total_sync_bytes: u64 = 0;
@@ -36,10 +87,25 @@ pub fn show_indicator(
         let mut total_warning_count: u64 = 0;
         let mut total_e_tag_verified_count: u64 = 0;
         let mut total_checksum_verified_count: u64 = 0;
+        // only with --checksum-verify-sampling.
+        let mut total_checksum_not_sampled_count: u64 = 0;
+        let mut total_content_length_verified_count: u64 = 0;
+        let mut total_encryption_verified_count: u64 = 0;
+        let mut total_listed_count: u64 = 0;
+        let mut total_bucket_key_count: u64 = 0;
+        // only with --skip-glacier. these objects are also counted as skipped.
+        let mut total_archived_skip_count: u64 = 0;
+        // only with --verify-only-on-mismatch-redownload. the still corrupted objects are also counted as errors.
+        let mut total_redownload_healed_count: u64 = 0;
+        let mut total_redownload_still_corrupted_count: u64 = 0;
+        // only with --concurrency-auto.
+        let mut auto_concurrency: Option<u16> = None;
+        let mut object_size_histogram = ObjectSizeHistogram::default();
 
         loop {
             let mut sync_bytes: u64 = 0;
             let mut sync_count: u64 = 0;
+            let mut processed_count: u64 = 0;
 
             let period = Instant::now();
             loop {
@@ -47,6 +113,7 @@ pub fn show_indicator(
                     match sync_stats {
                         SyncStatistics::SyncComplete { .. } => {
                             sync_count += 1;
+                            processed_count += 1;
                             total_sync_count += 1;
                         }
                         SyncStatistics::SyncBytes(size) => {
@@ -54,9 +121,11 @@ pub fn show_indicator(
                             total_sync_bytes += size
                         }
                         SyncStatistics::SyncError { .. } => {
+                            processed_count += 1;
                             total_error_count += 1;
                         }
                         SyncStatistics::SyncSkip { .. } => {
+                            processed_count += 1;
                             total_skip_count += 1;
                         }
                         SyncStatistics::SyncDelete { .. } => {
@@ -71,6 +140,39 @@ pub fn show_indicator(
                         SyncStatistics::ChecksumVerified { .. } => {
                             total_checksum_verified_count += 1;
                         }
+                        SyncStatistics::ChecksumVerifyNotSampled { .. } => {
+                            total_checksum_not_sampled_count += 1;
+                        }
+                        SyncStatistics::ContentLengthVerified { .. } => {
+                            total_content_length_verified_count += 1;
+                        }
+                        SyncStatistics::EncryptionVerified { .. } => {
+                            total_encryption_verified_count += 1;
+                        }
+                        SyncStatistics::RestoreStatus(_) => {}
+                        SyncStatistics::ObjectListed => {
+                            total_listed_count += 1;
+                        }
+                        // already counted as SyncError.
+                        SyncStatistics::RetryExhausted { .. } => {}
+                        SyncStatistics::AutoConcurrency(concurrency) => {
+                            auto_concurrency = Some(concurrency);
+                        }
+                        SyncStatistics::TransferredObjectSize(size) => {
+                            object_size_histogram.add(size);
+                        }
+                        SyncStatistics::BucketKeyEnabled { .. } => {
+                            total_bucket_key_count += 1;
+                        }
+                        SyncStatistics::ArchivedSkip { .. } => {
+                            total_archived_skip_count += 1;
+                        }
+                        SyncStatistics::RedownloadHealed { .. } => {
+                            total_redownload_healed_count += 1;
+                        }
+                        SyncStatistics::RedownloadStillCorrupted { .. } => {
+                            total_redownload_still_corrupted_count += 1;
+                        }
                     }
                 }
 
@@ -99,48 +201,133 @@ pub fn show_indicator(
 
                         progress_text.set_style(ProgressStyle::with_template("{msg}").unwrap());
 
+                        let auto_concurrency = auto_concurrency
+                            .map(|concurrency| format!(",  concurrency {concurrency}"))
+                            .unwrap_or_default();
+                        // only when the target objects have been stored with S3 Bucket Keys.
+                        let bucket_key = if 0 < total_bucket_key_count {
+                            format!(",  bucket key {total_bucket_key_count} objects")
+                        } else {
+                            String::new()
+                        };
+                        let archived_skip = if 0 < total_archived_skip_count {
+                            format!(" (archived {total_archived_skip_count} objects)")
+                        } else {
+                            String::new()
+                        };
+                        let checksum_not_sampled = if 0 < total_checksum_not_sampled_count {
+                            format!(" (not sampled {total_checksum_not_sampled_count} objects)")
+                        } else {
+                            String::new()
+                        };
+                        // only when an object has been re-downloaded.
+                        let redownload = if 0 < total_redownload_healed_count
+                            + total_redownload_still_corrupted_count
+                        {
+                            format!(",  healed {total_redownload_healed_count} objects,  still corrupted {total_redownload_still_corrupted_count} objects")
+                        } else {
+                            String::new()
+                        };
+
                         progress_text.finish_with_message(format!(
-                            "{:>3} | {:>3}/sec,  transferred {:>3} objects | {:>3} objects/sec,  etag verified {} objects,  checksum verified {} objects,  deleted {} objects,  skipped {} objects,  error {} objects, warning {} objects,  duration {}",
+                            "{:>3} | {:>3}/sec,  transferred {:>3} objects | {:>3} objects/sec,  etag verified {} objects,  checksum verified {} objects{},  content length verified {} objects,  encryption verified {} objects,  deleted {} objects,  skipped {} objects{},  error {} objects, warning {} objects,  duration {}{}{}{}",
                             HumanBytes(total_sync_bytes),
                             HumanBytes(sync_bytes_per_sec),
                             total_sync_count,
                             HumanCount(objects_per_sec),
                             total_e_tag_verified_count,
                             total_checksum_verified_count,
+                            checksum_not_sampled,
+                            total_content_length_verified_count,
+                            total_encryption_verified_count,
                             total_delete_count,
                             total_skip_count,
+                            archived_skip,
                             total_error_count,
                             total_warning_count,
                             HumanDuration(elapsed),
+                            auto_concurrency,
+                            bucket_key,
+                            redownload,
                         ));
 
                         println!();
+                        if show_object_size_histogram {
+                            println!("{}", object_size_histogram.to_summary());
+                        }
                         io::stdout().flush().unwrap()
                     }
+                    if show_object_size_histogram {
+                        object_size_histogram.log();
+                    }
                     return;
                 }
 
                 tokio::time::sleep(std::time::Duration::from_secs_f32(0.05)).await;
             }
+            if let Some(stats_interval) = stats_interval {
+                if stats_interval <= last_stats_time.elapsed() {
+                    last_stats_time = Instant::now();
+
+                    let elapsed_secs_f64 = start_time.elapsed().as_secs_f64();
+                    info!(
+                        transferred_bytes = total_sync_bytes,
+                        transferred_bytes_per_sec =
+                            (total_sync_bytes as f64 / elapsed_secs_f64) as u64,
+                        transferred_objects = total_sync_count,
+                        transferred_objects_per_sec =
+                            (total_sync_count as f64 / elapsed_secs_f64) as u64,
+                        etag_verified_objects = total_e_tag_verified_count,
+                        checksum_verified_objects = total_checksum_verified_count,
+                        checksum_not_sampled_objects = total_checksum_not_sampled_count,
+                        content_length_verified_objects = total_content_length_verified_count,
+                        encryption_verified_objects = total_encryption_verified_count,
+                        deleted_objects = total_delete_count,
+                        skipped_objects = total_skip_count,
+                        error_objects = total_error_count,
+                        warning_objects = total_warning_count,
+                        bucket_key_objects = total_bucket_key_count,
+                        archived_skipped_objects = total_archived_skip_count,
+                        redownload_healed_objects = total_redownload_healed_count,
+                        redownload_still_corrupted_objects = total_redownload_still_corrupted_count,
+                        elapsed_secs = elapsed_secs_f64 as u64,
+                        "sync statistics."
+                    );
+                }
+            }
+
             ma_synced_bytes.add_sample(sync_bytes);
+            ma_processed_count.add_sample(processed_count);
 
             if !dry_run {
                 ma_synced_count.add_sample(sync_count);
             }
 
             if show_progress {
+                // the remaining known work is the listed objects that have not been transferred, skipped or failed yet.
+                // it grows while the source is being listed.
+                let remaining_count = total_listed_count
+                    .saturating_sub(total_sync_count + total_skip_count + total_error_count);
+                let eta =
+                    estimate_remaining_time(remaining_count, ma_processed_count.get_average())
+                        .map(|eta| HumanDuration(eta).to_string())
+                        .unwrap_or_else(|| "-".to_string());
+
                 progress_text.set_message(format!(
-                    "{:>3} | {:>3}/sec,  transferred {:>3} objects | {:>3} objects/sec,  etag verified {} objects,  checksum verified {} objects,  deleted {} objects,  skipped {} objects,  error {} objects, warning {} objects",
+                    "{:>3} | {:>3}/sec,  transferred {:>3} objects | {:>3} objects/sec,  etag verified {} objects,  checksum verified {} objects,  content length verified {} objects,  encryption verified {} objects,  deleted {} objects,  skipped {} objects,  error {} objects, warning {} objects,  eta {}",
                     HumanBytes(total_sync_bytes),
-                    HumanBytes(ma_synced_bytes.get_average()).to_string(),
+                    HumanBytes(ma_synced_bytes.get_average() as u64).to_string(),
                     total_sync_count,
-                    HumanCount(ma_synced_count.get_average()).to_string(),
+                    HumanCount(ma_synced_count.get_average() as u64).to_string(),
                     total_e_tag_verified_count,
                     total_checksum_verified_count,
+                    total_content_length_verified_count,
+                    total_encryption_verified_count,
                     total_delete_count,
                     total_skip_count,
                     total_error_count,
                     total_warning_count,
+                    eta,
                 ));
             }
         }
@@ -149,8 +336,6 @@ pub fn show_indicator(
 
 #[cfg(test)]
 mod tests {
-    use std::time::Duration;
-
     use super::*;
 
     const WAITING_TIME_MILLIS_FOR_ASYNC_INDICATOR_SET_MESSAGE: u64 = 1500;
@@ -160,7 +345,7 @@ mod tests {
         init_dummy_tracing_subscriber();
 
         let (stats_sender, stats_receiver) = async_channel::unbounded();
-        let join_handle = show_indicator(stats_receiver, true, true, false);
+        let join_handle = show_indicator(stats_receiver, true, true, false, 10, None, true);
 
         stats_sender
             .send(SyncStatistics::SyncBytes(1))
@@ -202,6 +387,109 @@ mod tests {
             })
             .await
             .unwrap();
+        stats_sender
+            .send(SyncStatistics::ContentLengthVerified {
+                key: "test".to_string(),
+            })
+            .await
+            .unwrap();
+        stats_sender
+            .send(SyncStatistics::ObjectListed)
+            .await
+            .unwrap();
+        stats_sender
+            .send(SyncStatistics::TransferredObjectSize(1))
+            .await
+            .unwrap();
+        stats_sender
+            .send(SyncStatistics::BucketKeyEnabled {
+                key: "test".to_string(),
+            })
+            .await
+            .unwrap();
+        stats_sender
+            .send(SyncStatistics::ArchivedSkip {
+                key: "test".to_string(),
+            })
+            .await
+            .unwrap();
+        stats_sender
+            .send(SyncStatistics::ChecksumVerifyNotSampled {
+                key: "test".to_string(),
+            })
+            .await
+            .unwrap();
+        stats_sender
+            .send(SyncStatistics::RedownloadHealed {
+                key: "test".to_string(),
+            })
+            .await
+            .unwrap();
+        stats_sender
+            .send(SyncStatistics::RedownloadStillCorrupted {
+                key: "test".to_string(),
+            })
+            .await
+            .unwrap();
+
+        tokio::time::sleep(Duration::from_millis(
+            WAITING_TIME_MILLIS_FOR_ASYNC_INDICATOR_SET_MESSAGE,
+        ))
+        .await;
+        stats_sender.close();
+
+        join_handle.await.unwrap();
+    }
+
+    #[test]
+    fn rolling_rate() {
+        let mut rolling_rate = RollingRate::new(3);
+        assert_eq!(rolling_rate.get_average(), 0.0);
+
+        rolling_rate.add_sample(3);
+        rolling_rate.add_sample(6);
+        assert_eq!(rolling_rate.get_average(), 4.5);
+
+        rolling_rate.add_sample(9);
+        rolling_rate.add_sample(12);
+        assert_eq!(rolling_rate.get_average(), 9.0);
+    }
+
+    #[test]
+    fn estimate_remaining_time_test() {
+        assert_eq!(
+            estimate_remaining_time(100, 4.0),
+            Some(Duration::from_secs(25))
+        );
+        assert_eq!(estimate_remaining_time(0, 4.0), Some(Duration::ZERO));
+        assert_eq!(estimate_remaining_time(100, 0.0), None);
+    }
+
+    #[tokio::test]
+    async fn indicator_test_with_stats_interval() {
+        init_dummy_tracing_subscriber();
+
+        let (stats_sender, stats_receiver) = async_channel::unbounded();
+        let join_handle = show_indicator(
+            stats_receiver,
+            false,
+            true,
+            false,
+            10,
+            Some(Duration::from_millis(1)),
+            false,
+        );
+
+        stats_sender
+            .send(SyncStatistics::SyncBytes(1))
+            .await
+            .unwrap();
+        stats_sender
+            .send(SyncStatistics::SyncComplete {
+                key: "test".to_string(),
+            })
+            .await
+            .unwrap();
 
         tokio::time::sleep(Duration::from_millis(
             WAITING_TIME_MILLIS_FOR_ASYNC_INDICATOR_SET_MESSAGE,
@@ -217,7 +505,7 @@ mod tests {
         init_dummy_tracing_subscriber();
 
         let (stats_sender, stats_receiver) = async_channel::unbounded();
-        let join_handle = show_indicator(stats_receiver, true, false, false);
+        let join_handle = show_indicator(stats_receiver, true, false, false, 10, None, false);
 
         stats_sender
             .send(SyncStatistics::SyncBytes(1))
@@ -259,6 +547,12 @@ mod tests {
             })
             .await
             .unwrap();
+        stats_sender
+            .send(SyncStatistics::ContentLengthVerified {
+                key: "test".to_string(),
+            })
+            .await
+            .unwrap();
 
         tokio::time::sleep(Duration::from_millis(
             WAITING_TIME_MILLIS_FOR_ASYNC_INDICATOR_SET_MESSAGE,
@@ -274,7 +568,7 @@ mod tests {
         init_dummy_tracing_subscriber();
 
         let (stats_sender, stats_receiver) = async_channel::unbounded();
-        let join_handle = show_indicator(stats_receiver, true, true, true);
+        let join_handle = show_indicator(stats_receiver, true, true, true, 10, None, false);
 
         stats_sender
             .send(SyncStatistics::SyncBytes(1))
@@ -316,6 +610,12 @@ mod tests {
             })
             .await
             .unwrap();
+        stats_sender
+            .send(SyncStatistics::ContentLengthVerified {
+                key: "test".to_string(),
+            })
+            .await
+            .unwrap();
 
         tokio::time::sleep(Duration::from_millis(
             WAITING_TIME_MILLIS_FOR_ASYNC_INDICATOR_SET_MESSAGE,
@@ -0,0 +1,311 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use async_channel::Receiver;
+use serde_json::{json, Value};
+use tokio::task::JoinHandle;
+
+use s3sync::types::{ObjectRestoreStatus, SyncStatistics};
+
+// keys are recorded up to this number per category to bound the memory usage and the report size.
+const MAX_KEYS_PER_CATEGORY: usize = 100000;
+
+#[derive(Default)]
+struct ReportCategory {
+    count: u64,
+    keys: Vec<String>,
+}
+
+impl ReportCategory {
+    fn add(&mut self, key: String) {
+        self.count += 1;
+        if self.keys.len() < MAX_KEYS_PER_CATEGORY {
+            self.keys.push(key);
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "count": self.count,
+            "keys": self.keys,
+            "truncated": self.count > self.keys.len() as u64,
+        })
+    }
+}
+
+#[derive(Default)]
+struct IntegrityReport {
+    transferred_bytes: u64,
+    transferred: ReportCategory,
+    e_tag_verified: ReportCategory,
+    checksum_verified: ReportCategory,
+    checksum_not_sampled: ReportCategory,
+    content_length_verified: ReportCategory,
+    encryption_verified: ReportCategory,
+    skipped: ReportCategory,
+    deleted: ReportCategory,
+    warning: ReportCategory,
+    error: ReportCategory,
+    redownload_healed: ReportCategory,
+    redownload_still_corrupted: ReportCategory,
+    retry_exhausted: ReportCategory,
+    restore_in_progress: ReportCategory,
+    restored: ReportCategory,
+    // key -> restore expiry date of the keys recorded in `restored`.
+    restore_expiry_dates: BTreeMap<String, String>,
+}
+
+impl IntegrityReport {
+    fn record(&mut self, sync_stats: &SyncStatistics) {
+        match sync_stats {
+            SyncStatistics::SyncBytes(size) => self.transferred_bytes += size,
+            SyncStatistics::SyncComplete { key } => self.transferred.add(key.clone()),
+            SyncStatistics::SyncSkip { key } => self.skipped.add(key.clone()),
+            SyncStatistics::SyncDelete { key } => self.deleted.add(key.clone()),
+            SyncStatistics::SyncError { key } => self.error.add(key.clone()),
+            SyncStatistics::SyncWarning { key } => self.warning.add(key.clone()),
+            SyncStatistics::ETagVerified { key } => self.e_tag_verified.add(key.clone()),
+            SyncStatistics::ChecksumVerified { key } => self.checksum_verified.add(key.clone()),
+            SyncStatistics::ChecksumVerifyNotSampled { key } => {
+                self.checksum_not_sampled.add(key.clone())
+            }
+            SyncStatistics::ContentLengthVerified { key } => {
+                self.content_length_verified.add(key.clone())
+            }
+            SyncStatistics::EncryptionVerified { key } => self.encryption_verified.add(key.clone()),
+            SyncStatistics::RestoreStatus(restore_status) => {
+                self.record_restore_status(restore_status)
+            }
+            SyncStatistics::ObjectListed => {}
+            SyncStatistics::AutoConcurrency(_) => {}
+            SyncStatistics::TransferredObjectSize(_) => {}
+            SyncStatistics::BucketKeyEnabled { .. } => {}
+            // already recorded as skipped.
+            SyncStatistics::ArchivedSkip { .. } => {}
+            SyncStatistics::RetryExhausted { key } => self.retry_exhausted.add(key.clone()),
+            SyncStatistics::RedownloadHealed { key } => self.redownload_healed.add(key.clone()),
+            SyncStatistics::RedownloadStillCorrupted { key } => {
+                self.redownload_still_corrupted.add(key.clone())
+            }
+        }
+    }
+
+    fn record_restore_status(&mut self, restore_status: &ObjectRestoreStatus) {
+        let key = restore_status.key.clone();
+        if restore_status.in_progress {
+            self.restore_in_progress.add(key);
+            return;
+        }
+
+        if let Some(expiry_date) = &restore_status.expiry_date {
+            if self.restored.keys.len() < MAX_KEYS_PER_CATEGORY {
+                self.restore_expiry_dates
+                    .insert(key.clone(), expiry_date.clone());
+            }
+        }
+        self.restored.add(key);
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "transferred_bytes": self.transferred_bytes,
+            "transferred": self.transferred.to_json(),
+            "etag_verified": self.e_tag_verified.to_json(),
+            "checksum_verified": self.checksum_verified.to_json(),
+            "checksum_not_sampled": self.checksum_not_sampled.to_json(),
+            "content_length_verified": self.content_length_verified.to_json(),
+            "encryption_verified": self.encryption_verified.to_json(),
+            "skipped": self.skipped.to_json(),
+            "deleted": self.deleted.to_json(),
+            "warning": self.warning.to_json(),
+            "error": self.error.to_json(),
+            "retry_exhausted": self.retry_exhausted.to_json(),
+            "redownload_healed": self.redownload_healed.to_json(),
+            "redownload_still_corrupted": self.redownload_still_corrupted.to_json(),
+            "restore_in_progress": self.restore_in_progress.to_json(),
+            "restored": self.restored.to_json(),
+            "restore_expiry_dates": self.restore_expiry_dates,
+        })
+    }
+
+    fn write(&self, path: &Path) -> Result<()> {
+        let report = serde_json::to_string_pretty(&self.to_json())?;
+        std::fs::write(path, report)
+            .with_context(|| format!("failed to write integrity report: {}", path.display()))
+    }
+}
+
+// forwards the statistics to the returned receiver, and writes the report when the stream is closed.
+pub fn spawn_integrity_reporter(
+    stats_receiver: Receiver<SyncStatistics>,
+    path: PathBuf,
+) -> (Receiver<SyncStatistics>, JoinHandle<Result<()>>) {
+    let (forward_sender, forward_receiver) = async_channel::unbounded();
+
+    let join_handle = tokio::spawn(async move {
+        let mut report = IntegrityReport::default();
+
+        while let Ok(sync_stats) = stats_receiver.recv().await {
+            report.record(&sync_stats);
+            let _ = forward_sender.send(sync_stats).await;
+        }
+        forward_sender.close();
+
+        report.write(&path)
+    });
+
+    (forward_receiver, join_handle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_statistics() {
+        let mut report = IntegrityReport::default();
+
+        report.record(&SyncStatistics::SyncBytes(10));
+        report.record(&SyncStatistics::SyncBytes(5));
+        report.record(&SyncStatistics::SyncComplete {
+            key: "key1".to_string(),
+        });
+        report.record(&SyncStatistics::ETagVerified {
+            key: "key1".to_string(),
+        });
+        report.record(&SyncStatistics::ChecksumVerified {
+            key: "key1".to_string(),
+        });
+        report.record(&SyncStatistics::ChecksumVerifyNotSampled {
+            key: "key2".to_string(),
+        });
+        report.record(&SyncStatistics::ContentLengthVerified {
+            key: "key2".to_string(),
+        });
+        report.record(&SyncStatistics::EncryptionVerified {
+            key: "key2".to_string(),
+        });
+        report.record(&SyncStatistics::SyncWarning {
+            key: "key3".to_string(),
+        });
+        report.record(&SyncStatistics::SyncError {
+            key: "key4".to_string(),
+        });
+        report.record(&SyncStatistics::SyncSkip {
+            key: "key5".to_string(),
+        });
+        report.record(&SyncStatistics::SyncDelete {
+            key: "key6".to_string(),
+        });
+        report.record(&SyncStatistics::RetryExhausted {
+            key: "key4".to_string(),
+        });
+        report.record(&SyncStatistics::RedownloadHealed {
+            key: "key7".to_string(),
+        });
+        report.record(&SyncStatistics::RedownloadStillCorrupted {
+            key: "key4".to_string(),
+        });
+
+        let json = report.to_json();
+        assert_eq!(json["transferred_bytes"], 15);
+        assert_eq!(json["transferred"]["count"], 1);
+        assert_eq!(json["etag_verified"]["keys"], json!(["key1"]));
+        assert_eq!(json["checksum_verified"]["keys"], json!(["key1"]));
+        assert_eq!(json["checksum_not_sampled"]["keys"], json!(["key2"]));
+        assert_eq!(json["content_length_verified"]["keys"], json!(["key2"]));
+        assert_eq!(json["encryption_verified"]["keys"], json!(["key2"]));
+        assert_eq!(json["warning"]["keys"], json!(["key3"]));
+        assert_eq!(json["error"]["keys"], json!(["key4"]));
+        assert_eq!(json["skipped"]["keys"], json!(["key5"]));
+        assert_eq!(json["deleted"]["keys"], json!(["key6"]));
+        assert_eq!(json["retry_exhausted"]["keys"], json!(["key4"]));
+        assert_eq!(json["redownload_healed"]["keys"], json!(["key7"]));
+        assert_eq!(json["redownload_still_corrupted"]["keys"], json!(["key4"]));
+        assert_eq!(json["warning"]["truncated"], false);
+    }
+
+    #[test]
+    fn record_restore_status() {
+        let mut report = IntegrityReport::default();
+
+        report.record(&SyncStatistics::RestoreStatus(ObjectRestoreStatus {
+            key: "key1".to_string(),
+            in_progress: true,
+            expiry_date: None,
+        }));
+        report.record(&SyncStatistics::RestoreStatus(ObjectRestoreStatus {
+            key: "key2".to_string(),
+            in_progress: false,
+            expiry_date: Some("2026-01-01T00:00:00+00:00".to_string()),
+        }));
+
+        let json = report.to_json();
+        assert_eq!(json["restore_in_progress"]["keys"], json!(["key1"]));
+        assert_eq!(json["restored"]["keys"], json!(["key2"]));
+        assert_eq!(
+            json["restore_expiry_dates"],
+            json!({"key2": "2026-01-01T00:00:00+00:00"})
+        );
+    }
+
+    #[test]
+    fn truncate_keys() {
+        let mut category = ReportCategory::default();
+        for i in 0..MAX_KEYS_PER_CATEGORY + 1 {
+            category.add(i.to_string());
+        }
+
+        let json = category.to_json();
+        assert_eq!(json["count"], MAX_KEYS_PER_CATEGORY as u64 + 1);
+        assert_eq!(
+            json["keys"].as_array().unwrap().len(),
+            MAX_KEYS_PER_CATEGORY
+        );
+        assert_eq!(json["truncated"], true);
+    }
+
+    #[tokio::test]
+    async fn write_report() {
+        let (stats_sender, stats_receiver) = async_channel::unbounded();
+        let report_file = tempfile::NamedTempFile::new().unwrap();
+
+        let (forward_receiver, join_handle) =
+            spawn_integrity_reporter(stats_receiver, report_file.path().to_path_buf());
+
+        stats_sender
+            .send(SyncStatistics::ETagVerified {
+                key: "key1".to_string(),
+            })
+            .await
+            .unwrap();
+        stats_sender.close();
+
+        assert!(matches!(
+            forward_receiver.recv().await.unwrap(),
+            SyncStatistics::ETagVerified { .. }
+        ));
+
+        join_handle.await.unwrap().unwrap();
+        assert!(forward_receiver.is_closed());
+
+        let report: Value =
+            serde_json::from_str(&std::fs::read_to_string(report_file.path()).unwrap()).unwrap();
+        assert_eq!(report["etag_verified"]["count"], 1);
+        assert_eq!(report["etag_verified"]["keys"], json!(["key1"]));
+    }
+
+    #[tokio::test]
+    async fn write_report_error() {
+        let (stats_sender, stats_receiver) = async_channel::unbounded();
+        stats_sender.close();
+
+        let (_, join_handle) = spawn_integrity_reporter(
+            stats_receiver,
+            PathBuf::from("./test_data/not_found_dir/report.json"),
+        );
+
+        assert!(join_handle.await.unwrap().is_err());
+    }
+}
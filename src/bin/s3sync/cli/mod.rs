@@ -1,6 +1,10 @@
+use std::time::Duration;
+
 use anyhow::{anyhow, Result};
+use chrono::{SecondsFormat, Utc};
+use serde_json::json;
 use tokio::time::Instant;
-use tracing::{error, trace};
+use tracing::{error, info, trace, warn};
 
 use s3sync::pipeline::Pipeline;
 use s3sync::types::token::create_pipeline_cancellation_token;
@@ -8,39 +12,184 @@ use s3sync::Config;
 
 mod ctrl_c_handler;
 mod indicator;
+mod integrity_report;
+mod object_size_histogram;
 mod ui_config;
 
 pub async fn run(config: Config) -> Result<()> {
     let cancellation_token = create_pipeline_cancellation_token();
 
-    ctrl_c_handler::spawn_ctrl_c_handler(cancellation_token.clone());
-
     let start_time = Instant::now();
     trace!("sync pipeline start.");
 
-    let mut pipeline = Pipeline::new(config.clone(), cancellation_token).await;
+    let mut pipeline = Pipeline::new(config.clone(), cancellation_token.clone()).await;
+
+    // the preflight check reports authentication, region and endpoint problems before the sync starts.
+    if !pipeline.preflight().await {
+        let duration_sec = format!("{:.3}", start_time.elapsed().as_secs_f32());
+        error!(duration_sec = duration_sec, "s3sync failed.");
+
+        return Err(anyhow!("s3sync failed."));
+    }
+
+    if let Some(timeout) = config.graceful_shutdown_timeout_seconds {
+        ctrl_c_handler::spawn_graceful_ctrl_c_handler(
+            cancellation_token.clone(),
+            pipeline.get_drain_token(),
+            Duration::from_secs(timeout),
+        );
+    } else {
+        ctrl_c_handler::spawn_ctrl_c_handler(cancellation_token.clone());
+    }
+
+    let mut stats_receiver = pipeline.get_stats_receiver();
+    let mut integrity_report_join_handle = None;
+    if let Some(path) = config.integrity_report.clone() {
+        let (forward_receiver, join_handle) =
+            integrity_report::spawn_integrity_reporter(stats_receiver, path);
+        stats_receiver = forward_receiver;
+        integrity_report_join_handle = Some(join_handle);
+    }
+
     let indicator_join_handle = indicator::show_indicator(
-        pipeline.get_stats_receiver(),
+        stats_receiver,
         ui_config::is_progress_indicator_needed(&config),
         ui_config::is_show_result_needed(&config),
         config.dry_run,
+        config.progress_rate_window_seconds as usize,
+        config.stats_interval_seconds.map(Duration::from_secs),
+        config.object_size_histogram,
     );
 
     pipeline.run().await;
     indicator_join_handle.await.unwrap();
 
+    let mut integrity_report_failed = false;
+    if let Some(join_handle) = integrity_report_join_handle {
+        if let Err(e) = join_handle.await.unwrap() {
+            error!(error = e.to_string(), "failed to write integrity report.");
+            integrity_report_failed = true;
+        }
+    }
+
+    if pipeline.is_quota_reached() {
+        warn!(
+            transferred_objects = pipeline.get_transferred_object_count(),
+            transferred_bytes = pipeline.get_transferred_bytes(),
+            "s3sync has stopped because the quota(--max-objects/--max-bytes) was reached."
+        );
+    } else if pipeline.is_draining() {
+        warn!(
+            drained_objects = pipeline.get_drained_object_count(),
+            "graceful shutdown has been completed."
+        );
+    }
+
+    if let Some(retry_budget) = config.force_retry_config.retry_budget {
+        let retry_elapsed_milliseconds = pipeline.get_retry_elapsed_milliseconds();
+        if pipeline.is_retry_budget_exhausted() {
+            warn!(
+                retry_elapsed_milliseconds = retry_elapsed_milliseconds,
+                retry_budget_milliseconds = retry_budget.as_millis() as u64,
+                "retry budget(--retry-budget) was exhausted. force retry was disabled."
+            );
+        } else {
+            info!(
+                retry_elapsed_milliseconds = retry_elapsed_milliseconds,
+                retry_budget_milliseconds = retry_budget.as_millis() as u64,
+                "retry budget(--retry-budget) consumed."
+            );
+        }
+    }
+
+    for (side, request_rate_limiter) in [
+        ("source", pipeline.get_source_request_rate_limiter()),
+        ("target", pipeline.get_target_request_rate_limiter()),
+    ] {
+        if let Some(request_rate_limiter) = request_rate_limiter {
+            info!(
+                side = side,
+                requests = request_rate_limiter.get_request_count(),
+                effective_requests_per_sec =
+                    format!("{:.2}", request_rate_limiter.get_effective_rate()),
+                "request rate limit(--{}-request-rate-limit) applied.",
+                side
+            );
+        }
+    }
+
+    if pipeline.is_aborted_by_fail_fast() {
+        error!(
+            skipped_errors = pipeline.get_skipped_error_count(),
+            fail_fast_after = config.fail_fast_after,
+            "s3sync has been aborted because the number of errors reached --fail-fast-after."
+        );
+    }
+
     let duration_sec = format!("{:.3}", start_time.elapsed().as_secs_f32());
-    if pipeline.has_error() {
+    if pipeline.has_error() || integrity_report_failed {
         error!(duration_sec = duration_sec, "s3sync failed.");
 
         return Err(anyhow!("s3sync failed."));
     }
 
+    if let Some(key) = &config.write_completion_marker {
+        if config.dry_run {
+            info!(
+                key = key,
+                "completion marker is not written with --dry-run."
+            );
+        } else if cancellation_token.is_cancelled()
+            || pipeline.is_draining()
+            || pipeline.is_quota_reached()
+        {
+            warn!(
+                key = key,
+                "completion marker is not written because the run was interrupted."
+            );
+        } else {
+            let summary = config.completion_marker_with_summary.then(|| {
+                build_completion_marker_summary(
+                    &Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+                    start_time.elapsed().as_secs_f64(),
+                    pipeline.get_transferred_object_count(),
+                    pipeline.get_transferred_bytes(),
+                )
+            });
+
+            if let Err(e) = pipeline.write_completion_marker(key, summary).await {
+                error!(
+                    error = e.to_string(),
+                    source = e.source(),
+                    "failed to write completion marker."
+                );
+                error!(duration_sec = duration_sec, "s3sync failed.");
+
+                return Err(anyhow!("s3sync failed."));
+            }
+        }
+    }
+
     trace!(duration_sec = duration_sec, "s3sync has been completed.");
 
     Ok(())
 }
 
+fn build_completion_marker_summary(
+    completed_at: &str,
+    duration_sec: f64,
+    transferred_objects: u64,
+    transferred_bytes: u64,
+) -> String {
+    json!({
+        "completed_at": completed_at,
+        "duration_sec": duration_sec,
+        "transferred_objects": transferred_objects,
+        "transferred_bytes": transferred_bytes,
+    })
+    .to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use s3sync::config::args::parse_from_args;
@@ -63,6 +212,95 @@ mod tests {
         run(config).await.unwrap();
     }
 
+    #[tokio::test]
+    async fn run_pipeline_with_integrity_report() {
+        init_dummy_tracing_subscriber();
+
+        let report_file = tempfile::NamedTempFile::new().unwrap();
+        let args = vec![
+            "s3sync",
+            "--allow-both-local-storage",
+            "--integrity-report",
+            report_file.path().to_str().unwrap(),
+            "./test_data/source/dir1/",
+            "./test_data/target/dir1/",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+
+        run(config).await.unwrap();
+
+        let report = std::fs::read_to_string(report_file.path()).unwrap();
+        assert!(report.contains("\"etag_verified\""));
+    }
+
+    #[tokio::test]
+    async fn run_pipeline_with_completion_marker() {
+        init_dummy_tracing_subscriber();
+
+        let target_dir = tempfile::tempdir().unwrap();
+        let args = vec![
+            "s3sync",
+            "--allow-both-local-storage",
+            "--write-completion-marker",
+            "_SUCCESS",
+            "--completion-marker-with-summary",
+            "./test_data/source/dir1/",
+            target_dir.path().to_str().unwrap(),
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+
+        run(config).await.unwrap();
+
+        let marker = std::fs::read_to_string(target_dir.path().join("_SUCCESS")).unwrap();
+        let summary: serde_json::Value = serde_json::from_str(&marker).unwrap();
+        assert!(0 < summary["transferred_objects"].as_u64().unwrap());
+        assert!(summary["completed_at"].is_string());
+    }
+
+    #[tokio::test]
+    async fn run_pipeline_with_completion_marker_dry_run() {
+        init_dummy_tracing_subscriber();
+
+        let target_dir = tempfile::tempdir().unwrap();
+        let args = vec![
+            "s3sync",
+            "--allow-both-local-storage",
+            "--write-completion-marker",
+            "_SUCCESS",
+            "--dry-run",
+            "./test_data/source/dir1/",
+            target_dir.path().to_str().unwrap(),
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+
+        run(config).await.unwrap();
+
+        assert!(!target_dir.path().join("_SUCCESS").exists());
+    }
+
+    #[test]
+    fn build_completion_marker_summary_test() {
+        init_dummy_tracing_subscriber();
+
+        let summary: serde_json::Value = serde_json::from_str(&build_completion_marker_summary(
+            "2024-01-01T00:00:00.000Z",
+            1.5,
+            3,
+            1024,
+        ))
+        .unwrap();
+
+        assert_eq!(
+            summary,
+            json!({
+                "completed_at": "2024-01-01T00:00:00.000Z",
+                "duration_sec": 1.5,
+                "transferred_objects": 3,
+                "transferred_bytes": 1024,
+            })
+        );
+    }
+
     #[tokio::test]
     async fn run_pipeline_error() {
         init_dummy_tracing_subscriber();
@@ -0,0 +1,107 @@
+use tracing::info;
+
+const KIB: u64 = 1024;
+const MIB: u64 = 1024 * KIB;
+const GIB: u64 = 1024 * MIB;
+
+// exclusive upper bounds of the bins after the empty object bin. the last bin has no upper bound.
+const BIN_UPPER_BOUNDS: [u64; 5] = [KIB, MIB, 8 * MIB, 100 * MIB, GIB];
+const BIN_LABELS: [&str; 7] = ["0", "<1KiB", "<1MiB", "<8MiB", "<100MiB", "<1GiB", ">=1GiB"];
+
+// distribution of the transferred object sizes(--object-size-histogram).
+#[derive(Debug, Default)]
+pub struct ObjectSizeHistogram {
+    counts: [u64; 7],
+}
+
+impl ObjectSizeHistogram {
+    pub fn add(&mut self, size: u64) {
+        let index = if size == 0 {
+            0
+        } else {
+            BIN_UPPER_BOUNDS
+                .iter()
+                .position(|upper_bound| size < *upper_bound)
+                .map_or(BIN_LABELS.len() - 1, |position| position + 1)
+        };
+
+        self.counts[index] += 1;
+    }
+
+    pub fn to_summary(&self) -> String {
+        let bins = BIN_LABELS
+            .iter()
+            .zip(self.counts.iter())
+            .map(|(label, count)| format!("{label} {count} objects"))
+            .collect::<Vec<String>>()
+            .join(",  ");
+
+        format!("object size histogram | {bins}")
+    }
+
+    // with --json-tracing, the histogram is output as JSON.
+    pub fn log(&self) {
+        info!(
+            size_0 = self.counts[0],
+            size_under_1kib = self.counts[1],
+            size_under_1mib = self.counts[2],
+            size_under_8mib = self.counts[3],
+            size_under_100mib = self.counts[4],
+            size_under_1gib = self.counts[5],
+            size_1gib_or_more = self.counts[6],
+            "object size histogram."
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_to_bins() {
+        init_dummy_tracing_subscriber();
+
+        let mut histogram = ObjectSizeHistogram::default();
+
+        histogram.add(0);
+        histogram.add(1);
+        histogram.add(KIB - 1);
+        histogram.add(KIB);
+        histogram.add(MIB - 1);
+        histogram.add(MIB);
+        histogram.add(8 * MIB - 1);
+        histogram.add(8 * MIB);
+        histogram.add(100 * MIB - 1);
+        histogram.add(100 * MIB);
+        histogram.add(GIB - 1);
+        histogram.add(GIB);
+        histogram.add(5 * 1024 * GIB);
+
+        assert_eq!(histogram.counts, [1, 2, 2, 2, 2, 2, 2]);
+    }
+
+    #[test]
+    fn to_summary() {
+        init_dummy_tracing_subscriber();
+
+        let mut histogram = ObjectSizeHistogram::default();
+        histogram.add(0);
+        histogram.add(100);
+        histogram.add(100);
+        histogram.add(GIB);
+
+        assert_eq!(
+            histogram.to_summary(),
+            "object size histogram | 0 1 objects,  <1KiB 2 objects,  <1MiB 0 objects,  <8MiB 0 objects,  <100MiB 0 objects,  <1GiB 0 objects,  >=1GiB 1 objects"
+        );
+
+        histogram.log();
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
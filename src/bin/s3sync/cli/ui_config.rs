@@ -1,6 +1,10 @@
 use s3sync::Config;
 
 pub fn is_progress_indicator_needed(config: &Config) -> bool {
+    if config.only_show_errors || config.summary_only || config.download_to_stdout {
+        return false;
+    }
+
     if config.tracing_config.is_none() {
         return true;
     }
@@ -13,6 +17,11 @@ pub fn is_progress_indicator_needed(config: &Config) -> bool {
 }
 
 pub fn is_show_result_needed(config: &Config) -> bool {
+    // the standard output is used for the object data.
+    if config.download_to_stdout {
+        return false;
+    }
+
     if config.tracing_config.is_none() {
         return true;
     }
@@ -104,6 +113,63 @@ mod tests {
         assert!(!is_progress_indicator_needed(&config))
     }
 
+    #[test]
+    fn is_progress_indicator_needed_only_show_errors() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "-vv",
+            "--source-profile",
+            "source_profile",
+            "--only-show-errors",
+            "s3://source-bucket",
+            "/target-dir",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+        assert!(!is_progress_indicator_needed(&config));
+        assert!(is_show_result_needed(&config));
+        assert_eq!(
+            config.tracing_config.unwrap().tracing_level,
+            log::Level::Warn
+        );
+    }
+
+    #[test]
+    fn is_progress_indicator_needed_download_to_stdout() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "s3://source-bucket/key",
+            "-",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+        assert!(!is_progress_indicator_needed(&config));
+        assert!(!is_show_result_needed(&config));
+    }
+
+    #[test]
+    fn is_progress_indicator_needed_summary_only() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "-v",
+            "--source-profile",
+            "source_profile",
+            "--summary-only",
+            "s3://source-bucket",
+            "/target-dir",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+        assert!(!is_progress_indicator_needed(&config));
+        assert!(is_show_result_needed(&config));
+        assert!(config.tracing_config.is_none());
+    }
+
     #[test]
     fn is_show_result_needed_default() {
         init_dummy_tracing_subscriber();
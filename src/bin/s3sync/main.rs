@@ -26,6 +26,12 @@ async fn main() -> Result<()> {
         return Ok(());
     }
 
+    if config.dump_config {
+        println!("{}", config.to_json()?);
+
+        return Ok(());
+    }
+
     start_tracing_if_necessary(&config);
 
     trace!("config = {:?}", config);
@@ -50,7 +56,18 @@ fn start_tracing_if_necessary(config: &Config) -> bool {
         return false;
     }
 
-    tracing::init_tracing(config.tracing_config.as_ref().unwrap());
+    if let Err(e) = tracing::init_tracing(
+        config.tracing_config.as_ref().unwrap(),
+        &config.log_file_config,
+        config.download_to_stdout,
+    ) {
+        clap::Error::raw(
+            clap::error::ErrorKind::Io,
+            format!("failed to open the log file: {e}\n"),
+        )
+        .exit();
+    }
+
     true
 }
 
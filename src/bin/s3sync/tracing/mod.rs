@@ -1,25 +1,29 @@
 use std::env;
+use std::io;
+use std::sync::Mutex;
 
 use rusty_fork::rusty_fork_test;
+use tracing::Subscriber;
 use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer};
 
-use s3sync::config::TracingConfig;
+use s3sync::config::{LogFileConfig, TracingConfig};
 
-const EVENT_FILTER_ENV_VAR: &str = "RUST_LOG";
+use crate::tracing::rotating_file::RotatingFile;
 
-pub fn init_tracing(config: &TracingConfig) {
-    let fmt_span = if config.span_events_tracing {
-        FmtSpan::NEW | FmtSpan::CLOSE
-    } else {
-        FmtSpan::NONE
-    };
+mod rotating_file;
 
-    let subscriber_builder = tracing_subscriber::fmt()
-        .compact()
-        .with_target(false)
-        .with_ansi(!config.disable_color_tracing)
-        .with_span_events(fmt_span);
+const EVENT_FILTER_ENV_VAR: &str = "RUST_LOG";
 
+pub fn init_tracing(
+    config: &TracingConfig,
+    log_file_config: &Option<LogFileConfig>,
+    to_stderr: bool,
+) -> io::Result<()> {
     let mut show_target = true;
     let tracing_level = config.tracing_level;
     let event_filter = if config.aws_sdk_tracing {
@@ -31,13 +35,65 @@ pub fn init_tracing(config: &TracingConfig) {
         format!("s3sync={tracing_level}")
     };
 
-    let subscriber_builder = subscriber_builder
-        .with_env_filter(event_filter)
-        .with_target(show_target);
+    let mut layers = if to_stderr {
+        vec![build_fmt_layer(
+            config,
+            show_target,
+            !config.disable_color_tracing,
+            io::stderr,
+        )]
+    } else {
+        vec![build_fmt_layer(
+            config,
+            show_target,
+            !config.disable_color_tracing,
+            io::stdout,
+        )]
+    };
+    if let Some(log_file_config) = log_file_config {
+        layers.push(build_fmt_layer(
+            config,
+            show_target,
+            false,
+            Mutex::new(RotatingFile::new(log_file_config)?),
+        ));
+    }
+
+    tracing_subscriber::registry()
+        .with(EnvFilter::new(event_filter))
+        .with(layers)
+        .init();
+
+    Ok(())
+}
+
+fn build_fmt_layer<S, W>(
+    config: &TracingConfig,
+    show_target: bool,
+    ansi: bool,
+    writer: W,
+) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+    W: for<'writer> MakeWriter<'writer> + Send + Sync + 'static,
+{
+    let fmt_span = if config.span_events_tracing {
+        FmtSpan::NEW | FmtSpan::CLOSE
+    } else {
+        FmtSpan::NONE
+    };
+
+    let layer = tracing_subscriber::fmt::layer()
+        .compact()
+        .with_target(show_target)
+        .with_ansi(ansi)
+        .with_span_events(fmt_span)
+        .with_writer(writer);
+
     if config.json_tracing {
-        subscriber_builder.json().init();
+        layer.json().boxed()
     } else {
-        subscriber_builder.init();
+        layer.boxed()
     }
 }
 
@@ -49,7 +105,7 @@ rusty_fork_test! {
             json_tracing: true,
             aws_sdk_tracing: false,
             span_events_tracing: false,
-            disable_color_tracing: false});
+            disable_color_tracing: false}, &None, false).unwrap();
     }
 
     #[test]
@@ -60,7 +116,7 @@ rusty_fork_test! {
             aws_sdk_tracing: true,
             span_events_tracing: false,
             disable_color_tracing: false,
-        });
+        }, &None, false).unwrap();
     }
 
     #[test]
@@ -71,7 +127,7 @@ rusty_fork_test! {
             aws_sdk_tracing: true,
             span_events_tracing: true,
             disable_color_tracing: false,
-        });
+        }, &None, false).unwrap();
     }
 
     #[test]
@@ -82,7 +138,18 @@ rusty_fork_test! {
             aws_sdk_tracing: false,
             span_events_tracing: false,
             disable_color_tracing: true,
-        });
+        }, &None, false).unwrap();
+    }
+
+    #[test]
+    fn init_tracing_to_stderr() {
+        init_tracing(&TracingConfig {
+            tracing_level: log::Level::Info,
+            json_tracing: false,
+            aws_sdk_tracing: false,
+            span_events_tracing: false,
+            disable_color_tracing: false,
+        }, &None, true).unwrap();
     }
 
     #[test]
@@ -95,6 +162,42 @@ rusty_fork_test! {
             aws_sdk_tracing: false,
             span_events_tracing: false,
             disable_color_tracing: true,
-        });
+        }, &None, false).unwrap();
+    }
+
+    #[test]
+    fn init_tracing_with_log_file() {
+        let dir = tempfile::tempdir().unwrap();
+
+        init_tracing(&TracingConfig {
+            tracing_level: log::Level::Info,
+            json_tracing: false,
+            aws_sdk_tracing: false,
+            span_events_tracing: false,
+            disable_color_tracing: false,
+        }, &Some(LogFileConfig {
+            path: dir.path().join("s3sync.log"),
+            max_size: 1024,
+            max_files: 2,
+        }), false).unwrap();
+
+        tracing::info!("log file test.");
+
+        assert!(std::fs::read_to_string(dir.path().join("s3sync.log")).unwrap().contains("log file test."));
+    }
+
+    #[test]
+    fn init_tracing_with_log_file_error() {
+        assert!(init_tracing(&TracingConfig {
+            tracing_level: log::Level::Info,
+            json_tracing: false,
+            aws_sdk_tracing: false,
+            span_events_tracing: false,
+            disable_color_tracing: false,
+        }, &Some(LogFileConfig {
+            path: "./no_such_dir/s3sync.log".into(),
+            max_size: 1024,
+            max_files: 2,
+        }), false).is_err());
     }
 }
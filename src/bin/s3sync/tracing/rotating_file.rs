@@ -0,0 +1,143 @@
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use s3sync::config::LogFileConfig;
+
+// Size-based rotating log file.
+// When the size exceeds max_size, <path> is renamed to <path>.1, <path>.1 to <path>.2, and so on.
+// At most max_files rotated files are kept.
+pub struct RotatingFile {
+    path: PathBuf,
+    max_size: u64,
+    max_files: u16,
+    file: File,
+    size: u64,
+}
+
+impl RotatingFile {
+    pub fn new(config: &LogFileConfig) -> io::Result<Self> {
+        let file = open_log_file(&config.path)?;
+        let size = file.metadata()?.len();
+
+        Ok(Self {
+            path: config.path.clone(),
+            max_size: config.max_size,
+            max_files: config.max_files,
+            file,
+            size,
+        })
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+
+        for index in (1..self.max_files).rev() {
+            let from = rotated_path(&self.path, index);
+            if from.exists() {
+                rename_with_overwrite(&from, &rotated_path(&self.path, index + 1))?;
+            }
+        }
+        rename_with_overwrite(&self.path, &rotated_path(&self.path, 1))?;
+
+        self.file = open_log_file(&self.path)?;
+        self.size = 0;
+
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if 0 < self.size && self.max_size < self.size + buf.len() as u64 {
+            self.rotate()?;
+        }
+
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+fn open_log_file(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+// On Windows, std::fs::rename() fails if the destination exists.
+fn rename_with_overwrite(from: &Path, to: &Path) -> io::Result<()> {
+    if to.exists() {
+        std::fs::remove_file(to)?;
+    }
+    std::fs::rename(from, to)
+}
+
+fn rotated_path(path: &Path, index: u16) -> PathBuf {
+    let mut rotated = path.as_os_str().to_owned();
+    rotated.push(format!(".{index}"));
+    PathBuf::from(rotated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotate_by_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("s3sync.log");
+
+        let mut rotating_file = RotatingFile::new(&LogFileConfig {
+            path: path.clone(),
+            max_size: 10,
+            max_files: 2,
+        })
+        .unwrap();
+
+        rotating_file.write_all(b"0123456789").unwrap();
+        assert!(!rotated_path(&path, 1).exists());
+
+        rotating_file.write_all(b"abc").unwrap();
+        rotating_file.write_all(b"defghijk").unwrap();
+        rotating_file.write_all(b"xyz").unwrap();
+        rotating_file.flush().unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "xyz");
+        assert_eq!(
+            std::fs::read_to_string(rotated_path(&path, 1)).unwrap(),
+            "defghijk"
+        );
+        assert_eq!(
+            std::fs::read_to_string(rotated_path(&path, 2)).unwrap(),
+            "abc"
+        );
+        assert!(!rotated_path(&path, 3).exists());
+    }
+
+    #[test]
+    fn append_to_existing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("s3sync.log");
+        std::fs::write(&path, "0123456789").unwrap();
+
+        let mut rotating_file = RotatingFile::new(&LogFileConfig {
+            path: path.clone(),
+            max_size: 10,
+            max_files: 1,
+        })
+        .unwrap();
+        rotating_file.write_all(b"abc").unwrap();
+        rotating_file.flush().unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "abc");
+        assert_eq!(
+            std::fs::read_to_string(rotated_path(&path, 1)).unwrap(),
+            "0123456789"
+        );
+    }
+}
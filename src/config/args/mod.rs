@@ -1,6 +1,8 @@
 use std::ffi::OsString;
 use std::path::PathBuf;
 use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
 
 use aws_sdk_s3::types::{
     ChecksumAlgorithm, ChecksumMode, ObjectCannedAcl, ServerSideEncryption, StorageClass,
@@ -14,15 +16,24 @@ use regex::Regex;
 #[cfg(feature = "version")]
 use shadow_rs::shadow;
 
+#[cfg(feature = "s3_select")]
+use crate::config::args::value_parser::select_format;
 use crate::config::args::value_parser::{
-    canned_acl, checksum_algorithm, human_bytes, metadata, sse, storage_class, storage_path,
-    tagging, url,
+    account_id, canned_acl, checksum_algorithm, content_disposition_template, content_encoding_map,
+    content_type_map, date_partition, duration, file_mode, hardlink_mode, human_bytes, key_case,
+    metadata, prefix_file, retry_jitter, source_version_map, sse, sse_c_key_file, storage_class,
+    storage_path, tag_guard_mode, tagging, transfer_order, unicode_normalization, upload_manifest,
+    url, user_agent, versions_order, zero_byte_object_policy,
 };
 use crate::config::{
-    ClientConfig, FilterConfig, ForceRetryConfig, RetryConfig, TracingConfig, TransferConfig,
+    ClientConfig, FilterConfig, ForceRetryConfig, LogFileConfig, PerObjectTimeoutConfig,
+    RetryConfig, SourceListCacheConfig, TracingConfig, TransferConfig,
 };
+#[cfg(feature = "s3_select")]
+use crate::types::SourceSelectConfig;
 use crate::types::{
-    AccessKeys, ClientConfigLocation, S3Credentials, SseCustomerKey, SseKmsKeyId, StoragePath,
+    AccessKeys, ClientConfigLocation, DatePartition, HardlinkMode, S3Credentials, SseCustomerKey,
+    SseKmsKeyId, StoragePath,
 };
 use crate::Config;
 
@@ -32,38 +43,102 @@ mod value_parser;
 const EXPRESS_ONEZONE_STORAGE_SUFFIX: &str = "--x-s3";
 
 const DEFAULT_WORKER_SIZE: u16 = 16;
+const DEFAULT_CONCURRENCY_AUTO: bool = false;
 const DEFAULT_AWS_MAX_ATTEMPTS: u32 = 10;
 const DEFAULT_FORCE_RETRY_COUNT: u32 = 5;
 const DEFAULT_FORCE_RETRY_INTERVAL_MILLISECONDS: u64 = 1000;
 const DEFAULT_INITIAL_BACKOFF_MILLISECONDS: u64 = 100;
 const DEFAULT_JSON_TRACING: bool = false;
+const DEFAULT_ONLY_SHOW_ERRORS: bool = false;
+const DEFAULT_SUMMARY_ONLY: bool = false;
+const DEFAULT_PROGRESS_RATE_WINDOW: u64 = 10;
+const DEFAULT_OBJECT_SIZE_HISTOGRAM: bool = false;
+const DEFAULT_SOURCE_SCAN_PROGRESS: bool = false;
 const DEFAULT_AWS_SDK_TRACING: bool = false;
 const DEFAULT_SPAN_EVENTS_TRACING: bool = false;
 const DEFAULT_DISABLE_COLOR_TRACING: bool = false;
+const DEFAULT_LOG_FILE_MAX_SIZE: &str = "100MiB";
+const DEFAULT_LOG_FILE_MAX_FILES: u16 = 5;
 const DEFAULT_MULTIPART_THRESHOLD: &str = "8MiB";
 const DEFAULT_MULTIPART_CHUNKSIZE: &str = "8MiB";
+const DEFAULT_PER_OBJECT_TIMEOUT_MIN_THROUGHPUT: &str = "1MiB";
 const DEFAULT_AUTO_CHUNKSIZE: bool = false;
+const DEFAULT_CHUNK_SIZE_FROM_TARGET: bool = false;
+const DEFAULT_VERIFY_MULTIPART_PARTS_ATTRIBUTES: bool = false;
+const DEFAULT_TARGET_BUCKET_KEY_REUSE: bool = false;
 const DEFAULT_WARN_AS_ERROR: bool = false;
+const DEFAULT_STRICT_VERIFY: bool = false;
+const DEFAULT_FAIL_FAST_AFTER: u64 = 0;
+const DEFAULT_FAIL_ON_EMPTY_SOURCE: bool = false;
+const DEFAULT_COMPLETION_MARKER_WITH_SUMMARY: bool = false;
 const DEFAULT_IGNORE_SYMLINKS: bool = false;
 const DEFAULT_FORCE_PATH_STYLE: bool = false;
+const DEFAULT_SOURCE_REGION_AUTO_DETECT: bool = false;
+const DEFAULT_DISABLE_PREFLIGHT: bool = false;
+const DEFAULT_REQUIRE_NO_DEFAULT_RETENTION: bool = false;
+const DEFAULT_ANONYMOUS: bool = false;
 const DEFAULT_HEAD_EACH_TARGET: bool = false;
+const DEFAULT_HEAD_NOT_FOUND_RETRIES: u32 = 0;
+const DEFAULT_TARGET_LIST_CACHE_INVALIDATE_ON_WRITE: bool = false;
+const DEFAULT_SKIP_HEAD_FOR_UNLISTED_OBJECTS: bool = false;
+const DEFAULT_SOURCE_IF_MODIFIED_SINCE: bool = false;
+const DEFAULT_SKIP_IF_TARGET_NEWER: bool = false;
 const DEFAULT_ENABLE_VERSIONING: bool = false;
+const DEFAULT_VERSIONS_ORDER: &str = "oldest-first";
+const DEFAULT_SSE_C_KEY_FORMAT: &str = "auto";
 const DEFAULT_REMOVE_MODIFIED_FILTER: bool = false;
+const DEFAULT_SKIP_GLACIER: bool = false;
+const DEFAULT_SOURCE_LIST_INCLUDE_RESTORE_STATUS: bool = false;
+const DEFAULT_SOURCE_LIST_INCLUDE_OWNER: bool = false;
 const DEFAULT_CHECK_SIZE: bool = false;
 const DEFAULT_CHECK_ETAG: bool = false;
 const DEFAULT_SYNC_WITH_DELETE: bool = false;
 const DEFAULT_DISABLE_TAGGING: bool = false;
 const DEFAULT_SYNC_LATEST_TAGGING: bool = false;
 const DEFAULT_NO_GUESS_MIME_TYPE: bool = false;
+const DEFAULT_FAST_EMPTY_FILES: bool = false;
 const DEFAULT_DISABLE_MULTIPART_VERIFY: bool = false;
 const DEFAULT_DISABLE_ETAG_VERIFY: bool = false;
+const DEFAULT_VERIFY_CONTENT_LENGTH_ONLY: bool = false;
+const DEFAULT_VERIFY_VIA_HEAD: bool = false;
+const DEFAULT_VERIFY_RANGE_READS: bool = false;
+const DEFAULT_VERIFY_TARGET_ENCRYPTION: bool = false;
+const DEFAULT_SOURCE_SSE_C_PROBE: bool = false;
+const DEFAULT_PREALLOCATE: bool = false;
+const DEFAULT_FSYNC_ON_COMPLETE: bool = false;
+const DEFAULT_VERIFY_ONLY_ON_MISMATCH_REDOWNLOAD: bool = false;
+const DEFAULT_DELETE_REMOVED_VERSIONS: bool = false;
+const DEFAULT_TRANSFER_ORDER_BUFFER_SIZE: u32 = 100000;
 const DEFAULT_ENABLE_ADDITIONAL_CHECKSUM: bool = false;
+const DEFAULT_CHECKSUM_ON_DOWNLOAD_ONLY_CHANGED: bool = false;
+const DEFAULT_CHECKSUM_DISPLAY: bool = false;
+const DEFAULT_FORCE_REVERIFY: bool = false;
+const DEFAULT_RENAME_ON_CONFLICT: bool = false;
+const DEFAULT_SKIP_ON_CONFLICT: bool = false;
+const DEFAULT_HARDLINK_MODE: &str = "copy";
+const DEFAULT_PRESERVE_FILE_MODE: bool = false;
+#[cfg(feature = "s3_select")]
+const DEFAULT_SOURCE_SELECT_INPUT_FORMAT: &str = "csv";
+#[cfg(feature = "s3_select")]
+const DEFAULT_SOURCE_SELECT_OUTPUT_FORMAT: &str = "csv";
 const DEFAULT_DRY_RUN: bool = false;
 const DEFAULT_NO_VERIFY_SSL: bool = false;
 const DEFAULT_MAX_KEYS: i32 = 1000;
+const DEFAULT_NON_RECURSIVE: bool = false;
+const DEFAULT_INCLUDE_ONLY_PREFIXES_FROM_STDIN: bool = false;
+const DEFAULT_LIST_CACHE_MAX_AGE: &str = "1h";
+const DEFAULT_DATE_PARTITION_TIMEZONE: &str = "UTC";
 const DEFAULT_PUT_LAST_MODIFIED_METADATA: bool = false;
+const DEFAULT_PUT_FILE_MODE_METADATA: bool = false;
+const DEFAULT_METADATA_MERGE: bool = false;
 const DEFAULT_DISABLE_STALLED_STREAM_PROTECTION: bool = false;
+const DEFAULT_DUMP_CONFIG: bool = false;
 const DEFAULT_DISABLE_PAYLOAD_SIGNING: bool = false;
+const DEFAULT_ABORT_INCOMPLETE_MULTIPART_UPLOADS: bool = false;
+const DEFAULT_CLEANUP_TEMP_ON_START: bool = false;
+const DEFAULT_CLEANUP_TEMP_OLDER_THAN: &str = "1h";
+const DEFAULT_PARTIAL_CLEANUP_OLDER_THAN: &str = "1h";
+const DEFAULT_ZERO_BYTE_OBJECT_POLICY: &str = "sync";
 const NO_S3_STORAGE_SPECIFIED: &str = "either SOURCE or TARGET must be s3://\n";
 const LOCAL_STORAGE_SPECIFIED: &str =
     "with --enable-versioning/--sync-latest-tagging, both storage must be s3://\n";
@@ -78,16 +153,108 @@ const TARGET_LOCAL_STORAGE_SPECIFIED_WITH_ADDITIONAL_CHECKSUM_ALGORITHM: &str =
     "with --additional-checksum-algorithm, target storage must be s3://\n";
 const SOURCE_LOCAL_STORAGE_SPECIFIED_WITH_AUTO_CHUNKSIZE: &str =
     "with --auto-chunksize, source storage must be s3://\n";
+const TARGET_LOCAL_STORAGE_SPECIFIED_WITH_CHUNK_SIZE_FROM_TARGET: &str =
+    "with --chunk-size-from-target, target storage must be s3://\n";
+const SOURCE_LOCAL_STORAGE_SPECIFIED_WITH_TARGET_BUCKET_KEY_REUSE: &str =
+    "with --target-bucket-key-reuse, source storage must be s3://\n";
+const TARGET_LOCAL_STORAGE_SPECIFIED_WITH_TARGET_BUCKET_KEY_REUSE: &str =
+    "with --target-bucket-key-reuse, target storage must be s3://\n";
+const TARGET_BUCKET_KEY_REUSE_CONFLICT_SSE: &str =
+    "with --target-bucket-key-reuse, --sse must be aws:kms if specified. S3 Bucket Keys are for SSE-KMS only\n";
+const SOURCE_LOCAL_STORAGE_SPECIFIED_WITH_VERIFY_RANGE_READS: &str =
+    "with --verify-range-reads, source storage must be s3://\n";
+const SOURCE_LOCAL_STORAGE_SPECIFIED_WITH_SKIP_GLACIER: &str =
+    "with --skip-glacier, source storage must be s3://\n";
+const SOURCE_LOCAL_STORAGE_SPECIFIED_WITH_SOURCE_LIST_INCLUDE_RESTORE_STATUS: &str =
+    "with --source-list-include-restore-status, source storage must be s3://\n";
+const SOURCE_LOCAL_STORAGE_SPECIFIED_WITH_SOURCE_LIST_INCLUDE_OWNER: &str =
+    "with --source-list-include-owner, source storage must be s3://\n";
+const SOURCE_LOCAL_STORAGE_SPECIFIED_WITH_LIST_PARALLEL_PREFIX_FILE: &str =
+    "with --list-parallel-prefix-file, source storage must be s3://\n";
+const SOURCE_LOCAL_STORAGE_SPECIFIED_WITH_SOURCE_LIST_CACHE: &str =
+    "with --source-list-cache, source storage must be s3://\n";
+const SOURCE_LOCAL_STORAGE_SPECIFIED_WITH_KEY_RANGE: &str =
+    "with --start-after/--stop-at, source storage must be s3://\n";
+const INVALID_KEY_RANGE: &str = "--stop-at must be greater than --start-after\n";
 const SOURCE_REMOTE_STORAGE_SPECIFIED_WITH_IGNORE_SYMLINKS: &str =
     "with --ignore-symlinks, source storage must be local storage\n";
+const SOURCE_REMOTE_STORAGE_SPECIFIED_WITH_FAST_EMPTY_FILES: &str =
+    "with --fast-empty-files, source storage must be local storage\n";
 const SOURCE_REMOTE_STORAGE_SPECIFIED_WITH_NO_GUESS_MIME_TYPE: &str =
     "with --no-guess-mime-type, source storage must be local storage\n";
+const SOURCE_REMOTE_STORAGE_SPECIFIED_WITH_CONTENT_TYPE_OVERRIDE_MAP: &str =
+    "with --content-type-override-map, source storage must be local storage\n";
+const SOURCE_REMOTE_STORAGE_SPECIFIED_WITH_CONTENT_ENCODING_MAP: &str =
+    "with --content-encoding-map, source storage must be local storage\n";
+const SOURCE_REMOTE_STORAGE_SPECIFIED_WITH_MD5_XATTR: &str =
+    "with --md5-xattr, source storage must be local storage\n";
+const TARGET_REMOTE_STORAGE_SPECIFIED_WITH_PARTIAL_SUFFIX: &str =
+    "with --partial-suffix, target storage must be local storage\n";
+const TARGET_REMOTE_STORAGE_SPECIFIED_WITH_VERIFY_ONLY_ON_MISMATCH_REDOWNLOAD: &str =
+    "with --verify-only-on-mismatch-redownload, target storage must be local storage\n";
+const TARGET_REMOTE_STORAGE_SPECIFIED_WITH_PREALLOCATE: &str =
+    "with --preallocate, target storage must be local storage\n";
+const TARGET_REMOTE_STORAGE_SPECIFIED_WITH_FSYNC_ON_COMPLETE: &str =
+    "with --fsync-on-complete, target storage must be local storage\n";
+const TARGET_REMOTE_STORAGE_SPECIFIED_WITH_LOCAL_TMP_DIR: &str =
+    "with --local-tmp-dir, target storage must be local storage\n";
+const TARGET_REMOTE_STORAGE_SPECIFIED_WITH_CLEANUP_TEMP_ON_START: &str =
+    "with --cleanup-temp-on-start, target storage must be local storage\n";
+const TARGET_STDOUT_SPECIFIED_WITHOUT_SOURCE_OBJECT: &str =
+    "with TARGET `-`(stdout), SOURCE must be s3://<BUCKET_NAME>/<OBJECT_KEY>\n";
+const TARGET_STDOUT_SPECIFIED_WITH_SYNC_OPTION: &str =
+    "with TARGET `-`(stdout), --delete/--enable-versioning/--sync-latest-tagging/--dry-run cannot be specified\n";
+const CHECKSUM_VERIFY_SAMPLING_WITHOUT_ADDITIONAL_CHECKSUM: &str =
+    "with --checksum-verify-sampling, --enable-additional-checksum or --additional-checksum-algorithm must be specified\n";
+const LOCAL_TMP_DIR_NOT_WRITABLE: &str = "--local-tmp-dir must be a writable directory\n";
+const TARGET_REMOTE_STORAGE_SPECIFIED_WITH_ON_CONFLICT: &str =
+    "with --rename-on-conflict/--skip-on-conflict, target storage must be local storage\n";
+const TARGET_REMOTE_STORAGE_SPECIFIED_WITH_CHECKSUM_ON_DOWNLOAD_ONLY_CHANGED: &str =
+    "with --checksum-on-download-only-changed, target storage must be local storage\n";
+const TARGET_LOCAL_STORAGE_SPECIFIED_WITH_PER_OBJECT_TIMEOUT_BASE: &str =
+    "with --per-object-timeout-base, target storage must be s3://\n";
 const TARGET_LOCAL_STORAGE_SPECIFIED_WITH_METADATA_OPTION: &str =
     "with metadata related option, target storage must be s3://\n";
 const SOURCE_LOCAL_STORAGE_SPECIFIED_WITH_ENDPOINT_URL: &str =
     "with --source-endpoint-url, source storage must be s3://\n";
+const SOURCE_LOCAL_STORAGE_SPECIFIED_WITH_SOURCE_REGION_AUTO_DETECT: &str =
+    "with --source-region-auto-detect, source storage must be s3://\n";
 const TARGET_LOCAL_STORAGE_SPECIFIED_WITH_ENDPOINT_URL: &str =
     "with --target-endpoint-url, target storage must be s3://\n";
+const SOURCE_LOCAL_STORAGE_SPECIFIED_WITH_EXPECTED_BUCKET_OWNER: &str =
+    "with --source-expected-bucket-owner, source storage must be s3://\n";
+const TARGET_LOCAL_STORAGE_SPECIFIED_WITH_EXPECTED_BUCKET_OWNER: &str =
+    "with --target-expected-bucket-owner, target storage must be s3://\n";
+const TARGET_LOCAL_STORAGE_SPECIFIED_WITH_REQUIRE_NO_DEFAULT_RETENTION: &str =
+    "with --require-no-default-retention, target storage must be s3://\n";
+const SOURCE_LOCAL_STORAGE_SPECIFIED_WITH_SOURCE_IF_MODIFIED_SINCE: &str =
+    "with --source-if-modified-since, source storage must be s3://\n";
+const TARGET_LOCAL_STORAGE_SPECIFIED_WITH_TARGET_LIST_CACHE_INVALIDATE_ON_WRITE: &str =
+    "with --target-list-cache-invalidate-on-write, target storage must be s3://\n";
+const NORMALIZE_UNICODE_WITHOUT_LOCAL_STORAGE: &str =
+    "with --normalize-unicode, source or target storage must be local storage\n";
+const NORMALIZE_UNICODE_WITH_DELETE_FROM_S3: &str =
+    "with --normalize-unicode, --delete cannot be specified if source storage is s3://\n";
+const HARDLINK_MODE_WITHOUT_LOCAL_STORAGE: &str =
+    "with --hardlink-mode, source or target storage must be local storage\n";
+const HARDLINK_MODE_SKIP_WITH_REMOTE_SOURCE: &str =
+    "with --hardlink-mode skip, source storage must be local storage\n";
+const HARDLINK_MODE_DEDUP_METADATA_WITH_CONTENT_CHECK: &str =
+    "with --hardlink-mode dedup-metadata, --check-etag, --check-additional-checksum and --check-etag-and-checksum cannot be specified\n";
+const HARDLINK_MODE_NOT_SUPPORTED: &str = "--hardlink-mode is supported on Unix only\n";
+const FILE_MODE_NOT_SUPPORTED: &str =
+    "--download-file-mode, --preserve-file-mode and --put-file-mode-metadata are supported on Unix only\n";
+const TARGET_REMOTE_STORAGE_SPECIFIED_WITH_FILE_MODE: &str =
+    "with --download-file-mode or --preserve-file-mode, target storage must be local storage\n";
+const SOURCE_REMOTE_STORAGE_SPECIFIED_WITH_PUT_FILE_MODE_METADATA: &str =
+    "with --put-file-mode-metadata, source storage must be local storage\n";
+const SOURCE_REMOTE_STORAGE_SPECIFIED_WITH_UPLOAD_MANIFEST: &str =
+    "with --upload-manifest, source storage must be local storage\n";
+const SOURCE_LOCAL_STORAGE_SPECIFIED_WITH_SOURCE_VERSIONS_AT: &str =
+    "with --source-versions-at, source storage must be s3://\n";
+#[cfg(feature = "s3_select")]
+const SOURCE_LOCAL_STORAGE_SPECIFIED_WITH_SOURCE_SELECT_EXPRESSION: &str =
+    "with --source-select-expression, source storage must be s3://\n";
 const CHECK_SIZE_CONFLICT: &str =
     "--head-each-target is required for --check-size, or remove --remove-modified-filter\n";
 
@@ -96,6 +263,14 @@ const CHECK_ETAG_CONFLICT: &str =
 const CHECK_ETAG_CONFLICT_SSE_KMS: &str = "--check-etag is not supported with --sse aws:kms\n";
 const CHECK_ETAG_NOT_SUPPORTED_WITH_EXPRESS_ONEZONE: &str =
     "--check-etag is not supported with express onezone storage class\n";
+const CHECK_ETAG_AND_CHECKSUM_CONFLICT_SSE_KMS: &str =
+    "--check-etag-and-checksum is not supported with --sse aws:kms\n";
+const CHECK_ETAG_AND_CHECKSUM_NOT_SUPPORTED_WITH_EXPRESS_ONEZONE: &str =
+    "--check-etag-and-checksum is not supported with express onezone storage class\n";
+const CHECK_ETAG_AND_CHECKSUM_WITHOUT_ADDITIONAL_CHECKSUM_ALGORITHM: &str =
+    "with --check-etag-and-checksum, --additional-checksum-algorithm with the same algorithm is required if target storage is s3://\n";
+const CHECK_ETAG_AND_CHECKSUM_WITHOUT_ENABLE_ADDITIONAL_CHECKSUM: &str =
+    "with --check-etag-and-checksum, --enable-additional-checksum is required if source storage is s3://\n";
 
 const SOURCE_LOCAL_STORAGE_DIR_NOT_FOUND: &str = "directory must be specified as a source\n";
 const TARGET_LOCAL_STORAGE_INVALID: &str = "invalid target path\n";
@@ -105,6 +280,8 @@ const LOCAL_STORAGE_SPECIFIED_WITH_SSE_C: &str =
     "with --source-sse-c/--target-sse-c, remote storage must be s3://\n";
 const TARGET_LOCAL_STORAGE_SPECIFIED_WITH_DISABLE_PAYLOAD_SIGNING: &str =
     "with --disable-payload-signing, target storage must be s3://\n";
+const TARGET_LOCAL_STORAGE_SPECIFIED_WITH_ABORT_INCOMPLETE_MULTIPART_UPLOADS: &str =
+    "with --abort-incomplete-multipart-uploads, target storage must be s3://\n";
 
 const NO_SOURCE_CREDENTIAL_REQUIRED: &str = "no source credential required\n";
 const NO_TARGET_CREDENTIAL_REQUIRED: &str = "no target credential required\n";
@@ -118,7 +295,7 @@ pub struct CLIArgs {
     #[arg(env, help = "s3://<BUCKET_NAME>[/prefix] or local path", value_parser = storage_path::check_storage_path, default_value_if("auto_complete_shell", ArgPredicate::IsPresent, "s3://ignored"), required = false)]
     source: String,
 
-    #[arg(env, help = "s3://<BUCKET_NAME>[/prefix] or local path", value_parser = storage_path::check_storage_path, default_value_if("auto_complete_shell", ArgPredicate::IsPresent, "s3://ignored"), required = false)]
+    #[arg(env, help = "s3://<BUCKET_NAME>[/prefix] or local path. `-` writes a single source object to stdout", value_parser = storage_path::check_storage_path, default_value_if("auto_complete_shell", ArgPredicate::IsPresent, "s3://ignored"), required = false)]
     target: String,
 
     /// location of the file that the AWS CLI uses to store configuration profiles
@@ -133,6 +310,10 @@ pub struct CLIArgs {
     #[arg(long, env, conflicts_with_all = ["source_access_key", "source_secret_access_key", "source_session_token"])]
     source_profile: Option<String>,
 
+    /// access the source storage without credentials(unsigned requests). e.g. public buckets
+    #[arg(long, env, conflicts_with_all = ["source_profile", "source_access_key", "source_secret_access_key", "source_session_token"], default_value_t = DEFAULT_ANONYMOUS)]
+    source_anonymous: bool,
+
     /// source access key
     #[arg(long, env, conflicts_with_all = ["source_profile"], requires = "source_secret_access_key")]
     source_access_key: Option<String>,
@@ -149,6 +330,11 @@ pub struct CLIArgs {
     #[arg(long, env, value_parser = NonEmptyStringValueParser::new())]
     source_region: Option<String>,
 
+    /// detect the source region from the bucket location when --source-region is not specified.
+    /// the region is detected once at start. AWS endpoint only
+    #[arg(long, env, conflicts_with_all = ["source_region", "source_endpoint_url"], default_value_t = DEFAULT_SOURCE_REGION_AUTO_DETECT)]
+    source_region_auto_detect: bool,
+
     /// source endpoint url
     #[arg(long, env, value_parser = url::check_scheme)]
     source_endpoint_url: Option<String>,
@@ -157,10 +343,20 @@ pub struct CLIArgs {
     #[arg(long, env, default_value_t = DEFAULT_FORCE_PATH_STYLE)]
     source_force_path_style: bool,
 
+    /// AWS account ID that must own the source bucket. requests to a bucket owned by another account fail with 403(Access Denied)
+    #[arg(long, env, value_parser = account_id::check_account_id)]
+    source_expected_bucket_owner: Option<String>,
+
     /// target AWS CLI profile
     #[arg(long, env, conflicts_with_all = ["target_access_key", "target_secret_access_key", "target_session_token"])]
     target_profile: Option<String>,
 
+    /// access the target storage without credentials(unsigned requests).
+    /// s3sync does not check the bucket policy: every write(PutObject, multipart upload, tagging) fails with 403(Access Denied)
+    /// unless the target bucket or the S3-compatible storage explicitly allows anonymous writes
+    #[arg(long, env, conflicts_with_all = ["target_profile", "target_access_key", "target_secret_access_key", "target_session_token"], default_value_t = DEFAULT_ANONYMOUS)]
+    target_anonymous: bool,
+
     /// target access key
     #[arg(long, env, conflicts_with_all = ["target_profile"], requires = "target_secret_access_key")]
     target_access_key: Option<String>,
@@ -173,6 +369,14 @@ pub struct CLIArgs {
     #[arg(long, env, conflicts_with_all = ["target_profile"], requires = "target_access_key")]
     target_session_token: Option<String>,
 
+    /// web identity(OIDC) token file to assume --target-role-arn. e.g. GitHub Actions, EKS
+    #[arg(long, env, conflicts_with_all = ["target_profile", "target_access_key", "target_secret_access_key", "target_session_token", "target_anonymous"], requires = "target_role_arn")]
+    target_web_identity_token_file: Option<PathBuf>,
+
+    /// IAM role ARN to assume with --target-web-identity-token-file
+    #[arg(long, env, requires = "target_web_identity_token_file", value_parser = NonEmptyStringValueParser::new())]
+    target_role_arn: Option<String>,
+
     /// target region
     #[arg(long, env, value_parser = NonEmptyStringValueParser::new())]
     target_region: Option<String>,
@@ -185,6 +389,19 @@ pub struct CLIArgs {
     #[arg(long, env, default_value_t = DEFAULT_FORCE_PATH_STYLE)]
     target_force_path_style: bool,
 
+    /// AWS account ID that must own the target bucket. requests to a bucket owned by another account fail with 403(Access Denied)
+    #[arg(long, env, value_parser = account_id::check_account_id)]
+    target_expected_bucket_owner: Option<String>,
+
+    /// disable the preflight check(ListObjectsV2 with max-keys=1) of the source and target buckets before the sync
+    #[arg(long, env, default_value_t = DEFAULT_DISABLE_PREFLIGHT)]
+    disable_preflight: bool,
+
+    /// abort the sync if the target bucket has the default retention of Object Lock.
+    /// without this option, the default retention is reported as a warning by the preflight check
+    #[arg(long, env, conflicts_with = "disable_preflight", default_value_t = DEFAULT_REQUIRE_NO_DEFAULT_RETENTION)]
+    require_no_default_retention: bool,
+
     /// maximum retry attempts that s3sync retry handler use
     #[arg(long, env, default_value_t = DEFAULT_AWS_MAX_ATTEMPTS, value_name = "max_attempts")]
     aws_max_attempts: u32,
@@ -193,6 +410,13 @@ pub struct CLIArgs {
     #[arg(long, env, default_value_t = DEFAULT_INITIAL_BACKOFF_MILLISECONDS, value_name = "initial_backoff")]
     initial_backoff_milliseconds: u64,
 
+    /// jitter strategy of the retry backoff. with full, the backoff is randomized between 0 and the backoff time.
+    /// with equal, between half of the backoff time and the backoff time. with none, the backoff is not randomized.
+    /// it is also applied to --force-retry-interval-milliseconds. the AWS SDK retries do not support equal, so full is used for them.
+    /// if not specified, the AWS SDK retries use full jitter, and the force retries sleep for the fixed interval
+    #[arg(long, env, value_name = "full|equal|none", value_parser = retry_jitter::parse_retry_jitter)]
+    retry_jitter: Option<String>,
+
     /// maximum force retry attempts that s3sync retry handler use
     #[arg(long, env, default_value_t = DEFAULT_FORCE_RETRY_COUNT)]
     force_retry_count: u32,
@@ -201,10 +425,49 @@ pub struct CLIArgs {
     #[arg(long, env, default_value_t = DEFAULT_FORCE_RETRY_INTERVAL_MILLISECONDS, value_name = "force_retry_interval")]
     force_retry_interval_milliseconds: u64,
 
+    /// cap the cumulative time spent in force retries across the whole run.
+    /// once exceeded, further force retries are disabled and errors surface immediately.
+    /// Allow suffixes: s, m, h, d (e.g. 10m)
+    #[arg(long, env, value_name = "DURATION", value_parser = duration::check_duration)]
+    retry_budget: Option<String>,
+
+    /// cap the force retries of each object. once exceeded, the object is marked as failed and
+    /// the worker moves on to the next object(counted towards --fail-fast-after).
+    #[arg(long, env, value_name = "N")]
+    max_retries_per_object: Option<u32>,
+
     /// trace verbosity(-v: show info, -vv: show debug, -vvv show trace)
     #[clap(flatten)]
     verbosity: Verbosity<WarnLevel>,
 
+    /// show only warnings, errors and the final summary. the progress indicator is not shown.
+    /// it takes precedence over the verbosity flags
+    #[arg(long, env, conflicts_with_all = ["summary_only", "dry_run"], default_value_t = DEFAULT_ONLY_SHOW_ERRORS)]
+    only_show_errors: bool,
+
+    /// show only the final summary. neither trace nor the progress indicator is shown
+    #[arg(long, env, conflicts_with_all = ["dry_run", "json_tracing", "log_file"], default_value_t = DEFAULT_SUMMARY_ONLY)]
+    summary_only: bool,
+
+    /// log the cumulative statistics(objects, bytes and rates) every SECONDS at the info level(-v).
+    /// the final summary is still shown
+    #[arg(long, env, value_name = "SECONDS", conflicts_with = "summary_only", value_parser = clap::value_parser!(u64).range(1..))]
+    stats_interval: Option<u64>,
+
+    /// window(seconds) of the moving average of the rates and the estimated remaining time in the progress indicator
+    #[arg(long, env, value_name = "SECONDS", default_value_t = DEFAULT_PROGRESS_RATE_WINDOW, value_parser = clap::value_parser!(u64).range(1..=3600))]
+    progress_rate_window: u64,
+
+    /// show the distribution of the transferred object sizes(0, <1KiB, <1MiB, <8MiB, <100MiB, <1GiB, >=1GiB) in the summary.
+    /// it is also logged at the info level(-v), as JSON with --json-tracing
+    #[arg(long, env, default_value_t = DEFAULT_OBJECT_SIZE_HISTOGRAM)]
+    object_size_histogram: bool,
+
+    /// log the number of the listed source objects and the listing rate every 10 seconds at the info level(-v),
+    /// until the source listing is completed
+    #[arg(long, env, conflicts_with = "summary_only", default_value_t = DEFAULT_SOURCE_SCAN_PROGRESS)]
+    source_scan_progress: bool,
+
     /// show trace as json format
     #[arg(long, env, default_value_t = DEFAULT_JSON_TRACING)]
     json_tracing: bool,
@@ -221,6 +484,22 @@ pub struct CLIArgs {
     #[arg(long, env, default_value_t = DEFAULT_DISABLE_COLOR_TRACING)]
     disable_color_tracing: bool,
 
+    /// write trace to the file in addition to the standard output. the file is rotated by size
+    #[arg(long, env, value_name = "FILE")]
+    log_file: Option<PathBuf>,
+
+    /// maximum size of the log file before rotation, Allow suffixes: KB, KiB, MB, MiB, GB, GiB
+    #[arg(long, env, requires = "log_file", default_value = DEFAULT_LOG_FILE_MAX_SIZE, value_parser = human_bytes::check_human_bytes_without_limit)]
+    log_file_max_size: String,
+
+    /// maximum number of rotated log files to keep
+    #[arg(long, env, requires = "log_file", default_value_t = DEFAULT_LOG_FILE_MAX_FILES, value_parser = clap::value_parser!(u16).range(1..))]
+    log_file_max_files: u16,
+
+    /// write a JSON report of the verification results(verified, warning and error keys) to the file at the end
+    #[arg(long, env, value_name = "FILE")]
+    integrity_report: Option<PathBuf>,
+
     /// object size threshold that s3sync uses for multipart upload, Allow suffixes: MB, MiB, GB, GiB.
     /// the larger the size, the larger the memory usage.
     #[arg(long, env, conflicts_with_all = ["auto_chunksize"], default_value = DEFAULT_MULTIPART_THRESHOLD, value_parser = human_bytes::check_human_bytes)]
@@ -235,6 +514,16 @@ pub struct CLIArgs {
     #[arg(long, env, conflicts_with_all = ["multipart_threshold", "multipart_chunksize"], default_value_t = DEFAULT_AUTO_CHUNKSIZE)]
     auto_chunksize: bool,
 
+    /// upload with the part sizes of the existing multipart object in the target, so that the re-uploaded object has the same ETag.
+    /// if the target object does not exist, is not multipart, or its size is different, --multipart-chunksize is used
+    #[arg(long, env, conflicts_with_all = ["auto_chunksize"], default_value_t = DEFAULT_CHUNK_SIZE_FROM_TARGET)]
+    chunk_size_from_target: bool,
+
+    /// with --auto-chunksize, get the part sizes by GetObjectAttributes(1 API call per 1000 parts) instead of HEAD per part.
+    /// if the object has no parts information(uploaded without an additional checksum) or the request fails, HEAD per part is used
+    #[arg(long, env, requires = "auto_chunksize", default_value_t = DEFAULT_VERIFY_MULTIPART_PARTS_ATTRIBUTES)]
+    verify_multipart_parts_attributes: bool,
+
     /// proxy server to use for HTTPS
     #[arg(long, env, value_parser = url::check_scheme)]
     https_proxy: Option<String>,
@@ -243,27 +532,163 @@ pub struct CLIArgs {
     #[arg(long, env, value_parser = url::check_scheme_and_no_authority_exist)]
     http_proxy: Option<String>,
 
+    /// comma-separated list of hosts that bypass the proxy. subdomains of each host are also bypassed.
+    /// e.g. --no-proxy localhost,.internal.example.com
+    #[arg(long, env, value_parser = NonEmptyStringValueParser::new())]
+    no_proxy: Option<String>,
+
+    /// order in which objects are transferred. objects are buffered and sorted before transfer.
+    /// valid choices: size-asc | size-desc | key | random
+    #[arg(long, env, conflicts_with = "enable_versioning", value_parser = transfer_order::parse_transfer_order)]
+    transfer_order: Option<String>,
+
+    /// with --transfer-order, maximum number of objects to buffer and sort at a time.
+    /// a larger value gives a better order, but uses more memory
+    #[arg(long, env, requires = "transfer_order", default_value_t = DEFAULT_TRANSFER_ORDER_BUFFER_SIZE, value_parser = clap::value_parser!(u32).range(1..))]
+    transfer_order_buffer_size: u32,
+
     /// number of workers for synchronization
     #[arg(long, env, default_value_t = DEFAULT_WORKER_SIZE, value_parser = clap::value_parser!(u16).range(1..))]
     worker_size: u16,
 
+    /// start with a few active workers, and increase them up to --worker-size while the throughput improves.
+    /// on errors, the active workers are decreased
+    #[arg(long, env, default_value_t = DEFAULT_CONCURRENCY_AUTO)]
+    concurrency_auto: bool,
+
+    /// maximum number of in-flight objects per top-level prefix. objects are dispatched round-robin across prefixes
+    /// objects waiting for dispatch are buffered in memory
+    #[arg(long, env, value_name = "N", conflicts_with = "enable_versioning", value_parser = clap::value_parser!(u16).range(1..))]
+    max_concurrent_objects_per_prefix: Option<u16>,
+
+    /// maximum number of concurrent checksum/ETag computations of local files, independently of --worker-size.
+    /// without this option, it is not limited
+    #[arg(long, env, value_name = "N", value_parser = clap::value_parser!(u16).range(1..))]
+    checksum_parallelism: Option<u16>,
+
+    /// maximum number of objects read from the source concurrently(GetObject), independently of --worker-size.
+    /// the permit is held until the object body has been transferred. without this option, it is --worker-size
+    #[arg(long, env, value_name = "N", value_parser = clap::value_parser!(u16).range(1..))]
+    source_concurrency_limit: Option<u16>,
+
+    /// maximum number of objects written to the target concurrently(PutObject/multipart upload), independently of --worker-size.
+    /// without this option, it is --worker-size
+    #[arg(long, env, value_name = "N", value_parser = clap::value_parser!(u16).range(1..))]
+    target_concurrency_limit: Option<u16>,
+
+    /// on ctrl-c, stop starting new objects and wait up to this many seconds for in-flight objects to finish.
+    /// without this option, ctrl-c aborts in-flight objects immediately. a second ctrl-c also aborts them
+    #[arg(long, env, value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..))]
+    graceful_shutdown_timeout: Option<u64>,
+
+    /// stop starting new objects once this many objects have been transferred, and wait for in-flight objects to finish
+    #[arg(long, env, value_name = "N", value_parser = clap::value_parser!(u64).range(1..))]
+    max_objects: Option<u64>,
+
+    /// stop starting new objects once this many bytes have been transferred, and wait for in-flight objects to finish.
+    /// Allow suffixes: KB, KiB, MB, MiB, GB, GiB, TB, TiB
+    #[arg(long, env, value_parser = human_bytes::check_human_bytes_without_limit)]
+    max_bytes: Option<String>,
+
     /// treat warnings as errors(except for the case of etag/checksum mismatch, etc.)
     #[arg(long, env, default_value_t = DEFAULT_WARN_AS_ERROR)]
     warn_as_error: bool,
 
+    /// treat etag/additional checksum/content length mismatches after transfer as errors, and exit with an error.
+    /// the skipped verifications(e.g. --disable-multipart-verify) are not regarded as mismatches
+    #[arg(long, env, default_value_t = DEFAULT_STRICT_VERIFY)]
+    strict_verify: bool,
+
+    /// abort the run once the number of skipped per-key errors(e.g. access denied, not found) reaches N.
+    /// 0 means no limit
+    #[arg(long, env, value_name = "N", conflicts_with = "warn_as_error", default_value_t = DEFAULT_FAIL_FAST_AFTER)]
+    fail_fast_after: u64,
+
+    /// abort the run with an error if no source object is found after filtering.
+    /// the target objects are not deleted. recommended with --delete
+    #[arg(long, env, default_value_t = DEFAULT_FAIL_ON_EMPTY_SOURCE)]
+    fail_on_empty_source: bool,
+
+    /// write an empty marker object(e.g. _SUCCESS) with the key under the target prefix after the run completed without errors.
+    /// it is not written with --dry-run, or if the run was interrupted
+    #[arg(long, env, value_name = "KEY", value_parser = NonEmptyStringValueParser::new())]
+    write_completion_marker: Option<String>,
+
+    /// write the run summary in JSON to the marker of --write-completion-marker
+    #[arg(long, env, default_value_t = DEFAULT_COMPLETION_MARKER_WITH_SUMMARY, requires = "write_completion_marker")]
+    completion_marker_with_summary: bool,
+
     /// ignore symbolic links
     #[arg(long, env, default_value_t = DEFAULT_IGNORE_SYMLINKS)]
     ignore_symlinks: bool,
 
+    /// normalize keys of local files to the unicode normalization form(NFC/NFD) before comparison and transfer.
+    /// e.g. macOS file names are NFD, Linux file names are usually NFC
+    #[arg(long, env, value_name = "NFC|NFD", value_parser = unicode_normalization::parse_unicode_normalization)]
+    normalize_unicode: Option<String>,
+
+    /// convert the keys of the target objects derived from the source keys to lowercase or uppercase.
+    /// the target prefix is not converted. source objects whose converted keys collide are skipped with a warning
+    #[arg(long, env, value_name = "lower|upper", conflicts_with_all = ["enable_versioning"], value_parser = key_case::parse_key_case)]
+    target_object_key_case: Option<String>,
+
+    /// prepend a date partition formatted from the LastModified of the source object to the target keys.
+    /// the pattern is a strftime pattern. e.g. %Y/%m/%d
+    #[arg(long, env, value_name = "PATTERN", conflicts_with_all = ["enable_versioning"], value_parser = date_partition::check_date_partition)]
+    date_partition: Option<String>,
+
+    /// timezone of --date-partition. UTC or a fixed offset(e.g. +09:00)
+    #[arg(long, env, value_name = "TIMEZONE", default_value = DEFAULT_DATE_PARTITION_TIMEZONE, requires = "date_partition", value_parser = date_partition::check_date_partition_timezone)]
+    date_partition_timezone: String,
+
     /// HeadObject is used to check whether an object has been modified in the target storage
     /// it reduces the possibility of race condition issue
     #[arg(long, env, conflicts_with_all = ["enable_versioning"], default_value_t = DEFAULT_HEAD_EACH_TARGET)]
     head_each_target: bool,
 
+    /// retry HeadObject of the target object up to N times when it returns NotFound.
+    /// for eventually consistent S3-compatible storages
+    #[arg(long, env, default_value_t = DEFAULT_HEAD_NOT_FOUND_RETRIES, value_name = "N")]
+    head_not_found_retries: u32,
+
+    /// HeadObject is used for the objects that appear modified in the target listing or do not exist in it.
+    /// the objects that appear unchanged in the target listing are not checked again
+    #[arg(long, env, conflicts_with_all = ["head_each_target", "enable_versioning", "remove_modified_filter"], default_value_t = DEFAULT_TARGET_LIST_CACHE_INVALIDATE_ON_WRITE)]
+    target_list_cache_invalidate_on_write: bool,
+
+    /// with --target-list-cache-invalidate-on-write, the objects that do not exist in the target listing are synced without HeadObject.
+    /// an object created in the target by another process after the listing is overwritten without a check
+    #[arg(long, env, requires = "target_list_cache_invalidate_on_write", default_value_t = DEFAULT_SKIP_HEAD_FOR_UNLISTED_OBJECTS)]
+    skip_head_for_unlisted_objects: bool,
+
+    /// GetObject of the source object is sent with If-Modified-Since of the target object.
+    /// the object is skipped if the source responds with 304 Not Modified
+    #[arg(long, env, requires = "head_each_target", default_value_t = DEFAULT_SOURCE_IF_MODIFIED_SINCE)]
+    source_if_modified_since: bool,
+
+    /// skip the object if LastModified of the target object is strictly newer than the source object.
+    /// the clocks of the source and target systems should be synchronized
+    #[arg(long, env, requires = "head_each_target", default_value_t = DEFAULT_SKIP_IF_TARGET_NEWER)]
+    skip_if_target_newer: bool,
+
     /// sync all version objects in the source storage to the target versioning storage
     #[arg(long, env, conflicts_with_all = ["delete", "head_each_target", "remove_modified_filter"], default_value_t = DEFAULT_ENABLE_VERSIONING)]
     enable_versioning: bool,
 
+    /// with --enable-versioning, sync only the newest N versions of each object
+    #[arg(long, env, requires = "enable_versioning", value_parser = clap::value_parser!(u32).range(1..))]
+    versions_limit: Option<u32>,
+
+    /// with --enable-versioning, order in which versions of each object are synced.
+    /// valid choices: oldest-first | newest-first
+    #[arg(long, env, requires = "enable_versioning", default_value = DEFAULT_VERSIONS_ORDER, value_parser = versions_order::parse_versions_order)]
+    versions_order: String,
+
+    /// with --enable-versioning, delete the versions in the target storage whose source versions have been removed.
+    /// only the versions synced by s3sync are deleted. it is recommended to check with --dry-run first
+    #[arg(long, env, requires = "enable_versioning", conflicts_with_all = ["versions_limit", "filter_mtime_before", "filter_mtime_after", "filter_smaller_size", "filter_larger_size", "filter_owner"], default_value_t = DEFAULT_DELETE_REMOVED_VERSIONS)]
+    delete_removed_versions: bool,
+
     /// Cache-Control HTTP header to set on the target object
     #[arg(long, env)]
     cache_control: Option<String>,
@@ -272,6 +697,11 @@ pub struct CLIArgs {
     #[arg(long, env)]
     content_disposition: Option<String>,
 
+    /// Content-Disposition HTTP header to set on the target object, expanded per object.
+    /// {basename} and {key} are expanded to quoted strings. e.g. 'attachment; filename={basename}'
+    #[arg(long, env, conflicts_with = "content_disposition", value_parser = content_disposition_template::check_content_disposition_template)]
+    content_disposition_template: Option<String>,
+
     /// Content-Encoding HTTP header to set on the target object
     #[arg(long, env)]
     content_encoding: Option<String>,
@@ -292,10 +722,20 @@ pub struct CLIArgs {
     #[arg(long, env, value_parser = metadata::check_metadata)]
     metadata: Option<String>,
 
+    /// keep the metadata of the source object, and add the --metadata entries on top of it.
+    /// the --metadata values override the source values of the same keys(case-insensitive)
+    #[arg(long, env, default_value_t = DEFAULT_METADATA_MERGE, requires = "metadata")]
+    metadata_merge: bool,
+
     /// tagging to set on the target object. e.g. --tagging "key1=value1&key2=value2". must be encoded as UTF-8 then URLEncoded URL query parameters without tag name duplicates.
     #[arg(long, env, conflicts_with_all = ["disable_tagging", "sync_latest_tagging"], value_parser = tagging::parse_tagging)]
     tagging: Option<String>,
 
+    /// check the tags to be set on the target object against the S3 limits(10 tags, 128 characters key, 256 characters value).
+    /// warn: skip the object with a warning. error: fail the sync
+    #[arg(long, env, value_name = "warn|error", conflicts_with_all = ["disable_tagging"], value_parser = tag_guard_mode::parse_tag_guard_mode)]
+    object_tag_count_guard: Option<String>,
+
     /// sync only objects older than given time (RFC3339 datetime such as 2023-02-19T12:00:00Z)
     #[arg(long, env)]
     filter_mtime_before: Option<DateTime<Utc>>,
@@ -320,6 +760,29 @@ pub struct CLIArgs {
     #[arg(long, env, value_parser = human_bytes::check_human_bytes_without_limit)]
     filter_larger_size: Option<String>,
 
+    /// do not sync objects in GLACIER/DEEP_ARCHIVE storage class. they are counted as skipped
+    #[arg(long, env, default_value_t = DEFAULT_SKIP_GLACIER)]
+    skip_glacier: bool,
+
+    /// how to handle zero-byte source objects(including directory markers). sync | skip.
+    /// with skip, they are not transferred and counted as skipped
+    #[arg(long, env, value_name = "POLICY", default_value = DEFAULT_ZERO_BYTE_OBJECT_POLICY, value_parser = zero_byte_object_policy::parse_zero_byte_object_policy)]
+    zero_byte_object_policy: String,
+
+    /// report the restore status(in progress/restored with the expiry date) of the archived source objects.
+    /// the status is retrieved by the listing and written to the log and --integrity-report
+    #[arg(long, env, default_value_t = DEFAULT_SOURCE_LIST_INCLUDE_RESTORE_STATUS)]
+    source_list_include_restore_status: bool,
+
+    /// request the owner of the source objects in the listing(fetch-owner of ListObjectsV2).
+    /// the owner is always included in the listing of versions
+    #[arg(long, env, default_value_t = DEFAULT_SOURCE_LIST_INCLUDE_OWNER)]
+    source_list_include_owner: bool,
+
+    /// sync only objects owned by the given canonical user ID. requires --source-list-include-owner
+    #[arg(long, env, value_name = "CANONICAL_ID", requires = "source_list_include_owner", value_parser = NonEmptyStringValueParser::new())]
+    filter_owner: Option<String>,
+
     /// do not check(ListObjectsV2) for modification in the target storage
     #[arg(long, env, conflicts_with_all = ["enable_versioning"], default_value_t = DEFAULT_REMOVE_MODIFIED_FILTER)]
     remove_modified_filter: bool,
@@ -329,18 +792,45 @@ pub struct CLIArgs {
     check_size: bool,
 
     /// use etag for update checking
-    #[arg(long, env, conflicts_with_all = ["enable_versioning", "check_size", "source_sse_c_key", "target_sse_c_key"], default_value_t = DEFAULT_CHECK_ETAG)]
+    #[arg(long, env, conflicts_with_all = ["enable_versioning", "check_size", "source_sse_c_key", "target_sse_c_key", "source_sse_c_key_file", "target_sse_c_key_file"], default_value_t = DEFAULT_CHECK_ETAG)]
     check_etag: bool,
 
     /// use additional checksum for update checking
     #[arg(long, env, conflicts_with_all = ["enable_versioning", "check_size", "check_etag"], value_parser = checksum_algorithm::parse_checksum_algorithm)]
     check_additional_checksum: Option<String>,
 
+    /// use both etag and additional checksum for update checking. the object is transferred if either is different.
+    /// both are verified after transfer
+    #[arg(long, env, value_name = "ALGORITHM", conflicts_with_all = ["enable_versioning", "check_size", "check_etag", "check_additional_checksum", "disable_etag_verify", "source_sse_c_key", "target_sse_c_key", "source_sse_c_key_file", "target_sse_c_key_file"], value_parser = checksum_algorithm::parse_checksum_algorithm)]
+    check_etag_and_checksum: Option<String>,
+
     /// delete objects that exist in the target but not in the source.
     /// [Warning] Since this can cause data loss, test first with the --dry-run option
     #[arg(long, env, conflicts_with_all = ["enable_versioning"], default_value_t = DEFAULT_SYNC_WITH_DELETE)]
     delete: bool,
 
+    /// append every deleted object(key, version id and timestamp) to the file as JSON lines.
+    /// the entries of --dry-run are marked as such
+    #[arg(long, env, value_name = "FILE")]
+    delete_log: Option<PathBuf>,
+
+    /// list the source and the target, and write the keys that --delete would delete to the file as JSON lines.
+    /// nothing is transferred or deleted. review the file, then run with --delete-from-manifest
+    #[arg(
+        long,
+        env,
+        value_name = "FILE",
+        requires = "delete",
+        conflicts_with = "delete_from_manifest"
+    )]
+    emit_manifest_for_delete: Option<PathBuf>,
+
+    /// delete only the keys in the manifest written by --emit-manifest-for-delete.
+    /// a key that has appeared in the source again since the manifest was written is not deleted.
+    /// no object is transferred
+    #[arg(long, env, value_name = "FILE", requires = "delete")]
+    delete_from_manifest: Option<PathBuf>,
+
     /// do not copy tagging.
     #[arg(long, env, default_value_t = DEFAULT_DISABLE_TAGGING)]
     disable_tagging: bool,
@@ -358,43 +848,112 @@ pub struct CLIArgs {
     #[arg(long, env, value_parser = sse::parse_sse)]
     sse: Option<String>,
 
-    /// SSE KMS ID key
-    #[arg(long, env)]
+    /// SSE KMS ID key. key ID, key ARN, alias name(alias/my-key) and alias ARN are accepted.
+    /// only the syntax of an alias is checked. an alias that does not exist fails the first upload
+    #[arg(long, env, value_parser = sse::parse_sse_kms_key_id)]
     sse_kms_key_id: Option<String>,
 
+    /// after upload, verify that the target object has the server-side encryption(and the KMS key id) specified by --sse.
+    /// a mismatch is reported as a warning
+    #[arg(long, env, default_value_t = DEFAULT_VERIFY_TARGET_ENCRYPTION, requires = "sse")]
+    verify_target_encryption: bool,
+
+    /// propagate the S3 Bucket Key setting(BucketKeyEnabled) of the source object to the target object.
+    /// S3 Bucket Keys reduce the KMS requests of SSE-KMS
+    #[arg(long, env, conflicts_with_all = ["target_sse_c"], default_value_t = DEFAULT_TARGET_BUCKET_KEY_REUSE)]
+    target_bucket_key_reuse: bool,
+
     /// source SSE-C algorithm. valid choices: AES256
-    #[arg(long, env, conflicts_with_all = ["sse", "sse_kms_key_id"], requires = "source_sse_c_key", value_parser = sse::parse_sse_c)]
+    #[arg(long, env, conflicts_with_all = ["sse", "sse_kms_key_id"], requires = "source_sse_c_key_source", value_parser = sse::parse_sse_c)]
     source_sse_c: Option<String>,
 
     /// source SSE-C customer-provided encryption key(256bit key. must be base64 encoded)
-    #[arg(long, env, requires = "source_sse_c_key_md5")]
+    #[arg(long, env, group = "source_sse_c_key_source")]
     source_sse_c_key: Option<String>,
 
-    /// source base64 encoded MD5 digest of source_sse_c_key
+    /// source base64 encoded MD5 digest of source_sse_c_key.
+    /// if not specified, it is computed from source_sse_c_key
     #[arg(long, env, requires = "source_sse_c")]
     source_sse_c_key_md5: Option<String>,
 
+    /// file of source SSE-C customer-provided encryption key(256bit key. binary or base64 encoded).
+    /// the MD5 digest of the key is computed automatically
+    #[arg(
+        long,
+        env,
+        value_name = "FILE",
+        group = "source_sse_c_key_source",
+        conflicts_with = "source_sse_c_key_md5",
+        requires = "source_sse_c"
+    )]
+    source_sse_c_key_file: Option<String>,
+
+    /// for a source bucket where only some objects are SSE-C encrypted, send the source SSE-C parameters
+    /// only to the objects that require them. GetObject/HeadObject is retried with(or without) the SSE-C parameters
+    /// when S3 rejects them, so an extra request is sent whenever the encryption differs from the previous object
+    #[arg(long, env, default_value_t = DEFAULT_SOURCE_SSE_C_PROBE, requires = "source_sse_c")]
+    source_sse_c_probe: bool,
+
     /// target SSE-C algorithm. valid choices: AES256
-    #[arg(long, env, conflicts_with_all = ["sse", "sse_kms_key_id"], requires = "target_sse_c_key", value_parser = sse::parse_sse_c)]
+    #[arg(long, env, conflicts_with_all = ["sse", "sse_kms_key_id"], requires = "target_sse_c_key_source", value_parser = sse::parse_sse_c)]
     target_sse_c: Option<String>,
 
     /// target SSE-C customer-provided encryption key(256bit key. must be base64 encoded)
-    #[arg(long, env, requires = "target_sse_c_key_md5")]
+    #[arg(long, env, group = "target_sse_c_key_source")]
     target_sse_c_key: Option<String>,
 
-    /// target base64 encoded MD5 digest of source-sse-c-key
+    /// target base64 encoded MD5 digest of source-sse-c-key.
+    /// if not specified, it is computed from target_sse_c_key
     #[arg(long, env, requires = "target_sse_c")]
     target_sse_c_key_md5: Option<String>,
 
+    /// file of target SSE-C customer-provided encryption key(256bit key. binary or base64 encoded).
+    /// the MD5 digest of the key is computed automatically
+    #[arg(
+        long,
+        env,
+        value_name = "FILE",
+        group = "target_sse_c_key_source",
+        conflicts_with = "target_sse_c_key_md5",
+        requires = "target_sse_c"
+    )]
+    target_sse_c_key_file: Option<String>,
+
+    /// format of the SSE-C key files. valid choices: auto | binary | base64.
+    /// with auto, a 32 bytes file is treated as binary, otherwise as base64
+    #[arg(long, env, default_value = DEFAULT_SSE_C_KEY_FORMAT, value_parser = sse_c_key_file::parse_sse_c_key_format)]
+    sse_c_key_format: String,
+
     /// ACL for the objects
     /// valid choices: private | public-read | public-read-write | authenticated-read | aws-exec-read | bucket-owner-read | bucket-owner-full-control
     #[arg(long, env, value_parser = canned_acl::parse_canned_acl)]
     acl: Option<String>,
 
+    /// upload empty local files by a minimal PutObject without reading, hashing and verification.
+    /// mtime and metadata are still set
+    #[arg(long, env, default_value_t = DEFAULT_FAST_EMPTY_FILES)]
+    fast_empty_files: bool,
+
     /// do not try to guess the mime type of local file
     #[arg(long, env, default_value_t = DEFAULT_NO_GUESS_MIME_TYPE)]
     no_guess_mime_type: bool,
 
+    /// Content-Type by file extension for local files. it takes precedence over the guessed mime type,
+    /// but --content-type takes precedence over it. e.g. --content-type-override-map "wasm=application/wasm"
+    #[arg(long, env, value_name = "MAP", value_parser = content_type_map::check_content_type_map)]
+    content_type_override_map: Option<String>,
+
+    /// Content-Encoding by file extension for pre-compressed local files. e.g. --content-encoding-map "gz=gzip,br=br"
+    /// the Content-Type is guessed from the file name without the extension. --content-encoding takes precedence over it.
+    /// valid encodings: gzip | compress | deflate | br | zstd
+    #[arg(long, env, value_name = "MAP", value_parser = content_encoding_map::check_content_encoding_map)]
+    content_encoding_map: Option<String>,
+
+    /// name of the extended attribute that holds a precomputed MD5 digest of the local file(`<MD5>:<size>:<mtime>`). e.g. user.md5
+    /// used instead of hashing for ETag calculation of single part objects. Linux and macOS only
+    #[arg(long, env, value_name = "NAME", value_parser = NonEmptyStringValueParser::new())]
+    md5_xattr: Option<String>,
+
     /// disable multipart upload verification with ETag/additional checksum
     #[arg(long, env, default_value_t = DEFAULT_DISABLE_MULTIPART_VERIFY)]
     disable_multipart_verify: bool,
@@ -403,14 +962,115 @@ pub struct CLIArgs {
     #[arg(long, env, default_value_t = DEFAULT_DISABLE_ETAG_VERIFY)]
     disable_etag_verify: bool,
 
-    /// additional checksum algorithm for upload
-    #[arg(long, env, value_parser = checksum_algorithm::parse_checksum_algorithm)]
+    /// with --disable-etag-verify, verify only that the content length of the target object matches the source.
+    /// useful for S3-compatible storages that do not return reliable ETags
+    #[arg(long, env, default_value_t = DEFAULT_VERIFY_CONTENT_LENGTH_ONLY, requires = "disable_etag_verify")]
+    verify_content_length_only: bool,
+
+    /// do not send the Content-MD5 header with PutObject/UploadPart, and verify the ETag of the target object
+    /// with a follow-up HeadObject instead. for S3-compatible storages that reject Content-MD5
+    #[arg(long, env, default_value_t = DEFAULT_VERIFY_VIA_HEAD, conflicts_with = "disable_etag_verify")]
+    verify_via_head: bool,
+
+    /// verify the Content-Range and Content-Length of the GetObject responses from the source,
+    /// and that the source returns exactly the bytes of each part for a multipart upload.
+    /// for S3-compatible storages that return wrong bytes
+    #[arg(long, env, default_value_t = DEFAULT_VERIFY_RANGE_READS)]
+    verify_range_reads: bool,
+
+    /// additional checksum algorithm for upload.
+    /// with "negotiate", the strongest algorithm supported by the target is selected by probing at startup
+    #[arg(long, env, value_parser = checksum_algorithm::parse_additional_checksum_algorithm)]
     additional_checksum_algorithm: Option<String>,
 
     /// enable additional checksum for download
     #[arg(long, env, default_value_t = DEFAULT_ENABLE_ADDITIONAL_CHECKSUM)]
     enable_additional_checksum: bool,
 
+    /// verify additional checksums of only PERCENT% of objects. objects are sampled by the hash of the key,
+    /// so that re-runs verify the same objects. ETag verification is not affected
+    #[arg(long, env, value_name = "PERCENT", value_parser = clap::value_parser!(u8).range(1..=100))]
+    checksum_verify_sampling: Option<u8>,
+
+    /// log(info level) the ETag and additional checksum of each target object after verification.
+    /// with --json-tracing, each object is output as a JSON line
+    #[arg(long, env, default_value_t = DEFAULT_CHECKSUM_DISPLAY)]
+    checksum_display: bool,
+
+    /// if the verification of a downloaded local file fails, re-download the object once and verify it again.
+    /// if the verification fails again, the object is treated as an error
+    #[arg(long, env, default_value_t = DEFAULT_VERIFY_ONLY_ON_MISMATCH_REDOWNLOAD)]
+    verify_only_on_mismatch_redownload: bool,
+
+    /// download to `<key><SUFFIX>` in the target directory, and rename it to the final name after verification.
+    /// stale partial files created by s3sync are removed at start. e.g. --partial-suffix .partial
+    #[arg(long, env, value_name = "SUFFIX", value_parser = NonEmptyStringValueParser::new())]
+    partial_suffix: Option<String>,
+
+    /// with --partial-suffix, remove only the stale partial files not modified for this duration,
+    /// so that the partial files of another running s3sync are kept. Allow suffixes: s, m, h, d
+    #[arg(long, env, value_name = "DURATION", default_value = DEFAULT_PARTIAL_CLEANUP_OLDER_THAN, requires = "partial_suffix", value_parser = duration::check_duration)]
+    partial_cleanup_older_than: String,
+
+    /// preallocate the local file to the size of the source object before download.
+    /// it may reduce fragmentation and detect insufficient disk space early. Linux only
+    #[arg(long, env, default_value_t = DEFAULT_PREALLOCATE)]
+    preallocate: bool,
+
+    /// flush each downloaded file and its parent directory to the disk before it is reported as completed.
+    /// it makes the downloaded files durable against a power loss, but slows down the sync of many small files
+    #[arg(long, env, default_value_t = DEFAULT_FSYNC_ON_COMPLETE)]
+    fsync_on_complete: bool,
+
+    /// create temp files for download in the directory, and move them to the target directory after download.
+    /// if the directory is on another file system, the file is copied.
+    #[arg(long, env, conflicts_with = "partial_suffix", value_name = "PATH")]
+    local_tmp_dir: Option<PathBuf>,
+
+    /// before the sync, remove the stale temp files(`.s3sync-tmp-*`) left by a crashed run
+    /// in the target directory and --local-tmp-dir. with --dry-run, they are only reported
+    #[arg(long, env, default_value_t = DEFAULT_CLEANUP_TEMP_ON_START)]
+    cleanup_temp_on_start: bool,
+
+    /// with --cleanup-temp-on-start, remove only the temp files not modified for this duration,
+    /// so that the temp files of another running s3sync are kept. Allow suffixes: s, m, h, d
+    #[arg(long, env, value_name = "DURATION", default_value = DEFAULT_CLEANUP_TEMP_OLDER_THAN, requires = "cleanup_temp_on_start", value_parser = duration::check_duration)]
+    cleanup_temp_older_than: String,
+
+    /// if the path of an object conflicts with an existing path in the target directory
+    /// (e.g. a file `foo` exists for `foo/bar`), suffix the conflicting component with ` (n)`
+    #[arg(long, env, conflicts_with = "skip_on_conflict", default_value_t = DEFAULT_RENAME_ON_CONFLICT)]
+    rename_on_conflict: bool,
+
+    /// if the path of an object conflicts with an existing path in the target directory, skip the object with a warning
+    #[arg(long, env, default_value_t = DEFAULT_SKIP_ON_CONFLICT)]
+    skip_on_conflict: bool,
+
+    /// how to handle hard links of local files. Unix only.
+    /// copy: transfer each link as a separate object.
+    /// dedup-metadata: upload subsequent links as empty objects with a metadata pointer to the first key, and recreate hard links on download.
+    /// skip: skip subsequent links with a warning
+    #[arg(long, env, value_name = "MODE", default_value = DEFAULT_HARDLINK_MODE, value_parser = hardlink_mode::parse_hardlink_mode)]
+    hardlink_mode: String,
+
+    /// set the permission bits of the downloaded files(octal, e.g. 644). Unix only
+    #[arg(long, env, value_name = "MODE", conflicts_with = "preserve_file_mode", value_parser = file_mode::parse_file_mode)]
+    download_file_mode: Option<u32>,
+
+    /// set the permission bits of the downloaded files from the metadata put by --put-file-mode-metadata. Unix only.
+    /// the files without the metadata are created with the default permission
+    #[arg(long, env, default_value_t = DEFAULT_PRESERVE_FILE_MODE)]
+    preserve_file_mode: bool,
+
+    /// cache verified additional checksums of local files in an extended attribute,
+    /// and skip recomputing them while the size and mtime of the file are unchanged
+    #[arg(long, env, default_value_t = DEFAULT_CHECKSUM_ON_DOWNLOAD_ONLY_CHANGED)]
+    checksum_on_download_only_changed: bool,
+
+    /// with --checksum-on-download-only-changed, ignore the cached checksums and recompute them
+    #[arg(long, env, requires = "checksum_on_download_only_changed", default_value_t = DEFAULT_FORCE_REVERIFY)]
+    force_reverify: bool,
+
     /// A simulation mode. no actions will be performed
     #[arg(long, env, default_value_t = DEFAULT_DRY_RUN)]
     dry_run: bool,
@@ -423,6 +1083,31 @@ pub struct CLIArgs {
     #[arg(long, env, value_parser = human_bytes::check_human_bandwidth)]
     rate_limit_bandwidth: Option<String>,
 
+    /// rate limit of all S3 API requests to the source(requests per second), including the list, head and tagging requests.
+    /// independent of --rate-limit-objects
+    #[arg(long, env, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    source_request_rate_limit: Option<u32>,
+
+    /// rate limit of all S3 API requests to the target(requests per second), including the multipart upload requests.
+    /// independent of --rate-limit-objects
+    #[arg(long, env, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    target_request_rate_limit: Option<u32>,
+
+    /// maximum rate of the listing requests(ListObjectsV2/ListObjectVersions) to each bucket(requests per second).
+    /// the rate is halved on a throttling response(e.g. 503 Slow Down) and the request is retried,
+    /// then increased gradually up to the maximum. shared by the parallel listing workers
+    #[arg(long, env, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    list_max_rps: Option<u32>,
+
+    /// operation timeout of PutObject/UploadPart scaled with the size:
+    /// SECONDS + size / --per-object-timeout-min-throughput. without this option, no operation timeout is set
+    #[arg(long, env, value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..))]
+    per_object_timeout_base: Option<u64>,
+
+    /// expected minimum throughput(bytes per sec) for --per-object-timeout-base. Allow suffixes: MB, MiB, GB, GiB
+    #[arg(long, env, requires = "per_object_timeout_base", default_value = DEFAULT_PER_OBJECT_TIMEOUT_MIN_THROUGHPUT, value_parser = human_bytes::check_human_bandwidth)]
+    per_object_timeout_min_throughput: String,
+
     /// [dangerous] disable to verify SSL certificates.
     #[arg(long, env, conflicts_with_all = ["https_proxy", "http_proxy"], default_value_t = DEFAULT_NO_VERIFY_SSL)]
     no_verify_ssl: bool,
@@ -431,10 +1116,84 @@ pub struct CLIArgs {
     #[arg(long, env, default_value_t = DEFAULT_MAX_KEYS, value_parser = clap::value_parser!(i32).range(1..=32767))]
     max_keys: i32,
 
+    /// list only the objects directly under the prefix(not recursive).
+    /// with --delete, only the objects directly under the target prefix are deleted
+    #[arg(long, env, default_value_t = DEFAULT_NON_RECURSIVE, conflicts_with_all = ["list_parallel_prefix_file", "upload_manifest", "source_versions_at"])]
+    non_recursive: bool,
+
+    /// file of first-level prefixes(one per line) of the source to list in parallel.
+    /// objects outside of these prefixes are still listed
+    #[arg(long, env, value_name = "FILE", conflicts_with = "enable_versioning", value_parser = prefix_file::check_prefix_file)]
+    list_parallel_prefix_file: Option<String>,
+
+    /// read the prefixes(one per line) of the source to sync from stdin, and list only the objects under them.
+    /// each prefix is relative to the source prefix. for a local source, each line is a file or a directory
+    #[arg(long, env, default_value_t = DEFAULT_INCLUDE_ONLY_PREFIXES_FROM_STDIN, conflicts_with_all = ["enable_versioning", "delete", "non_recursive", "list_parallel_prefix_file", "source_list_cache", "upload_manifest", "source_versions_at"])]
+    include_only_prefixes_from_stdin: bool,
+
+    /// cache the source listing in the file, and reuse it instead of listing the source
+    /// while the file is newer than --list-cache-max-age.
+    /// [Warning] objects changed in the source after the cache was written are not detected
+    #[arg(long, env, value_name = "FILE", conflicts_with_all = ["enable_versioning", "source_versions_at", "source_list_include_restore_status", "source_list_include_owner"])]
+    source_list_cache: Option<PathBuf>,
+
+    /// maximum age of the --source-list-cache file to reuse it. an older cache is rewritten by listing the source.
+    /// Allow suffixes: s, m, h, d (e.g. 30m)
+    #[arg(long, env, value_name = "DURATION", default_value = DEFAULT_LIST_CACHE_MAX_AGE, requires = "source_list_cache", value_parser = duration::check_duration)]
+    list_cache_max_age: String,
+
+    /// CSV file of `local_path,target_key` rows. upload exactly those files to those keys instead of walking the source directory.
+    /// relative local paths are relative to the source directory
+    #[arg(long, env, value_name = "CSV", conflicts_with_all = ["enable_versioning", "delete"], value_parser = upload_manifest::check_upload_manifest)]
+    upload_manifest: Option<String>,
+
+    /// CSV file of `key,version_id` rows. sync exactly those versions of the source objects instead of listing the source.
+    /// keys are relative to the source prefix. the source bucket must have versioning enabled
+    #[arg(long, env, value_name = "VERSION_ID_MAP_FILE", conflicts_with_all = ["enable_versioning", "delete", "list_parallel_prefix_file", "start_after", "stop_at", "source_if_modified_since", "check_additional_checksum", "check_etag_and_checksum"], value_parser = source_version_map::check_source_version_map)]
+    source_versions_at: Option<String>,
+
+    /// run the S3 Select SQL expression on each source object, and write the result to the target instead of the object body.
+    /// it changes the content of the objects. ETag/checksum verification against the source is disabled for them.
+    /// e.g. "SELECT * FROM S3Object s WHERE s.status = 'active'"
+    #[cfg(feature = "s3_select")]
+    #[arg(long, env, value_name = "SQL", conflicts_with_all = ["enable_versioning", "source_versions_at", "source_if_modified_since", "check_etag", "check_additional_checksum", "check_etag_and_checksum"], value_parser = NonEmptyStringValueParser::new())]
+    source_select_expression: Option<String>,
+
+    /// format of the source objects for --source-select-expression. csv requires a header line. csv | json(JSON Lines) | parquet
+    #[cfg(feature = "s3_select")]
+    #[arg(long, env, value_name = "FORMAT", default_value = DEFAULT_SOURCE_SELECT_INPUT_FORMAT, value_parser = select_format::parse_select_input_format)]
+    source_select_input_format: String,
+
+    /// format of the result of --source-select-expression. csv | json(JSON Lines)
+    #[cfg(feature = "s3_select")]
+    #[arg(long, env, value_name = "FORMAT", default_value = DEFAULT_SOURCE_SELECT_OUTPUT_FORMAT, value_parser = select_format::parse_select_output_format)]
+    source_select_output_format: String,
+
+    /// list only keys after this key(relative to the prefix) in S3 listing. e.g. to shard a bucket by key range
+    #[arg(long, env, value_name = "KEY", conflicts_with_all = ["enable_versioning", "delete"], value_parser = NonEmptyStringValueParser::new())]
+    start_after: Option<String>,
+
+    /// list only keys up to and including this key(relative to the prefix) in S3 listing
+    #[arg(long, env, value_name = "KEY", conflicts_with_all = ["enable_versioning", "delete"], value_parser = NonEmptyStringValueParser::new())]
+    stop_at: Option<String>,
+
     /// put last modified of the source to metadata
     #[arg(long, env, default_value_t = DEFAULT_PUT_LAST_MODIFIED_METADATA)]
     put_last_modified_metadata: bool,
 
+    /// put the permission bits of the source file to metadata, to be restored by --preserve-file-mode. Unix only
+    #[arg(long, env, default_value_t = DEFAULT_PUT_FILE_MODE_METADATA)]
+    put_file_mode_metadata: bool,
+
+    /// instead of synchronization, abort incomplete multipart uploads in the target bucket/prefix
+    #[arg(long, env, default_value_t = DEFAULT_ABORT_INCOMPLETE_MULTIPART_UPLOADS)]
+    abort_incomplete_multipart_uploads: bool,
+
+    /// with --abort-incomplete-multipart-uploads, abort only uploads initiated before this duration ago.
+    /// Allow suffixes: s, m, h, d (e.g. 7d)
+    #[arg(long, env, value_name = "DURATION", requires = "abort_incomplete_multipart_uploads", value_parser = duration::check_duration)]
+    abort_incomplete_multipart_uploads_older_than: Option<String>,
+
     /// unit test purpose only
     #[arg(long, hide = true, default_value_t = false)]
     allow_both_local_storage: bool,
@@ -443,10 +1202,18 @@ pub struct CLIArgs {
     #[arg(long, env, value_name = "SHELL", value_parser = clap_complete::shells::Shell::from_str)]
     auto_complete_shell: Option<clap_complete::shells::Shell>,
 
+    /// print the resolved configuration as JSON and exit. the credentials and the SSE keys are redacted
+    #[arg(long, env, default_value_t = DEFAULT_DUMP_CONFIG)]
+    dump_config: bool,
+
     /// disable stalled stream protection
     #[arg(long, env, default_value_t = DEFAULT_DISABLE_STALLED_STREAM_PROTECTION)]
     disable_stalled_stream_protection: bool,
 
+    /// append the token to the User-Agent of the requests(as the app name of AWS SDK). e.g. my-batch-job
+    #[arg(long, env, value_name = "STR", value_parser = user_agent::check_user_agent_suffix)]
+    user_agent_suffix: Option<String>,
+
     /// disable payload signing for object uploads
     #[arg(long, env, default_value_t = DEFAULT_DISABLE_PAYLOAD_SIGNING)]
     disable_payload_signing: bool,
@@ -484,13 +1251,51 @@ impl CLIArgs {
         self.check_enable_additional_checksum_conflict()?;
         self.check_additional_checksum_algorithm_conflict()?;
         self.check_auto_chunksize_conflict()?;
+        self.check_chunk_size_from_target_conflict()?;
+        self.check_target_bucket_key_reuse_conflict()?;
         self.check_metadata_conflict()?;
         self.check_check_size_conflict()?;
         self.check_check_e_tag_conflict()?;
+        self.check_check_e_tag_and_checksum_conflict()?;
+        self.check_skip_glacier_conflict()?;
+        self.check_verify_range_reads_conflict()?;
+        self.check_source_list_include_restore_status_conflict()?;
+        self.check_source_list_include_owner_conflict()?;
+        self.check_list_parallel_prefix_file_conflict()?;
+        self.check_source_list_cache_conflict()?;
+        self.check_upload_manifest_conflict()?;
+        self.check_source_versions_at_conflict()?;
+        #[cfg(feature = "s3_select")]
+        self.check_source_select_expression_conflict()?;
+        self.check_key_range_conflict()?;
         self.check_ignore_symlinks_conflict()?;
+        self.check_fast_empty_files_conflict()?;
         self.check_no_guess_mime_type_conflict()?;
+        self.check_content_type_override_map_conflict()?;
+        self.check_content_encoding_map_conflict()?;
+        self.check_md5_xattr_conflict()?;
+        self.check_checksum_on_download_only_changed_conflict()?;
+        self.check_partial_suffix_conflict()?;
+        self.check_verify_only_on_mismatch_redownload_conflict()?;
+        self.check_preallocate_conflict()?;
+        self.check_fsync_on_complete_conflict()?;
+        self.check_local_tmp_dir_conflict()?;
+        self.check_cleanup_temp_on_start_conflict()?;
+        self.check_rename_or_skip_on_conflict_conflict()?;
+        self.check_hardlink_mode_conflict()?;
+        self.check_file_mode_conflict()?;
         self.check_endpoint_url_conflict()?;
+        self.check_source_region_auto_detect_conflict()?;
+        self.check_per_object_timeout_base_conflict()?;
+        self.check_expected_bucket_owner_conflict()?;
+        self.check_require_no_default_retention_conflict()?;
         self.check_disable_payload_signing_conflict()?;
+        self.check_abort_incomplete_multipart_uploads_conflict()?;
+        self.check_download_to_stdout_conflict()?;
+        self.check_checksum_verify_sampling_conflict()?;
+        self.check_source_if_modified_since_conflict()?;
+        self.check_target_list_cache_invalidate_on_write_conflict()?;
+        self.check_normalize_unicode_conflict()?;
 
         Ok(())
     }
@@ -571,13 +1376,18 @@ impl CLIArgs {
         let target = storage_path::parse_storage_path(&self.target);
 
         if matches!(source, StoragePath::Local(_))
-            && (self.source_profile.is_some() || self.source_access_key.is_some())
+            && (self.source_profile.is_some()
+                || self.source_access_key.is_some()
+                || self.source_anonymous)
         {
             return Err(NO_SOURCE_CREDENTIAL_REQUIRED.to_string());
         }
 
         if matches!(target, StoragePath::Local(_))
-            && (self.target_profile.is_some() || self.target_access_key.is_some())
+            && (self.target_profile.is_some()
+                || self.target_access_key.is_some()
+                || self.target_anonymous
+                || self.target_web_identity_token_file.is_some())
         {
             return Err(NO_TARGET_CREDENTIAL_REQUIRED.to_string());
         }
@@ -676,22 +1486,65 @@ impl CLIArgs {
 
         let source = storage_path::parse_storage_path(&self.source);
 
-        if !self.check_etag && matches!(source, StoragePath::Local(_)) {
+        if !self.check_etag
+            && self.check_etag_and_checksum.is_none()
+            && matches!(source, StoragePath::Local(_))
+        {
             return Err(SOURCE_LOCAL_STORAGE_SPECIFIED_WITH_AUTO_CHUNKSIZE.to_string());
         }
 
         Ok(())
     }
 
+    fn check_chunk_size_from_target_conflict(&self) -> Result<(), String> {
+        if !self.chunk_size_from_target {
+            return Ok(());
+        }
+
+        let target = storage_path::parse_storage_path(&self.target);
+        if matches!(target, StoragePath::Local(_)) {
+            return Err(TARGET_LOCAL_STORAGE_SPECIFIED_WITH_CHUNK_SIZE_FROM_TARGET.to_string());
+        }
+
+        Ok(())
+    }
+
+    fn check_target_bucket_key_reuse_conflict(&self) -> Result<(), String> {
+        if !self.target_bucket_key_reuse {
+            return Ok(());
+        }
+
+        let source = storage_path::parse_storage_path(&self.source);
+        if matches!(source, StoragePath::Local(_)) {
+            return Err(SOURCE_LOCAL_STORAGE_SPECIFIED_WITH_TARGET_BUCKET_KEY_REUSE.to_string());
+        }
+
+        let target = storage_path::parse_storage_path(&self.target);
+        if matches!(target, StoragePath::Local(_)) {
+            return Err(TARGET_LOCAL_STORAGE_SPECIFIED_WITH_TARGET_BUCKET_KEY_REUSE.to_string());
+        }
+
+        if self.sse.is_some()
+            && ServerSideEncryption::from_str(self.sse.as_ref().unwrap()).unwrap()
+                != ServerSideEncryption::AwsKms
+        {
+            return Err(TARGET_BUCKET_KEY_REUSE_CONFLICT_SSE.to_string());
+        }
+
+        Ok(())
+    }
+
     fn check_metadata_conflict(&self) -> Result<(), String> {
         if self.cache_control.is_none()
             && self.content_disposition.is_none()
+            && self.content_disposition_template.is_none()
             && self.content_encoding.is_none()
             && self.content_language.is_none()
             && self.content_type.is_none()
             && self.expires.is_none()
             && self.tagging.is_none()
             && !self.put_last_modified_metadata
+            && !self.put_file_mode_metadata
         {
             return Ok(());
         }
@@ -742,6 +1595,399 @@ impl CLIArgs {
         Ok(())
     }
 
+    fn check_check_e_tag_and_checksum_conflict(&self) -> Result<(), String> {
+        if self.check_etag_and_checksum.is_none() {
+            return Ok(());
+        }
+
+        if self.sse.is_some()
+            && ServerSideEncryption::from_str(self.sse.as_ref().unwrap()).unwrap()
+                == ServerSideEncryption::AwsKms
+        {
+            return Err(CHECK_ETAG_AND_CHECKSUM_CONFLICT_SSE_KMS.to_string());
+        }
+
+        let source = storage_path::parse_storage_path(&self.source);
+        if let StoragePath::S3 { bucket, .. } = &source {
+            if is_express_onezone_storage(bucket) {
+                return Err(CHECK_ETAG_AND_CHECKSUM_NOT_SUPPORTED_WITH_EXPRESS_ONEZONE.to_string());
+            }
+
+            // the additional checksum of the source object is needed to verify it after transfer.
+            if !self.enable_additional_checksum {
+                return Err(CHECK_ETAG_AND_CHECKSUM_WITHOUT_ENABLE_ADDITIONAL_CHECKSUM.to_string());
+            }
+        }
+
+        let target = storage_path::parse_storage_path(&self.target);
+        if let StoragePath::S3 { bucket, .. } = &target {
+            if is_express_onezone_storage(bucket) {
+                return Err(CHECK_ETAG_AND_CHECKSUM_NOT_SUPPORTED_WITH_EXPRESS_ONEZONE.to_string());
+            }
+
+            // the target object must have the same additional checksum to be compared in the next run.
+            if self.additional_checksum_algorithm != self.check_etag_and_checksum {
+                return Err(
+                    CHECK_ETAG_AND_CHECKSUM_WITHOUT_ADDITIONAL_CHECKSUM_ALGORITHM.to_string(),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_verify_range_reads_conflict(&self) -> Result<(), String> {
+        if !self.verify_range_reads {
+            return Ok(());
+        }
+
+        let source = storage_path::parse_storage_path(&self.source);
+        if matches!(source, StoragePath::Local(_)) {
+            return Err(SOURCE_LOCAL_STORAGE_SPECIFIED_WITH_VERIFY_RANGE_READS.to_string());
+        }
+
+        Ok(())
+    }
+
+    fn check_skip_glacier_conflict(&self) -> Result<(), String> {
+        if !self.skip_glacier {
+            return Ok(());
+        }
+
+        let source = storage_path::parse_storage_path(&self.source);
+        if matches!(source, StoragePath::Local(_)) {
+            return Err(SOURCE_LOCAL_STORAGE_SPECIFIED_WITH_SKIP_GLACIER.to_string());
+        }
+
+        Ok(())
+    }
+
+    fn check_source_list_include_restore_status_conflict(&self) -> Result<(), String> {
+        if !self.source_list_include_restore_status {
+            return Ok(());
+        }
+
+        let source = storage_path::parse_storage_path(&self.source);
+        if matches!(source, StoragePath::Local(_)) {
+            return Err(
+                SOURCE_LOCAL_STORAGE_SPECIFIED_WITH_SOURCE_LIST_INCLUDE_RESTORE_STATUS.to_string(),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn check_source_list_include_owner_conflict(&self) -> Result<(), String> {
+        if !self.source_list_include_owner {
+            return Ok(());
+        }
+
+        let source = storage_path::parse_storage_path(&self.source);
+        if matches!(source, StoragePath::Local(_)) {
+            return Err(SOURCE_LOCAL_STORAGE_SPECIFIED_WITH_SOURCE_LIST_INCLUDE_OWNER.to_string());
+        }
+
+        Ok(())
+    }
+
+    fn check_list_parallel_prefix_file_conflict(&self) -> Result<(), String> {
+        if self.list_parallel_prefix_file.is_none() {
+            return Ok(());
+        }
+
+        let source = storage_path::parse_storage_path(&self.source);
+        if matches!(source, StoragePath::Local(_)) {
+            return Err(SOURCE_LOCAL_STORAGE_SPECIFIED_WITH_LIST_PARALLEL_PREFIX_FILE.to_string());
+        }
+
+        Ok(())
+    }
+
+    fn check_source_list_cache_conflict(&self) -> Result<(), String> {
+        if self.source_list_cache.is_none() {
+            return Ok(());
+        }
+
+        let source = storage_path::parse_storage_path(&self.source);
+        if matches!(source, StoragePath::Local(_)) {
+            return Err(SOURCE_LOCAL_STORAGE_SPECIFIED_WITH_SOURCE_LIST_CACHE.to_string());
+        }
+
+        Ok(())
+    }
+
+    fn check_upload_manifest_conflict(&self) -> Result<(), String> {
+        if self.upload_manifest.is_none() {
+            return Ok(());
+        }
+
+        let source = storage_path::parse_storage_path(&self.source);
+        if matches!(source, StoragePath::S3 { .. }) {
+            return Err(SOURCE_REMOTE_STORAGE_SPECIFIED_WITH_UPLOAD_MANIFEST.to_string());
+        }
+
+        Ok(())
+    }
+
+    fn check_source_versions_at_conflict(&self) -> Result<(), String> {
+        if self.source_versions_at.is_none() {
+            return Ok(());
+        }
+
+        let source = storage_path::parse_storage_path(&self.source);
+        if matches!(source, StoragePath::Local(_)) {
+            return Err(SOURCE_LOCAL_STORAGE_SPECIFIED_WITH_SOURCE_VERSIONS_AT.to_string());
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "s3_select")]
+    fn check_source_select_expression_conflict(&self) -> Result<(), String> {
+        if self.source_select_expression.is_none() {
+            return Ok(());
+        }
+
+        let source = storage_path::parse_storage_path(&self.source);
+        if matches!(source, StoragePath::Local(_)) {
+            return Err(SOURCE_LOCAL_STORAGE_SPECIFIED_WITH_SOURCE_SELECT_EXPRESSION.to_string());
+        }
+
+        Ok(())
+    }
+
+    fn check_rename_or_skip_on_conflict_conflict(&self) -> Result<(), String> {
+        if !self.rename_on_conflict && !self.skip_on_conflict {
+            return Ok(());
+        }
+
+        let target = storage_path::parse_storage_path(&self.target);
+        if matches!(target, StoragePath::S3 { .. }) {
+            return Err(TARGET_REMOTE_STORAGE_SPECIFIED_WITH_ON_CONFLICT.to_string());
+        }
+
+        Ok(())
+    }
+
+    fn check_hardlink_mode_conflict(&self) -> Result<(), String> {
+        let hardlink_mode = hardlink_mode::to_hardlink_mode(&self.hardlink_mode);
+        if hardlink_mode == HardlinkMode::Copy {
+            return Ok(());
+        }
+
+        if !cfg!(unix) {
+            return Err(HARDLINK_MODE_NOT_SUPPORTED.to_string());
+        }
+
+        let source = storage_path::parse_storage_path(&self.source);
+        let target = storage_path::parse_storage_path(&self.target);
+        if !matches!(source, StoragePath::Local(_)) && !matches!(target, StoragePath::Local(_)) {
+            return Err(HARDLINK_MODE_WITHOUT_LOCAL_STORAGE.to_string());
+        }
+
+        if hardlink_mode == HardlinkMode::Skip && !matches!(source, StoragePath::Local(_)) {
+            return Err(HARDLINK_MODE_SKIP_WITH_REMOTE_SOURCE.to_string());
+        }
+
+        // the objects of the subsequent links are empty, so their contents can not be compared.
+        if hardlink_mode == HardlinkMode::DedupMetadata
+            && (self.check_etag
+                || self.check_additional_checksum.is_some()
+                || self.check_etag_and_checksum.is_some())
+        {
+            return Err(HARDLINK_MODE_DEDUP_METADATA_WITH_CONTENT_CHECK.to_string());
+        }
+
+        Ok(())
+    }
+
+    fn check_file_mode_conflict(&self) -> Result<(), String> {
+        let download_file_mode = self.download_file_mode.is_some() || self.preserve_file_mode;
+        if !download_file_mode && !self.put_file_mode_metadata {
+            return Ok(());
+        }
+
+        if !cfg!(unix) {
+            return Err(FILE_MODE_NOT_SUPPORTED.to_string());
+        }
+
+        let target = storage_path::parse_storage_path(&self.target);
+        if download_file_mode && !matches!(target, StoragePath::Local(_)) {
+            return Err(TARGET_REMOTE_STORAGE_SPECIFIED_WITH_FILE_MODE.to_string());
+        }
+
+        let source = storage_path::parse_storage_path(&self.source);
+        if self.put_file_mode_metadata && !matches!(source, StoragePath::Local(_)) {
+            return Err(SOURCE_REMOTE_STORAGE_SPECIFIED_WITH_PUT_FILE_MODE_METADATA.to_string());
+        }
+
+        Ok(())
+    }
+
+    fn check_key_range_conflict(&self) -> Result<(), String> {
+        if self.start_after.is_none() && self.stop_at.is_none() {
+            return Ok(());
+        }
+
+        let source = storage_path::parse_storage_path(&self.source);
+        if matches!(source, StoragePath::Local(_)) {
+            return Err(SOURCE_LOCAL_STORAGE_SPECIFIED_WITH_KEY_RANGE.to_string());
+        }
+
+        if let (Some(start_after), Some(stop_at)) = (&self.start_after, &self.stop_at) {
+            if stop_at <= start_after {
+                return Err(INVALID_KEY_RANGE.to_string());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_partial_suffix_conflict(&self) -> Result<(), String> {
+        if self.partial_suffix.is_none() {
+            return Ok(());
+        }
+
+        let target = storage_path::parse_storage_path(&self.target);
+        if matches!(target, StoragePath::S3 { .. }) {
+            return Err(TARGET_REMOTE_STORAGE_SPECIFIED_WITH_PARTIAL_SUFFIX.to_string());
+        }
+
+        Ok(())
+    }
+
+    fn check_verify_only_on_mismatch_redownload_conflict(&self) -> Result<(), String> {
+        if !self.verify_only_on_mismatch_redownload {
+            return Ok(());
+        }
+
+        let target = storage_path::parse_storage_path(&self.target);
+        if matches!(target, StoragePath::S3 { .. }) {
+            return Err(
+                TARGET_REMOTE_STORAGE_SPECIFIED_WITH_VERIFY_ONLY_ON_MISMATCH_REDOWNLOAD.to_string(),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn check_preallocate_conflict(&self) -> Result<(), String> {
+        if !self.preallocate {
+            return Ok(());
+        }
+
+        let target = storage_path::parse_storage_path(&self.target);
+        if matches!(target, StoragePath::S3 { .. }) {
+            return Err(TARGET_REMOTE_STORAGE_SPECIFIED_WITH_PREALLOCATE.to_string());
+        }
+
+        Ok(())
+    }
+
+    fn check_fsync_on_complete_conflict(&self) -> Result<(), String> {
+        if !self.fsync_on_complete {
+            return Ok(());
+        }
+
+        let target = storage_path::parse_storage_path(&self.target);
+        if matches!(target, StoragePath::S3 { .. }) {
+            return Err(TARGET_REMOTE_STORAGE_SPECIFIED_WITH_FSYNC_ON_COMPLETE.to_string());
+        }
+
+        Ok(())
+    }
+
+    fn check_local_tmp_dir_conflict(&self) -> Result<(), String> {
+        let Some(local_tmp_dir) = &self.local_tmp_dir else {
+            return Ok(());
+        };
+
+        let target = storage_path::parse_storage_path(&self.target);
+        if matches!(target, StoragePath::S3 { .. }) {
+            return Err(TARGET_REMOTE_STORAGE_SPECIFIED_WITH_LOCAL_TMP_DIR.to_string());
+        }
+
+        if tempfile::tempfile_in(local_tmp_dir).is_err() {
+            return Err(LOCAL_TMP_DIR_NOT_WRITABLE.to_string());
+        }
+
+        Ok(())
+    }
+
+    fn check_cleanup_temp_on_start_conflict(&self) -> Result<(), String> {
+        if !self.cleanup_temp_on_start {
+            return Ok(());
+        }
+
+        let target = storage_path::parse_storage_path(&self.target);
+        if matches!(target, StoragePath::S3 { .. }) {
+            return Err(TARGET_REMOTE_STORAGE_SPECIFIED_WITH_CLEANUP_TEMP_ON_START.to_string());
+        }
+
+        Ok(())
+    }
+
+    fn check_checksum_on_download_only_changed_conflict(&self) -> Result<(), String> {
+        if !self.checksum_on_download_only_changed {
+            return Ok(());
+        }
+
+        let target = storage_path::parse_storage_path(&self.target);
+        if matches!(target, StoragePath::S3 { .. }) {
+            return Err(
+                TARGET_REMOTE_STORAGE_SPECIFIED_WITH_CHECKSUM_ON_DOWNLOAD_ONLY_CHANGED.to_string(),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn check_abort_incomplete_multipart_uploads_conflict(&self) -> Result<(), String> {
+        if !self.abort_incomplete_multipart_uploads {
+            return Ok(());
+        }
+
+        let target = storage_path::parse_storage_path(&self.target);
+        if matches!(target, StoragePath::Local(_)) {
+            return Err(
+                TARGET_LOCAL_STORAGE_SPECIFIED_WITH_ABORT_INCOMPLETE_MULTIPART_UPLOADS.to_string(),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn check_download_to_stdout_conflict(&self) -> Result<(), String> {
+        if self.target != storage_path::STDOUT_PATH {
+            return Ok(());
+        }
+
+        let source = storage_path::parse_storage_path(&self.source);
+        let StoragePath::S3 { prefix, .. } = source else {
+            return Err(TARGET_STDOUT_SPECIFIED_WITHOUT_SOURCE_OBJECT.to_string());
+        };
+        if prefix.is_empty() || prefix.ends_with('/') {
+            return Err(TARGET_STDOUT_SPECIFIED_WITHOUT_SOURCE_OBJECT.to_string());
+        }
+
+        if self.delete || self.enable_versioning || self.sync_latest_tagging || self.dry_run {
+            return Err(TARGET_STDOUT_SPECIFIED_WITH_SYNC_OPTION.to_string());
+        }
+
+        Ok(())
+    }
+
+    fn check_checksum_verify_sampling_conflict(&self) -> Result<(), String> {
+        if self.checksum_verify_sampling.is_some()
+            && !self.enable_additional_checksum
+            && self.additional_checksum_algorithm.is_none()
+        {
+            return Err(CHECKSUM_VERIFY_SAMPLING_WITHOUT_ADDITIONAL_CHECKSUM.to_string());
+        }
+
+        Ok(())
+    }
+
     fn check_ignore_symlinks_conflict(&self) -> Result<(), String> {
         if !self.ignore_symlinks {
             return Ok(());
@@ -755,6 +2001,19 @@ impl CLIArgs {
         Ok(())
     }
 
+    fn check_fast_empty_files_conflict(&self) -> Result<(), String> {
+        if !self.fast_empty_files {
+            return Ok(());
+        }
+
+        let source = storage_path::parse_storage_path(&self.source);
+        if matches!(source, StoragePath::S3 { .. }) {
+            return Err(SOURCE_REMOTE_STORAGE_SPECIFIED_WITH_FAST_EMPTY_FILES.to_string());
+        }
+
+        Ok(())
+    }
+
     fn check_no_guess_mime_type_conflict(&self) -> Result<(), String> {
         if !self.no_guess_mime_type {
             return Ok(());
@@ -768,6 +2027,71 @@ impl CLIArgs {
         Ok(())
     }
 
+    fn check_content_type_override_map_conflict(&self) -> Result<(), String> {
+        if self.content_type_override_map.is_none() {
+            return Ok(());
+        }
+
+        let source = storage_path::parse_storage_path(&self.source);
+        if matches!(source, StoragePath::S3 { .. }) {
+            return Err(SOURCE_REMOTE_STORAGE_SPECIFIED_WITH_CONTENT_TYPE_OVERRIDE_MAP.to_string());
+        }
+
+        Ok(())
+    }
+
+    fn check_content_encoding_map_conflict(&self) -> Result<(), String> {
+        if self.content_encoding_map.is_none() {
+            return Ok(());
+        }
+
+        let source = storage_path::parse_storage_path(&self.source);
+        if matches!(source, StoragePath::S3 { .. }) {
+            return Err(SOURCE_REMOTE_STORAGE_SPECIFIED_WITH_CONTENT_ENCODING_MAP.to_string());
+        }
+
+        Ok(())
+    }
+
+    fn check_md5_xattr_conflict(&self) -> Result<(), String> {
+        if self.md5_xattr.is_none() {
+            return Ok(());
+        }
+
+        let source = storage_path::parse_storage_path(&self.source);
+        if matches!(source, StoragePath::S3 { .. }) {
+            return Err(SOURCE_REMOTE_STORAGE_SPECIFIED_WITH_MD5_XATTR.to_string());
+        }
+
+        Ok(())
+    }
+
+    fn check_per_object_timeout_base_conflict(&self) -> Result<(), String> {
+        if self.per_object_timeout_base.is_none() {
+            return Ok(());
+        }
+
+        let target = storage_path::parse_storage_path(&self.target);
+        if matches!(target, StoragePath::Local(_)) {
+            return Err(TARGET_LOCAL_STORAGE_SPECIFIED_WITH_PER_OBJECT_TIMEOUT_BASE.to_string());
+        }
+
+        Ok(())
+    }
+
+    fn check_source_region_auto_detect_conflict(&self) -> Result<(), String> {
+        if !self.source_region_auto_detect {
+            return Ok(());
+        }
+
+        let source = storage_path::parse_storage_path(&self.source);
+        if matches!(source, StoragePath::Local(_)) {
+            return Err(SOURCE_LOCAL_STORAGE_SPECIFIED_WITH_SOURCE_REGION_AUTO_DETECT.to_string());
+        }
+
+        Ok(())
+    }
+
     fn check_endpoint_url_conflict(&self) -> Result<(), String> {
         let source = storage_path::parse_storage_path(&self.source);
         if matches!(source, StoragePath::Local(_)) && self.source_endpoint_url.is_some() {
@@ -782,6 +2106,83 @@ impl CLIArgs {
         Ok(())
     }
 
+    fn check_expected_bucket_owner_conflict(&self) -> Result<(), String> {
+        let source = storage_path::parse_storage_path(&self.source);
+        if matches!(source, StoragePath::Local(_)) && self.source_expected_bucket_owner.is_some() {
+            return Err(SOURCE_LOCAL_STORAGE_SPECIFIED_WITH_EXPECTED_BUCKET_OWNER.to_string());
+        }
+
+        let target = storage_path::parse_storage_path(&self.target);
+        if matches!(target, StoragePath::Local(_)) && self.target_expected_bucket_owner.is_some() {
+            return Err(TARGET_LOCAL_STORAGE_SPECIFIED_WITH_EXPECTED_BUCKET_OWNER.to_string());
+        }
+
+        Ok(())
+    }
+
+    fn check_require_no_default_retention_conflict(&self) -> Result<(), String> {
+        if !self.require_no_default_retention {
+            return Ok(());
+        }
+
+        let target = storage_path::parse_storage_path(&self.target);
+        if matches!(target, StoragePath::Local(_)) {
+            return Err(
+                TARGET_LOCAL_STORAGE_SPECIFIED_WITH_REQUIRE_NO_DEFAULT_RETENTION.to_string(),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn check_source_if_modified_since_conflict(&self) -> Result<(), String> {
+        if !self.source_if_modified_since {
+            return Ok(());
+        }
+
+        let source = storage_path::parse_storage_path(&self.source);
+        if matches!(source, StoragePath::Local(_)) {
+            return Err(SOURCE_LOCAL_STORAGE_SPECIFIED_WITH_SOURCE_IF_MODIFIED_SINCE.to_string());
+        }
+
+        Ok(())
+    }
+
+    fn check_target_list_cache_invalidate_on_write_conflict(&self) -> Result<(), String> {
+        if !self.target_list_cache_invalidate_on_write {
+            return Ok(());
+        }
+
+        let target = storage_path::parse_storage_path(&self.target);
+        if matches!(target, StoragePath::Local(_)) {
+            return Err(
+                TARGET_LOCAL_STORAGE_SPECIFIED_WITH_TARGET_LIST_CACHE_INVALIDATE_ON_WRITE
+                    .to_string(),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn check_normalize_unicode_conflict(&self) -> Result<(), String> {
+        if self.normalize_unicode.is_none() {
+            return Ok(());
+        }
+
+        let source = storage_path::parse_storage_path(&self.source);
+        let target = storage_path::parse_storage_path(&self.target);
+        if !matches!(source, StoragePath::Local(_)) && !matches!(target, StoragePath::Local(_)) {
+            return Err(NORMALIZE_UNICODE_WITHOUT_LOCAL_STORAGE.to_string());
+        }
+
+        // the keys of the source objects can not be compared with the normalized keys of the target files.
+        if matches!(source, StoragePath::S3 { .. }) && self.delete {
+            return Err(NORMALIZE_UNICODE_WITH_DELETE_FROM_S3.to_string());
+        }
+
+        Ok(())
+    }
+
     fn check_disable_payload_signing_conflict(&self) -> Result<(), String> {
         if !self.disable_payload_signing {
             return Ok(());
@@ -796,7 +2197,9 @@ impl CLIArgs {
     }
 
     fn build_client_configs(&self) -> (Option<ClientConfig>, Option<ClientConfig>) {
-        let source_credential = if let Some(source_profile) = self.source_profile.clone() {
+        let source_credential = if self.source_anonymous {
+            Some(S3Credentials::Anonymous)
+        } else if let Some(source_profile) = self.source_profile.clone() {
             Some(S3Credentials::Profile(source_profile))
         } else if self.source_access_key.is_some() {
             self.source_access_key
@@ -816,7 +2219,9 @@ impl CLIArgs {
             Some(S3Credentials::FromEnvironment)
         };
 
-        let target_credential = if let Some(target_profile) = self.target_profile.clone() {
+        let target_credential = if self.target_anonymous {
+            Some(S3Credentials::Anonymous)
+        } else if let Some(target_profile) = self.target_profile.clone() {
             Some(S3Credentials::Profile(target_profile))
         } else if self.target_access_key.is_some() {
             self.target_access_key
@@ -832,6 +2237,11 @@ impl CLIArgs {
                         session_token: self.target_session_token.clone(),
                     },
                 })
+        } else if let Some(web_identity_token_file) = self.target_web_identity_token_file.clone() {
+            Some(S3Credentials::WebIdentity {
+                web_identity_token_file,
+                role_arn: self.target_role_arn.clone().unwrap(),
+            })
         } else {
             Some(S3Credentials::FromEnvironment)
         };
@@ -848,12 +2258,19 @@ impl CLIArgs {
             retry_config: RetryConfig {
                 aws_max_attempts: self.aws_max_attempts,
                 initial_backoff_milliseconds: self.initial_backoff_milliseconds,
+                retry_jitter: self
+                    .retry_jitter
+                    .as_deref()
+                    .map(retry_jitter::to_retry_jitter),
             },
             https_proxy: self.https_proxy.clone(),
             http_proxy: self.http_proxy.clone(),
+            no_proxy: self.parse_no_proxy(),
             no_verify_ssl: self.no_verify_ssl,
             disable_stalled_stream_protection: self.disable_stalled_stream_protection,
             request_checksum_calculation: RequestChecksumCalculation::WhenRequired,
+            expected_bucket_owner: self.source_expected_bucket_owner.clone(),
+            user_agent_suffix: self.user_agent_suffix.clone(),
         });
 
         let request_checksum_calculation = if self.additional_checksum_algorithm.is_some() {
@@ -874,16 +2291,36 @@ impl CLIArgs {
             retry_config: RetryConfig {
                 aws_max_attempts: self.aws_max_attempts,
                 initial_backoff_milliseconds: self.initial_backoff_milliseconds,
+                retry_jitter: self
+                    .retry_jitter
+                    .as_deref()
+                    .map(retry_jitter::to_retry_jitter),
             },
             https_proxy: self.https_proxy.clone(),
             http_proxy: self.http_proxy.clone(),
+            no_proxy: self.parse_no_proxy(),
             no_verify_ssl: self.no_verify_ssl,
             disable_stalled_stream_protection: self.disable_stalled_stream_protection,
             request_checksum_calculation,
+            expected_bucket_owner: self.target_expected_bucket_owner.clone(),
+            user_agent_suffix: self.user_agent_suffix.clone(),
         });
 
         (source_client_config, target_client_config)
     }
+
+    fn parse_no_proxy(&self) -> Vec<String> {
+        self.no_proxy
+            .as_ref()
+            .map(|no_proxy| {
+                no_proxy
+                    .split(',')
+                    .map(|host| host.trim().to_string())
+                    .filter(|host| !host.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
 }
 
 impl TryFrom<CLIArgs> for Config {
@@ -922,6 +2359,25 @@ impl TryFrom<CLIArgs> for Config {
             }
         }
 
+        if value.summary_only {
+            tracing_config = None;
+        } else if value.only_show_errors {
+            tracing_config = Some(TracingConfig {
+                tracing_level: log::Level::Warn,
+                json_tracing: value.json_tracing,
+                aws_sdk_tracing: value.aws_sdk_tracing,
+                span_events_tracing: value.span_events_tracing,
+                disable_color_tracing: value.disable_color_tracing,
+            });
+        }
+
+        let log_file_config = value.log_file.map(|path| LogFileConfig {
+            path,
+            max_size: human_bytes::parse_human_bytes_without_limit(&value.log_file_max_size)
+                .unwrap(),
+            max_files: value.log_file_max_files,
+        });
+
         let storage_class = value
             .storage_class
             .map(|storage_class| StorageClass::from_str(&storage_class).unwrap());
@@ -946,13 +2402,21 @@ impl TryFrom<CLIArgs> for Config {
             .rate_limit_bandwidth
             .map(|bandwidth| human_bytes::parse_human_bandwidth(&bandwidth).unwrap());
 
+        // with "negotiate", the algorithm is selected when the storages are created.
+        let negotiate_additional_checksum_algorithm =
+            value.additional_checksum_algorithm.as_deref()
+                == Some(checksum_algorithm::NEGOTIATE_CHECKSUM_ALGORITHM);
         let additional_checksum_algorithm = value
             .additional_checksum_algorithm
+            .filter(|_| !negotiate_additional_checksum_algorithm)
             .map(|algorithm| ChecksumAlgorithm::from(algorithm.as_str()));
 
+        // with --check-etag-and-checksum, both etag and additional checksum are used for update checking.
         let check_additional_checksum_algorithm = value
             .check_additional_checksum
+            .or(value.check_etag_and_checksum.clone())
             .map(|algorithm| ChecksumAlgorithm::from(algorithm.as_str()));
+        let check_etag = value.check_etag || value.check_etag_and_checksum.is_some();
 
         let checksum_mode = if value.enable_additional_checksum {
             Some(ChecksumMode::Enabled)
@@ -971,46 +2435,180 @@ impl TryFrom<CLIArgs> for Config {
             .filter_smaller_size
             .map(|human_bytes| human_bytes::parse_human_bytes_without_limit(&human_bytes).unwrap());
 
+        let (source_sse_c_key, source_sse_c_key_md5) =
+            if let Some(path) = value.source_sse_c_key_file.as_ref() {
+                let (key, key_md5) =
+                    sse_c_key_file::read_sse_c_key_file(path, &value.sse_c_key_format)?;
+                (Some(key), Some(key_md5))
+            } else {
+                let key_md5 = match (&value.source_sse_c_key, value.source_sse_c_key_md5) {
+                    (Some(key), None) => Some(sse_c_key_file::generate_sse_c_key_md5(key)?),
+                    (_, key_md5) => key_md5,
+                };
+                (value.source_sse_c_key, key_md5)
+            };
+        let (target_sse_c_key, target_sse_c_key_md5) =
+            if let Some(path) = value.target_sse_c_key_file.as_ref() {
+                let (key, key_md5) =
+                    sse_c_key_file::read_sse_c_key_file(path, &value.sse_c_key_format)?;
+                (Some(key), Some(key_md5))
+            } else {
+                let key_md5 = match (&value.target_sse_c_key, value.target_sse_c_key_md5) {
+                    (Some(key), None) => Some(sse_c_key_file::generate_sse_c_key_md5(key)?),
+                    (_, key_md5) => key_md5,
+                };
+                (value.target_sse_c_key, key_md5)
+            };
+
         let metadata = if value.metadata.is_some() {
             Some(metadata::parse_metadata(&value.metadata.unwrap())?)
         } else {
             None
         };
 
+        let content_type_override_map = value
+            .content_type_override_map
+            .as_deref()
+            .map(content_type_map::parse_content_type_map)
+            .transpose()?;
+
+        let content_encoding_map = value
+            .content_encoding_map
+            .as_deref()
+            .map(content_encoding_map::parse_content_encoding_map)
+            .transpose()?;
+
         Ok(Config {
             source: storage_path::parse_storage_path(&value.source),
             target: storage_path::parse_storage_path(&value.target),
 
             source_client_config,
+            source_region_auto_detect: value.source_region_auto_detect,
+            source_list_include_restore_status: value.source_list_include_restore_status,
+            source_list_include_owner: value.source_list_include_owner,
             target_client_config,
 
             tracing_config,
+            only_show_errors: value.only_show_errors,
+            summary_only: value.summary_only,
+            log_file_config,
+            integrity_report: value.integrity_report,
 
             force_retry_config: ForceRetryConfig {
                 force_retry_count: value.force_retry_count,
                 force_retry_interval_milliseconds: value.force_retry_interval_milliseconds,
+                retry_budget: value
+                    .retry_budget
+                    .map(|retry_budget| duration::parse_duration(&retry_budget).unwrap()),
+                max_retries_per_object: value.max_retries_per_object,
+                retry_jitter: value
+                    .retry_jitter
+                    .as_deref()
+                    .map(retry_jitter::to_retry_jitter),
             },
 
+            per_object_timeout_config: value.per_object_timeout_base.map(|base| {
+                PerObjectTimeoutConfig {
+                    base: Duration::from_secs(base),
+                    min_throughput: human_bytes::parse_human_bandwidth(
+                        &value.per_object_timeout_min_throughput,
+                    )
+                    .unwrap(),
+                }
+            }),
+
             transfer_config: TransferConfig {
                 multipart_threshold: human_bytes::parse_human_bytes(&value.multipart_threshold)
                     .unwrap(),
                 multipart_chunksize: human_bytes::parse_human_bytes(&value.multipart_chunksize)
                     .unwrap(),
                 auto_chunksize: value.auto_chunksize,
+                chunk_size_from_target: value.chunk_size_from_target,
+                verify_multipart_parts_attributes: value.verify_multipart_parts_attributes,
             },
 
             worker_size: value.worker_size,
+            concurrency_auto: value.concurrency_auto,
+            max_concurrent_objects_per_prefix: value.max_concurrent_objects_per_prefix,
+            checksum_parallelism: value.checksum_parallelism,
+            source_concurrency_limit: value.source_concurrency_limit,
+            target_concurrency_limit: value.target_concurrency_limit,
+            abort_incomplete_multipart_uploads: value.abort_incomplete_multipart_uploads,
+            abort_incomplete_multipart_uploads_older_than: value
+                .abort_incomplete_multipart_uploads_older_than
+                .map(|older_than| duration::parse_duration(&older_than).unwrap()),
+            graceful_shutdown_timeout_seconds: value.graceful_shutdown_timeout,
+            stats_interval_seconds: value.stats_interval,
+            progress_rate_window_seconds: value.progress_rate_window,
+            object_size_histogram: value.object_size_histogram,
+            source_scan_progress: value.source_scan_progress,
+            max_objects: value.max_objects,
+            max_bytes: value.max_bytes.map(|human_bytes| {
+                human_bytes::parse_human_bytes_without_limit(&human_bytes).unwrap()
+            }),
 
             warn_as_error: value.warn_as_error,
+            strict_verify: value.strict_verify,
+            fail_fast_after: value.fail_fast_after,
+            fail_on_empty_source: value.fail_on_empty_source,
+            write_completion_marker: value.write_completion_marker,
+            completion_marker_with_summary: value.completion_marker_with_summary,
             follow_symlinks: !value.ignore_symlinks,
             head_each_target: value.head_each_target,
+            head_not_found_retries: value.head_not_found_retries,
+            target_list_cache_invalidate_on_write: value.target_list_cache_invalidate_on_write,
+            skip_head_for_unlisted_objects: value.skip_head_for_unlisted_objects,
+            source_if_modified_since: value.source_if_modified_since,
+            skip_if_target_newer: value.skip_if_target_newer,
             sync_with_delete: value.delete,
+            delete_log: value.delete_log,
+            emit_manifest_for_delete: value.emit_manifest_for_delete,
+            delete_from_manifest: value.delete_from_manifest,
+            disable_preflight: value.disable_preflight,
+            require_no_default_retention: value.require_no_default_retention,
             disable_tagging: value.disable_tagging,
             sync_latest_tagging: value.sync_latest_tagging,
+            fast_empty_files: value.fast_empty_files,
             no_guess_mime_type: value.no_guess_mime_type,
+            content_type_override_map,
+            content_encoding_map,
+            md5_xattr: value.md5_xattr,
+            partial_suffix: value.partial_suffix,
+            partial_cleanup_older_than: duration::parse_duration(&value.partial_cleanup_older_than)
+                .unwrap(),
+            local_tmp_dir: value.local_tmp_dir,
+            cleanup_temp_on_start: value.cleanup_temp_on_start,
+            cleanup_temp_older_than: duration::parse_duration(&value.cleanup_temp_older_than)
+                .unwrap(),
+            download_to_stdout: value.target == storage_path::STDOUT_PATH,
+            checksum_verify_sampling: value.checksum_verify_sampling,
+            checksum_display: value.checksum_display,
+            preallocate: value.preallocate,
+            fsync_on_complete: value.fsync_on_complete,
+            verify_only_on_mismatch_redownload: value.verify_only_on_mismatch_redownload,
+            rename_on_conflict: value.rename_on_conflict,
+            skip_on_conflict: value.skip_on_conflict,
+            hardlink_mode: hardlink_mode::to_hardlink_mode(&value.hardlink_mode),
+            download_file_mode: value.download_file_mode,
+            preserve_file_mode: value.preserve_file_mode,
+            checksum_on_download_only_changed: value.checksum_on_download_only_changed,
+            force_reverify: value.force_reverify,
             disable_multipart_verify: value.disable_multipart_verify,
             disable_etag_verify: value.disable_etag_verify,
+            verify_content_length_only: value.verify_content_length_only,
+            verify_via_head: value.verify_via_head,
+            verify_range_reads: value.verify_range_reads,
+            verify_target_encryption: value.verify_target_encryption,
+            target_bucket_key_reuse: value.target_bucket_key_reuse,
             enable_versioning: value.enable_versioning,
+            versions_limit: value.versions_limit,
+            versions_newest_first: versions_order::is_newest_first(&value.versions_order),
+            delete_removed_versions: value.delete_removed_versions,
+            transfer_order: value
+                .transfer_order
+                .as_deref()
+                .map(transfer_order::to_transfer_order),
+            transfer_order_buffer_size: value.transfer_order_buffer_size as usize,
             storage_class,
             sse,
             sse_kms_key_id: SseKmsKeyId {
@@ -1018,43 +2616,105 @@ impl TryFrom<CLIArgs> for Config {
             },
             source_sse_c: value.source_sse_c,
             source_sse_c_key: SseCustomerKey {
-                key: value.source_sse_c_key,
+                key: source_sse_c_key,
             },
-            source_sse_c_key_md5: value.source_sse_c_key_md5,
+            source_sse_c_key_md5,
+            source_sse_c_probe: value.source_sse_c_probe,
             target_sse_c: value.target_sse_c,
             target_sse_c_key: SseCustomerKey {
-                key: value.target_sse_c_key,
+                key: target_sse_c_key,
             },
-            target_sse_c_key_md5: value.target_sse_c_key_md5,
+            target_sse_c_key_md5,
             canned_acl,
             additional_checksum_algorithm,
+            negotiate_additional_checksum_algorithm,
             additional_checksum_mode: checksum_mode,
             dry_run: value.dry_run,
             rate_limit_objects: value.rate_limit_objects,
             rate_limit_bandwidth,
+            source_request_rate_limit: value.source_request_rate_limit,
+            target_request_rate_limit: value.target_request_rate_limit,
+            list_max_rps: value.list_max_rps,
             cache_control: value.cache_control,
             content_disposition: value.content_disposition,
+            content_disposition_template: value.content_disposition_template,
             content_encoding: value.content_encoding,
             content_language: value.content_language,
             content_type: value.content_type,
             expires: value.expires,
             metadata,
+            metadata_merge: value.metadata_merge,
             tagging,
+            object_tag_count_guard: value
+                .object_tag_count_guard
+                .as_deref()
+                .map(tag_guard_mode::to_tag_guard_mode),
             filter_config: FilterConfig {
                 before_time: value.filter_mtime_before,
                 after_time: value.filter_mtime_after,
                 remove_modified_filter: value.remove_modified_filter,
                 check_size: value.check_size,
-                check_etag: value.check_etag,
+                check_etag,
                 check_checksum_algorithm: check_additional_checksum_algorithm,
                 include_regex,
                 exclude_regex,
                 larger_size: filter_larger_size,
                 smaller_size: filter_smaller_size,
+                owner: value.filter_owner,
+                skip_glacier: value.skip_glacier,
+                skip_zero_byte_objects: zero_byte_object_policy::is_skip_policy(
+                    &value.zero_byte_object_policy,
+                ),
+                normalize_unicode: value
+                    .normalize_unicode
+                    .as_deref()
+                    .map(unicode_normalization::to_unicode_normalization),
+                target_key_case: value
+                    .target_object_key_case
+                    .as_deref()
+                    .map(key_case::to_key_case),
+                date_partition: value.date_partition.map(|pattern| DatePartition {
+                    pattern,
+                    offset: date_partition::parse_date_partition_timezone(
+                        &value.date_partition_timezone,
+                    )
+                    .unwrap(),
+                }),
             },
             max_keys: value.max_keys,
+            non_recursive: value.non_recursive,
+            start_after: value.start_after,
+            stop_at: value.stop_at,
+            upload_manifest: value
+                .upload_manifest
+                .map(|path| Arc::new(upload_manifest::parse_upload_manifest(&path).unwrap())),
+            source_version_map: value
+                .source_versions_at
+                .map(|path| Arc::new(source_version_map::parse_source_version_map(&path).unwrap())),
+            #[cfg(feature = "s3_select")]
+            source_select: value
+                .source_select_expression
+                .map(|expression| SourceSelectConfig {
+                    expression,
+                    input_format: select_format::to_select_input_format(
+                        &value.source_select_input_format,
+                    ),
+                    output_format: select_format::to_select_output_format(
+                        &value.source_select_output_format,
+                    ),
+                }),
+            list_parallel_prefixes: value
+                .list_parallel_prefix_file
+                .map(|path| prefix_file::parse_prefix_file(&path).unwrap()),
+            include_only_prefixes_from_stdin: value.include_only_prefixes_from_stdin,
+            source_list_cache_config: value.source_list_cache.map(|path| SourceListCacheConfig {
+                path,
+                max_age: duration::parse_duration(&value.list_cache_max_age).unwrap(),
+            }),
             put_last_modified_metadata: value.put_last_modified_metadata,
+            put_file_mode_metadata: value.put_file_mode_metadata,
             auto_complete_shell: value.auto_complete_shell,
+            dump_config: value.dump_config,
             disable_payload_signing: value.disable_payload_signing,
         })
     }
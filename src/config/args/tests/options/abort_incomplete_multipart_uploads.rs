@@ -0,0 +1,123 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::config::args::*;
+
+    #[test]
+    fn with_default_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert!(!config.abort_incomplete_multipart_uploads);
+        assert!(config
+            .abort_incomplete_multipart_uploads_older_than
+            .is_none());
+    }
+
+    #[test]
+    fn with_custom_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--abort-incomplete-multipart-uploads",
+            "--abort-incomplete-multipart-uploads-older-than",
+            "7d",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert!(config.abort_incomplete_multipart_uploads);
+        assert_eq!(
+            config.abort_incomplete_multipart_uploads_older_than,
+            Some(Duration::from_secs(7 * 24 * 60 * 60))
+        );
+    }
+
+    #[test]
+    fn older_than_without_abort() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--abort-incomplete-multipart-uploads-older-than",
+            "7d",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    #[test]
+    fn invalid_older_than() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--abort-incomplete-multipart-uploads",
+            "--abort-incomplete-multipart-uploads-older-than",
+            "7w",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    #[test]
+    fn with_local_target() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--abort-incomplete-multipart-uploads",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        let result = build_config_from_args(args);
+
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert_eq!(
+                e,
+                TARGET_LOCAL_STORAGE_SPECIFIED_WITH_ABORT_INCOMPLETE_MULTIPART_UPLOADS.to_string()
+            );
+        }
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
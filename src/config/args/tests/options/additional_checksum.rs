@@ -19,6 +19,7 @@ mod tests {
         let config = build_config_from_args(args).unwrap();
 
         assert!(config.additional_checksum_algorithm.is_none());
+        assert!(!config.negotiate_additional_checksum_algorithm);
     }
 
     #[test]
@@ -45,6 +46,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn with_negotiate() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--additional-checksum-algorithm",
+            "negotiate",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert!(config.additional_checksum_algorithm.is_none());
+        assert!(config.negotiate_additional_checksum_algorithm);
+        assert_eq!(
+            config
+                .target_client_config
+                .unwrap()
+                .request_checksum_calculation,
+            RequestChecksumCalculation::WhenSupported
+        );
+    }
+
     #[test]
     fn with_custom_value_with_error() {
         init_dummy_tracing_subscriber();
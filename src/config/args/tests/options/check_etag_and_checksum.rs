@@ -0,0 +1,252 @@
+#[cfg(test)]
+mod tests {
+    use crate::config::args::*;
+
+    #[test]
+    fn with_default_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert!(!config.filter_config.check_etag);
+        assert!(config.filter_config.check_checksum_algorithm.is_none());
+    }
+
+    #[test]
+    fn with_s3_to_s3() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--check-etag-and-checksum",
+            "SHA256",
+            "--enable-additional-checksum",
+            "--additional-checksum-algorithm",
+            "SHA256",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert!(config.filter_config.check_etag);
+        assert_eq!(
+            config
+                .filter_config
+                .check_checksum_algorithm
+                .unwrap()
+                .to_string(),
+            "SHA256"
+        );
+    }
+
+    #[test]
+    fn with_local_to_s3() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--target-profile",
+            "target_profile",
+            "--check-etag-and-checksum",
+            "CRC64NVME",
+            "--additional-checksum-algorithm",
+            "CRC64NVME",
+            "./test_data/source",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert!(config.filter_config.check_etag);
+        assert_eq!(
+            config
+                .filter_config
+                .check_checksum_algorithm
+                .unwrap()
+                .to_string(),
+            "CRC64NVME"
+        );
+    }
+
+    #[test]
+    fn with_s3_to_local() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--check-etag-and-checksum",
+            "SHA256",
+            "--enable-additional-checksum",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        assert!(build_config_from_args(args).is_ok());
+    }
+
+    #[test]
+    fn without_additional_checksum_algorithm() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--target-profile",
+            "target_profile",
+            "--check-etag-and-checksum",
+            "SHA256",
+            "./test_data/source",
+            "s3://target-bucket/target_key",
+        ];
+
+        let result = build_config_from_args(args);
+        assert!(result.is_err());
+
+        if let Err(error_message) = result {
+            assert_eq!(
+                error_message,
+                CHECK_ETAG_AND_CHECKSUM_WITHOUT_ADDITIONAL_CHECKSUM_ALGORITHM
+            );
+        }
+    }
+
+    #[test]
+    fn with_different_additional_checksum_algorithm() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--target-profile",
+            "target_profile",
+            "--check-etag-and-checksum",
+            "SHA256",
+            "--additional-checksum-algorithm",
+            "CRC32",
+            "./test_data/source",
+            "s3://target-bucket/target_key",
+        ];
+
+        let result = build_config_from_args(args);
+        assert!(result.is_err());
+
+        if let Err(error_message) = result {
+            assert_eq!(
+                error_message,
+                CHECK_ETAG_AND_CHECKSUM_WITHOUT_ADDITIONAL_CHECKSUM_ALGORITHM
+            );
+        }
+    }
+
+    #[test]
+    fn without_enable_additional_checksum() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--check-etag-and-checksum",
+            "SHA256",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        let result = build_config_from_args(args);
+        assert!(result.is_err());
+
+        if let Err(error_message) = result {
+            assert_eq!(
+                error_message,
+                CHECK_ETAG_AND_CHECKSUM_WITHOUT_ENABLE_ADDITIONAL_CHECKSUM
+            );
+        }
+    }
+
+    #[test]
+    fn with_sse_kms() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--target-profile",
+            "target_profile",
+            "--check-etag-and-checksum",
+            "SHA256",
+            "--additional-checksum-algorithm",
+            "SHA256",
+            "--sse",
+            "aws:kms",
+            "./test_data/source",
+            "s3://target-bucket/target_key",
+        ];
+
+        let result = build_config_from_args(args);
+        assert!(result.is_err());
+
+        if let Err(error_message) = result {
+            assert_eq!(error_message, CHECK_ETAG_AND_CHECKSUM_CONFLICT_SSE_KMS);
+        }
+    }
+
+    #[test]
+    fn with_conflict_check_etag() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--target-profile",
+            "target_profile",
+            "--check-etag",
+            "--check-etag-and-checksum",
+            "SHA256",
+            "--additional-checksum-algorithm",
+            "SHA256",
+            "./test_data/source",
+            "s3://target-bucket/target_key",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    #[test]
+    fn with_conflict_disable_etag_verify() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--target-profile",
+            "target_profile",
+            "--disable-etag-verify",
+            "--check-etag-and-checksum",
+            "SHA256",
+            "--additional-checksum-algorithm",
+            "SHA256",
+            "./test_data/source",
+            "s3://target-bucket/target_key",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod tests {
+    use crate::config::args::*;
+
+    #[test]
+    fn with_default_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert!(!config.checksum_on_download_only_changed);
+        assert!(!config.force_reverify);
+    }
+
+    #[test]
+    fn with_custom_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--enable-additional-checksum",
+            "--checksum-on-download-only-changed",
+            "--force-reverify",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert!(config.checksum_on_download_only_changed);
+        assert!(config.force_reverify);
+    }
+
+    #[test]
+    fn force_reverify_without_checksum_on_download_only_changed() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--force-reverify",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    #[test]
+    fn with_remote_target() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--target-profile",
+            "target_profile",
+            "--checksum-on-download-only-changed",
+            "./test_data/source",
+            "s3://target-bucket/target_key",
+        ];
+
+        let result = build_config_from_args(args);
+
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert_eq!(
+                e,
+                TARGET_REMOTE_STORAGE_SPECIFIED_WITH_CHECKSUM_ON_DOWNLOAD_ONLY_CHANGED.to_string()
+            );
+        }
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
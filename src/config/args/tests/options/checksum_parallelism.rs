@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod tests {
+    use crate::config::args::*;
+
+    #[test]
+    fn with_default_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert!(config.checksum_parallelism.is_none());
+    }
+
+    #[test]
+    fn with_custom_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--checksum-parallelism",
+            "2",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert_eq!(config.checksum_parallelism, Some(2));
+    }
+
+    #[test]
+    fn with_zero() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--checksum-parallelism",
+            "0",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
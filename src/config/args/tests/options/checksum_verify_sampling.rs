@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod tests {
+    use crate::config::args::*;
+
+    #[test]
+    fn with_default_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--additional-checksum-algorithm",
+            "SHA256",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert!(config.checksum_verify_sampling.is_none());
+    }
+
+    #[test]
+    fn with_custom_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--enable-additional-checksum",
+            "--checksum-verify-sampling",
+            "5",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert_eq!(config.checksum_verify_sampling, Some(5));
+    }
+
+    #[test]
+    fn with_out_of_range_value() {
+        init_dummy_tracing_subscriber();
+
+        for sampling in ["0", "101"] {
+            let args = vec![
+                "s3sync",
+                "--source-profile",
+                "source_profile",
+                "--enable-additional-checksum",
+                "--checksum-verify-sampling",
+                sampling,
+                "s3://source-bucket/source_key",
+                "./test_data/target",
+            ];
+
+            assert!(build_config_from_args(args).is_err());
+        }
+    }
+
+    #[test]
+    fn without_additional_checksum() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--checksum-verify-sampling",
+            "5",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        let result = build_config_from_args(args);
+
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert_eq!(
+                e,
+                CHECKSUM_VERIFY_SAMPLING_WITHOUT_ADDITIONAL_CHECKSUM.to_string()
+            );
+        }
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
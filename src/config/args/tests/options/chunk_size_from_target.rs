@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod tests {
+    use crate::config::args::*;
+
+    #[test]
+    fn with_default_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--target-profile",
+            "target_profile",
+            "./test_data/source",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert!(!config.transfer_config.chunk_size_from_target);
+    }
+
+    #[test]
+    fn with_custom_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--target-profile",
+            "target_profile",
+            "--chunk-size-from-target",
+            "./test_data/source",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert!(config.transfer_config.chunk_size_from_target);
+        assert!(!config.transfer_config.auto_chunksize);
+    }
+
+    #[test]
+    fn with_auto_chunksize() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--chunk-size-from-target",
+            "--auto-chunksize",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    #[test]
+    fn with_local_target() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--chunk-size-from-target",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        let result = build_config_from_args(args);
+
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert_eq!(
+                e,
+                TARGET_LOCAL_STORAGE_SPECIFIED_WITH_CHUNK_SIZE_FROM_TARGET.to_string()
+            );
+        }
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
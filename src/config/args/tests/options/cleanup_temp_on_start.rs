@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::config::args::*;
+
+    #[test]
+    fn with_default_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert!(!config.cleanup_temp_on_start);
+        assert_eq!(config.cleanup_temp_older_than, Duration::from_secs(3600));
+    }
+
+    #[test]
+    fn with_custom_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--cleanup-temp-on-start",
+            "--cleanup-temp-older-than",
+            "30m",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert!(config.cleanup_temp_on_start);
+        assert_eq!(config.cleanup_temp_older_than, Duration::from_secs(1800));
+    }
+
+    #[test]
+    fn older_than_without_cleanup_temp_on_start() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--cleanup-temp-older-than",
+            "30m",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    #[test]
+    fn with_remote_target() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--target-profile",
+            "target_profile",
+            "--cleanup-temp-on-start",
+            "./test_data/source",
+            "s3://target-bucket/target_key",
+        ];
+
+        let result = build_config_from_args(args);
+
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert_eq!(
+                e,
+                TARGET_REMOTE_STORAGE_SPECIFIED_WITH_CLEANUP_TEMP_ON_START.to_string()
+            );
+        }
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod tests {
+    use crate::config::args::*;
+
+    #[test]
+    fn with_default_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert!(config.source_concurrency_limit.is_none());
+        assert!(config.target_concurrency_limit.is_none());
+    }
+
+    #[test]
+    fn with_custom_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--source-concurrency-limit",
+            "4",
+            "--target-concurrency-limit",
+            "32",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert_eq!(config.source_concurrency_limit, Some(4));
+        assert_eq!(config.target_concurrency_limit, Some(32));
+    }
+
+    #[test]
+    fn with_zero() {
+        init_dummy_tracing_subscriber();
+
+        for option in ["--source-concurrency-limit", "--target-concurrency-limit"] {
+            let args = vec![
+                "s3sync",
+                "--source-profile",
+                "source_profile",
+                "--target-profile",
+                "target_profile",
+                option,
+                "0",
+                "s3://source-bucket/source_key",
+                "s3://target-bucket/target_key",
+            ];
+
+            assert!(build_config_from_args(args).is_err());
+        }
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod tests {
+    use crate::config::args::*;
+
+    #[test]
+    fn with_default_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--target-profile",
+            "target_profile",
+            "./test_data/source",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert!(config.content_type_override_map.is_none());
+    }
+
+    #[test]
+    fn with_custom_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--target-profile",
+            "target_profile",
+            "--content-type-override-map",
+            "wasm=application/wasm,.WebManifest=application/manifest+json",
+            "./test_data/source",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        let content_type_override_map = config.content_type_override_map.unwrap();
+        assert_eq!(
+            content_type_override_map.get("wasm").unwrap(),
+            "application/wasm"
+        );
+        assert_eq!(
+            content_type_override_map.get("webmanifest").unwrap(),
+            "application/manifest+json"
+        );
+    }
+
+    #[test]
+    fn with_invalid_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--target-profile",
+            "target_profile",
+            "--content-type-override-map",
+            "wasm=application",
+            "./test_data/source",
+            "s3://target-bucket/target_key",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    #[test]
+    fn with_remote_source() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--content-type-override-map",
+            "wasm=application/wasm",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        let result = build_config_from_args(args);
+
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert_eq!(
+                e,
+                SOURCE_REMOTE_STORAGE_SPECIFIED_WITH_CONTENT_TYPE_OVERRIDE_MAP.to_string()
+            );
+        }
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
@@ -0,0 +1,151 @@
+#[cfg(test)]
+mod tests {
+    use chrono::FixedOffset;
+
+    use crate::config::args::*;
+
+    #[test]
+    fn with_default_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert!(config.filter_config.date_partition.is_none());
+    }
+
+    #[test]
+    fn with_custom_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--date-partition",
+            "%Y/%m/%d",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert_eq!(
+            config.filter_config.date_partition,
+            Some(DatePartition {
+                pattern: "%Y/%m/%d".to_string(),
+                offset: FixedOffset::east_opt(0).unwrap(),
+            })
+        );
+    }
+
+    #[test]
+    fn with_timezone() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--target-profile",
+            "target_profile",
+            "--date-partition",
+            "dt=%F",
+            "--date-partition-timezone",
+            "+09:00",
+            "./test_data/source",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert_eq!(
+            config.filter_config.date_partition,
+            Some(DatePartition {
+                pattern: "dt=%F".to_string(),
+                offset: FixedOffset::east_opt(9 * 3600).unwrap(),
+            })
+        );
+    }
+
+    #[test]
+    fn with_invalid_value() {
+        init_dummy_tracing_subscriber();
+
+        for date_partition in ["", "%Y/%Q", "/%Y/%m", "%Y/%m/", "%Y/../%m"] {
+            let args = vec![
+                "s3sync",
+                "--source-profile",
+                "source_profile",
+                "--date-partition",
+                date_partition,
+                "s3://source-bucket/source_key",
+                "./test_data/target",
+            ];
+
+            assert!(build_config_from_args(args).is_err());
+        }
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--date-partition",
+            "%Y/%m/%d",
+            "--date-partition-timezone",
+            "Asia/Tokyo",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    #[test]
+    fn timezone_without_date_partition() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--date-partition-timezone",
+            "+09:00",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    #[test]
+    fn with_enable_versioning() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--date-partition",
+            "%Y/%m/%d",
+            "--enable-versioning",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod tests {
+    use crate::config::args::*;
+
+    #[test]
+    fn with_default_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert!(!config.download_to_stdout);
+    }
+
+    #[test]
+    fn with_stdout_target() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "s3://source-bucket/dir/source_key",
+            "-",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert!(config.download_to_stdout);
+    }
+
+    #[test]
+    fn with_source_prefix() {
+        init_dummy_tracing_subscriber();
+
+        for source in ["s3://source-bucket", "s3://source-bucket/dir/"] {
+            let args = vec!["s3sync", "--source-profile", "source_profile", source, "-"];
+
+            let result = build_config_from_args(args);
+
+            assert!(result.is_err());
+            if let Err(e) = result {
+                assert_eq!(e, TARGET_STDOUT_SPECIFIED_WITHOUT_SOURCE_OBJECT.to_string());
+            }
+        }
+    }
+
+    #[test]
+    fn with_local_source() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--allow-both-local-storage",
+            "./test_data/source",
+            "-",
+        ];
+
+        let result = build_config_from_args(args);
+
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert_eq!(e, TARGET_STDOUT_SPECIFIED_WITHOUT_SOURCE_OBJECT.to_string());
+        }
+    }
+
+    #[test]
+    fn with_delete() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--delete",
+            "s3://source-bucket/source_key",
+            "-",
+        ];
+
+        let result = build_config_from_args(args);
+
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert_eq!(e, TARGET_STDOUT_SPECIFIED_WITH_SYNC_OPTION.to_string());
+        }
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
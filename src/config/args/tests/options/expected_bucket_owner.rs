@@ -0,0 +1,137 @@
+#[cfg(test)]
+mod tests {
+    use crate::config::args::*;
+
+    #[test]
+    fn with_default_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert!(config
+            .source_client_config
+            .unwrap()
+            .expected_bucket_owner
+            .is_none());
+        assert!(config
+            .target_client_config
+            .unwrap()
+            .expected_bucket_owner
+            .is_none());
+    }
+
+    #[test]
+    fn with_custom_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--source-expected-bucket-owner",
+            "111111111111",
+            "--target-expected-bucket-owner",
+            "222222222222",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert_eq!(
+            config.source_client_config.unwrap().expected_bucket_owner,
+            Some("111111111111".to_string())
+        );
+        assert_eq!(
+            config.target_client_config.unwrap().expected_bucket_owner,
+            Some("222222222222".to_string())
+        );
+    }
+
+    #[test]
+    fn with_invalid_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--target-expected-bucket-owner",
+            "my-account",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    #[test]
+    fn with_local_source() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--target-profile",
+            "target_profile",
+            "--source-expected-bucket-owner",
+            "111111111111",
+            "./test_data/source",
+            "s3://target-bucket/target_key",
+        ];
+
+        let result = build_config_from_args(args);
+
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert_eq!(
+                e,
+                SOURCE_LOCAL_STORAGE_SPECIFIED_WITH_EXPECTED_BUCKET_OWNER.to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn with_local_target() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-expected-bucket-owner",
+            "222222222222",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        let result = build_config_from_args(args);
+
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert_eq!(
+                e,
+                TARGET_LOCAL_STORAGE_SPECIFIED_WITH_EXPECTED_BUCKET_OWNER.to_string()
+            );
+        }
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
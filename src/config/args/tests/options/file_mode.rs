@@ -0,0 +1,213 @@
+#[cfg(test)]
+mod tests {
+    use crate::config::args::*;
+
+    #[test]
+    fn with_default_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert!(config.download_file_mode.is_none());
+        assert!(!config.preserve_file_mode);
+        assert!(!config.put_file_mode_metadata);
+    }
+
+    #[test]
+    #[cfg(target_family = "unix")]
+    fn with_download_file_mode() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--download-file-mode",
+            "0644",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+        assert_eq!(config.download_file_mode, Some(0o644));
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--preserve-file-mode",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+        assert!(config.download_file_mode.is_none());
+        assert!(config.preserve_file_mode);
+    }
+
+    #[test]
+    #[cfg(target_family = "unix")]
+    fn with_put_file_mode_metadata() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--target-profile",
+            "target_profile",
+            "--put-file-mode-metadata",
+            "./test_data/source",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+        assert!(config.put_file_mode_metadata);
+    }
+
+    #[test]
+    fn with_invalid_download_file_mode() {
+        init_dummy_tracing_subscriber();
+
+        for file_mode in ["0648", "17777", "rw-r--r--"] {
+            let args = vec![
+                "s3sync",
+                "--source-profile",
+                "source_profile",
+                "--download-file-mode",
+                file_mode,
+                "s3://source-bucket/source_key",
+                "./test_data/target",
+            ];
+
+            assert!(build_config_from_args(args).is_err());
+        }
+    }
+
+    #[test]
+    fn download_file_mode_with_preserve_file_mode() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--download-file-mode",
+            "0644",
+            "--preserve-file-mode",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    #[test]
+    #[cfg(target_family = "unix")]
+    fn download_file_mode_with_remote_target() {
+        init_dummy_tracing_subscriber();
+
+        for option in ["--download-file-mode", "--preserve-file-mode"] {
+            let mut args = vec!["s3sync", "--target-profile", "target_profile", option];
+            if option == "--download-file-mode" {
+                args.push("0644");
+            }
+            args.extend_from_slice(&["./test_data/source", "s3://target-bucket/target_key"]);
+
+            let result = build_config_from_args(args);
+            assert!(result.is_err());
+
+            if let Err(error_message) = result {
+                assert_eq!(
+                    error_message,
+                    TARGET_REMOTE_STORAGE_SPECIFIED_WITH_FILE_MODE
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(target_family = "unix")]
+    fn put_file_mode_metadata_with_remote_source() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--put-file-mode-metadata",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        let result = build_config_from_args(args);
+        assert!(result.is_err());
+
+        if let Err(error_message) = result {
+            assert_eq!(
+                error_message,
+                SOURCE_REMOTE_STORAGE_SPECIFIED_WITH_PUT_FILE_MODE_METADATA
+            );
+        }
+    }
+
+    #[test]
+    fn put_file_mode_metadata_with_local_target() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--put-file-mode-metadata",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        let result = build_config_from_args(args);
+        assert!(result.is_err());
+
+        if let Err(error_message) = result {
+            assert_eq!(
+                error_message,
+                TARGET_LOCAL_STORAGE_SPECIFIED_WITH_METADATA_OPTION
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(not(target_family = "unix"))]
+    fn not_supported() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--preserve-file-mode",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        let result = build_config_from_args(args);
+        assert!(result.is_err());
+
+        if let Err(error_message) = result {
+            assert_eq!(error_message, FILE_MODE_NOT_SUPPORTED);
+        }
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
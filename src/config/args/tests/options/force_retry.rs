@@ -23,6 +23,8 @@ mod tests {
             config.force_retry_config.force_retry_interval_milliseconds,
             1000
         );
+        assert!(config.force_retry_config.retry_budget.is_none());
+        assert!(config.force_retry_config.max_retries_per_object.is_none());
     }
 
     #[test]
@@ -52,6 +54,71 @@ mod tests {
         );
     }
 
+    #[test]
+    fn with_retry_budget() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--retry-budget",
+            "10m",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert_eq!(
+            config.force_retry_config.retry_budget,
+            Some(std::time::Duration::from_secs(600))
+        );
+    }
+
+    #[test]
+    fn with_invalid_retry_budget() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--retry-budget",
+            "10x",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    #[test]
+    fn with_max_retries_per_object() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--max-retries-per-object",
+            "2",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert_eq!(config.force_retry_config.max_retries_per_object, Some(2));
+        assert_eq!(config.force_retry_config.force_retry_count, 5);
+    }
+
     fn init_dummy_tracing_subscriber() {
         let _ = tracing_subscriber::fmt()
             .with_env_filter("dummy=trace")
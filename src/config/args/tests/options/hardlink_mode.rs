@@ -0,0 +1,152 @@
+#[cfg(test)]
+mod tests {
+    use crate::config::args::*;
+    use crate::types::HardlinkMode;
+
+    #[test]
+    fn with_default_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--target-profile",
+            "target_profile",
+            "./test_data/source",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert_eq!(config.hardlink_mode, HardlinkMode::Copy);
+    }
+
+    #[test]
+    #[cfg(target_family = "unix")]
+    fn with_custom_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--target-profile",
+            "target_profile",
+            "--hardlink-mode",
+            "skip",
+            "./test_data/source",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+        assert_eq!(config.hardlink_mode, HardlinkMode::Skip);
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--hardlink-mode",
+            "dedup-metadata",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+        assert_eq!(config.hardlink_mode, HardlinkMode::DedupMetadata);
+    }
+
+    #[test]
+    fn with_invalid_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--target-profile",
+            "target_profile",
+            "--hardlink-mode",
+            "link",
+            "./test_data/source",
+            "s3://target-bucket/target_key",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    #[test]
+    #[cfg(target_family = "unix")]
+    fn without_local_storage() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--hardlink-mode",
+            "dedup-metadata",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        let result = build_config_from_args(args);
+        assert!(result.is_err());
+
+        if let Err(error_message) = result {
+            assert_eq!(error_message, HARDLINK_MODE_WITHOUT_LOCAL_STORAGE);
+        }
+    }
+
+    #[test]
+    #[cfg(target_family = "unix")]
+    fn skip_with_remote_source() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--hardlink-mode",
+            "skip",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        let result = build_config_from_args(args);
+        assert!(result.is_err());
+
+        if let Err(error_message) = result {
+            assert_eq!(error_message, HARDLINK_MODE_SKIP_WITH_REMOTE_SOURCE);
+        }
+    }
+
+    #[test]
+    #[cfg(target_family = "unix")]
+    fn dedup_metadata_with_check_etag() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--target-profile",
+            "target_profile",
+            "--hardlink-mode",
+            "dedup-metadata",
+            "--check-etag",
+            "./test_data/source",
+            "s3://target-bucket/target_key",
+        ];
+
+        let result = build_config_from_args(args);
+        assert!(result.is_err());
+
+        if let Err(error_message) = result {
+            assert_eq!(
+                error_message,
+                HARDLINK_MODE_DEDUP_METADATA_WITH_CONTENT_CHECK
+            );
+        }
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
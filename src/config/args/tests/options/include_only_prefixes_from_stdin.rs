@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod tests {
+    use crate::config::args::*;
+
+    #[test]
+    fn with_default_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert!(!config.include_only_prefixes_from_stdin);
+    }
+
+    #[test]
+    fn with_custom_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--include-only-prefixes-from-stdin",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert!(config.include_only_prefixes_from_stdin);
+    }
+
+    #[test]
+    fn with_conflicting_options() {
+        init_dummy_tracing_subscriber();
+
+        for option in [
+            "--delete",
+            "--enable-versioning",
+            "--non-recursive",
+            "--source-list-cache=./source_list.cache",
+        ] {
+            let args = vec![
+                "s3sync",
+                "--source-profile",
+                "source_profile",
+                "--target-profile",
+                "target_profile",
+                "--include-only-prefixes-from-stdin",
+                option,
+                "s3://source-bucket/source_key",
+                "s3://target-bucket/target_key",
+            ];
+
+            assert!(build_config_from_args(args).is_err());
+        }
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
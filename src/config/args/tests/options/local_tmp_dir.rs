@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod tests {
+    use crate::config::args::*;
+
+    #[test]
+    fn with_default_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert!(config.local_tmp_dir.is_none());
+    }
+
+    #[test]
+    fn with_custom_value() {
+        init_dummy_tracing_subscriber();
+
+        let local_tmp_dir = tempfile::tempdir().unwrap();
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--local-tmp-dir",
+            local_tmp_dir.path().to_str().unwrap(),
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert_eq!(config.local_tmp_dir.unwrap(), local_tmp_dir.path());
+    }
+
+    #[test]
+    fn with_not_found_dir() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--local-tmp-dir",
+            "./test_data/not_found_dir",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        let result = build_config_from_args(args);
+
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert_eq!(e, LOCAL_TMP_DIR_NOT_WRITABLE.to_string());
+        }
+    }
+
+    #[test]
+    fn with_remote_target() {
+        init_dummy_tracing_subscriber();
+
+        let local_tmp_dir = tempfile::tempdir().unwrap();
+        let args = vec![
+            "s3sync",
+            "--target-profile",
+            "target_profile",
+            "--local-tmp-dir",
+            local_tmp_dir.path().to_str().unwrap(),
+            "./test_data/source",
+            "s3://target-bucket/target_key",
+        ];
+
+        let result = build_config_from_args(args);
+
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert_eq!(
+                e,
+                TARGET_REMOTE_STORAGE_SPECIFIED_WITH_LOCAL_TMP_DIR.to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn with_partial_suffix() {
+        init_dummy_tracing_subscriber();
+
+        let local_tmp_dir = tempfile::tempdir().unwrap();
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--local-tmp-dir",
+            local_tmp_dir.path().to_str().unwrap(),
+            "--partial-suffix",
+            ".partial",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
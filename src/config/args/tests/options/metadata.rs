@@ -19,6 +19,7 @@ mod tests {
         let config = build_config_from_args(args).unwrap();
 
         assert!(config.metadata.is_none());
+        assert!(!config.metadata_merge);
     }
 
     #[test]
@@ -48,6 +49,48 @@ mod tests {
             config.metadata.as_ref().unwrap().get("key2").unwrap(),
             "value2"
         );
+        assert!(!config.metadata_merge);
+    }
+
+    #[test]
+    fn with_metadata_merge() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--metadata",
+            "key1=value1",
+            "--metadata-merge",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert_eq!(config.metadata.as_ref().unwrap().len(), 1);
+        assert!(config.metadata_merge);
+    }
+
+    #[test]
+    fn metadata_merge_without_metadata() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--metadata-merge",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
     }
 
     #[test]
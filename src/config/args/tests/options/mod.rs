@@ -1,49 +1,132 @@
+mod abort_incomplete_multipart_uploads;
 mod acl;
 mod additional_checksum;
 mod cache_control;
 mod check_additional_checksum;
 mod check_etag;
+mod check_etag_and_checksum;
 mod check_size;
+mod checksum_display;
+mod checksum_on_download_only_changed;
+mod checksum_parallelism;
+mod checksum_verify_sampling;
+mod chunk_size_from_target;
+mod cleanup_temp_on_start;
+mod concurrency_auto;
+mod concurrency_limit;
 mod content_disposition;
+mod content_disposition_template;
 mod content_encoding;
+mod content_encoding_map;
 mod content_language;
 mod content_type;
+mod content_type_override_map;
+mod date_partition;
 mod delete;
+mod delete_from_manifest;
+mod delete_log;
+mod delete_removed_versions;
 mod disable_etag_verify;
 mod disable_multipart_verify;
 mod disable_payload_signing;
+mod disable_preflight;
 mod disable_stalled_stream_protection;
 mod disable_tagging;
+mod download_to_stdout;
 mod dry_run;
+mod dump_config;
+mod emit_manifest_for_delete;
 mod enable_additional_checksum;
 mod enable_versioning;
+mod expected_bucket_owner;
 mod expires;
+mod fail_fast_after;
+mod fail_on_empty_source;
+mod fast_empty_files;
+mod file_mode;
 mod filter_exclude_regex;
 mod filter_include_regex;
 mod filter_larger_size;
 mod filter_mtime_after;
 mod filter_mtime_before;
+mod filter_owner;
 mod filter_smaller_size;
 mod force_retry;
+mod fsync_on_complete;
+mod graceful_shutdown_timeout;
+mod hardlink_mode;
 mod head_each_target;
+mod head_not_found_retries;
 mod http_proxy;
 mod https_proxy;
 mod ignore_symlinks;
+mod include_only_prefixes_from_stdin;
+mod integrity_report;
+mod list_max_rps;
+mod list_parallel_prefix_file;
+mod local_tmp_dir;
+mod log_file;
+mod max_concurrent_objects_per_prefix;
 mod max_keys;
+mod max_objects_and_bytes;
+mod md5_xattr;
 mod metadata;
 mod no_guess_mime_type;
+mod no_proxy;
 mod no_verify_ssl;
+mod non_recursive;
+mod normalize_unicode;
+mod object_size_histogram;
+mod object_tag_count_guard;
+mod only_show_errors;
+mod partial_suffix;
+mod per_object_timeout;
+mod preallocate;
+mod progress_rate_window;
 mod put_last_modified_metadata;
 mod rate_limit_objects;
 mod remove_modified_filter;
+mod rename_on_conflict;
+mod request_rate_limit;
+mod require_no_default_retention;
+mod retry_jitter;
+mod skip_glacier;
+mod skip_if_target_newer;
+mod source_if_modified_since;
+mod source_list_cache;
+mod source_list_include_restore_status;
+mod source_region_auto_detect;
+mod source_scan_progress;
+#[cfg(feature = "s3_select")]
+mod source_select_expression;
 mod source_sse_c;
+mod source_versions_at;
 mod sse;
+mod sse_c_key_file;
 mod sse_kms_key_id;
+mod start_after_and_stop_at;
+mod stats_interval;
 mod storage_class;
+mod strict_verify;
 mod sync_latest_tagging;
 mod tagging;
+mod target_bucket_key_reuse;
+mod target_list_cache_invalidate_on_write;
+mod target_object_key_case;
 mod target_sse_c;
 mod tracing;
 mod transfer;
+mod transfer_order;
+mod upload_manifest;
+mod user_agent_suffix;
+mod verify_content_length_only;
+mod verify_multipart_parts_attributes;
+mod verify_only_on_mismatch_redownload;
+mod verify_range_reads;
+mod verify_target_encryption;
+mod verify_via_head;
+mod versions_limit;
 mod warn_as_error;
 mod worker_size;
+mod write_completion_marker;
+mod zero_byte_object_policy;
@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod tests {
+    use crate::config::args::*;
+
+    #[test]
+    fn with_default_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert!(config.source_client_config.unwrap().no_proxy.is_empty());
+        assert!(config.target_client_config.unwrap().no_proxy.is_empty());
+    }
+
+    #[test]
+    fn with_custom_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--https-proxy",
+            "http://localhost:8080",
+            "--no-proxy",
+            "localhost, .internal.example.com,,169.254.169.254",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        let expected = vec![
+            "localhost".to_string(),
+            ".internal.example.com".to_string(),
+            "169.254.169.254".to_string(),
+        ];
+        assert_eq!(config.source_client_config.unwrap().no_proxy, expected);
+        assert_eq!(config.target_client_config.unwrap().no_proxy, expected);
+    }
+
+    #[test]
+    fn with_empty_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--no-proxy",
+            "",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
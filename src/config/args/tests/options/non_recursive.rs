@@ -0,0 +1,71 @@
+#[cfg(test)]
+mod tests {
+    use crate::config::args::*;
+
+    #[test]
+    fn with_default_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert!(!config.non_recursive);
+    }
+
+    #[test]
+    fn with_custom_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--non-recursive",
+            "--delete",
+            "s3://source-bucket/source_key/",
+            "s3://target-bucket/target_key/",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert!(config.non_recursive);
+        assert!(config.sync_with_delete);
+    }
+
+    #[test]
+    fn with_list_parallel_prefix_file() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--non-recursive",
+            "--list-parallel-prefix-file",
+            "./test_data/prefix_file/prefixes.txt",
+            "s3://source-bucket/source_key/",
+            "s3://target-bucket/target_key/",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
@@ -0,0 +1,154 @@
+#[cfg(test)]
+mod tests {
+    use crate::config::args::*;
+    use crate::types::UnicodeNormalization;
+
+    #[test]
+    fn with_default_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--target-profile",
+            "target_profile",
+            "./test_data/source",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert!(config.filter_config.normalize_unicode.is_none());
+    }
+
+    #[test]
+    fn with_nfc() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--target-profile",
+            "target_profile",
+            "--normalize-unicode",
+            "NFC",
+            "./test_data/source",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert_eq!(
+            config.filter_config.normalize_unicode,
+            Some(UnicodeNormalization::Nfc)
+        );
+    }
+
+    #[test]
+    fn with_nfd() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--normalize-unicode",
+            "nfd",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert_eq!(
+            config.filter_config.normalize_unicode,
+            Some(UnicodeNormalization::Nfd)
+        );
+    }
+
+    #[test]
+    fn with_invalid_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--target-profile",
+            "target_profile",
+            "--normalize-unicode",
+            "NFKC",
+            "./test_data/source",
+            "s3://target-bucket/target_key",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    #[test]
+    fn without_local_storage() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--normalize-unicode",
+            "NFC",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        let result = build_config_from_args(args);
+        assert!(result.is_err());
+
+        if let Err(error_message) = result {
+            assert_eq!(error_message, NORMALIZE_UNICODE_WITHOUT_LOCAL_STORAGE);
+        }
+    }
+
+    #[test]
+    fn with_delete_from_s3() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--normalize-unicode",
+            "NFC",
+            "--delete",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        let result = build_config_from_args(args);
+        assert!(result.is_err());
+
+        if let Err(error_message) = result {
+            assert_eq!(error_message, NORMALIZE_UNICODE_WITH_DELETE_FROM_S3);
+        }
+    }
+
+    #[test]
+    fn with_delete_from_local() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--target-profile",
+            "target_profile",
+            "--normalize-unicode",
+            "NFC",
+            "--delete",
+            "./test_data/source",
+            "s3://target-bucket/target_key",
+        ];
+
+        assert!(build_config_from_args(args).is_ok());
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
@@ -0,0 +1,111 @@
+#[cfg(test)]
+mod tests {
+    use crate::config::args::*;
+    use crate::types::TagGuardMode;
+
+    #[test]
+    fn with_default_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert!(config.object_tag_count_guard.is_none());
+    }
+
+    #[test]
+    fn with_custom_value() {
+        init_dummy_tracing_subscriber();
+
+        for (object_tag_count_guard, expected) in
+            [("warn", TagGuardMode::Warn), ("ERROR", TagGuardMode::Error)]
+        {
+            let args = vec![
+                "s3sync",
+                "--source-profile",
+                "source_profile",
+                "--target-profile",
+                "target_profile",
+                "--object-tag-count-guard",
+                object_tag_count_guard,
+                "s3://source-bucket/source_key",
+                "s3://target-bucket/target_key",
+            ];
+
+            let config = build_config_from_args(args).unwrap();
+
+            assert_eq!(config.object_tag_count_guard, Some(expected));
+        }
+    }
+
+    #[test]
+    fn with_invalid_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--object-tag-count-guard",
+            "skip",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    #[test]
+    fn conflict_with_disable_tagging() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--object-tag-count-guard",
+            "warn",
+            "--disable-tagging",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    #[test]
+    fn tagging_exceeds_limits() {
+        init_dummy_tracing_subscriber();
+
+        let tagging = (1..=11)
+            .map(|i| format!("key{i}=value{i}"))
+            .collect::<Vec<String>>()
+            .join("&");
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--tagging",
+            &tagging,
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod tests {
+    use crate::config::args::*;
+
+    #[test]
+    fn with_default_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert!(config.source_request_rate_limit.is_none());
+        assert!(config.target_request_rate_limit.is_none());
+    }
+
+    #[test]
+    fn with_custom_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--source-request-rate-limit",
+            "5",
+            "--target-request-rate-limit",
+            "3500",
+            "--rate-limit-objects",
+            "100",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert_eq!(config.source_request_rate_limit, Some(5));
+        assert_eq!(config.target_request_rate_limit, Some(3500));
+        assert_eq!(config.rate_limit_objects, Some(100));
+    }
+
+    #[test]
+    fn with_zero() {
+        init_dummy_tracing_subscriber();
+
+        for option in ["--source-request-rate-limit", "--target-request-rate-limit"] {
+            let args = vec![
+                "s3sync",
+                "--source-profile",
+                "source_profile",
+                "--target-profile",
+                "target_profile",
+                option,
+                "0",
+                "s3://source-bucket/source_key",
+                "s3://target-bucket/target_key",
+            ];
+
+            assert!(build_config_from_args(args).is_err());
+        }
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
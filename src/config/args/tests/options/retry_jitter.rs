@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod tests {
+    use crate::config::args::*;
+    use crate::types::RetryJitter;
+
+    #[test]
+    fn with_default_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert!(config
+            .source_client_config
+            .unwrap()
+            .retry_config
+            .retry_jitter
+            .is_none());
+        assert!(config
+            .target_client_config
+            .unwrap()
+            .retry_config
+            .retry_jitter
+            .is_none());
+        assert!(config.force_retry_config.retry_jitter.is_none());
+    }
+
+    #[test]
+    fn with_custom_value() {
+        init_dummy_tracing_subscriber();
+
+        for (retry_jitter, expected) in [
+            ("full", RetryJitter::Full),
+            ("equal", RetryJitter::Equal),
+            ("none", RetryJitter::Disabled),
+        ] {
+            let args = vec![
+                "s3sync",
+                "--source-profile",
+                "source_profile",
+                "--target-profile",
+                "target_profile",
+                "--retry-jitter",
+                retry_jitter,
+                "s3://source-bucket/source_key",
+                "s3://target-bucket/target_key",
+            ];
+
+            let config = build_config_from_args(args).unwrap();
+
+            assert_eq!(
+                config
+                    .source_client_config
+                    .unwrap()
+                    .retry_config
+                    .retry_jitter,
+                Some(expected)
+            );
+            assert_eq!(
+                config
+                    .target_client_config
+                    .unwrap()
+                    .retry_config
+                    .retry_jitter,
+                Some(expected)
+            );
+            assert_eq!(config.force_retry_config.retry_jitter, Some(expected));
+        }
+    }
+
+    #[test]
+    fn with_invalid_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--retry-jitter",
+            "decorrelated",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
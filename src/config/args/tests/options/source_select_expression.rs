@@ -0,0 +1,142 @@
+#[cfg(test)]
+mod tests {
+    use crate::config::args::*;
+    use crate::types::{SelectInputFormat, SelectOutputFormat};
+
+    #[test]
+    fn with_default_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert!(config.source_select.is_none());
+    }
+
+    #[test]
+    fn with_expression() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--source-select-expression",
+            "SELECT * FROM S3Object s WHERE s.status = 'active'",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        let source_select = config.source_select.unwrap();
+        assert_eq!(
+            source_select.expression,
+            "SELECT * FROM S3Object s WHERE s.status = 'active'"
+        );
+        assert_eq!(source_select.input_format, SelectInputFormat::Csv);
+        assert_eq!(source_select.output_format, SelectOutputFormat::Csv);
+    }
+
+    #[test]
+    fn with_formats() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--source-select-expression",
+            "SELECT s.id FROM S3Object s",
+            "--source-select-input-format",
+            "parquet",
+            "--source-select-output-format",
+            "json",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        let source_select = config.source_select.unwrap();
+        assert_eq!(source_select.input_format, SelectInputFormat::Parquet);
+        assert_eq!(source_select.output_format, SelectOutputFormat::Json);
+    }
+
+    #[test]
+    fn with_invalid_format() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--source-select-expression",
+            "SELECT s.id FROM S3Object s",
+            "--source-select-output-format",
+            "parquet",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    #[test]
+    fn with_local_source() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--target-profile",
+            "target_profile",
+            "--source-select-expression",
+            "SELECT s.id FROM S3Object s",
+            "./test_data/source",
+            "s3://target-bucket/target_key",
+        ];
+
+        let result = build_config_from_args(args);
+        assert!(result.is_err());
+
+        if let Err(error_message) = result {
+            assert_eq!(
+                error_message,
+                SOURCE_LOCAL_STORAGE_SPECIFIED_WITH_SOURCE_SELECT_EXPRESSION
+            );
+        }
+    }
+
+    #[test]
+    fn with_check_etag() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--source-select-expression",
+            "SELECT s.id FROM S3Object s",
+            "--check-etag",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
@@ -21,6 +21,49 @@ mod tests {
         assert!(config.source_sse_c.is_none());
         assert!(config.source_sse_c_key.key.is_none());
         assert!(config.source_sse_c_key_md5.is_none());
+        assert!(!config.source_sse_c_probe);
+    }
+
+    #[test]
+    fn with_probe() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--source-sse-c",
+            "AES256",
+            "--source-sse-c-key",
+            "MDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDA=",
+            "--source-sse-c-probe",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert!(config.source_sse_c_probe);
+    }
+
+    #[test]
+    fn probe_without_source_sse_c() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--source-sse-c-probe",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
     }
 
     #[test]
@@ -56,6 +99,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn with_custom_value_without_md5() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--source-sse-c",
+            "AES256",
+            "--source-sse-c-key",
+            "MTExMTExMTExMTExMTExMTExMTExMTExMTExMTExMTE=",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert_eq!(
+            config.source_sse_c_key_md5.unwrap(),
+            "GoDL8oWeAZVZNl1r5Hh5Tg==".to_string()
+        );
+    }
+
+    #[test]
+    fn with_custom_value_with_invalid_key_without_md5() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--source-sse-c",
+            "AES256",
+            "--source-sse-c-key",
+            "MTExMTExMTExMTExMTExMQ==",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
     #[test]
     fn with_custom_value_with_error() {
         init_dummy_tracing_subscriber();
@@ -0,0 +1,133 @@
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use tempfile::NamedTempFile;
+
+    use crate::config::args::*;
+
+    #[test]
+    fn with_default_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert!(config.source_version_map.is_none());
+    }
+
+    #[test]
+    fn with_custom_value() {
+        init_dummy_tracing_subscriber();
+
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "key,version_id\ndir1/data1,version1\ndata2,version2").unwrap();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--source-versions-at",
+            file.path().to_str().unwrap(),
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        let source_version_map = config.source_version_map.unwrap();
+        assert_eq!(
+            source_version_map.entries,
+            vec![
+                ("dir1/data1".to_string(), "version1".to_string()),
+                ("data2".to_string(), "version2".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn with_local_source() {
+        init_dummy_tracing_subscriber();
+
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "data1,version1").unwrap();
+
+        let args = vec![
+            "s3sync",
+            "--target-profile",
+            "target_profile",
+            "--source-versions-at",
+            file.path().to_str().unwrap(),
+            "./test_data/source",
+            "s3://target-bucket/target_key",
+        ];
+
+        let result = build_config_from_args(args);
+        assert!(result.is_err());
+
+        if let Err(error_message) = result {
+            assert_eq!(
+                error_message,
+                SOURCE_LOCAL_STORAGE_SPECIFIED_WITH_SOURCE_VERSIONS_AT
+            );
+        }
+    }
+
+    #[test]
+    fn with_enable_versioning() {
+        init_dummy_tracing_subscriber();
+
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "data1,version1").unwrap();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--source-versions-at",
+            file.path().to_str().unwrap(),
+            "--enable-versioning",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    #[test]
+    fn with_check_additional_checksum() {
+        init_dummy_tracing_subscriber();
+
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "data1,version1").unwrap();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--source-versions-at",
+            file.path().to_str().unwrap(),
+            "--check-additional-checksum",
+            "SHA256",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
@@ -0,0 +1,145 @@
+#[cfg(test)]
+mod tests {
+    use crate::config::args::*;
+
+    const TEST_SSE_C_KEY: &str = "MDEyMzQ1Njc4OWFiY2RlZjAxMjM0NTY3ODlhYmNkZWY=";
+    const TEST_SSE_C_KEY_MD5: &str = "hRasmdxgYDKV3nvbahU1MA==";
+
+    #[test]
+    fn with_source_key_file() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--source-sse-c",
+            "AES256",
+            "--source-sse-c-key-file",
+            "./test_data/sse_c_key_file/binary.key",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert_eq!(config.source_sse_c.unwrap(), "AES256".to_string());
+        assert_eq!(
+            config.source_sse_c_key.key.clone().unwrap(),
+            TEST_SSE_C_KEY.to_string()
+        );
+        assert_eq!(
+            config.source_sse_c_key_md5.unwrap(),
+            TEST_SSE_C_KEY_MD5.to_string()
+        );
+        assert!(config.target_sse_c_key.key.is_none());
+    }
+
+    #[test]
+    fn with_target_key_file() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--target-sse-c",
+            "AES256",
+            "--target-sse-c-key-file",
+            "./test_data/sse_c_key_file/base64.key",
+            "--sse-c-key-format",
+            "base64",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert_eq!(config.target_sse_c.unwrap(), "AES256".to_string());
+        assert_eq!(
+            config.target_sse_c_key.key.clone().unwrap(),
+            TEST_SSE_C_KEY.to_string()
+        );
+        assert_eq!(
+            config.target_sse_c_key_md5.unwrap(),
+            TEST_SSE_C_KEY_MD5.to_string()
+        );
+    }
+
+    #[test]
+    fn with_wrong_key_format() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--source-sse-c",
+            "AES256",
+            "--source-sse-c-key-file",
+            "./test_data/sse_c_key_file/base64.key",
+            "--sse-c-key-format",
+            "binary",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    #[test]
+    fn with_key_and_key_file() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--source-sse-c",
+            "AES256",
+            "--source-sse-c-key",
+            TEST_SSE_C_KEY,
+            "--source-sse-c-key-md5",
+            TEST_SSE_C_KEY_MD5,
+            "--source-sse-c-key-file",
+            "./test_data/sse_c_key_file/binary.key",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    #[test]
+    fn without_sse_c() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--source-sse-c-key-file",
+            "./test_data/sse_c_key_file/binary.key",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
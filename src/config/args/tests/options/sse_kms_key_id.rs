@@ -44,6 +44,50 @@ mod tests {
         assert!(config.sse_kms_key_id.id.is_some());
     }
 
+    #[test]
+    fn with_alias() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--sse",
+            "aws:kms",
+            "--sse-kms-key-id",
+            "alias/my-key",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert_eq!(config.sse_kms_key_id.id.clone().unwrap(), "alias/my-key");
+    }
+
+    #[test]
+    fn with_invalid_alias() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--sse",
+            "aws:kms",
+            "--sse-kms-key-id",
+            "alias/",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
     #[test]
     fn with_custom_value_without_sse_error() {
         init_dummy_tracing_subscriber();
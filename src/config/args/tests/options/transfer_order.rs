@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod tests {
+    use crate::config::args::*;
+    use crate::types::TransferOrder;
+
+    #[test]
+    fn with_default_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert!(config.transfer_order.is_none());
+        assert_eq!(config.transfer_order_buffer_size, 100000);
+    }
+
+    #[test]
+    fn with_custom_value() {
+        init_dummy_tracing_subscriber();
+
+        for (order, expected) in [
+            ("size-asc", TransferOrder::SizeAsc),
+            ("size-desc", TransferOrder::SizeDesc),
+            ("key", TransferOrder::Key),
+            ("random", TransferOrder::Random),
+        ] {
+            let args = vec![
+                "s3sync",
+                "--source-profile",
+                "source_profile",
+                "--target-profile",
+                "target_profile",
+                "--transfer-order",
+                order,
+                "--transfer-order-buffer-size",
+                "1000",
+                "s3://source-bucket/source_key",
+                "s3://target-bucket/target_key",
+            ];
+
+            let config = build_config_from_args(args).unwrap();
+
+            assert_eq!(config.transfer_order.unwrap(), expected);
+            assert_eq!(config.transfer_order_buffer_size, 1000);
+        }
+    }
+
+    #[test]
+    fn with_invalid_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--transfer-order",
+            "size",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    #[test]
+    fn buffer_size_without_transfer_order() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--transfer-order-buffer-size",
+            "1000",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    #[test]
+    fn with_enable_versioning() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--transfer-order",
+            "size-desc",
+            "--enable-versioning",
+            "s3://source-bucket/source_key",
+            "s3://target-bucket/target_key",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
@@ -0,0 +1,123 @@
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use tempfile::NamedTempFile;
+
+    use crate::config::args::*;
+
+    #[test]
+    fn with_default_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--target-profile",
+            "target_profile",
+            "./test_data/source",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert!(config.upload_manifest.is_none());
+    }
+
+    #[test]
+    fn with_custom_value() {
+        init_dummy_tracing_subscriber();
+
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "dir1/data1,flat/one\n/tmp/data2,flat/two").unwrap();
+
+        let args = vec![
+            "s3sync",
+            "--target-profile",
+            "target_profile",
+            "--upload-manifest",
+            file.path().to_str().unwrap(),
+            "./test_data/source",
+            "s3://target-bucket/target_key",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        let upload_manifest = config.upload_manifest.unwrap();
+        assert_eq!(upload_manifest.keys, vec!["flat/one", "flat/two"]);
+    }
+
+    #[test]
+    fn with_duplicate_target_key() {
+        init_dummy_tracing_subscriber();
+
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "data1,flat/one\ndata2,flat/one").unwrap();
+
+        let args = vec![
+            "s3sync",
+            "--target-profile",
+            "target_profile",
+            "--upload-manifest",
+            file.path().to_str().unwrap(),
+            "./test_data/source",
+            "s3://target-bucket/target_key",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    #[test]
+    fn with_remote_source() {
+        init_dummy_tracing_subscriber();
+
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "data1,flat/one").unwrap();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--upload-manifest",
+            file.path().to_str().unwrap(),
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        let result = build_config_from_args(args);
+        assert!(result.is_err());
+
+        if let Err(error_message) = result {
+            assert_eq!(
+                error_message,
+                SOURCE_REMOTE_STORAGE_SPECIFIED_WITH_UPLOAD_MANIFEST
+            );
+        }
+    }
+
+    #[test]
+    fn with_delete() {
+        init_dummy_tracing_subscriber();
+
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "data1,flat/one").unwrap();
+
+        let args = vec![
+            "s3sync",
+            "--target-profile",
+            "target_profile",
+            "--upload-manifest",
+            file.path().to_str().unwrap(),
+            "--delete",
+            "./test_data/source",
+            "s3://target-bucket/target_key",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
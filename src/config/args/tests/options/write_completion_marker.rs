@@ -0,0 +1,82 @@
+#[cfg(test)]
+mod tests {
+    use crate::config::args::*;
+
+    #[test]
+    fn with_default_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "s3://source-bucket/source_key",
+            "./test_data/target",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert!(config.write_completion_marker.is_none());
+        assert!(!config.completion_marker_with_summary);
+    }
+
+    #[test]
+    fn with_custom_value() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--target-profile",
+            "target_profile",
+            "--write-completion-marker",
+            "_SUCCESS",
+            "--completion-marker-with-summary",
+            "./test_data/source",
+            "s3://target-bucket/target_key/",
+        ];
+
+        let config = build_config_from_args(args).unwrap();
+
+        assert_eq!(config.write_completion_marker.unwrap(), "_SUCCESS");
+        assert!(config.completion_marker_with_summary);
+    }
+
+    #[test]
+    fn with_empty_key() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--target-profile",
+            "target_profile",
+            "--write-completion-marker",
+            "",
+            "./test_data/source",
+            "s3://target-bucket/target_key/",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    #[test]
+    fn summary_without_marker() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--target-profile",
+            "target_profile",
+            "--completion-marker-with-summary",
+            "./test_data/source",
+            "s3://target-bucket/target_key/",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
@@ -96,6 +96,138 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_from_args_both_anonymous() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-anonymous",
+            "--target-anonymous",
+            "s3://source-bucket",
+            "s3://target-bucket",
+        ];
+
+        if let Ok(config_args) = parse_from_args(args) {
+            let (source_config_result, target_config_result) = config_args.build_client_configs();
+
+            assert!(matches!(
+                source_config_result.unwrap().credential,
+                S3Credentials::Anonymous
+            ));
+            assert!(matches!(
+                target_config_result.unwrap().credential,
+                S3Credentials::Anonymous
+            ));
+        } else {
+            panic!("error occurred.");
+        }
+    }
+
+    #[test]
+    fn parse_from_args_anonymous_conflict() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-anonymous",
+            "--source-profile",
+            "source_profile",
+            "s3://source-bucket",
+            "s3://target-bucket",
+        ];
+
+        assert!(parse_from_args(args).is_err());
+
+        let args = vec![
+            "s3sync",
+            "--source-anonymous",
+            "s3://source-bucket",
+            "./test_data/",
+            "--target-anonymous",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
+    #[test]
+    fn parse_from_args_target_web_identity() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-web-identity-token-file",
+            "/var/run/secrets/token",
+            "--target-role-arn",
+            "arn:aws:iam::123456789012:role/s3sync",
+            "s3://source-bucket",
+            "s3://target-bucket",
+        ];
+
+        if let Ok(config_args) = parse_from_args(args) {
+            let (_, target_config_result) = config_args.build_client_configs();
+
+            if let S3Credentials::WebIdentity {
+                web_identity_token_file,
+                role_arn,
+            } = target_config_result.unwrap().credential
+            {
+                assert_eq!(
+                    web_identity_token_file,
+                    std::path::PathBuf::from("/var/run/secrets/token")
+                );
+                assert_eq!(role_arn, "arn:aws:iam::123456789012:role/s3sync");
+            } else {
+                panic!("web identity credential not found.");
+            }
+        } else {
+            panic!("error occurred.");
+        }
+    }
+
+    #[test]
+    fn parse_from_args_target_web_identity_conflict() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--target-web-identity-token-file",
+            "/var/run/secrets/token",
+            "s3://source-bucket",
+            "s3://target-bucket",
+        ];
+
+        assert!(parse_from_args(args).is_err());
+
+        let args = vec![
+            "s3sync",
+            "--target-profile",
+            "target_profile",
+            "--target-web-identity-token-file",
+            "/var/run/secrets/token",
+            "--target-role-arn",
+            "arn:aws:iam::123456789012:role/s3sync",
+            "s3://source-bucket",
+            "s3://target-bucket",
+        ];
+
+        assert!(parse_from_args(args).is_err());
+
+        let args = vec![
+            "s3sync",
+            "--target-web-identity-token-file",
+            "/var/run/secrets/token",
+            "--target-role-arn",
+            "arn:aws:iam::123456789012:role/s3sync",
+            "s3://source-bucket",
+            "./test_data/",
+        ];
+
+        assert!(build_config_from_args(args).is_err());
+    }
+
     fn init_dummy_tracing_subscriber() {
         let _ = tracing_subscriber::fmt()
             .with_env_filter("dummy=trace")
@@ -0,0 +1,30 @@
+const INVALID_ACCOUNT_ID: &str = "invalid AWS account ID. a 12-digit number (e.g. 123456789012).";
+
+pub fn check_account_id(value: &str) -> Result<String, String> {
+    if value.len() != 12 || !value.chars().all(|c| c.is_ascii_digit()) {
+        return Err(INVALID_ACCOUNT_ID.to_string());
+    }
+
+    Ok(value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_account_id_valid() {
+        assert_eq!(check_account_id("123456789012").unwrap(), "123456789012");
+        assert_eq!(check_account_id("000000000000").unwrap(), "000000000000");
+    }
+
+    #[test]
+    fn check_account_id_invalid() {
+        assert!(check_account_id("").is_err());
+        assert!(check_account_id("12345678901").is_err());
+        assert!(check_account_id("1234567890123").is_err());
+        assert!(check_account_id("12345678901a").is_err());
+        assert!(check_account_id(" 123456789012").is_err());
+        assert!(check_account_id("１２３４５６７８９０１２").is_err());
+    }
+}
@@ -1,7 +1,11 @@
 use aws_sdk_s3::types::ChecksumAlgorithm;
 
+pub const NEGOTIATE_CHECKSUM_ALGORITHM: &str = "negotiate";
+
 const INVALID_CHECKSUM_ALGORITHM: &str =
     "invalid checksum_algorithm. valid choices: CRC32 | CRC32C | CRC64NVME | SHA1 | SHA256 .";
+const INVALID_ADDITIONAL_CHECKSUM_ALGORITHM: &str =
+    "invalid checksum_algorithm. valid choices: CRC32 | CRC32C | CRC64NVME | SHA1 | SHA256 | negotiate .";
 
 pub fn parse_checksum_algorithm(checksum_algorithm: &str) -> Result<String, String> {
     #[allow(deprecated)]
@@ -14,3 +18,12 @@ pub fn parse_checksum_algorithm(checksum_algorithm: &str) -> Result<String, Stri
 
     Ok(checksum_algorithm.to_string())
 }
+
+pub fn parse_additional_checksum_algorithm(checksum_algorithm: &str) -> Result<String, String> {
+    if checksum_algorithm == NEGOTIATE_CHECKSUM_ALGORITHM {
+        return Ok(checksum_algorithm.to_string());
+    }
+
+    parse_checksum_algorithm(checksum_algorithm)
+        .map_err(|_| INVALID_ADDITIONAL_CHECKSUM_ALGORITHM.to_string())
+}
@@ -0,0 +1,72 @@
+use crate::types::{CONTENT_DISPOSITION_TEMPLATE_BASENAME, CONTENT_DISPOSITION_TEMPLATE_KEY};
+
+const INVALID_CONTENT_DISPOSITION_TEMPLATE: &str =
+    "invalid content disposition template. only printable ASCII characters are allowed.";
+const UNKNOWN_PLACEHOLDER: &str =
+    "invalid content disposition template. valid placeholders: {basename} | {key} .";
+const QUOTED_PLACEHOLDER: &str =
+    "invalid content disposition template. placeholders are expanded to quoted strings, so they must not be quoted.";
+
+pub fn check_content_disposition_template(template: &str) -> Result<String, String> {
+    if template.is_empty() || !template.chars().all(|c| (' '..='~').contains(&c)) {
+        return Err(INVALID_CONTENT_DISPOSITION_TEMPLATE.to_string());
+    }
+
+    let mut rest = template;
+    while let Some(start) = rest.find(['{', '}']) {
+        if rest[start..].starts_with('}') {
+            return Err(UNKNOWN_PLACEHOLDER.to_string());
+        }
+
+        let end = start
+            + rest[start..]
+                .find('}')
+                .ok_or_else(|| UNKNOWN_PLACEHOLDER.to_string())?;
+        let placeholder = &rest[start + 1..end];
+        if placeholder != CONTENT_DISPOSITION_TEMPLATE_BASENAME
+            && placeholder != CONTENT_DISPOSITION_TEMPLATE_KEY
+        {
+            return Err(UNKNOWN_PLACEHOLDER.to_string());
+        }
+
+        if rest[..start].ends_with('"') || rest[end + 1..].starts_with('"') {
+            return Err(QUOTED_PLACEHOLDER.to_string());
+        }
+
+        rest = &rest[end + 1..];
+    }
+
+    Ok(template.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_content_disposition_template_valid() {
+        assert_eq!(
+            check_content_disposition_template("attachment; filename={basename}").unwrap(),
+            "attachment; filename={basename}"
+        );
+        assert_eq!(
+            check_content_disposition_template("inline; name={key}; filename={basename}").unwrap(),
+            "inline; name={key}; filename={basename}"
+        );
+        assert_eq!(
+            check_content_disposition_template("attachment").unwrap(),
+            "attachment"
+        );
+    }
+
+    #[test]
+    fn check_content_disposition_template_invalid() {
+        assert!(check_content_disposition_template("").is_err());
+        assert!(check_content_disposition_template("attachment; filename={name}").is_err());
+        assert!(check_content_disposition_template("attachment; filename={basename").is_err());
+        assert!(check_content_disposition_template("attachment; filename=basename}").is_err());
+        assert!(check_content_disposition_template("attachment; filename=\"{basename}\"").is_err());
+        assert!(check_content_disposition_template("attachment; filename={basename}\n").is_err());
+        assert!(check_content_disposition_template("attachment; filename=ファイル").is_err());
+    }
+}
@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+
+use regex::Regex;
+
+const INVALID_CONTENT_ENCODING_MAP: &str =
+    "invalid content encoding map. e.g. gz=gzip,br=br . valid encodings: gzip | compress | deflate | br | zstd";
+
+const KNOWN_CONTENT_ENCODINGS: [&str; 5] = ["gzip", "compress", "deflate", "br", "zstd"];
+
+pub fn check_content_encoding_map(content_encoding_map: &str) -> Result<String, String> {
+    let extension_regex = Regex::new(r"^\.?[a-zA-Z0-9_+\-]+$").unwrap();
+
+    for entry in content_encoding_map.split(',') {
+        let Some((extension, content_encoding)) = entry.split_once('=') else {
+            return Err(INVALID_CONTENT_ENCODING_MAP.to_string());
+        };
+
+        if !extension_regex.is_match(extension)
+            || !KNOWN_CONTENT_ENCODINGS.contains(&content_encoding.to_ascii_lowercase().as_str())
+        {
+            return Err(INVALID_CONTENT_ENCODING_MAP.to_string());
+        }
+    }
+
+    Ok(content_encoding_map.to_string())
+}
+
+// Extensions are case-insensitive, and stored in lower case without the leading dot.
+// encodings are stored in lower case.
+pub fn parse_content_encoding_map(value: &str) -> Result<HashMap<String, String>, String> {
+    check_content_encoding_map(value)?;
+
+    Ok(value
+        .split(',')
+        .map(|entry| entry.split_once('=').unwrap())
+        .map(|(extension, content_encoding)| {
+            (
+                extension.trim_start_matches('.').to_ascii_lowercase(),
+                content_encoding.to_ascii_lowercase(),
+            )
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_content_encoding_map_test() {
+        init_dummy_tracing_subscriber();
+
+        check_content_encoding_map("gz=gzip").unwrap();
+        check_content_encoding_map(".gz=gzip").unwrap();
+        check_content_encoding_map("gz=gzip,br=br,zst=zstd").unwrap();
+        check_content_encoding_map("Z=compress,zz=deflate").unwrap();
+        check_content_encoding_map("gz=GZIP").unwrap();
+
+        assert!(check_content_encoding_map("").is_err());
+        assert!(check_content_encoding_map("gz").is_err());
+        assert!(check_content_encoding_map("gz=").is_err());
+        assert!(check_content_encoding_map("=gzip").is_err());
+        assert!(check_content_encoding_map("gz=gzip,").is_err());
+        assert!(check_content_encoding_map("tar.gz=gzip").is_err());
+        assert!(check_content_encoding_map("gz=x-unknown").is_err());
+        assert!(check_content_encoding_map("gz=gzip=1").is_err());
+    }
+
+    #[test]
+    fn parse_content_encoding_map_test() {
+        init_dummy_tracing_subscriber();
+
+        let content_encoding_map = parse_content_encoding_map("GZ=GZIP,.br=br").unwrap();
+
+        assert_eq!(content_encoding_map.len(), 2);
+        assert_eq!(content_encoding_map.get("gz").unwrap(), "gzip");
+        assert_eq!(content_encoding_map.get("br").unwrap(), "br");
+
+        assert!(parse_content_encoding_map("gz").is_err());
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
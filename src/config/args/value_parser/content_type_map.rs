@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+
+use regex::Regex;
+
+const INVALID_CONTENT_TYPE_MAP: &str =
+    "invalid content type map. e.g. wasm=application/wasm,webmanifest=application/manifest+json";
+
+pub fn check_content_type_map(content_type_map: &str) -> Result<String, String> {
+    let extension_regex = Regex::new(r"^\.?[a-zA-Z0-9_+\-]+$").unwrap();
+    let content_type_regex = Regex::new(
+        r"^[a-zA-Z0-9!#$&^_.+\-]+/[a-zA-Z0-9!#$&^_.+\-]+(\s*;\s*[a-zA-Z0-9!#$&^_.+\-]+=[a-zA-Z0-9!#$&^_.+\-]+)*$",
+    )
+    .unwrap();
+
+    for entry in content_type_map.split(',') {
+        let Some((extension, content_type)) = entry.split_once('=') else {
+            return Err(INVALID_CONTENT_TYPE_MAP.to_string());
+        };
+
+        if !extension_regex.is_match(extension) || !content_type_regex.is_match(content_type) {
+            return Err(INVALID_CONTENT_TYPE_MAP.to_string());
+        }
+    }
+
+    Ok(content_type_map.to_string())
+}
+
+// Extensions are case-insensitive, and stored in lower case without the leading dot.
+pub fn parse_content_type_map(value: &str) -> Result<HashMap<String, String>, String> {
+    check_content_type_map(value)?;
+
+    Ok(value
+        .split(',')
+        .map(|entry| entry.split_once('=').unwrap())
+        .map(|(extension, content_type)| {
+            (
+                extension.trim_start_matches('.').to_ascii_lowercase(),
+                content_type.to_string(),
+            )
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_content_type_map_test() {
+        init_dummy_tracing_subscriber();
+
+        check_content_type_map("wasm=application/wasm").unwrap();
+        check_content_type_map(".wasm=application/wasm").unwrap();
+        check_content_type_map("wasm=application/wasm,webmanifest=application/manifest+json")
+            .unwrap();
+        check_content_type_map("html=text/html; charset=utf-8").unwrap();
+
+        assert!(check_content_type_map("").is_err());
+        assert!(check_content_type_map("wasm").is_err());
+        assert!(check_content_type_map("wasm=").is_err());
+        assert!(check_content_type_map("=application/wasm").is_err());
+        assert!(check_content_type_map("wasm=application").is_err());
+        assert!(check_content_type_map("wasm=application/wasm,").is_err());
+        assert!(check_content_type_map("tar.gz=application/gzip").is_err());
+        assert!(check_content_type_map("wasm=application/wasm=1").is_err());
+    }
+
+    #[test]
+    fn parse_content_type_map_test() {
+        init_dummy_tracing_subscriber();
+
+        let content_type_map =
+            parse_content_type_map("WASM=application/wasm,.Webmanifest=application/manifest+json")
+                .unwrap();
+
+        assert_eq!(content_type_map.len(), 2);
+        assert_eq!(content_type_map.get("wasm").unwrap(), "application/wasm");
+        assert_eq!(
+            content_type_map.get("webmanifest").unwrap(),
+            "application/manifest+json"
+        );
+
+        assert!(parse_content_type_map("wasm").is_err());
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
@@ -0,0 +1,120 @@
+use chrono::format::{Item, StrftimeItems};
+use chrono::FixedOffset;
+
+const UTC: &str = "UTC";
+
+const INVALID_DATE_PARTITION: &str =
+    "invalid date partition. it must be a non-empty strftime pattern. e.g. %Y/%m/%d";
+const INVALID_DATE_PARTITION_PATH: &str =
+    "invalid date partition. it must not start or end with '/', or contain an empty, '.' or '..' segment.";
+const INVALID_DATE_PARTITION_TIMEZONE: &str =
+    "invalid date partition timezone. valid choices: UTC | +HH:MM | -HH:MM .";
+
+pub fn check_date_partition(date_partition: &str) -> Result<String, String> {
+    if date_partition.is_empty()
+        || StrftimeItems::new(date_partition).any(|item| matches!(item, Item::Error))
+    {
+        return Err(INVALID_DATE_PARTITION.to_string());
+    }
+
+    if date_partition
+        .split('/')
+        .any(|segment| segment.is_empty() || segment == "." || segment == "..")
+    {
+        return Err(INVALID_DATE_PARTITION_PATH.to_string());
+    }
+
+    Ok(date_partition.to_string())
+}
+
+pub fn check_date_partition_timezone(timezone: &str) -> Result<String, String> {
+    parse_date_partition_timezone(timezone)?;
+
+    Ok(timezone.to_string())
+}
+
+pub fn parse_date_partition_timezone(timezone: &str) -> Result<FixedOffset, String> {
+    if timezone.eq_ignore_ascii_case(UTC) {
+        return Ok(FixedOffset::east_opt(0).unwrap());
+    }
+
+    let invalid = || INVALID_DATE_PARTITION_TIMEZONE.to_string();
+
+    let (sign, offset) = match timezone.split_at_checked(1) {
+        Some(("+", offset)) => (1, offset),
+        Some(("-", offset)) => (-1, offset),
+        _ => return Err(invalid()),
+    };
+    let (hours, minutes) = offset.split_once(':').ok_or_else(invalid)?;
+    if hours.len() != 2 || minutes.len() != 2 {
+        return Err(invalid());
+    }
+    let hours = hours.parse::<i32>().map_err(|_| invalid())?;
+    let minutes = minutes.parse::<i32>().map_err(|_| invalid())?;
+    if 23 < hours || 59 < minutes {
+        return Err(invalid());
+    }
+
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60)).ok_or_else(invalid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_valid_date_partition() {
+        assert_eq!(check_date_partition("%Y/%m/%d").unwrap(), "%Y/%m/%d");
+        assert_eq!(
+            check_date_partition("year=%Y/month=%m").unwrap(),
+            "year=%Y/month=%m"
+        );
+        assert_eq!(check_date_partition("%F").unwrap(), "%F");
+        assert_eq!(check_date_partition("archive").unwrap(), "archive");
+    }
+
+    #[test]
+    fn check_invalid_date_partition() {
+        assert!(check_date_partition("").is_err());
+        assert!(check_date_partition("%Y/%Q").is_err());
+        assert!(check_date_partition("%").is_err());
+        assert!(check_date_partition("/%Y/%m").is_err());
+        assert!(check_date_partition("%Y/%m/").is_err());
+        assert!(check_date_partition("%Y//%m").is_err());
+        assert!(check_date_partition("%Y/../%m").is_err());
+        assert!(check_date_partition("./%Y").is_err());
+    }
+
+    #[test]
+    fn parse_valid_date_partition_timezone() {
+        assert_eq!(
+            parse_date_partition_timezone("UTC").unwrap(),
+            FixedOffset::east_opt(0).unwrap()
+        );
+        assert_eq!(
+            parse_date_partition_timezone("utc").unwrap(),
+            FixedOffset::east_opt(0).unwrap()
+        );
+        assert_eq!(
+            parse_date_partition_timezone("+09:00").unwrap(),
+            FixedOffset::east_opt(9 * 3600).unwrap()
+        );
+        assert_eq!(
+            parse_date_partition_timezone("-05:30").unwrap(),
+            FixedOffset::west_opt(5 * 3600 + 30 * 60).unwrap()
+        );
+        assert_eq!(check_date_partition_timezone("+00:00").unwrap(), "+00:00");
+    }
+
+    #[test]
+    fn parse_invalid_date_partition_timezone() {
+        assert!(parse_date_partition_timezone("").is_err());
+        assert!(parse_date_partition_timezone("JST").is_err());
+        assert!(parse_date_partition_timezone("09:00").is_err());
+        assert!(parse_date_partition_timezone("+9:00").is_err());
+        assert!(parse_date_partition_timezone("+0900").is_err());
+        assert!(parse_date_partition_timezone("+24:00").is_err());
+        assert!(parse_date_partition_timezone("+09:60").is_err());
+        assert!(check_date_partition_timezone("+ab:00").is_err());
+    }
+}
@@ -0,0 +1,60 @@
+use std::time::Duration;
+
+const INVALID_DURATION: &str =
+    "invalid duration. a number followed by an optional unit: s | m | h | d (e.g. 90s, 30m, 7d).";
+
+pub fn check_duration(value: &str) -> Result<String, String> {
+    parse_duration(value)?;
+
+    Ok(value.to_string())
+}
+
+pub fn parse_duration(value: &str) -> Result<Duration, String> {
+    let (number, multiplier) = match value.char_indices().last() {
+        Some((index, 's')) => (&value[..index], 1),
+        Some((index, 'm')) => (&value[..index], 60),
+        Some((index, 'h')) => (&value[..index], 60 * 60),
+        Some((index, 'd')) => (&value[..index], 24 * 60 * 60),
+        _ => (value, 1),
+    };
+
+    let number = number
+        .parse::<u64>()
+        .map_err(|_| INVALID_DURATION.to_string())?;
+    let seconds = number
+        .checked_mul(multiplier)
+        .ok_or_else(|| INVALID_DURATION.to_string())?;
+
+    Ok(Duration::from_secs(seconds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_duration_valid() {
+        assert_eq!(parse_duration("0").unwrap(), Duration::from_secs(0));
+        assert_eq!(parse_duration("90").unwrap(), Duration::from_secs(90));
+        assert_eq!(parse_duration("90s").unwrap(), Duration::from_secs(90));
+        assert_eq!(parse_duration("30m").unwrap(), Duration::from_secs(1800));
+        assert_eq!(parse_duration("2h").unwrap(), Duration::from_secs(7200));
+        assert_eq!(parse_duration("7d").unwrap(), Duration::from_secs(604800));
+    }
+
+    #[test]
+    fn parse_duration_invalid() {
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("d").is_err());
+        assert!(parse_duration("-1d").is_err());
+        assert!(parse_duration("1w").is_err());
+        assert!(parse_duration("1.5h").is_err());
+        assert!(parse_duration("18446744073709551615d").is_err());
+    }
+
+    #[test]
+    fn check_duration_returns_value() {
+        assert_eq!(check_duration("7d").unwrap(), "7d");
+        assert!(check_duration("7x").is_err());
+    }
+}
@@ -0,0 +1,28 @@
+use crate::types;
+
+const INVALID_FILE_MODE: &str =
+    "invalid file mode. specify the permission bits in octal, e.g. 644 or 0644 .";
+
+pub fn parse_file_mode(mode: &str) -> Result<u32, String> {
+    types::parse_file_mode(mode).ok_or_else(|| INVALID_FILE_MODE.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_valid_file_mode() {
+        assert_eq!(parse_file_mode("644").unwrap(), 0o644);
+        assert_eq!(parse_file_mode("0600").unwrap(), 0o600);
+        assert_eq!(parse_file_mode("2775").unwrap(), 0o2775);
+    }
+
+    #[test]
+    fn parse_invalid_file_mode() {
+        assert!(parse_file_mode("").is_err());
+        assert!(parse_file_mode("999").is_err());
+        assert!(parse_file_mode("17777").is_err());
+        assert!(parse_file_mode("u+rw").is_err());
+    }
+}
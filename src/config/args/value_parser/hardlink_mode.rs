@@ -0,0 +1,25 @@
+use crate::types::HardlinkMode;
+
+const COPY: &str = "copy";
+const DEDUP_METADATA: &str = "dedup-metadata";
+const SKIP: &str = "skip";
+
+const INVALID_HARDLINK_MODE: &str =
+    "invalid hardlink mode. valid choices: copy | dedup-metadata | skip .";
+
+pub fn parse_hardlink_mode(mode: &str) -> Result<String, String> {
+    if mode != COPY && mode != DEDUP_METADATA && mode != SKIP {
+        return Err(INVALID_HARDLINK_MODE.to_string());
+    }
+
+    Ok(mode.to_string())
+}
+
+pub fn to_hardlink_mode(mode: &str) -> HardlinkMode {
+    match mode {
+        COPY => HardlinkMode::Copy,
+        DEDUP_METADATA => HardlinkMode::DedupMetadata,
+        SKIP => HardlinkMode::Skip,
+        _ => panic!("unknown hardlink mode"),
+    }
+}
@@ -0,0 +1,46 @@
+use crate::types::KeyCase;
+
+const LOWER: &str = "lower";
+const UPPER: &str = "upper";
+
+const INVALID_KEY_CASE: &str = "invalid key case. valid choices: lower | upper .";
+
+pub fn parse_key_case(key_case: &str) -> Result<String, String> {
+    let key_case = key_case.to_ascii_lowercase();
+    if key_case != LOWER && key_case != UPPER {
+        return Err(INVALID_KEY_CASE.to_string());
+    }
+
+    Ok(key_case)
+}
+
+pub fn to_key_case(key_case: &str) -> KeyCase {
+    match key_case {
+        LOWER => KeyCase::Lower,
+        UPPER => KeyCase::Upper,
+        _ => panic!("unknown key case"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_valid_key_case() {
+        assert_eq!(parse_key_case("lower").unwrap(), "lower");
+        assert_eq!(parse_key_case("UPPER").unwrap(), "upper");
+    }
+
+    #[test]
+    fn parse_invalid_key_case() {
+        assert!(parse_key_case("title").is_err());
+        assert!(parse_key_case("").is_err());
+    }
+
+    #[test]
+    fn to_key_case_test() {
+        assert_eq!(to_key_case("lower"), KeyCase::Lower);
+        assert_eq!(to_key_case("upper"), KeyCase::Upper);
+    }
+}
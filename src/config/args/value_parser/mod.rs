@@ -1,10 +1,32 @@
+pub mod account_id;
 pub mod canned_acl;
 pub mod checksum_algorithm;
+pub mod content_disposition_template;
+pub mod content_encoding_map;
+pub mod content_type_map;
+pub mod date_partition;
+pub mod duration;
+pub mod file_mode;
+pub mod hardlink_mode;
 pub mod human_bytes;
+pub mod key_case;
 pub mod metadata;
+pub mod prefix_file;
 pub mod regex;
+pub mod retry_jitter;
+#[cfg(feature = "s3_select")]
+pub mod select_format;
+pub mod source_version_map;
 pub mod sse;
+pub mod sse_c_key_file;
 pub mod storage_class;
 pub mod storage_path;
+pub mod tag_guard_mode;
 pub mod tagging;
+pub mod transfer_order;
+pub mod unicode_normalization;
+pub mod upload_manifest;
 pub mod url;
+pub mod user_agent;
+pub mod versions_order;
+pub mod zero_byte_object_policy;
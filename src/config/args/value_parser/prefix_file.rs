@@ -0,0 +1,80 @@
+use std::fs;
+
+const PREFIX_FILE_READ_FAILED: &str = "failed to read the prefix file.";
+const NO_PREFIX_FOUND: &str = "no prefix found in the prefix file.";
+const INVALID_PREFIX: &str = "prefix must be a first-level prefix(e.g. dir1/): ";
+
+pub fn check_prefix_file(path: &str) -> Result<String, String> {
+    parse_prefix_file(path)?;
+
+    Ok(path.to_string())
+}
+
+/// Reads first-level prefixes, one per line. Blank lines and lines starting with `#` are ignored.
+/// A trailing `/` is appended if it is missing.
+pub fn parse_prefix_file(path: &str) -> Result<Vec<String>, String> {
+    let content = fs::read_to_string(path).map_err(|_| PREFIX_FILE_READ_FAILED.to_string())?;
+
+    let mut prefixes: Vec<String> = Vec::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let prefix = if line.ends_with('/') {
+            line.to_string()
+        } else {
+            format!("{}/", line)
+        };
+        if prefix == "/" || prefix[..prefix.len() - 1].contains('/') {
+            return Err(format!("{}{}", INVALID_PREFIX, line));
+        }
+
+        if !prefixes.contains(&prefix) {
+            prefixes.push(prefix);
+        }
+    }
+
+    if prefixes.is_empty() {
+        return Err(NO_PREFIX_FOUND.to_string());
+    }
+
+    Ok(prefixes)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use tempfile::NamedTempFile;
+
+    use super::*;
+
+    #[test]
+    fn parse_prefix_file_valid() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "# comment\ndir1/\n\ndir2\n dir3/ \ndir1").unwrap();
+
+        let prefixes = parse_prefix_file(file.path().to_str().unwrap()).unwrap();
+        assert_eq!(prefixes, vec!["dir1/", "dir2/", "dir3/"]);
+        assert!(check_prefix_file(file.path().to_str().unwrap()).is_ok());
+    }
+
+    #[test]
+    fn parse_prefix_file_invalid() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "dir1/dir2/").unwrap();
+        assert!(parse_prefix_file(file.path().to_str().unwrap()).is_err());
+
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "/").unwrap();
+        assert!(parse_prefix_file(file.path().to_str().unwrap()).is_err());
+
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "# comment only").unwrap();
+        assert!(parse_prefix_file(file.path().to_str().unwrap()).is_err());
+
+        assert!(check_prefix_file("./test_data/prefix_file/not_found.txt").is_err());
+    }
+}
@@ -0,0 +1,50 @@
+use crate::types::RetryJitter;
+
+const FULL: &str = "full";
+const EQUAL: &str = "equal";
+const NONE: &str = "none";
+
+const INVALID_RETRY_JITTER: &str = "invalid retry jitter. valid choices: full | equal | none .";
+
+pub fn parse_retry_jitter(retry_jitter: &str) -> Result<String, String> {
+    let retry_jitter = retry_jitter.to_ascii_lowercase();
+    if retry_jitter != FULL && retry_jitter != EQUAL && retry_jitter != NONE {
+        return Err(INVALID_RETRY_JITTER.to_string());
+    }
+
+    Ok(retry_jitter)
+}
+
+pub fn to_retry_jitter(retry_jitter: &str) -> RetryJitter {
+    match retry_jitter {
+        FULL => RetryJitter::Full,
+        EQUAL => RetryJitter::Equal,
+        NONE => RetryJitter::Disabled,
+        _ => panic!("unknown retry jitter"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_valid_retry_jitter() {
+        assert_eq!(parse_retry_jitter("full").unwrap(), "full");
+        assert_eq!(parse_retry_jitter("Equal").unwrap(), "equal");
+        assert_eq!(parse_retry_jitter("NONE").unwrap(), "none");
+    }
+
+    #[test]
+    fn parse_invalid_retry_jitter() {
+        assert!(parse_retry_jitter("decorrelated").is_err());
+        assert!(parse_retry_jitter("").is_err());
+    }
+
+    #[test]
+    fn to_retry_jitter_test() {
+        assert_eq!(to_retry_jitter("full"), RetryJitter::Full);
+        assert_eq!(to_retry_jitter("equal"), RetryJitter::Equal);
+        assert_eq!(to_retry_jitter("none"), RetryJitter::Disabled);
+    }
+}
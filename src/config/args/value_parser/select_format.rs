@@ -0,0 +1,79 @@
+use crate::types::{SelectInputFormat, SelectOutputFormat};
+
+const CSV: &str = "csv";
+const JSON: &str = "json";
+const PARQUET: &str = "parquet";
+
+const INVALID_SELECT_INPUT_FORMAT: &str =
+    "invalid select input format. valid choices: csv | json | parquet .";
+const INVALID_SELECT_OUTPUT_FORMAT: &str =
+    "invalid select output format. valid choices: csv | json .";
+
+pub fn parse_select_input_format(format: &str) -> Result<String, String> {
+    if format != CSV && format != JSON && format != PARQUET {
+        return Err(INVALID_SELECT_INPUT_FORMAT.to_string());
+    }
+
+    Ok(format.to_string())
+}
+
+pub fn parse_select_output_format(format: &str) -> Result<String, String> {
+    if format != CSV && format != JSON {
+        return Err(INVALID_SELECT_OUTPUT_FORMAT.to_string());
+    }
+
+    Ok(format.to_string())
+}
+
+pub fn to_select_input_format(format: &str) -> SelectInputFormat {
+    match format {
+        CSV => SelectInputFormat::Csv,
+        JSON => SelectInputFormat::Json,
+        PARQUET => SelectInputFormat::Parquet,
+        _ => panic!("unknown select input format"),
+    }
+}
+
+pub fn to_select_output_format(format: &str) -> SelectOutputFormat {
+    match format {
+        CSV => SelectOutputFormat::Csv,
+        JSON => SelectOutputFormat::Json,
+        _ => panic!("unknown select output format"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_select_input_format_test() {
+        assert_eq!(parse_select_input_format("csv").unwrap(), "csv");
+        assert_eq!(parse_select_input_format("json").unwrap(), "json");
+        assert_eq!(parse_select_input_format("parquet").unwrap(), "parquet");
+
+        assert!(parse_select_input_format("CSV").is_err());
+        assert!(parse_select_input_format("").is_err());
+    }
+
+    #[test]
+    fn parse_select_output_format_test() {
+        assert_eq!(parse_select_output_format("csv").unwrap(), "csv");
+        assert_eq!(parse_select_output_format("json").unwrap(), "json");
+
+        assert!(parse_select_output_format("parquet").is_err());
+        assert!(parse_select_output_format("").is_err());
+    }
+
+    #[test]
+    fn to_select_format_test() {
+        assert_eq!(to_select_input_format("csv"), SelectInputFormat::Csv);
+        assert_eq!(to_select_input_format("json"), SelectInputFormat::Json);
+        assert_eq!(
+            to_select_input_format("parquet"),
+            SelectInputFormat::Parquet
+        );
+        assert_eq!(to_select_output_format("csv"), SelectOutputFormat::Csv);
+        assert_eq!(to_select_output_format("json"), SelectOutputFormat::Json);
+    }
+}
@@ -0,0 +1,109 @@
+use std::collections::HashSet;
+use std::fs;
+
+use crate::types::SourceVersionMap;
+
+const SOURCE_VERSION_MAP_READ_FAILED: &str = "failed to read the source version map.";
+const NO_ENTRY_FOUND: &str = "no entry found in the source version map.";
+const INVALID_ENTRY: &str = "source version map entry must be `key,version_id`: ";
+const DUPLICATE_KEY: &str = "duplicate key in the source version map: ";
+const HEADER: &str = "key,version_id";
+
+pub fn check_source_version_map(path: &str) -> Result<String, String> {
+    parse_source_version_map(path)?;
+
+    Ok(path.to_string())
+}
+
+/// Reads `key,version_id` rows. Blank lines, lines starting with `#` and the header row are ignored.
+/// The line is split at the last comma, so the key may contain commas.
+pub fn parse_source_version_map(path: &str) -> Result<SourceVersionMap, String> {
+    let content =
+        fs::read_to_string(path).map_err(|_| SOURCE_VERSION_MAP_READ_FAILED.to_string())?;
+
+    let mut entries = Vec::new();
+    let mut keys = HashSet::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line == HEADER {
+            continue;
+        }
+
+        let Some((key, version_id)) = line.rsplit_once(',') else {
+            return Err(format!("{}{}", INVALID_ENTRY, line));
+        };
+        let (key, version_id) = (key.trim(), version_id.trim());
+        if key.is_empty() || version_id.is_empty() || key.ends_with('/') {
+            return Err(format!("{}{}", INVALID_ENTRY, line));
+        }
+
+        if !keys.insert(key.to_string()) {
+            return Err(format!("{}{}", DUPLICATE_KEY, key));
+        }
+        entries.push((key.to_string(), version_id.to_string()));
+    }
+
+    if entries.is_empty() {
+        return Err(NO_ENTRY_FOUND.to_string());
+    }
+
+    Ok(SourceVersionMap { entries })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use tempfile::NamedTempFile;
+
+    use super::*;
+
+    #[test]
+    fn parse_source_version_map_valid() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(
+            file,
+            "key,version_id\n# comment\ndir1/data1,version1\n\n a,b.csv , version2 "
+        )
+        .unwrap();
+
+        let source_version_map = parse_source_version_map(file.path().to_str().unwrap()).unwrap();
+        assert_eq!(
+            source_version_map.entries,
+            vec![
+                ("dir1/data1".to_string(), "version1".to_string()),
+                ("a,b.csv".to_string(), "version2".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_source_version_map_duplicate_key() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "key,version1\nkey,version2").unwrap();
+
+        assert_eq!(
+            parse_source_version_map(file.path().to_str().unwrap()).unwrap_err(),
+            format!("{}key", DUPLICATE_KEY)
+        );
+    }
+
+    #[test]
+    fn parse_source_version_map_invalid() {
+        for content in ["key", "key,", ",version1", "dir/,version1"] {
+            let mut file = NamedTempFile::new().unwrap();
+            writeln!(file, "{}", content).unwrap();
+
+            assert!(parse_source_version_map(file.path().to_str().unwrap()).is_err());
+        }
+
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "# comment only").unwrap();
+        assert_eq!(
+            parse_source_version_map(file.path().to_str().unwrap()).unwrap_err(),
+            NO_ENTRY_FOUND
+        );
+
+        assert!(parse_source_version_map("./not_found_version_map.csv").is_err());
+    }
+}
@@ -1,8 +1,14 @@
 use std::str::FromStr;
 
 use aws_sdk_s3::types::ServerSideEncryption;
+use regex::Regex;
 
 const INVALID_SSE_VALUE: &str = "invalid sse value. valid choices: AES256 | aws:kms .";
+const INVALID_KMS_KEY_ALIAS: &str =
+    "invalid KMS key alias. e.g. alias/my-key | arn:aws:kms:us-east-1:123456789012:alias/my-key .";
+
+const KMS_KEY_ALIAS_NAME_REGEX: &str = r"^alias/[a-zA-Z0-9/_-]+$";
+const KMS_KEY_ALIAS_ARN_REGEX: &str = r"^arn:[^:]+:kms:[^:]*:[^:]*:alias/[a-zA-Z0-9/_-]+$";
 
 pub fn parse_sse(sse: &str) -> Result<String, String> {
     #[allow(deprecated)]
@@ -26,3 +32,56 @@ pub fn parse_sse_c(sse: &str) -> Result<String, String> {
 
     Ok(sse.to_string())
 }
+
+// KMS key ID, key ARN, alias name(alias/my-key) and alias ARN are accepted.
+// only the syntax of an alias is checked here. s3sync does not call KMS(DescribeKey), so the alias is
+// resolved to the KMS key by S3 when the object is uploaded, and an alias that does not exist fails
+// the first upload, not the startup.
+pub fn parse_sse_kms_key_id(key_id: &str) -> Result<String, String> {
+    if is_kms_key_alias(key_id)
+        && !Regex::new(KMS_KEY_ALIAS_NAME_REGEX)
+            .unwrap()
+            .is_match(key_id)
+        && !Regex::new(KMS_KEY_ALIAS_ARN_REGEX)
+            .unwrap()
+            .is_match(key_id)
+    {
+        return Err(INVALID_KMS_KEY_ALIAS.to_string());
+    }
+
+    Ok(key_id.to_string())
+}
+
+fn is_kms_key_alias(key_id: &str) -> bool {
+    key_id.starts_with("alias/") || key_id.contains(":alias/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_sse_kms_key_id_test() {
+        assert!(parse_sse_kms_key_id("1234abcd-12ab-34cd-56ef-1234567890ab").is_ok());
+        assert!(parse_sse_kms_key_id(
+            "arn:aws:kms:us-east-1:123456789012:key/1234abcd-12ab-34cd-56ef-1234567890ab"
+        )
+        .is_ok());
+        assert!(parse_sse_kms_key_id("alias/my-key").is_ok());
+        assert!(parse_sse_kms_key_id("alias/team/my_key-1").is_ok());
+        assert!(parse_sse_kms_key_id("arn:aws:kms:us-east-1:123456789012:alias/my-key").is_ok());
+
+        assert!(parse_sse_kms_key_id("alias/").is_err());
+        assert!(parse_sse_kms_key_id("alias/my key").is_err());
+        assert!(parse_sse_kms_key_id("arn:aws:kms:us-east-1:123456789012:alias/").is_err());
+    }
+
+    #[test]
+    fn is_kms_key_alias_test() {
+        assert!(is_kms_key_alias("alias/my-key"));
+        assert!(is_kms_key_alias(
+            "arn:aws:kms:us-east-1:123456789012:alias/my-key"
+        ));
+        assert!(!is_kms_key_alias("1234abcd-12ab-34cd-56ef-1234567890ab"));
+    }
+}
@@ -0,0 +1,144 @@
+use std::fs;
+
+use base64::{engine::general_purpose, Engine as _};
+use zeroize::Zeroizing;
+
+const AUTO: &str = "auto";
+const BINARY: &str = "binary";
+const BASE64: &str = "base64";
+
+const SSE_C_KEY_LENGTH: usize = 32;
+
+const INVALID_SSE_C_KEY_FORMAT: &str =
+    "invalid sse-c key format. valid choices: auto | binary | base64 .";
+const SSE_C_KEY_FILE_READ_FAILED: &str = "failed to read the sse-c key file: ";
+const INVALID_SSE_C_KEY: &str = "sse-c key must be a 256bit key(32 bytes): ";
+const INVALID_SSE_C_KEY_VALUE: &str = "sse-c key must be a base64 encoded 256bit key(32 bytes).";
+
+pub fn parse_sse_c_key_format(format: &str) -> Result<String, String> {
+    if format != AUTO && format != BINARY && format != BASE64 {
+        return Err(INVALID_SSE_C_KEY_FORMAT.to_string());
+    }
+
+    Ok(format.to_string())
+}
+
+/// Reads a raw 256bit SSE-C key from the file, and returns the base64 encoded key and its base64 encoded MD5 digest.
+/// With `auto`, a 32 bytes file is treated as binary, otherwise as base64.
+pub fn read_sse_c_key_file(path: &str, format: &str) -> Result<(String, String), String> {
+    let content = Zeroizing::new(
+        fs::read(path).map_err(|e| format!("{}{}: {}", SSE_C_KEY_FILE_READ_FAILED, path, e))?,
+    );
+
+    let is_binary = format == BINARY || (format == AUTO && content.len() == SSE_C_KEY_LENGTH);
+    let key = if is_binary {
+        content.clone()
+    } else {
+        let encoded = Zeroizing::new(content.trim_ascii().to_vec());
+        Zeroizing::new(
+            general_purpose::STANDARD
+                .decode(encoded.as_slice())
+                .map_err(|_| format!("{}{}", INVALID_SSE_C_KEY, path))?,
+        )
+    };
+
+    if key.len() != SSE_C_KEY_LENGTH {
+        return Err(format!("{}{}", INVALID_SSE_C_KEY, path));
+    }
+
+    Ok((
+        general_purpose::STANDARD.encode(key.as_slice()),
+        general_purpose::STANDARD.encode(md5::compute(key.as_slice()).as_slice()),
+    ))
+}
+
+/// Returns the base64 encoded MD5 digest of the base64 encoded 256bit SSE-C key.
+pub fn generate_sse_c_key_md5(key: &str) -> Result<String, String> {
+    let decoded = Zeroizing::new(
+        general_purpose::STANDARD
+            .decode(key.trim())
+            .map_err(|_| INVALID_SSE_C_KEY_VALUE.to_string())?,
+    );
+
+    if decoded.len() != SSE_C_KEY_LENGTH {
+        return Err(INVALID_SSE_C_KEY_VALUE.to_string());
+    }
+
+    Ok(general_purpose::STANDARD.encode(md5::compute(decoded.as_slice()).as_slice()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use tempfile::NamedTempFile;
+
+    use super::*;
+
+    const TEST_SSE_C_KEY: &str = "MDEyMzQ1Njc4OWFiY2RlZjAxMjM0NTY3ODlhYmNkZWY=";
+    const TEST_SSE_C_KEY_MD5: &str = "hRasmdxgYDKV3nvbahU1MA==";
+
+    #[test]
+    fn parse_sse_c_key_format_test() {
+        assert!(parse_sse_c_key_format("auto").is_ok());
+        assert!(parse_sse_c_key_format("binary").is_ok());
+        assert!(parse_sse_c_key_format("base64").is_ok());
+        assert!(parse_sse_c_key_format("hex").is_err());
+    }
+
+    #[test]
+    fn read_binary_key_file() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"0123456789abcdef0123456789abcdef").unwrap();
+
+        for format in ["auto", "binary"] {
+            let (key, md5) = read_sse_c_key_file(file.path().to_str().unwrap(), format).unwrap();
+            assert_eq!(key, TEST_SSE_C_KEY);
+            assert_eq!(md5, TEST_SSE_C_KEY_MD5);
+        }
+
+        assert!(read_sse_c_key_file(file.path().to_str().unwrap(), "base64").is_err());
+    }
+
+    #[test]
+    fn read_base64_key_file() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "{}", TEST_SSE_C_KEY).unwrap();
+
+        for format in ["auto", "base64"] {
+            let (key, md5) = read_sse_c_key_file(file.path().to_str().unwrap(), format).unwrap();
+            assert_eq!(key, TEST_SSE_C_KEY);
+            assert_eq!(md5, TEST_SSE_C_KEY_MD5);
+        }
+
+        assert!(read_sse_c_key_file(file.path().to_str().unwrap(), "binary").is_err());
+    }
+
+    #[test]
+    fn generate_sse_c_key_md5_test() {
+        assert_eq!(
+            generate_sse_c_key_md5(TEST_SSE_C_KEY).unwrap(),
+            TEST_SSE_C_KEY_MD5
+        );
+        assert_eq!(
+            generate_sse_c_key_md5("MDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDA=").unwrap(),
+            "zZ5FnqcIqUjVwvWmyog4zw=="
+        );
+        assert_eq!(
+            generate_sse_c_key_md5("MTExMTExMTExMTExMTExMTExMTExMTExMTExMTExMTE=").unwrap(),
+            "GoDL8oWeAZVZNl1r5Hh5Tg=="
+        );
+
+        assert!(generate_sse_c_key_md5("MDEyMzQ1Njc4OWFiY2RlZg==").is_err());
+        assert!(generate_sse_c_key_md5("not base64").is_err());
+    }
+
+    #[test]
+    fn read_invalid_key_file() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"0123456789abcdef").unwrap();
+        assert!(read_sse_c_key_file(file.path().to_str().unwrap(), "auto").is_err());
+
+        assert!(read_sse_c_key_file("./test_data/sse_c_key_file/not_found", "auto").is_err());
+    }
+}
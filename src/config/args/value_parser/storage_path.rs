@@ -8,7 +8,13 @@ const INVALID_SCHEME: &str = "scheme must be s3:// .";
 const NO_BUCKET_NAME_SPECIFIED: &str = "bucket name must be specified.";
 const NO_PATH_SPECIFIED: &str = "path must be specified.";
 
+pub const STDOUT_PATH: &str = "-";
+
 pub fn check_storage_path(path: &str) -> Result<String, String> {
+    if path == STDOUT_PATH {
+        return Ok(path.to_string());
+    }
+
     let result = Url::parse(path);
     if result == Err(ParseError::RelativeUrlWithoutBase) {
         if path.is_empty() {
@@ -142,6 +148,17 @@ mod tests {
         check_storage_path("s3://my-bucket/hello/こんばんは/☃").unwrap();
     }
 
+    #[test]
+    fn check_stdout_path() {
+        init_dummy_tracing_subscriber();
+
+        assert_eq!(check_storage_path("-").unwrap(), "-");
+        assert_eq!(
+            check_storage_path("-dir").unwrap(),
+            format!("-dir{}", std::path::MAIN_SEPARATOR)
+        );
+    }
+
     #[test]
     fn parse_local_path() {
         init_dummy_tracing_subscriber();
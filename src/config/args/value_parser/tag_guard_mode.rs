@@ -0,0 +1,46 @@
+use crate::types::TagGuardMode;
+
+const WARN: &str = "warn";
+const ERROR: &str = "error";
+
+const INVALID_TAG_GUARD_MODE: &str = "invalid tag guard mode. valid choices: warn | error .";
+
+pub fn parse_tag_guard_mode(tag_guard_mode: &str) -> Result<String, String> {
+    let tag_guard_mode = tag_guard_mode.to_ascii_lowercase();
+    if tag_guard_mode != WARN && tag_guard_mode != ERROR {
+        return Err(INVALID_TAG_GUARD_MODE.to_string());
+    }
+
+    Ok(tag_guard_mode)
+}
+
+pub fn to_tag_guard_mode(tag_guard_mode: &str) -> TagGuardMode {
+    match tag_guard_mode {
+        WARN => TagGuardMode::Warn,
+        ERROR => TagGuardMode::Error,
+        _ => panic!("unknown tag guard mode"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_valid_tag_guard_mode() {
+        assert_eq!(parse_tag_guard_mode("warn").unwrap(), "warn");
+        assert_eq!(parse_tag_guard_mode("Error").unwrap(), "error");
+    }
+
+    #[test]
+    fn parse_invalid_tag_guard_mode() {
+        assert!(parse_tag_guard_mode("skip").is_err());
+        assert!(parse_tag_guard_mode("").is_err());
+    }
+
+    #[test]
+    fn to_tag_guard_mode_test() {
+        assert_eq!(to_tag_guard_mode("warn"), TagGuardMode::Warn);
+        assert_eq!(to_tag_guard_mode("error"), TagGuardMode::Error);
+    }
+}
@@ -1,5 +1,7 @@
 use regex::Regex;
 
+use crate::types::check_tag_limits;
+
 const INVALID_TAGGING: &str = "invalid tagging.";
 
 pub fn parse_tagging(tagging: &str) -> Result<String, String> {
@@ -14,9 +16,34 @@ pub fn parse_tagging(tagging: &str) -> Result<String, String> {
         return Err(INVALID_TAGGING.to_string());
     }
 
+    check_tagging_limits(tagging)?;
+
     Ok(tagging.to_string())
 }
 
+// S3 rejects the whole PutObject if the tagging exceeds the limits, so it is checked up front.
+fn check_tagging_limits(tagging: &str) -> Result<(), String> {
+    let mut tags = Vec::new();
+    for tag in tagging.split('&') {
+        let (key, value) = tag.split_once('=').unwrap();
+        let key = decode_tag_part(key)?;
+        let value = decode_tag_part(value)?;
+        tags.push((key, value));
+    }
+
+    check_tag_limits(
+        tags.iter()
+            .map(|(key, value)| (key.as_str(), value.as_str())),
+    )
+    .map_err(|e| format!("{} {}.", INVALID_TAGGING, e))
+}
+
+fn decode_tag_part(part: &str) -> Result<String, String> {
+    urlencoding::decode(&part.replace('+', " "))
+        .map(|decoded| decoded.to_string())
+        .map_err(|_| INVALID_TAGGING.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -41,6 +68,30 @@ mod tests {
         assert!(parse_tagging("key=value,key2=value2").is_err());
     }
 
+    #[test]
+    fn parse_tagging_limits() {
+        init_dummy_tracing_subscriber();
+
+        let ten_tags = (1..=10)
+            .map(|i| format!("key{i}=value{i}"))
+            .collect::<Vec<String>>()
+            .join("&");
+        parse_tagging(&ten_tags).unwrap();
+        assert!(parse_tagging(&format!("{ten_tags}&key11=value11")).is_err());
+
+        parse_tagging(&format!("{}=value", "k".repeat(128))).unwrap();
+        assert!(parse_tagging(&format!("{}=value", "k".repeat(129))).is_err());
+
+        parse_tagging(&format!("key={}", "v".repeat(256))).unwrap();
+        assert!(parse_tagging(&format!("key={}", "v".repeat(257))).is_err());
+
+        // the length is counted after URL decoding.
+        parse_tagging(&format!("key={}", "%E3%83%90".repeat(256))).unwrap();
+        assert!(parse_tagging(&format!("key={}", "%E3%83%90".repeat(257))).is_err());
+
+        assert!(parse_tagging("key=%FF").is_err());
+    }
+
     fn init_dummy_tracing_subscriber() {
         let _ = tracing_subscriber::fmt()
             .with_env_filter("dummy=trace")
@@ -0,0 +1,27 @@
+use crate::types::TransferOrder;
+
+const SIZE_ASC: &str = "size-asc";
+const SIZE_DESC: &str = "size-desc";
+const KEY: &str = "key";
+const RANDOM: &str = "random";
+
+const INVALID_TRANSFER_ORDER: &str =
+    "invalid transfer order. valid choices: size-asc | size-desc | key | random .";
+
+pub fn parse_transfer_order(order: &str) -> Result<String, String> {
+    if order != SIZE_ASC && order != SIZE_DESC && order != KEY && order != RANDOM {
+        return Err(INVALID_TRANSFER_ORDER.to_string());
+    }
+
+    Ok(order.to_string())
+}
+
+pub fn to_transfer_order(order: &str) -> TransferOrder {
+    match order {
+        SIZE_ASC => TransferOrder::SizeAsc,
+        SIZE_DESC => TransferOrder::SizeDesc,
+        KEY => TransferOrder::Key,
+        RANDOM => TransferOrder::Random,
+        _ => panic!("unknown transfer order"),
+    }
+}
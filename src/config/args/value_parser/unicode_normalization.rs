@@ -0,0 +1,47 @@
+use crate::types::UnicodeNormalization;
+
+const NFC: &str = "nfc";
+const NFD: &str = "nfd";
+
+const INVALID_UNICODE_NORMALIZATION: &str =
+    "invalid unicode normalization form. valid choices: NFC | NFD .";
+
+pub fn parse_unicode_normalization(form: &str) -> Result<String, String> {
+    let form = form.to_ascii_lowercase();
+    if form != NFC && form != NFD {
+        return Err(INVALID_UNICODE_NORMALIZATION.to_string());
+    }
+
+    Ok(form)
+}
+
+pub fn to_unicode_normalization(form: &str) -> UnicodeNormalization {
+    match form {
+        NFC => UnicodeNormalization::Nfc,
+        NFD => UnicodeNormalization::Nfd,
+        _ => panic!("unknown unicode normalization form"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_valid_form() {
+        assert_eq!(parse_unicode_normalization("NFC").unwrap(), "nfc");
+        assert_eq!(parse_unicode_normalization("nfd").unwrap(), "nfd");
+    }
+
+    #[test]
+    fn parse_invalid_form() {
+        assert!(parse_unicode_normalization("NFKC").is_err());
+        assert!(parse_unicode_normalization("").is_err());
+    }
+
+    #[test]
+    fn to_unicode_normalization_test() {
+        assert_eq!(to_unicode_normalization("nfc"), UnicodeNormalization::Nfc);
+        assert_eq!(to_unicode_normalization("nfd"), UnicodeNormalization::Nfd);
+    }
+}
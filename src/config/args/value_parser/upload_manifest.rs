@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::types::UploadManifest;
+
+const UPLOAD_MANIFEST_READ_FAILED: &str = "failed to read the upload manifest.";
+const NO_ENTRY_FOUND: &str = "no entry found in the upload manifest.";
+const INVALID_ENTRY: &str = "upload manifest entry must be `local_path,target_key`: ";
+const DUPLICATE_TARGET_KEY: &str = "duplicate target key in the upload manifest: ";
+const HEADER: &str = "local_path,target_key";
+
+pub fn check_upload_manifest(path: &str) -> Result<String, String> {
+    parse_upload_manifest(path)?;
+
+    Ok(path.to_string())
+}
+
+/// Reads `local_path,target_key` rows. Blank lines, lines starting with `#` and the header row are ignored.
+/// The line is split at the first comma, so the local path must not contain a comma.
+pub fn parse_upload_manifest(path: &str) -> Result<UploadManifest, String> {
+    let content = fs::read_to_string(path).map_err(|_| UPLOAD_MANIFEST_READ_FAILED.to_string())?;
+
+    let mut keys = Vec::new();
+    let mut local_paths = HashMap::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line == HEADER {
+            continue;
+        }
+
+        let Some((local_path, key)) = line.split_once(',') else {
+            return Err(format!("{}{}", INVALID_ENTRY, line));
+        };
+        let (local_path, key) = (local_path.trim(), key.trim());
+        if local_path.is_empty() || key.is_empty() || key.ends_with('/') {
+            return Err(format!("{}{}", INVALID_ENTRY, line));
+        }
+
+        if local_paths
+            .insert(key.to_string(), PathBuf::from(local_path))
+            .is_some()
+        {
+            return Err(format!("{}{}", DUPLICATE_TARGET_KEY, key));
+        }
+        keys.push(key.to_string());
+    }
+
+    if keys.is_empty() {
+        return Err(NO_ENTRY_FOUND.to_string());
+    }
+
+    Ok(UploadManifest { keys, local_paths })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use tempfile::NamedTempFile;
+
+    use super::*;
+
+    #[test]
+    fn parse_upload_manifest_valid() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(
+            file,
+            "local_path,target_key\n# comment\n/tmp/a/data1,flat/one\n\n dir1/data2 , flat/two "
+        )
+        .unwrap();
+
+        let manifest = parse_upload_manifest(file.path().to_str().unwrap()).unwrap();
+        assert_eq!(manifest.keys, vec!["flat/one", "flat/two"]);
+        assert_eq!(
+            manifest.local_paths.get("flat/one").unwrap(),
+            &PathBuf::from("/tmp/a/data1")
+        );
+        assert_eq!(
+            manifest.local_paths.get("flat/two").unwrap(),
+            &PathBuf::from("dir1/data2")
+        );
+    }
+
+    #[test]
+    fn parse_upload_manifest_duplicate_key() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "data1,key\ndata2,key").unwrap();
+
+        assert_eq!(
+            parse_upload_manifest(file.path().to_str().unwrap()).unwrap_err(),
+            format!("{}key", DUPLICATE_TARGET_KEY)
+        );
+    }
+
+    #[test]
+    fn parse_upload_manifest_invalid() {
+        for content in ["data1", "data1,", ",key", "data1,dir/"] {
+            let mut file = NamedTempFile::new().unwrap();
+            writeln!(file, "{}", content).unwrap();
+
+            assert!(parse_upload_manifest(file.path().to_str().unwrap()).is_err());
+        }
+
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "# comment only").unwrap();
+        assert_eq!(
+            parse_upload_manifest(file.path().to_str().unwrap()).unwrap_err(),
+            NO_ENTRY_FOUND
+        );
+
+        assert!(parse_upload_manifest("./not_found_manifest.csv").is_err());
+    }
+}
@@ -0,0 +1,38 @@
+const INVALID_USER_AGENT_SUFFIX: &str =
+    "invalid user agent suffix. alphanumeric characters and !#$%&'*+-.^_`|~ are allowed.";
+
+// the suffix is used as the app name of the SDK, so it must be a token of RFC 9110.
+pub fn check_user_agent_suffix(value: &str) -> Result<String, String> {
+    if value.is_empty()
+        || !value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c))
+    {
+        return Err(INVALID_USER_AGENT_SUFFIX.to_string());
+    }
+
+    Ok(value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_user_agent_suffix_valid() {
+        assert_eq!(check_user_agent_suffix("my-app").unwrap(), "my-app");
+        assert_eq!(
+            check_user_agent_suffix("team_a.batch~1").unwrap(),
+            "team_a.batch~1"
+        );
+    }
+
+    #[test]
+    fn check_user_agent_suffix_invalid() {
+        assert!(check_user_agent_suffix("").is_err());
+        assert!(check_user_agent_suffix("my app").is_err());
+        assert!(check_user_agent_suffix("my/app").is_err());
+        assert!(check_user_agent_suffix("my(app)").is_err());
+        assert!(check_user_agent_suffix("アプリ").is_err());
+    }
+}
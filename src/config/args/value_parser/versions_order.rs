@@ -0,0 +1,17 @@
+const OLDEST_FIRST: &str = "oldest-first";
+const NEWEST_FIRST: &str = "newest-first";
+
+const INVALID_VERSIONS_ORDER: &str =
+    "invalid versions order. valid choices: oldest-first | newest-first .";
+
+pub fn parse_versions_order(order: &str) -> Result<String, String> {
+    if order != OLDEST_FIRST && order != NEWEST_FIRST {
+        return Err(INVALID_VERSIONS_ORDER.to_string());
+    }
+
+    Ok(order.to_string())
+}
+
+pub fn is_newest_first(order: &str) -> bool {
+    order == NEWEST_FIRST
+}
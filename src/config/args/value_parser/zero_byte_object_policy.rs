@@ -0,0 +1,48 @@
+const SYNC: &str = "sync";
+const SKIP: &str = "skip";
+
+const INVALID_ZERO_BYTE_OBJECT_POLICY: &str =
+    "invalid zero byte object policy. valid choices: sync | skip .";
+
+pub fn parse_zero_byte_object_policy(policy: &str) -> Result<String, String> {
+    if policy != SYNC && policy != SKIP {
+        return Err(INVALID_ZERO_BYTE_OBJECT_POLICY.to_string());
+    }
+
+    Ok(policy.to_string())
+}
+
+pub fn is_skip_policy(policy: &str) -> bool {
+    policy == SKIP
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_zero_byte_object_policy_test() {
+        init_dummy_tracing_subscriber();
+
+        assert_eq!(parse_zero_byte_object_policy("sync").unwrap(), "sync");
+        assert_eq!(parse_zero_byte_object_policy("skip").unwrap(), "skip");
+
+        assert!(parse_zero_byte_object_policy("").is_err());
+        assert!(parse_zero_byte_object_policy("SKIP").is_err());
+        assert!(parse_zero_byte_object_policy("ignore").is_err());
+    }
+
+    #[test]
+    fn is_skip_policy_test() {
+        init_dummy_tracing_subscriber();
+
+        assert!(is_skip_policy("skip"));
+        assert!(!is_skip_policy("sync"));
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
@@ -1,67 +1,181 @@
 use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 
+use anyhow::{Context, Result};
 use aws_sdk_s3::types::{
     ChecksumAlgorithm, ChecksumMode, ObjectCannedAcl, ServerSideEncryption, StorageClass,
 };
 use aws_smithy_types::checksum_config::RequestChecksumCalculation;
 use chrono::{DateTime, Utc};
 use regex::Regex;
+use serde::Serialize;
 
-use crate::types::{ClientConfigLocation, S3Credentials, SseCustomerKey, SseKmsKeyId, StoragePath};
+#[cfg(feature = "s3_select")]
+use crate::types::SourceSelectConfig;
+use crate::types::{
+    ClientConfigLocation, DatePartition, HardlinkMode, KeyCase, RetryJitter, S3Credentials,
+    SourceVersionMap, SseCustomerKey, SseKmsKeyId, StoragePath, TagGuardMode, TransferOrder,
+    UnicodeNormalization, UploadManifest,
+};
 
 pub mod args;
+pub(crate) mod serialize;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Config {
     pub source: StoragePath,
     pub target: StoragePath,
     pub source_client_config: Option<ClientConfig>,
+    pub source_region_auto_detect: bool,
+    pub source_list_include_restore_status: bool,
+    pub source_list_include_owner: bool,
     pub target_client_config: Option<ClientConfig>,
     pub force_retry_config: ForceRetryConfig,
+    pub per_object_timeout_config: Option<PerObjectTimeoutConfig>,
     pub tracing_config: Option<TracingConfig>,
+    pub only_show_errors: bool,
+    pub summary_only: bool,
+    pub log_file_config: Option<LogFileConfig>,
+    pub integrity_report: Option<PathBuf>,
     pub transfer_config: TransferConfig,
     pub worker_size: u16,
+    pub concurrency_auto: bool,
+    pub max_concurrent_objects_per_prefix: Option<u16>,
+    pub checksum_parallelism: Option<u16>,
+    pub source_concurrency_limit: Option<u16>,
+    pub target_concurrency_limit: Option<u16>,
+    pub graceful_shutdown_timeout_seconds: Option<u64>,
+    pub stats_interval_seconds: Option<u64>,
+    pub progress_rate_window_seconds: u64,
+    pub object_size_histogram: bool,
+    pub source_scan_progress: bool,
+    pub max_objects: Option<u64>,
+    pub max_bytes: Option<u64>,
+    pub abort_incomplete_multipart_uploads: bool,
+    pub abort_incomplete_multipart_uploads_older_than: Option<Duration>,
     pub warn_as_error: bool,
+    pub strict_verify: bool,
+    pub fail_fast_after: u64,
+    pub fail_on_empty_source: bool,
+    pub write_completion_marker: Option<String>,
+    pub completion_marker_with_summary: bool,
     pub follow_symlinks: bool,
     pub head_each_target: bool,
+    pub head_not_found_retries: u32,
+    pub target_list_cache_invalidate_on_write: bool,
+    pub skip_head_for_unlisted_objects: bool,
+    pub source_if_modified_since: bool,
+    pub skip_if_target_newer: bool,
     pub sync_with_delete: bool,
+    pub delete_log: Option<PathBuf>,
+    pub emit_manifest_for_delete: Option<PathBuf>,
+    pub delete_from_manifest: Option<PathBuf>,
+    pub disable_preflight: bool,
+    pub require_no_default_retention: bool,
     pub disable_tagging: bool,
     pub sync_latest_tagging: bool,
+    pub fast_empty_files: bool,
     pub no_guess_mime_type: bool,
+    pub content_type_override_map: Option<HashMap<String, String>>,
+    pub content_encoding_map: Option<HashMap<String, String>>,
+    pub md5_xattr: Option<String>,
+    pub partial_suffix: Option<String>,
+    pub partial_cleanup_older_than: Duration,
+    pub local_tmp_dir: Option<PathBuf>,
+    pub cleanup_temp_on_start: bool,
+    pub cleanup_temp_older_than: Duration,
+    pub download_to_stdout: bool,
+    pub checksum_verify_sampling: Option<u8>,
+    pub checksum_display: bool,
+    pub preallocate: bool,
+    pub fsync_on_complete: bool,
+    pub verify_only_on_mismatch_redownload: bool,
+    pub rename_on_conflict: bool,
+    pub skip_on_conflict: bool,
+    pub hardlink_mode: HardlinkMode,
+    pub download_file_mode: Option<u32>,
+    pub preserve_file_mode: bool,
+    pub upload_manifest: Option<Arc<UploadManifest>>,
+    pub source_version_map: Option<Arc<SourceVersionMap>>,
+    #[cfg(feature = "s3_select")]
+    pub source_select: Option<SourceSelectConfig>,
+    pub checksum_on_download_only_changed: bool,
+    pub force_reverify: bool,
     pub disable_multipart_verify: bool,
     pub disable_etag_verify: bool,
+    pub verify_content_length_only: bool,
+    pub verify_via_head: bool,
+    pub verify_range_reads: bool,
+    pub verify_target_encryption: bool,
+    pub target_bucket_key_reuse: bool,
     pub enable_versioning: bool,
+    pub versions_limit: Option<u32>,
+    pub versions_newest_first: bool,
+    pub delete_removed_versions: bool,
+    pub transfer_order: Option<TransferOrder>,
+    pub transfer_order_buffer_size: usize,
+    #[serde(serialize_with = "serialize::as_str_option")]
     pub storage_class: Option<StorageClass>,
+    #[serde(serialize_with = "serialize::as_str_option")]
     pub sse: Option<ServerSideEncryption>,
     pub sse_kms_key_id: SseKmsKeyId,
     pub source_sse_c: Option<String>,
     pub source_sse_c_key: SseCustomerKey,
     pub source_sse_c_key_md5: Option<String>,
+    pub source_sse_c_probe: bool,
     pub target_sse_c: Option<String>,
     pub target_sse_c_key: SseCustomerKey,
     pub target_sse_c_key_md5: Option<String>,
+    #[serde(serialize_with = "serialize::as_str_option")]
     pub canned_acl: Option<ObjectCannedAcl>,
+    #[serde(serialize_with = "serialize::as_str_option")]
     pub additional_checksum_mode: Option<ChecksumMode>,
+    #[serde(serialize_with = "serialize::as_str_option")]
     pub additional_checksum_algorithm: Option<ChecksumAlgorithm>,
+    pub negotiate_additional_checksum_algorithm: bool,
     pub dry_run: bool,
     pub rate_limit_objects: Option<u32>,
     pub rate_limit_bandwidth: Option<u64>,
+    pub source_request_rate_limit: Option<u32>,
+    pub target_request_rate_limit: Option<u32>,
+    pub list_max_rps: Option<u32>,
     pub cache_control: Option<String>,
     pub content_disposition: Option<String>,
+    pub content_disposition_template: Option<String>,
     pub content_encoding: Option<String>,
     pub content_language: Option<String>,
     pub content_type: Option<String>,
+    #[serde(serialize_with = "serialize::date_time_option")]
     pub expires: Option<DateTime<Utc>>,
     pub metadata: Option<HashMap<String, String>>,
+    pub metadata_merge: bool,
     pub tagging: Option<String>,
+    pub object_tag_count_guard: Option<TagGuardMode>,
     pub filter_config: FilterConfig,
     pub put_last_modified_metadata: bool,
+    pub put_file_mode_metadata: bool,
     pub max_keys: i32,
+    pub non_recursive: bool,
+    pub list_parallel_prefixes: Option<Vec<String>>,
+    pub include_only_prefixes_from_stdin: bool,
+    pub source_list_cache_config: Option<SourceListCacheConfig>,
+    pub start_after: Option<String>,
+    pub stop_at: Option<String>,
+    #[serde(serialize_with = "serialize::debug_option")]
     pub auto_complete_shell: Option<clap_complete::shells::Shell>,
+    pub dump_config: bool,
     pub disable_payload_signing: bool,
 }
 
 impl Config {
+    // --dump-config. the secrets are redacted by the Serialize implementations of their types,
+    // and the proxy credentials by the serializer of the fields.
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).context("serde_json::to_string_pretty() failed.")
+    }
+
     pub fn is_sha1_digest_listing_required(&self) -> bool {
         is_sha1_digest_listing_required(self.sync_with_delete)
     }
@@ -71,7 +185,7 @@ fn is_sha1_digest_listing_required(sync_with_delete: bool) -> bool {
     !sync_with_delete
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ClientConfig {
     pub client_config_location: ClientConfigLocation,
     pub credential: S3Credentials,
@@ -79,21 +193,29 @@ pub struct ClientConfig {
     pub endpoint_url: Option<String>,
     pub force_path_style: bool,
     pub retry_config: RetryConfig,
+    #[serde(serialize_with = "serialize::proxy_url_option")]
     pub https_proxy: Option<String>,
+    #[serde(serialize_with = "serialize::proxy_url_option")]
     pub http_proxy: Option<String>,
+    pub no_proxy: Vec<String>,
     pub no_verify_ssl: bool,
     pub disable_stalled_stream_protection: bool,
+    #[serde(serialize_with = "serialize::debug")]
     pub request_checksum_calculation: RequestChecksumCalculation,
+    pub expected_bucket_owner: Option<String>,
+    pub user_agent_suffix: Option<String>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct RetryConfig {
     pub aws_max_attempts: u32,
     pub initial_backoff_milliseconds: u64,
+    pub retry_jitter: Option<RetryJitter>,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize)]
 pub struct TracingConfig {
+    #[serde(serialize_with = "serialize::debug")]
     pub tracing_level: log::Level,
     pub json_tracing: bool,
     pub aws_sdk_tracing: bool,
@@ -101,17 +223,62 @@ pub struct TracingConfig {
     pub disable_color_tracing: bool,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Serialize)]
+pub struct LogFileConfig {
+    pub path: PathBuf,
+    pub max_size: u64,
+    pub max_files: u16,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SourceListCacheConfig {
+    pub path: PathBuf,
+    pub max_age: Duration,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
 pub struct ForceRetryConfig {
     pub force_retry_count: u32,
     pub force_retry_interval_milliseconds: u64,
+    pub retry_budget: Option<Duration>,
+    pub max_retries_per_object: Option<u32>,
+    pub retry_jitter: Option<RetryJitter>,
+}
+
+impl ForceRetryConfig {
+    // without --retry-jitter, the force retries sleep for the fixed interval.
+    pub fn force_retry_interval(&self) -> Duration {
+        let interval = self.force_retry_interval_milliseconds;
+        let milliseconds = match self.retry_jitter {
+            Some(RetryJitter::Full) => fastrand::u64(0..=interval),
+            Some(RetryJitter::Equal) => interval / 2 + fastrand::u64(0..=interval - interval / 2),
+            Some(RetryJitter::Disabled) | None => interval,
+        };
+
+        Duration::from_millis(milliseconds)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct PerObjectTimeoutConfig {
+    pub base: Duration,
+    pub min_throughput: u64,
+}
+
+impl PerObjectTimeoutConfig {
+    // the operation timeout is base + size / min_throughput, so that large objects do not trip it.
+    pub fn timeout_for(&self, size: u64) -> Duration {
+        self.base + Duration::from_secs_f64(size as f64 / self.min_throughput as f64)
+    }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize)]
 pub struct TransferConfig {
     pub multipart_threshold: u64,
     pub multipart_chunksize: u64,
     pub auto_chunksize: bool,
+    pub chunk_size_from_target: bool,
+    pub verify_multipart_parts_attributes: bool,
 }
 
 impl TransferConfig {
@@ -120,18 +287,29 @@ impl TransferConfig {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct FilterConfig {
+    #[serde(serialize_with = "serialize::date_time_option")]
     pub before_time: Option<DateTime<Utc>>,
+    #[serde(serialize_with = "serialize::date_time_option")]
     pub after_time: Option<DateTime<Utc>>,
     pub remove_modified_filter: bool,
     pub check_size: bool,
     pub check_etag: bool,
+    #[serde(serialize_with = "serialize::as_str_option")]
     pub check_checksum_algorithm: Option<ChecksumAlgorithm>,
+    #[serde(serialize_with = "serialize::regex_option")]
     pub include_regex: Option<Regex>,
+    #[serde(serialize_with = "serialize::regex_option")]
     pub exclude_regex: Option<Regex>,
     pub larger_size: Option<u64>,
     pub smaller_size: Option<u64>,
+    pub owner: Option<String>,
+    pub skip_glacier: bool,
+    pub skip_zero_byte_objects: bool,
+    pub normalize_unicode: Option<UnicodeNormalization>,
+    pub target_key_case: Option<KeyCase>,
+    pub date_partition: Option<DatePartition>,
 }
 
 #[cfg(test)]
@@ -146,6 +324,8 @@ mod tests {
             multipart_threshold: 8 * 1024 * 1024,
             multipart_chunksize: 8 * 1024 * 1024,
             auto_chunksize: false,
+            chunk_size_from_target: false,
+            verify_multipart_parts_attributes: false,
         };
 
         assert!(transfer_config.is_multipart_upload_required(8 * 1024 * 1024));
@@ -153,6 +333,65 @@ mod tests {
         assert!(!transfer_config.is_multipart_upload_required((8 * 1024 * 1024) - 1));
     }
 
+    #[test]
+    fn per_object_timeout_config_timeout_for() {
+        init_dummy_tracing_subscriber();
+
+        let per_object_timeout_config = PerObjectTimeoutConfig {
+            base: Duration::from_secs(10),
+            min_throughput: 1024 * 1024,
+        };
+
+        assert_eq!(
+            per_object_timeout_config.timeout_for(0),
+            Duration::from_secs(10)
+        );
+        assert_eq!(
+            per_object_timeout_config.timeout_for(6 * 1024 * 1024 * 1024),
+            Duration::from_secs(10 + 6 * 1024)
+        );
+        assert_eq!(
+            per_object_timeout_config.timeout_for(512 * 1024),
+            Duration::from_millis(10500)
+        );
+    }
+
+    #[test]
+    fn force_retry_interval_with_retry_jitter() {
+        init_dummy_tracing_subscriber();
+
+        let mut force_retry_config = ForceRetryConfig {
+            force_retry_count: 5,
+            force_retry_interval_milliseconds: 1000,
+            retry_budget: None,
+            max_retries_per_object: None,
+            retry_jitter: None,
+        };
+        assert_eq!(
+            force_retry_config.force_retry_interval(),
+            Duration::from_millis(1000)
+        );
+
+        force_retry_config.retry_jitter = Some(RetryJitter::Disabled);
+        assert_eq!(
+            force_retry_config.force_retry_interval(),
+            Duration::from_millis(1000)
+        );
+
+        for _ in 0..100 {
+            force_retry_config.retry_jitter = Some(RetryJitter::Full);
+            assert!(force_retry_config.force_retry_interval() <= Duration::from_millis(1000));
+
+            force_retry_config.retry_jitter = Some(RetryJitter::Equal);
+            let interval = force_retry_config.force_retry_interval();
+            assert!(Duration::from_millis(500) <= interval);
+            assert!(interval <= Duration::from_millis(1000));
+        }
+
+        force_retry_config.force_retry_interval_milliseconds = 0;
+        assert_eq!(force_retry_config.force_retry_interval(), Duration::ZERO);
+    }
+
     #[test]
     fn is_sha1_digest_listing_required_test() {
         init_dummy_tracing_subscriber();
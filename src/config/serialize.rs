@@ -0,0 +1,80 @@
+use std::fmt::Debug;
+
+use chrono::{DateTime, FixedOffset, SecondsFormat, Utc};
+use regex::Regex;
+use serde::{Serialize, Serializer};
+
+// serializers for the fields of Config whose types do not implement Serialize(--dump-config).
+
+// AWS SDK enums(StorageClass, ChecksumAlgorithm, etc.) are serialized as their API values.
+pub fn as_str_option<T: AsRef<str>, S: Serializer>(
+    value: &Option<T>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    value
+        .as_ref()
+        .map(|value| value.as_ref())
+        .serialize(serializer)
+}
+
+pub fn debug<T: Debug, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    format!("{:?}", value).serialize(serializer)
+}
+
+pub fn debug_option<T: Debug, S: Serializer>(
+    value: &Option<T>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    value
+        .as_ref()
+        .map(|value| format!("{:?}", value))
+        .serialize(serializer)
+}
+
+pub fn regex_option<S: Serializer>(
+    value: &Option<Regex>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    value.as_ref().map(Regex::as_str).serialize(serializer)
+}
+
+pub fn date_time_option<S: Serializer>(
+    value: &Option<DateTime<Utc>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    value
+        .as_ref()
+        .map(|value| value.to_rfc3339_opts(SecondsFormat::Secs, true))
+        .serialize(serializer)
+}
+
+pub fn fixed_offset<S: Serializer>(value: &FixedOffset, serializer: S) -> Result<S::Ok, S::Error> {
+    value.to_string().serialize(serializer)
+}
+
+// the userinfo(user:password@) of the proxy URL is redacted, because the serialized Config is printed(--dump-config).
+pub fn proxy_url_option<S: Serializer>(
+    value: &Option<String>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    value
+        .as_deref()
+        .map(redact_url_userinfo)
+        .serialize(serializer)
+}
+
+fn redact_url_userinfo(url: &str) -> String {
+    let authority_start = url.find("://").map_or(0, |index| index + 3);
+    let authority_end = url[authority_start..]
+        .find(['/', '?', '#'])
+        .map_or(url.len(), |index| authority_start + index);
+
+    match url[authority_start..authority_end].rfind('@') {
+        Some(index) => format!(
+            "{}** redacted **{}",
+            &url[..authority_start],
+            &url[authority_start + index..]
+        ),
+        None => url.to_string(),
+    }
+}
@@ -0,0 +1,129 @@
+use anyhow::{Context, Result};
+use aws_sdk_s3::primitives::ByteStream;
+use tracing::info;
+
+use crate::storage::s3::generate_full_key;
+use crate::storage::Storage;
+use crate::types::StoragePath;
+use crate::Config;
+
+const SUMMARY_CONTENT_TYPE: &str = "application/json";
+
+// Writes the sentinel object(--write-completion-marker) to the target after a successful run.
+// the marker is put directly, so that the transfer options for the synced objects(metadata, tagging, etc.)
+// are not applied to it. the encryption and the ACL of the target are applied.
+pub async fn write_completion_marker(
+    config: &Config,
+    target: &Storage,
+    key: &str,
+    summary: Option<String>,
+) -> Result<()> {
+    match &config.target {
+        StoragePath::S3 { bucket, prefix } => {
+            let full_key = generate_full_key(prefix, key);
+            let content_type = summary.as_ref().map(|_| SUMMARY_CONTENT_TYPE.to_string());
+
+            target
+                .get_client()
+                .unwrap()
+                .put_object()
+                .bucket(bucket)
+                .set_expected_bucket_owner(
+                    config
+                        .target_client_config
+                        .as_ref()
+                        .unwrap()
+                        .expected_bucket_owner
+                        .clone(),
+                )
+                .key(&full_key)
+                .body(ByteStream::from(summary.unwrap_or_default().into_bytes()))
+                .set_content_type(content_type)
+                .set_server_side_encryption(config.sse.clone())
+                .set_ssekms_key_id(config.sse_kms_key_id.clone().id.clone())
+                .set_sse_customer_algorithm(config.target_sse_c.clone())
+                .set_sse_customer_key(config.target_sse_c_key.clone().key.clone())
+                .set_sse_customer_key_md5(config.target_sse_c_key_md5.clone())
+                .set_acl(config.canned_acl.clone())
+                .send()
+                .await
+                .context("aws_sdk_s3::client::put_object() failed.")?;
+
+            info!(key = full_key, "completion marker has been written.");
+        }
+        StoragePath::Local(path) => {
+            let marker_path = path.join(key);
+            if let Some(parent) = marker_path.parent() {
+                tokio::fs::create_dir_all(parent)
+                    .await
+                    .context("tokio::fs::create_dir_all() failed.")?;
+            }
+            tokio::fs::write(&marker_path, summary.unwrap_or_default())
+                .await
+                .context("tokio::fs::write() failed.")?;
+
+            info!(
+                path = marker_path.to_string_lossy().to_string(),
+                "completion marker has been written."
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::args::parse_from_args;
+    use crate::pipeline::storage_factory::{create_storage_limiters, create_storage_pair};
+    use crate::types::token::create_pipeline_cancellation_token;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn write_local_completion_marker() {
+        init_dummy_tracing_subscriber();
+
+        let target_dir = tempfile::tempdir().unwrap();
+        let args = vec![
+            "s3sync",
+            "--allow-both-local-storage",
+            "--write-completion-marker",
+            "dir1/_SUCCESS",
+            "./test_data/source/dir1/",
+            target_dir.path().to_str().unwrap(),
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+        let (stats_sender, _) = async_channel::unbounded();
+        let storage_pair = create_storage_pair(
+            config.clone(),
+            create_pipeline_cancellation_token(),
+            stats_sender,
+            create_storage_limiters(&config),
+        )
+        .await;
+
+        write_completion_marker(&config, &storage_pair.target, "dir1/_SUCCESS", None)
+            .await
+            .unwrap();
+        let marker = std::fs::read(target_dir.path().join("dir1/_SUCCESS")).unwrap();
+        assert!(marker.is_empty());
+
+        write_completion_marker(
+            &config,
+            &storage_pair.target,
+            "_SUCCESS",
+            Some(r#"{"transferred_objects":1}"#.to_string()),
+        )
+        .await
+        .unwrap();
+        let marker = std::fs::read_to_string(target_dir.path().join("_SUCCESS")).unwrap();
+        assert_eq!(marker, r#"{"transferred_objects":1}"#);
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
@@ -0,0 +1,374 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tracing::{debug, info};
+
+use crate::types::token::PipelineCancellationToken;
+use crate::types::SyncStatistics;
+
+use super::stage::Stage;
+
+// the number of the active workers when --concurrency-auto starts.
+const INITIAL_AUTO_CONCURRENCY: u16 = 4;
+const CONTROL_INTERVAL: Duration = Duration::from_secs(5);
+// the throughput must improve by this ratio to keep ramping up. smaller changes are regarded as noise.
+const IMPROVEMENT_THRESHOLD: f64 = 0.05;
+
+pub fn initial_auto_concurrency(worker_size: u16) -> u16 {
+    INITIAL_AUTO_CONCURRENCY.min(worker_size)
+}
+
+pub async fn acquire_concurrency_permit(
+    concurrency_gate: &Option<Arc<Semaphore>>,
+) -> Option<OwnedSemaphorePermit> {
+    match concurrency_gate {
+        Some(semaphore) => Some(semaphore.clone().acquire_owned().await.unwrap()),
+        None => None,
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct Throughput {
+    bytes: u64,
+    objects: u64,
+}
+
+impl Throughput {
+    fn is_empty(&self) -> bool {
+        self.bytes == 0 && self.objects == 0
+    }
+
+    fn is_improved_from(&self, base: &Throughput) -> bool {
+        is_improved(self.bytes, base.bytes) || is_improved(self.objects, base.objects)
+    }
+}
+
+// a dimension without base throughput(e.g. bytes of empty objects) is not evaluated, because any
+// value would be regarded as an improvement.
+fn is_improved(current: u64, base: u64) -> bool {
+    base != 0 && current as f64 >= base as f64 * (1.0 + IMPROVEMENT_THRESHOLD)
+}
+
+// The concurrency is doubled while the throughput improves. once it stops improving, the last improved
+// concurrency is restored and kept, so that it does not oscillate. on errors, the concurrency is halved.
+#[derive(Debug)]
+struct ConcurrencyTuner {
+    concurrency: u16,
+    max_concurrency: u16,
+    previous_concurrency: u16,
+    base_throughput: Option<Throughput>,
+    settled: bool,
+}
+
+impl ConcurrencyTuner {
+    fn new(concurrency: u16, max_concurrency: u16) -> Self {
+        Self {
+            concurrency,
+            max_concurrency,
+            previous_concurrency: concurrency,
+            base_throughput: None,
+            settled: false,
+        }
+    }
+
+    fn update(&mut self, throughput: Throughput, has_error: bool) -> u16 {
+        if has_error {
+            self.concurrency = (self.concurrency / 2).max(1);
+            self.base_throughput = None;
+            self.settled = true;
+            return self.concurrency;
+        }
+
+        // throughput is not evaluated if no object has been completed. e.g. the source is still being listed.
+        if self.settled || throughput.is_empty() {
+            return self.concurrency;
+        }
+
+        if let Some(base_throughput) = &self.base_throughput {
+            if !throughput.is_improved_from(base_throughput) {
+                self.concurrency = self.previous_concurrency;
+                self.settled = true;
+                return self.concurrency;
+            }
+        }
+
+        self.base_throughput = Some(throughput);
+        if self.max_concurrency <= self.concurrency {
+            self.settled = true;
+            return self.concurrency;
+        }
+
+        self.previous_concurrency = self.concurrency;
+        self.concurrency = self.concurrency.saturating_mul(2).min(self.max_concurrency);
+        self.concurrency
+    }
+}
+
+// With --concurrency-auto, --worker-size workers are spawned, but each worker must take a permit of
+// the gate to sync an object. this controller adjusts the number of permits at runtime.
+pub struct ConcurrencyController {
+    base: Stage,
+    concurrency_gate: Arc<Semaphore>,
+    drain_token: PipelineCancellationToken,
+    skipped_error_count: Arc<AtomicU64>,
+    retry_elapsed_milliseconds: Arc<AtomicU64>,
+    transferred_object_count: Arc<AtomicU64>,
+    transferred_bytes: Arc<AtomicU64>,
+}
+
+impl ConcurrencyController {
+    pub fn new(
+        base: Stage,
+        concurrency_gate: Arc<Semaphore>,
+        drain_token: PipelineCancellationToken,
+        skipped_error_count: Arc<AtomicU64>,
+        retry_elapsed_milliseconds: Arc<AtomicU64>,
+        transferred_object_count: Arc<AtomicU64>,
+        transferred_bytes: Arc<AtomicU64>,
+    ) -> Self {
+        Self {
+            base,
+            concurrency_gate,
+            drain_token,
+            skipped_error_count,
+            retry_elapsed_milliseconds,
+            transferred_object_count,
+            transferred_bytes,
+        }
+    }
+
+    pub async fn control(&self) {
+        let initial_concurrency = initial_auto_concurrency(self.base.config.worker_size);
+        let mut tuner = ConcurrencyTuner::new(initial_concurrency, self.base.config.worker_size);
+        self.base
+            .send_stats(SyncStatistics::AutoConcurrency(initial_concurrency))
+            .await;
+
+        let mut last_counts = self.load_counts();
+        loop {
+            tokio::select! {
+                _ = self.base.cancellation_token.cancelled() => break,
+                _ = self.drain_token.cancelled() => break,
+                _ = tokio::time::sleep(CONTROL_INTERVAL) => {}
+            }
+
+            // all objects have been dispatched to the workers.
+            let receiver = self.base.receiver.as_ref().unwrap();
+            if receiver.is_closed() && receiver.is_empty() {
+                break;
+            }
+
+            let counts = self.load_counts();
+            let throughput = Throughput {
+                bytes: counts.0 - last_counts.0,
+                objects: counts.1 - last_counts.1,
+            };
+            let has_error = counts.2 != last_counts.2 || counts.3 != last_counts.3;
+            last_counts = counts;
+
+            let current_concurrency = tuner.concurrency;
+            let next_concurrency = tuner.update(throughput, has_error);
+            debug!(
+                transferred_bytes = throughput.bytes,
+                transferred_objects = throughput.objects,
+                has_error = has_error,
+                concurrency = next_concurrency,
+                "concurrency has been evaluated."
+            );
+
+            if next_concurrency == current_concurrency {
+                continue;
+            }
+
+            if !self
+                .change_concurrency(current_concurrency, next_concurrency)
+                .await
+            {
+                break;
+            }
+
+            info!(
+                previous_concurrency = current_concurrency,
+                concurrency = next_concurrency,
+                "concurrency has been changed."
+            );
+            self.base
+                .send_stats(SyncStatistics::AutoConcurrency(next_concurrency))
+                .await;
+        }
+    }
+
+    // returns false if cancelled while waiting for the workers to release the permits.
+    async fn change_concurrency(&self, current_concurrency: u16, next_concurrency: u16) -> bool {
+        if current_concurrency < next_concurrency {
+            self.concurrency_gate
+                .add_permits((next_concurrency - current_concurrency) as usize);
+            return true;
+        }
+
+        tokio::select! {
+            _ = self.base.cancellation_token.cancelled() => false,
+            permits = self.concurrency_gate.acquire_many((current_concurrency - next_concurrency) as u32) => {
+                permits.unwrap().forget();
+                true
+            }
+        }
+    }
+
+    fn load_counts(&self) -> (u64, u64, u64, u64) {
+        (
+            self.transferred_bytes.load(Ordering::SeqCst),
+            self.transferred_object_count.load(Ordering::SeqCst),
+            self.skipped_error_count.load(Ordering::SeqCst),
+            self.retry_elapsed_milliseconds.load(Ordering::SeqCst),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ramp_up_while_improving() {
+        init_dummy_tracing_subscriber();
+
+        let mut tuner = ConcurrencyTuner::new(4, 64);
+
+        assert_eq!(tuner.update(throughput(100), false), 8);
+        assert_eq!(tuner.update(throughput(190), false), 16);
+        assert_eq!(tuner.update(throughput(350), false), 32);
+        assert_eq!(tuner.update(throughput(600), false), 64);
+        assert_eq!(tuner.update(throughput(900), false), 64);
+        assert!(tuner.settled);
+    }
+
+    #[test]
+    fn settle_when_not_improving() {
+        init_dummy_tracing_subscriber();
+
+        let mut tuner = ConcurrencyTuner::new(4, 64);
+
+        assert_eq!(tuner.update(throughput(100), false), 8);
+        assert_eq!(tuner.update(throughput(180), false), 16);
+        // less than 5% improvement. the last improved concurrency is restored.
+        assert_eq!(tuner.update(throughput(185), false), 8);
+        assert!(tuner.settled);
+
+        // no oscillation after settled.
+        assert_eq!(tuner.update(throughput(1000), false), 8);
+        assert_eq!(tuner.update(throughput(10), false), 8);
+    }
+
+    #[test]
+    fn is_improved_test() {
+        init_dummy_tracing_subscriber();
+
+        assert!(is_improved(105, 100));
+        assert!(!is_improved(104, 100));
+        assert!(!is_improved(0, 0));
+        assert!(!is_improved(100, 0));
+    }
+
+    #[test]
+    fn halve_on_error() {
+        init_dummy_tracing_subscriber();
+
+        let mut tuner = ConcurrencyTuner::new(4, 64);
+
+        assert_eq!(tuner.update(throughput(100), false), 8);
+        assert_eq!(tuner.update(throughput(200), true), 4);
+        assert_eq!(tuner.update(throughput(400), false), 4);
+        assert_eq!(tuner.update(throughput(0), true), 2);
+        assert_eq!(tuner.update(throughput(0), true), 1);
+        assert_eq!(tuner.update(throughput(0), true), 1);
+    }
+
+    #[test]
+    fn ignore_empty_throughput() {
+        init_dummy_tracing_subscriber();
+
+        let mut tuner = ConcurrencyTuner::new(4, 64);
+
+        assert_eq!(tuner.update(Throughput::default(), false), 4);
+        assert_eq!(tuner.update(throughput(100), false), 8);
+        assert_eq!(tuner.update(Throughput::default(), false), 8);
+        assert!(!tuner.settled);
+    }
+
+    #[test]
+    fn max_concurrency_is_not_exceeded() {
+        init_dummy_tracing_subscriber();
+
+        let mut tuner = ConcurrencyTuner::new(4, 6);
+
+        assert_eq!(tuner.update(throughput(100), false), 6);
+        assert_eq!(tuner.update(throughput(200), false), 6);
+        assert!(tuner.settled);
+
+        let mut tuner = ConcurrencyTuner::new(2, 2);
+        assert_eq!(tuner.update(throughput(100), false), 2);
+        assert!(tuner.settled);
+    }
+
+    #[test]
+    fn improvement_by_objects_or_bytes() {
+        init_dummy_tracing_subscriber();
+
+        let base = Throughput {
+            bytes: 1000,
+            objects: 100,
+        };
+
+        assert!(Throughput {
+            bytes: 1000,
+            objects: 110
+        }
+        .is_improved_from(&base));
+        assert!(Throughput {
+            bytes: 1100,
+            objects: 100
+        }
+        .is_improved_from(&base));
+        assert!(!Throughput {
+            bytes: 1049,
+            objects: 104
+        }
+        .is_improved_from(&base));
+    }
+
+    #[test]
+    fn initial_auto_concurrency_test() {
+        init_dummy_tracing_subscriber();
+
+        assert_eq!(initial_auto_concurrency(16), 4);
+        assert_eq!(initial_auto_concurrency(2), 2);
+    }
+
+    #[tokio::test]
+    async fn acquire_concurrency_permit_test() {
+        init_dummy_tracing_subscriber();
+
+        assert!(acquire_concurrency_permit(&None).await.is_none());
+
+        let semaphore = Some(Arc::new(Semaphore::new(1)));
+        let permit = acquire_concurrency_permit(&semaphore).await;
+        assert!(permit.is_some());
+        assert_eq!(semaphore.as_ref().unwrap().available_permits(), 0);
+
+        drop(permit);
+        assert_eq!(semaphore.as_ref().unwrap().available_permits(), 1);
+    }
+
+    fn throughput(objects: u64) -> Throughput {
+        Throughput { bytes: 0, objects }
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
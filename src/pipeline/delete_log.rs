@@ -0,0 +1,152 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use chrono::{SecondsFormat, Utc};
+use serde_json::json;
+use tokio::fs::{File, OpenOptions};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+// Append-only log of the deleted objects(--delete-log). each entry is a JSON line, and it is written
+// and fsynced as soon as the object has been deleted, so that the log survives even if s3sync is killed.
+pub struct DeleteLog {
+    file: Mutex<File>,
+    dry_run: bool,
+}
+
+impl DeleteLog {
+    pub async fn open(path: &Path, dry_run: bool) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .await
+            .context("tokio::fs::OpenOptions::open() failed.")?;
+
+        Ok(Self {
+            file: Mutex::new(file),
+            dry_run,
+        })
+    }
+
+    pub async fn append(&self, key: &str, version_id: Option<&str>) -> Result<()> {
+        let line = build_entry(
+            &Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            key,
+            version_id,
+            self.dry_run,
+        );
+
+        let mut file = self.file.lock().await;
+        file.write_all(line.as_bytes())
+            .await
+            .context("tokio::fs::File::write_all() failed.")?;
+        file.sync_data()
+            .await
+            .context("tokio::fs::File::sync_data() failed.")
+    }
+}
+
+pub async fn append_delete_log(
+    delete_log: &Option<Arc<DeleteLog>>,
+    key: &str,
+    version_id: Option<&str>,
+) -> Result<()> {
+    match delete_log {
+        Some(delete_log) => delete_log.append(key, version_id).await,
+        None => Ok(()),
+    }
+}
+
+fn build_entry(timestamp: &str, key: &str, version_id: Option<&str>, dry_run: bool) -> String {
+    let mut entry = json!({
+        "timestamp": timestamp,
+        "key": key,
+    });
+    if let Some(version_id) = version_id {
+        entry["version_id"] = json!(version_id);
+    }
+    if dry_run {
+        entry["dry_run"] = json!(true);
+    }
+
+    format!("{}\n", entry)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_entry_test() {
+        init_dummy_tracing_subscriber();
+
+        let entry = build_entry("2024-01-01T00:00:00.000Z", "dir1/data1", None, false);
+        assert!(entry.ends_with('\n'));
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&entry).unwrap(),
+            json!({"timestamp": "2024-01-01T00:00:00.000Z", "key": "dir1/data1"})
+        );
+
+        let entry = build_entry("2024-01-01T00:00:00.000Z", "data1", Some("version1"), false);
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&entry).unwrap(),
+            json!({"timestamp": "2024-01-01T00:00:00.000Z", "key": "data1", "version_id": "version1"})
+        );
+
+        let entry = build_entry("2024-01-01T00:00:00.000Z", "data1", None, true);
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&entry).unwrap(),
+            json!({"timestamp": "2024-01-01T00:00:00.000Z", "key": "data1", "dry_run": true})
+        );
+    }
+
+    #[tokio::test]
+    async fn append_test() {
+        init_dummy_tracing_subscriber();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("delete.log");
+
+        let delete_log = DeleteLog::open(&path, false).await.unwrap();
+        delete_log.append("data1", None).await.unwrap();
+        delete_log.append("data2", Some("version2")).await.unwrap();
+        drop(delete_log);
+
+        // the existing entries are kept.
+        let delete_log = DeleteLog::open(&path, true).await.unwrap();
+        delete_log.append("data3", None).await.unwrap();
+
+        let content = tokio::fs::read_to_string(&path).await.unwrap();
+        let entries: Vec<serde_json::Value> = content
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0]["key"], "data1");
+        assert!(entries[0].get("version_id").is_none());
+        assert!(entries[0]["timestamp"].is_string());
+        assert_eq!(entries[1]["key"], "data2");
+        assert_eq!(entries[1]["version_id"], "version2");
+        assert_eq!(entries[2]["key"], "data3");
+        assert_eq!(entries[2]["dry_run"], true);
+    }
+
+    #[tokio::test]
+    async fn open_error() {
+        init_dummy_tracing_subscriber();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("no_such_dir").join("delete.log");
+
+        assert!(DeleteLog::open(&path, false).await.is_err());
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
@@ -0,0 +1,147 @@
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use anyhow::{anyhow, Context, Result};
+use serde_json::{json, Value};
+use tempfile::NamedTempFile;
+
+use crate::types::StoragePath;
+use crate::Config;
+
+// The first line of the manifest is a header of the source and the target. the manifest can be
+// executed only against the same pair, so that a reviewed set is never applied to another target.
+// each following line is a target key that would be deleted.
+pub fn build_header(config: &Config) -> Value {
+    json!({
+        "source": describe_storage_path(&config.source),
+        "target": describe_storage_path(&config.target),
+    })
+}
+
+// The keys are sorted for review. the manifest is written to a temporary file, and it is
+// persisted only when all keys have been written.
+pub fn write_manifest(path: &Path, header: &Value, keys: &HashSet<String>) -> Result<u64> {
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let temp_file =
+        NamedTempFile::new_in(dir).context("tempfile::NamedTempFile::new_in() failed.")?;
+
+    let mut sorted_keys = keys.iter().collect::<Vec<&String>>();
+    sorted_keys.sort();
+
+    let mut writer = BufWriter::new(temp_file);
+    writeln!(writer, "{}", header)?;
+    for key in &sorted_keys {
+        writeln!(writer, "{}", json!({ "key": key }))?;
+    }
+
+    let temp_file = writer
+        .into_inner()
+        .context("std::io::BufWriter::into_inner() failed.")?;
+    temp_file
+        .persist(path)
+        .context("tempfile::NamedTempFile::persist() failed.")?;
+
+    Ok(sorted_keys.len() as u64)
+}
+
+pub fn read_manifest(path: &Path, header: &Value) -> Result<HashSet<String>> {
+    let mut reader = BufReader::new(File::open(path).context("std::fs::File::open() failed.")?);
+
+    let mut header_line = String::new();
+    reader.read_line(&mut header_line)?;
+    if serde_json::from_str::<Value>(&header_line).ok().as_ref() != Some(header) {
+        return Err(anyhow!(
+            "the delete manifest was written for another source or target."
+        ));
+    }
+
+    let mut keys = HashSet::new();
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let entry: Value = serde_json::from_str(&line).context("serde_json::from_str() failed.")?;
+        let key = entry["key"]
+            .as_str()
+            .ok_or_else(|| anyhow!("invalid entry in the delete manifest: {}", line))?;
+        keys.insert(key.to_string());
+    }
+
+    Ok(keys)
+}
+
+fn describe_storage_path(storage_path: &StoragePath) -> String {
+    match storage_path {
+        StoragePath::S3 { bucket, prefix } => format!("s3://{}/{}", bucket, prefix),
+        StoragePath::Local(path) => path.to_string_lossy().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::args::parse_from_args;
+
+    use super::*;
+
+    #[test]
+    fn write_and_read_manifest() {
+        init_dummy_tracing_subscriber();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("delete_manifest.jsonl");
+        let header = build_header(&build_config("s3://target-bucket/prefix/"));
+
+        let keys = HashSet::from(["dir1/data2".to_string(), "data1".to_string()]);
+        assert_eq!(write_manifest(&path, &header, &keys).unwrap(), 2);
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let lines = content.lines().collect::<Vec<&str>>();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[1], r#"{"key":"data1"}"#);
+        assert_eq!(lines[2], r#"{"key":"dir1/data2"}"#);
+
+        assert_eq!(read_manifest(&path, &header).unwrap(), keys);
+
+        let other_header = build_header(&build_config("s3://target-bucket/other_prefix/"));
+        assert!(read_manifest(&path, &other_header).is_err());
+    }
+
+    #[test]
+    fn read_invalid_manifest() {
+        init_dummy_tracing_subscriber();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("delete_manifest.jsonl");
+        let header = build_header(&build_config("s3://target-bucket/prefix/"));
+
+        std::fs::write(&path, format!("{}\n{{\"no_key\":1}}\n", header)).unwrap();
+        assert!(read_manifest(&path, &header).is_err());
+
+        assert!(read_manifest(&dir.path().join("no_such_file"), &header).is_err());
+    }
+
+    fn build_config(target: &str) -> Config {
+        let args = vec![
+            "s3sync",
+            "--target-profile",
+            "target_profile",
+            "./test_data/source/",
+            target,
+        ];
+
+        Config::try_from(parse_from_args(args).unwrap()).unwrap()
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
@@ -1,18 +1,26 @@
+use std::sync::Arc;
+
 use anyhow::{anyhow, Result};
 use tracing::{error, info, trace};
 
 use crate::types::SyncStatistics::SyncDelete;
 
+use super::delete_log::{append_delete_log, DeleteLog};
 use super::stage::Stage;
 
 pub struct ObjectDeleter {
     worker_index: u16,
     base: Stage,
+    delete_log: Option<Arc<DeleteLog>>,
 }
 
 impl ObjectDeleter {
-    pub fn new(base: Stage, worker_index: u16) -> Self {
-        Self { base, worker_index }
+    pub fn new(base: Stage, worker_index: u16, delete_log: Option<Arc<DeleteLog>>) -> Self {
+        Self {
+            base,
+            worker_index,
+            delete_log,
+        }
     }
 
     pub async fn delete_target(&self) -> Result<()> {
@@ -57,8 +65,9 @@ impl ObjectDeleter {
             .target
             .as_ref()
             .unwrap()
-            .delete_object(key, version_id)
+            .delete_object(key, version_id.clone())
             .await?;
+        append_delete_log(&self.delete_log, key, version_id.as_deref()).await?;
 
         self.base
             .send_stats(SyncDelete {
@@ -1,8 +1,9 @@
 use std::collections::HashSet;
+use std::sync::Arc;
 
 use anyhow::Result;
 use aws_sdk_s3::types::Object;
-use tracing::trace;
+use tracing::{info, trace, warn};
 
 use crate::types::{ObjectKey, ObjectKeyMap, S3syncObject};
 
@@ -10,11 +11,15 @@ use super::stage::Stage;
 
 pub struct DiffLister {
     base: Stage,
+    manifest_keys: Option<Arc<HashSet<String>>>,
 }
 
 impl DiffLister {
-    pub fn new(base: Stage) -> Self {
-        Self { base }
+    pub fn new(base: Stage, manifest_keys: Option<Arc<HashSet<String>>>) -> Self {
+        Self {
+            base,
+            manifest_keys,
+        }
     }
 
     pub async fn list(
@@ -24,7 +29,10 @@ impl DiffLister {
     ) -> Result<()> {
         trace!("diff generator has started.");
 
-        let diff_set = generate_diff(source_key_map, target_key_map);
+        let mut diff_set = generate_diff(source_key_map, target_key_map);
+        if let Some(manifest_keys) = &self.manifest_keys {
+            diff_set = restrict_to_manifest(diff_set, manifest_keys);
+        }
 
         for key in diff_set {
             if self.base.cancellation_token.is_cancelled() {
@@ -50,7 +58,10 @@ impl DiffLister {
     }
 }
 
-fn generate_diff(source_key_map: &ObjectKeyMap, target_key_map: &ObjectKeyMap) -> HashSet<String> {
+pub fn generate_diff(
+    source_key_map: &ObjectKeyMap,
+    target_key_map: &ObjectKeyMap,
+) -> HashSet<String> {
     let source_key_map = source_key_map.lock().unwrap();
     let source_key_set: HashSet<&ObjectKey> = HashSet::from_iter(source_key_map.keys());
 
@@ -71,6 +82,33 @@ fn generate_diff(source_key_map: &ObjectKeyMap, target_key_map: &ObjectKeyMap) -
     diff_set
 }
 
+// --delete-from-manifest deletes only the reviewed keys. a key in the manifest is not deleted if it
+// has appeared in the source again or has already gone from the target since the review.
+fn restrict_to_manifest(
+    diff_set: HashSet<String>,
+    manifest_keys: &HashSet<String>,
+) -> HashSet<String> {
+    for key in manifest_keys.difference(&diff_set) {
+        warn!(
+            key = key,
+            "the key in the delete manifest is no longer a deletion candidate. skipping."
+        );
+    }
+
+    let not_reviewed_count = diff_set.difference(manifest_keys).count();
+    if 0 < not_reviewed_count {
+        info!(
+            count = not_reviewed_count,
+            "deletion candidates that are not in the delete manifest have been skipped."
+        );
+    }
+
+    diff_set
+        .intersection(manifest_keys)
+        .cloned()
+        .collect::<HashSet<String>>()
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
@@ -139,6 +177,19 @@ mod tests {
         assert_eq!(diff_set, expected_set);
     }
 
+    #[test]
+    fn restrict_to_manifest_test() {
+        init_dummy_tracing_subscriber();
+
+        let diff_set = HashSet::from(["key2".to_string(), "key4".to_string()]);
+        let manifest_keys = HashSet::from(["key2".to_string(), "key3".to_string()]);
+
+        assert_eq!(
+            restrict_to_manifest(diff_set, &manifest_keys),
+            HashSet::from(["key2".to_string()])
+        );
+    }
+
     #[test]
     #[should_panic]
     fn generate_diff_panic_test() {
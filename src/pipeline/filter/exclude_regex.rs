@@ -85,6 +85,12 @@ mod tests {
             exclude_regex: Some(Regex::new(r".+\.(csv|pdf)$").unwrap()),
             larger_size: None,
             smaller_size: None,
+            owner: None,
+            skip_glacier: false,
+            skip_zero_byte_objects: false,
+            normalize_unicode: None,
+            target_key_case: None,
+            date_partition: None,
         };
 
         let object = S3syncObject::NotVersioning(Object::builder().key("dir1/aaa.txt").build());
@@ -117,6 +123,12 @@ mod tests {
             exclude_regex: Some(Regex::new(r".+\.(csv|pdf)$").unwrap()),
             larger_size: None,
             smaller_size: None,
+            owner: None,
+            skip_glacier: false,
+            skip_zero_byte_objects: false,
+            normalize_unicode: None,
+            target_key_case: None,
+            date_partition: None,
         };
 
         let object = S3syncObject::NotVersioning(Object::builder().key("aaa.csv").build());
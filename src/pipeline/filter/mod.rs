@@ -12,7 +12,11 @@ pub use crate::pipeline::filter::larger_size::LargerSizeFilter;
 pub use crate::pipeline::filter::modified::TargetModifiedFilter;
 pub use crate::pipeline::filter::mtime_after::MtimeAfterFilter;
 pub use crate::pipeline::filter::mtime_before::MtimeBeforeFilter;
+pub use crate::pipeline::filter::owner::OwnerFilter;
+pub use crate::pipeline::filter::skip_glacier::SkipGlacierFilter;
 pub use crate::pipeline::filter::smaller_size::SmallerSizeFilter;
+pub use crate::pipeline::filter::source_object_counter::SourceObjectCounter;
+pub use crate::pipeline::filter::zero_byte_object::ZeroByteObjectFilter;
 use crate::types::{ObjectKeyMap, S3syncObject, SyncStatistics};
 
 use super::stage::Stage;
@@ -23,7 +27,11 @@ mod larger_size;
 mod modified;
 mod mtime_after;
 mod mtime_before;
+mod owner;
+mod skip_glacier;
 mod smaller_size;
+mod source_object_counter;
+mod zero_byte_object;
 
 #[async_trait]
 pub trait ObjectFilter {
@@ -94,7 +102,7 @@ mod tests {
 
     use crate::config::args::parse_from_args;
     use crate::storage::local::LocalStorageFactory;
-    use crate::storage::StorageFactory;
+    use crate::storage::{StorageFactory, StorageLimiters};
     use crate::types::token;
     use crate::types::token::PipelineCancellationToken;
     use crate::Config;
@@ -215,8 +223,7 @@ mod tests {
             token::create_pipeline_cancellation_token(),
             stats_sender,
             config.target_client_config.clone(),
-            None,
-            None,
+            StorageLimiters::default(),
         )
         .await;
 
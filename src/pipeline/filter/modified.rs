@@ -8,7 +8,10 @@ use crate::config::FilterConfig;
 use crate::pipeline::filter::{ObjectFilter, ObjectFilterBase};
 use crate::pipeline::stage::Stage;
 use crate::storage::e_tag_verify::normalize_e_tag;
-use crate::types::{sha1_digest_from_key, ObjectKey, ObjectKeyMap, S3syncObject};
+use crate::types::{
+    convert_object_to_target_key, normalize_key, sha1_digest_from_key, ObjectKey, ObjectKeyMap,
+    S3syncObject,
+};
 
 pub struct TargetModifiedFilter<'a> {
     base: ObjectFilterBase<'a>,
@@ -35,6 +38,13 @@ impl ObjectFilter for TargetModifiedFilter<'_> {
             self.base.filter(is_modified_from_size).await
         } else if self.base.base.config.filter_config.check_etag
             && !self.base.base.config.transfer_config.auto_chunksize
+            && self
+                .base
+                .base
+                .config
+                .filter_config
+                .check_checksum_algorithm
+                .is_none()
         {
             self.base.filter(is_modified_from_e_tag).await
         } else if self
@@ -47,7 +57,7 @@ impl ObjectFilter for TargetModifiedFilter<'_> {
             || (self.base.base.config.filter_config.check_etag
                 && self.base.base.config.transfer_config.auto_chunksize)
         {
-            // check etag will be done within the head object checker
+            // check etag and/or checksum will be done within the head object checker
             self.base.filter(always_modified).await
         } else {
             self.base.filter(is_modified_from_timestamp).await
@@ -57,11 +67,15 @@ impl ObjectFilter for TargetModifiedFilter<'_> {
 
 fn is_modified_from_timestamp(
     object: &S3syncObject,
-    _: &FilterConfig,
+    config: &FilterConfig,
     target_key_map: &ObjectKeyMap,
 ) -> bool {
     let target_key_map_map = target_key_map.lock().unwrap();
-    let key = object.key();
+    let normalized_key = normalize_key(
+        &convert_object_to_target_key(object, config),
+        config.normalize_unicode,
+    );
+    let key = normalized_key.as_str();
     let source_last_modified_date = object.last_modified();
 
     let result = target_key_map_map.get(&ObjectKey::KeySHA1Digest(sha1_digest_from_key(key)));
@@ -118,11 +132,15 @@ fn is_source_last_modified_date_newer(
 
 fn is_modified_from_size(
     object: &S3syncObject,
-    _: &FilterConfig,
+    config: &FilterConfig,
     target_key_map: &ObjectKeyMap,
 ) -> bool {
     let target_key_map_map = target_key_map.lock().unwrap();
-    let key = object.key();
+    let normalized_key = normalize_key(
+        &convert_object_to_target_key(object, config),
+        config.normalize_unicode,
+    );
+    let key = normalized_key.as_str();
 
     let result = target_key_map_map.get(&ObjectKey::KeySHA1Digest(sha1_digest_from_key(key)));
     if let Some(entry) = result {
@@ -157,11 +175,15 @@ fn is_modified_from_size(
 
 fn is_modified_from_e_tag(
     object: &S3syncObject,
-    _: &FilterConfig,
+    config: &FilterConfig,
     target_key_map: &ObjectKeyMap,
 ) -> bool {
     let locked_target_key_map = target_key_map.lock().unwrap();
-    let key = object.key();
+    let normalized_key = normalize_key(
+        &convert_object_to_target_key(object, config),
+        config.normalize_unicode,
+    );
+    let key = normalized_key.as_str();
 
     let mut result =
         locked_target_key_map.get(&ObjectKey::KeySHA1Digest(sha1_digest_from_key(key)));
@@ -229,7 +251,7 @@ mod tests {
     use aws_sdk_s3::types::Object;
 
     use crate::config::FilterConfig;
-    use crate::types::{ObjectEntry, S3syncObject};
+    use crate::types::{KeyCase, ObjectEntry, S3syncObject};
 
     use super::*;
 
@@ -255,6 +277,12 @@ mod tests {
             exclude_regex: None,
             larger_size: None,
             smaller_size: None,
+            owner: None,
+            skip_glacier: false,
+            skip_zero_byte_objects: false,
+            normalize_unicode: None,
+            target_key_case: None,
+            date_partition: None,
         };
 
         assert!(is_modified_from_timestamp(
@@ -286,6 +314,12 @@ mod tests {
             exclude_regex: None,
             larger_size: None,
             smaller_size: None,
+            owner: None,
+            skip_glacier: false,
+            skip_zero_byte_objects: false,
+            normalize_unicode: None,
+            target_key_case: None,
+            date_partition: None,
         };
 
         let mut key_map = HashMap::new();
@@ -327,6 +361,12 @@ mod tests {
             exclude_regex: None,
             larger_size: None,
             smaller_size: None,
+            owner: None,
+            skip_glacier: false,
+            skip_zero_byte_objects: false,
+            normalize_unicode: None,
+            target_key_case: None,
+            date_partition: None,
         };
 
         assert!(is_modified_from_timestamp(
@@ -358,6 +398,12 @@ mod tests {
             exclude_regex: None,
             larger_size: None,
             smaller_size: None,
+            owner: None,
+            skip_glacier: false,
+            skip_zero_byte_objects: false,
+            normalize_unicode: None,
+            target_key_case: None,
+            date_partition: None,
         };
 
         let mut key_map = HashMap::new();
@@ -377,6 +423,53 @@ mod tests {
         ));
     }
 
+    #[tokio::test]
+    async fn is_modified_false_with_target_key_case() {
+        init_dummy_tracing_subscriber();
+
+        let object = S3syncObject::NotVersioning(
+            Object::builder()
+                .key("Dir1/Test")
+                .last_modified(DateTime::from_secs(1))
+                .build(),
+        );
+
+        let config = FilterConfig {
+            before_time: None,
+            after_time: None,
+            remove_modified_filter: false,
+            check_size: false,
+            check_etag: false,
+            check_checksum_algorithm: None,
+            include_regex: None,
+            exclude_regex: None,
+            larger_size: None,
+            smaller_size: None,
+            owner: None,
+            skip_glacier: false,
+            skip_zero_byte_objects: false,
+            normalize_unicode: None,
+            target_key_case: Some(KeyCase::Lower),
+            date_partition: None,
+        };
+
+        let mut key_map = HashMap::new();
+        key_map.insert(
+            ObjectKey::KeyString("dir1/test".to_string()),
+            ObjectEntry {
+                last_modified: DateTime::from_secs(1),
+                content_length: 1,
+                e_tag: None,
+            },
+        );
+
+        assert!(!is_modified_from_timestamp(
+            &object,
+            &config,
+            &ObjectKeyMap::new(Mutex::new(key_map))
+        ));
+    }
+
     #[test]
     fn filter_modified_false() {
         init_dummy_tracing_subscriber();
@@ -421,6 +514,12 @@ mod tests {
             exclude_regex: None,
             larger_size: None,
             smaller_size: None,
+            owner: None,
+            skip_glacier: false,
+            skip_zero_byte_objects: false,
+            normalize_unicode: None,
+            target_key_case: None,
+            date_partition: None,
         };
 
         assert!(is_modified_from_size(
@@ -447,6 +546,12 @@ mod tests {
             exclude_regex: None,
             larger_size: None,
             smaller_size: None,
+            owner: None,
+            skip_glacier: false,
+            skip_zero_byte_objects: false,
+            normalize_unicode: None,
+            target_key_case: None,
+            date_partition: None,
         };
 
         let mut key_map = HashMap::new();
@@ -483,6 +588,12 @@ mod tests {
             exclude_regex: None,
             larger_size: None,
             smaller_size: None,
+            owner: None,
+            skip_glacier: false,
+            skip_zero_byte_objects: false,
+            normalize_unicode: None,
+            target_key_case: None,
+            date_partition: None,
         };
 
         let mut key_map = HashMap::new();
@@ -519,6 +630,12 @@ mod tests {
             exclude_regex: None,
             larger_size: None,
             smaller_size: None,
+            owner: None,
+            skip_glacier: false,
+            skip_zero_byte_objects: false,
+            normalize_unicode: None,
+            target_key_case: None,
+            date_partition: None,
         };
 
         let mut key_map = HashMap::new();
@@ -555,6 +672,12 @@ mod tests {
             exclude_regex: None,
             larger_size: None,
             smaller_size: None,
+            owner: None,
+            skip_glacier: false,
+            skip_zero_byte_objects: false,
+            normalize_unicode: None,
+            target_key_case: None,
+            date_partition: None,
         };
 
         let mut key_map = HashMap::new();
@@ -604,6 +727,12 @@ mod tests {
             exclude_regex: None,
             larger_size: None,
             smaller_size: None,
+            owner: None,
+            skip_glacier: false,
+            skip_zero_byte_objects: false,
+            normalize_unicode: None,
+            target_key_case: None,
+            date_partition: None,
         };
 
         let mut key_map = HashMap::new();
@@ -647,6 +776,12 @@ mod tests {
             exclude_regex: None,
             larger_size: None,
             smaller_size: None,
+            owner: None,
+            skip_glacier: false,
+            skip_zero_byte_objects: false,
+            normalize_unicode: None,
+            target_key_case: None,
+            date_partition: None,
         };
 
         let mut key_map = HashMap::new();
@@ -690,6 +825,12 @@ mod tests {
             exclude_regex: None,
             larger_size: None,
             smaller_size: None,
+            owner: None,
+            skip_glacier: false,
+            skip_zero_byte_objects: false,
+            normalize_unicode: None,
+            target_key_case: None,
+            date_partition: None,
         };
 
         let mut key_map = HashMap::new();
@@ -733,6 +874,12 @@ mod tests {
             exclude_regex: None,
             larger_size: None,
             smaller_size: None,
+            owner: None,
+            skip_glacier: false,
+            skip_zero_byte_objects: false,
+            normalize_unicode: None,
+            target_key_case: None,
+            date_partition: None,
         };
 
         let mut key_map = HashMap::new();
@@ -99,6 +99,12 @@ mod tests {
             exclude_regex: None,
             larger_size: None,
             smaller_size: None,
+            owner: None,
+            skip_glacier: false,
+            skip_zero_byte_objects: false,
+            normalize_unicode: None,
+            target_key_case: None,
+            date_partition: None,
         };
 
         assert!(is_after_or_equal(
@@ -133,6 +139,12 @@ mod tests {
             exclude_regex: None,
             larger_size: None,
             smaller_size: None,
+            owner: None,
+            skip_glacier: false,
+            skip_zero_byte_objects: false,
+            normalize_unicode: None,
+            target_key_case: None,
+            date_partition: None,
         };
 
         assert!(!is_after_or_equal(
@@ -166,6 +178,12 @@ mod tests {
             exclude_regex: None,
             larger_size: None,
             smaller_size: None,
+            owner: None,
+            skip_glacier: false,
+            skip_zero_byte_objects: false,
+            normalize_unicode: None,
+            target_key_case: None,
+            date_partition: None,
         };
 
         assert!(is_after_or_equal(
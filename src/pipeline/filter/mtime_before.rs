@@ -97,6 +97,12 @@ mod tests {
             exclude_regex: None,
             larger_size: None,
             smaller_size: None,
+            owner: None,
+            skip_glacier: false,
+            skip_zero_byte_objects: false,
+            normalize_unicode: None,
+            target_key_case: None,
+            date_partition: None,
         };
 
         assert!(is_before(
@@ -131,6 +137,12 @@ mod tests {
             exclude_regex: None,
             larger_size: None,
             smaller_size: None,
+            owner: None,
+            skip_glacier: false,
+            skip_zero_byte_objects: false,
+            normalize_unicode: None,
+            target_key_case: None,
+            date_partition: None,
         };
 
         assert!(!is_before(
@@ -165,6 +177,12 @@ mod tests {
             exclude_regex: None,
             larger_size: None,
             smaller_size: None,
+            owner: None,
+            skip_glacier: false,
+            skip_zero_byte_objects: false,
+            normalize_unicode: None,
+            target_key_case: None,
+            date_partition: None,
         };
 
         assert!(!is_before(
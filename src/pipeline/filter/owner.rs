@@ -0,0 +1,159 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use tracing::debug;
+
+use crate::config::FilterConfig;
+use crate::pipeline::filter::{ObjectFilter, ObjectFilterBase};
+use crate::pipeline::stage::Stage;
+use crate::types::{ObjectKeyMap, S3syncObject};
+
+pub struct OwnerFilter<'a> {
+    base: ObjectFilterBase<'a>,
+}
+
+const FILTER_NAME: &str = "OwnerFilter";
+
+impl OwnerFilter<'_> {
+    pub fn new(base: Stage, target_key_map: Option<ObjectKeyMap>) -> Self {
+        Self {
+            base: ObjectFilterBase {
+                base,
+                target_key_map,
+                name: FILTER_NAME,
+            },
+        }
+    }
+}
+
+#[async_trait]
+impl ObjectFilter for OwnerFilter<'_> {
+    async fn filter(&self) -> Result<()> {
+        self.base.filter(is_owned_by).await
+    }
+}
+
+// the object without the owner is filtered, because its owner cannot be confirmed.
+fn is_owned_by(object: &S3syncObject, config: &FilterConfig, _: &ObjectKeyMap) -> bool {
+    if object.is_delete_marker() {
+        return true;
+    }
+
+    let owner_id = object.owner_id();
+    if owner_id != config.owner.as_deref() {
+        let key = object.key();
+        let version_id = object.version_id();
+        let config_owner = config.owner.as_deref().unwrap();
+
+        debug!(
+            name = FILTER_NAME,
+            key = key,
+            version_id = version_id,
+            owner_id = owner_id,
+            config_owner = config_owner,
+            "object filtered."
+        );
+        return false;
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    use aws_sdk_s3::types::{DeleteMarkerEntry, Object, ObjectVersion, Owner};
+
+    use super::*;
+
+    const OWNER_ID: &str = "79a59df900b949e55d96a1e698fbacedfd6e09d98eacf8f8d5218e7cd47ef2be";
+
+    #[test]
+    fn owned_by() {
+        init_dummy_tracing_subscriber();
+
+        let config = FilterConfig {
+            owner: Some(OWNER_ID.to_string()),
+            ..Default::default()
+        };
+
+        let object = S3syncObject::NotVersioning(
+            Object::builder()
+                .key("test")
+                .owner(Owner::builder().id(OWNER_ID).build())
+                .build(),
+        );
+        assert!(is_owned_by(
+            &object,
+            &config,
+            &ObjectKeyMap::new(Mutex::new(HashMap::new()))
+        ));
+
+        let object = S3syncObject::Versioning(
+            ObjectVersion::builder()
+                .key("test")
+                .version_id("version1")
+                .owner(Owner::builder().id(OWNER_ID).build())
+                .build(),
+        );
+        assert!(is_owned_by(
+            &object,
+            &config,
+            &ObjectKeyMap::new(Mutex::new(HashMap::new()))
+        ));
+    }
+
+    #[test]
+    fn not_owned_by() {
+        init_dummy_tracing_subscriber();
+
+        let config = FilterConfig {
+            owner: Some(OWNER_ID.to_string()),
+            ..Default::default()
+        };
+
+        let object = S3syncObject::NotVersioning(
+            Object::builder()
+                .key("test")
+                .owner(Owner::builder().id("other_owner_id").build())
+                .build(),
+        );
+        assert!(!is_owned_by(
+            &object,
+            &config,
+            &ObjectKeyMap::new(Mutex::new(HashMap::new()))
+        ));
+
+        let object = S3syncObject::NotVersioning(Object::builder().key("test").build());
+        assert!(!is_owned_by(
+            &object,
+            &config,
+            &ObjectKeyMap::new(Mutex::new(HashMap::new()))
+        ));
+    }
+
+    #[test]
+    fn delete_marker() {
+        init_dummy_tracing_subscriber();
+
+        let config = FilterConfig {
+            owner: Some(OWNER_ID.to_string()),
+            ..Default::default()
+        };
+
+        let delete_marker =
+            S3syncObject::DeleteMarker(DeleteMarkerEntry::builder().key("test").build());
+        assert!(is_owned_by(
+            &delete_marker,
+            &config,
+            &ObjectKeyMap::new(Mutex::new(HashMap::new()))
+        ));
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
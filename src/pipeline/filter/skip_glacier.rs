@@ -0,0 +1,270 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use tracing::debug;
+
+use crate::config::FilterConfig;
+use crate::pipeline::filter::{ObjectFilter, ObjectFilterBase};
+use crate::pipeline::stage::Stage;
+use crate::types::{ObjectKeyMap, S3syncObject, SyncStatistics};
+
+// GLACIER_IR is not included because it can be retrieved without restore.
+const ARCHIVED_STORAGE_CLASSES: [&str; 2] = ["GLACIER", "DEEP_ARCHIVE"];
+
+pub struct SkipGlacierFilter<'a> {
+    base: ObjectFilterBase<'a>,
+}
+
+const FILTER_NAME: &str = "SkipGlacierFilter";
+
+impl SkipGlacierFilter<'_> {
+    pub fn new(base: Stage, target_key_map: Option<ObjectKeyMap>) -> Self {
+        Self {
+            base: ObjectFilterBase {
+                base,
+                target_key_map,
+                name: FILTER_NAME,
+            },
+        }
+    }
+}
+
+#[async_trait]
+impl ObjectFilter for SkipGlacierFilter<'_> {
+    async fn filter(&self) -> Result<()> {
+        // the stats channel is unbounded, so try_send() does not fail unless it is closed.
+        let stats_sender = self.base.base.target.as_ref().unwrap().get_stats_sender();
+        self.base
+            .filter(|object, config, target_key_map| {
+                if is_not_archived(object, config, target_key_map) {
+                    return true;
+                }
+
+                let _ = stats_sender.try_send(SyncStatistics::ArchivedSkip {
+                    key: object.key().to_string(),
+                });
+                false
+            })
+            .await
+    }
+}
+
+fn is_not_archived(object: &S3syncObject, _: &FilterConfig, _: &ObjectKeyMap) -> bool {
+    if object.is_delete_marker() {
+        return true;
+    }
+
+    if let Some(storage_class) = object.storage_class() {
+        if ARCHIVED_STORAGE_CLASSES.contains(&storage_class) {
+            let key = object.key();
+            let version_id = object.version_id();
+
+            debug!(
+                name = FILTER_NAME,
+                key = key,
+                version_id = version_id,
+                storage_class = storage_class,
+                "archived object filtered."
+            );
+            return false;
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    use aws_sdk_s3::types::{
+        DeleteMarkerEntry, Object, ObjectStorageClass, ObjectVersion, ObjectVersionStorageClass,
+    };
+
+    use crate::config::args::parse_from_args;
+    use crate::storage::local::LocalStorageFactory;
+    use crate::storage::{StorageFactory, StorageLimiters};
+    use crate::types::token;
+    use crate::Config;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn filter_sends_archived_skip() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-access-key",
+            "source_access_key",
+            "--source-secret-access-key",
+            "source_secret_access_key",
+            "--skip-glacier",
+            "s3://source-bucket",
+            "/target-dir",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+        let (stats_sender, stats_receiver) = async_channel::unbounded();
+        let storage = LocalStorageFactory::create(
+            config.clone(),
+            config.target.clone(),
+            token::create_pipeline_cancellation_token(),
+            stats_sender,
+            config.target_client_config.clone(),
+            StorageLimiters::default(),
+        )
+        .await;
+
+        let (sender, receiver) = async_channel::bounded::<S3syncObject>(1000);
+        let (next_stage_sender, next_stage_receiver) = async_channel::bounded(1000);
+        let filter = SkipGlacierFilter::new(
+            Stage::new(
+                config,
+                None,
+                Some(storage),
+                Some(receiver),
+                Some(next_stage_sender),
+                token::create_pipeline_cancellation_token(),
+            ),
+            None,
+        );
+
+        sender
+            .send(S3syncObject::NotVersioning(
+                Object::builder()
+                    .key("archived")
+                    .storage_class(ObjectStorageClass::Glacier)
+                    .build(),
+            ))
+            .await
+            .unwrap();
+        sender
+            .send(S3syncObject::NotVersioning(
+                Object::builder().key("standard").build(),
+            ))
+            .await
+            .unwrap();
+        sender.close();
+
+        filter.filter().await.unwrap();
+
+        assert_eq!(next_stage_receiver.recv().await.unwrap().key(), "standard");
+        assert!(next_stage_receiver.try_recv().is_err());
+
+        let mut archived_skip_count = 0;
+        let mut skip_count = 0;
+        while let Ok(stats) = stats_receiver.try_recv() {
+            match stats {
+                SyncStatistics::ArchivedSkip { key } => {
+                    assert_eq!(key, "archived");
+                    archived_skip_count += 1;
+                }
+                SyncStatistics::SyncSkip { .. } => skip_count += 1,
+                _ => {}
+            }
+        }
+        assert_eq!(archived_skip_count, 1);
+        assert_eq!(skip_count, 1);
+    }
+
+    #[test]
+    fn archived() {
+        init_dummy_tracing_subscriber();
+
+        let config = FilterConfig::default();
+
+        let object = S3syncObject::NotVersioning(
+            Object::builder()
+                .key("test")
+                .storage_class(ObjectStorageClass::Glacier)
+                .build(),
+        );
+        assert!(!is_not_archived(
+            &object,
+            &config,
+            &ObjectKeyMap::new(Mutex::new(HashMap::new()))
+        ));
+
+        let object = S3syncObject::NotVersioning(
+            Object::builder()
+                .key("test")
+                .storage_class(ObjectStorageClass::DeepArchive)
+                .build(),
+        );
+        assert!(!is_not_archived(
+            &object,
+            &config,
+            &ObjectKeyMap::new(Mutex::new(HashMap::new()))
+        ));
+
+        let object = S3syncObject::Versioning(
+            ObjectVersion::builder()
+                .key("test")
+                .storage_class(ObjectVersionStorageClass::from("GLACIER"))
+                .build(),
+        );
+        assert!(!is_not_archived(
+            &object,
+            &config,
+            &ObjectKeyMap::new(Mutex::new(HashMap::new()))
+        ));
+    }
+
+    #[test]
+    fn not_archived() {
+        init_dummy_tracing_subscriber();
+
+        let config = FilterConfig::default();
+
+        let object = S3syncObject::NotVersioning(
+            Object::builder()
+                .key("test")
+                .storage_class(ObjectStorageClass::GlacierIr)
+                .build(),
+        );
+        assert!(is_not_archived(
+            &object,
+            &config,
+            &ObjectKeyMap::new(Mutex::new(HashMap::new()))
+        ));
+
+        let object = S3syncObject::NotVersioning(
+            Object::builder()
+                .key("test")
+                .storage_class(ObjectStorageClass::Standard)
+                .build(),
+        );
+        assert!(is_not_archived(
+            &object,
+            &config,
+            &ObjectKeyMap::new(Mutex::new(HashMap::new()))
+        ));
+
+        let object = S3syncObject::NotVersioning(Object::builder().key("test").build());
+        assert!(is_not_archived(
+            &object,
+            &config,
+            &ObjectKeyMap::new(Mutex::new(HashMap::new()))
+        ));
+    }
+
+    #[test]
+    fn delete_marker() {
+        init_dummy_tracing_subscriber();
+
+        let delete_marker =
+            S3syncObject::DeleteMarker(DeleteMarkerEntry::builder().key("test").build());
+
+        assert!(is_not_archived(
+            &delete_marker,
+            &FilterConfig::default(),
+            &ObjectKeyMap::new(Mutex::new(HashMap::new()))
+        ));
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
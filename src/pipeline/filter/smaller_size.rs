@@ -87,6 +87,12 @@ mod tests {
             exclude_regex: None,
             larger_size: None,
             smaller_size: Some(5),
+            owner: None,
+            skip_glacier: false,
+            skip_zero_byte_objects: false,
+            normalize_unicode: None,
+            target_key_case: None,
+            date_partition: None,
         };
 
         assert!(!is_smaller(
@@ -113,6 +119,12 @@ mod tests {
             exclude_regex: None,
             larger_size: None,
             smaller_size: Some(5),
+            owner: None,
+            skip_glacier: false,
+            skip_zero_byte_objects: false,
+            normalize_unicode: None,
+            target_key_case: None,
+            date_partition: None,
         };
 
         assert!(is_smaller(
@@ -139,6 +151,12 @@ mod tests {
             exclude_regex: None,
             larger_size: None,
             smaller_size: Some(5),
+            owner: None,
+            skip_glacier: false,
+            skip_zero_byte_objects: false,
+            normalize_unicode: None,
+            target_key_case: None,
+            date_partition: None,
         };
 
         assert!(!is_smaller(
@@ -166,6 +184,12 @@ mod tests {
             exclude_regex: None,
             larger_size: None,
             smaller_size: Some(5),
+            owner: None,
+            skip_glacier: false,
+            skip_zero_byte_objects: false,
+            normalize_unicode: None,
+            target_key_case: None,
+            date_partition: None,
         };
 
         assert!(is_smaller(
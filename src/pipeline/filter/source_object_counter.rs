@@ -0,0 +1,42 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::pipeline::filter::{ObjectFilter, ObjectFilterBase};
+use crate::pipeline::stage::Stage;
+
+// Counts the source objects that have passed the user filters(--fail-on-empty-source).
+// it passes all objects.
+pub struct SourceObjectCounter<'a> {
+    base: ObjectFilterBase<'a>,
+    source_object_count: Arc<AtomicU64>,
+}
+
+const FILTER_NAME: &str = "SourceObjectCounter";
+
+impl SourceObjectCounter<'_> {
+    pub fn new(base: Stage, source_object_count: Arc<AtomicU64>) -> Self {
+        Self {
+            base: ObjectFilterBase {
+                base,
+                target_key_map: None,
+                name: FILTER_NAME,
+            },
+            source_object_count,
+        }
+    }
+}
+
+#[async_trait]
+impl ObjectFilter for SourceObjectCounter<'_> {
+    async fn filter(&self) -> Result<()> {
+        self.base
+            .filter(|_, _, _| {
+                self.source_object_count.fetch_add(1, Ordering::SeqCst);
+                true
+            })
+            .await
+    }
+}
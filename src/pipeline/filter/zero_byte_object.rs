@@ -0,0 +1,135 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use tracing::debug;
+
+use crate::config::FilterConfig;
+use crate::pipeline::filter::{ObjectFilter, ObjectFilterBase};
+use crate::pipeline::stage::Stage;
+use crate::types::{ObjectKeyMap, S3syncObject};
+
+// With --zero-byte-object-policy skip, zero-byte source objects(including the directory markers
+// whose keys end with '/') are not transferred. they are counted as skipped.
+pub struct ZeroByteObjectFilter<'a> {
+    base: ObjectFilterBase<'a>,
+}
+
+const FILTER_NAME: &str = "ZeroByteObjectFilter";
+
+impl ZeroByteObjectFilter<'_> {
+    pub fn new(base: Stage, target_key_map: Option<ObjectKeyMap>) -> Self {
+        Self {
+            base: ObjectFilterBase {
+                base,
+                target_key_map,
+                name: FILTER_NAME,
+            },
+        }
+    }
+}
+
+#[async_trait]
+impl ObjectFilter for ZeroByteObjectFilter<'_> {
+    async fn filter(&self) -> Result<()> {
+        self.base.filter(is_not_zero_byte).await
+    }
+}
+
+fn is_not_zero_byte(object: &S3syncObject, _: &FilterConfig, _: &ObjectKeyMap) -> bool {
+    if object.is_delete_marker() {
+        return true;
+    }
+
+    if object.size() == 0 {
+        let key = object.key();
+        let version_id = object.version_id();
+
+        debug!(
+            name = FILTER_NAME,
+            key = key,
+            version_id = version_id,
+            "zero byte object filtered."
+        );
+        return false;
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    use aws_sdk_s3::types::{DeleteMarkerEntry, Object, ObjectVersion};
+
+    use super::*;
+
+    #[test]
+    fn zero_byte() {
+        init_dummy_tracing_subscriber();
+
+        let config = FilterConfig::default();
+
+        let object = S3syncObject::NotVersioning(Object::builder().key("test").size(0).build());
+        assert!(!is_not_zero_byte(
+            &object,
+            &config,
+            &ObjectKeyMap::new(Mutex::new(HashMap::new()))
+        ));
+
+        let object = S3syncObject::NotVersioning(Object::builder().key("dir1/").size(0).build());
+        assert!(!is_not_zero_byte(
+            &object,
+            &config,
+            &ObjectKeyMap::new(Mutex::new(HashMap::new()))
+        ));
+
+        let object = S3syncObject::Versioning(ObjectVersion::builder().key("test").size(0).build());
+        assert!(!is_not_zero_byte(
+            &object,
+            &config,
+            &ObjectKeyMap::new(Mutex::new(HashMap::new()))
+        ));
+    }
+
+    #[test]
+    fn not_zero_byte() {
+        init_dummy_tracing_subscriber();
+
+        let config = FilterConfig::default();
+
+        let object = S3syncObject::NotVersioning(Object::builder().key("test").size(1).build());
+        assert!(is_not_zero_byte(
+            &object,
+            &config,
+            &ObjectKeyMap::new(Mutex::new(HashMap::new()))
+        ));
+
+        let object = S3syncObject::Versioning(ObjectVersion::builder().key("test").size(6).build());
+        assert!(is_not_zero_byte(
+            &object,
+            &config,
+            &ObjectKeyMap::new(Mutex::new(HashMap::new()))
+        ));
+    }
+
+    #[test]
+    fn delete_marker() {
+        init_dummy_tracing_subscriber();
+
+        let delete_marker =
+            S3syncObject::DeleteMarker(DeleteMarkerEntry::builder().key("test").build());
+
+        assert!(is_not_zero_byte(
+            &delete_marker,
+            &FilterConfig::default(),
+            &ObjectKeyMap::new(Mutex::new(HashMap::new()))
+        ));
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
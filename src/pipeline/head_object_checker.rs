@@ -1,3 +1,5 @@
+use std::sync::{Arc, Mutex};
+
 use anyhow::{anyhow, Result};
 use aws_sdk_s3::operation::head_object::{HeadObjectError, HeadObjectOutput};
 use aws_sdk_s3::types::builders::ObjectPartBuilder;
@@ -7,45 +9,81 @@ use aws_smithy_runtime_api::http::Response;
 use aws_smithy_types::body::SdkBody;
 use aws_smithy_types::DateTime;
 use aws_smithy_types_convert::date_time::DateTimeExt;
-use tracing::{debug, warn};
+use tokio::sync::Semaphore;
+use tracing::{debug, info, trace, warn};
 
+use crate::config::FilterConfig;
 use crate::storage::additional_checksum_verify::{
     generate_checksum_from_path_for_check, generate_checksum_from_path_with_chunksize,
 };
 use crate::storage::e_tag_verify::{
-    generate_e_tag_hash_from_path, generate_e_tag_hash_from_path_with_auto_chunksize,
+    generate_e_tag_hash_from_path_or_md5_xattr, generate_e_tag_hash_from_path_with_auto_chunksize,
     normalize_e_tag,
 };
 use crate::storage::local::fs_util;
-use crate::storage::Storage;
-use crate::types::S3syncObject;
+use crate::storage::{acquire_checksum_permit, Storage};
 use crate::types::SyncStatistics::SyncWarning;
+use crate::types::{is_object_in_target_key_map, normalize_key, ObjectKeyMap, S3syncObject};
 use crate::{types, Config};
 
 const FILTER_NAME: &str = "HeadObjectChecker";
+const HEAD_NOT_FOUND_RETRY_INTERVAL_MILLISECONDS: u64 = 500;
 
 pub struct HeadObjectChecker {
     worker_index: u16,
     config: Config,
     source: Storage,
     target: Storage,
+    target_last_modified: Mutex<Option<DateTime>>,
+    target_key_map: Option<ObjectKeyMap>,
+    checksum_semaphore: Option<Arc<Semaphore>>,
 }
 
 impl HeadObjectChecker {
-    pub fn new(config: Config, source: Storage, target: Storage, worker_index: u16) -> Self {
+    pub fn new(
+        config: Config,
+        source: Storage,
+        target: Storage,
+        worker_index: u16,
+        target_key_map: Option<ObjectKeyMap>,
+        checksum_semaphore: Option<Arc<Semaphore>>,
+    ) -> Self {
         Self {
             config,
             source,
             target,
             worker_index,
+            target_last_modified: Mutex::new(None),
+            target_key_map,
+            checksum_semaphore,
         }
     }
 
+    // LastModified of the target object observed by is_sync_required(), if any.
+    pub(crate) fn get_target_last_modified(&self) -> Option<DateTime> {
+        *self.target_last_modified.lock().unwrap()
+    }
+
+    // with --skip-if-target-newer, the target object that is newer than the source is left as is,
+    // including its tagging.
+    pub(crate) fn is_target_newer_than_source(&self, source_object: &S3syncObject) -> bool {
+        self.config.skip_if_target_newer
+            && self
+                .get_target_last_modified()
+                .is_some_and(|target_last_modified| {
+                    source_object.last_modified().secs() < target_last_modified.secs()
+                })
+    }
+
     pub(crate) async fn is_sync_required(&self, source_object: &S3syncObject) -> Result<bool> {
         if !self.is_head_object_check_required() {
             return Ok(true);
         }
 
+        if self.is_new_object_in_target_listing(source_object) {
+            return Ok(true);
+        }
+
         if self.check_target_local_storage_allow_overwrite() {
             return Ok(true);
         }
@@ -67,18 +105,18 @@ impl HeadObjectChecker {
 
         let key = source_object.key();
         let head_target_object_output = self
-            .target
-            .head_object(
-                key,
-                None,
-                checksum_mode,
-                self.config.target_sse_c.clone(),
-                self.config.target_sse_c_key.clone(),
-                self.config.target_sse_c_key_md5.clone(),
-            )
+            .head_target_object(&self.target_key(source_object), checksum_mode)
             .await;
 
         if let Ok(target_object) = head_target_object_output {
+            *self.target_last_modified.lock().unwrap() = target_object.last_modified().copied();
+
+            if self.config.skip_if_target_newer
+                && is_target_object_newer(source_object, &target_object)
+            {
+                return Ok(false);
+            }
+
             return if self.config.filter_config.check_size {
                 let different_size =
                     source_object.size() != target_object.content_length().unwrap();
@@ -96,40 +134,31 @@ impl HeadObjectChecker {
 
                 Ok(different_size)
             } else if self.config.filter_config.check_etag
-                && (self.config.head_each_target || self.config.transfer_config.auto_chunksize)
+                && self.config.filter_config.check_checksum_algorithm.is_some()
             {
-                if !self.source.is_local_storage() && !self.target.is_local_storage() {
-                    return self
-                        .are_different_e_tags(key, source_object, &target_object)
-                        .await;
-                } else if self.source.is_local_storage() && !self.target.is_local_storage() {
-                    Ok(self
-                        .is_source_local_e_tag_different_from_target_s3(key, &target_object)
-                        .await?)
-                } else if !self.source.is_local_storage() && self.target.is_local_storage() {
-                    Ok(self
-                        .is_target_local_e_tag_different_from_source_s3(key, source_object)
-                        .await?)
-                } else {
-                    panic!("source and target are both local storage.")
+                // with --check-etag-and-checksum, the object is unchanged only if both match.
+                if self
+                    .is_e_tag_different(key, source_object, &target_object)
+                    .await?
+                {
+                    return Ok(true);
                 }
+
+                self.is_checksum_different(key, source_object, &target_object)
+                    .await
+            } else if self.config.filter_config.check_etag
+                && (self.config.head_each_target
+                    || self.config.target_list_cache_invalidate_on_write
+                    || self.config.transfer_config.auto_chunksize)
+            {
+                self.is_e_tag_different(key, source_object, &target_object)
+                    .await
             } else if self.config.filter_config.check_etag {
                 // ETag has been checked by modified filter
                 Ok(true)
             } else if self.config.filter_config.check_checksum_algorithm.is_some() {
-                if !self.source.is_local_storage() && !self.target.is_local_storage() {
-                    return self.are_different_checksums(key, &target_object).await;
-                } else if self.source.is_local_storage() && !self.target.is_local_storage() {
-                    return self
-                        .is_source_local_checksum_different_from_target_s3(key, &target_object)
-                        .await;
-                } else if !self.source.is_local_storage() && self.target.is_local_storage() {
-                    return self
-                        .is_target_local_checksum_different_from_source_s3(key, &target_object)
-                        .await;
-                } else {
-                    panic!("source and target are both local storage.")
-                }
+                self.is_checksum_different(key, source_object, &target_object)
+                    .await
             } else {
                 Ok(is_object_modified(source_object, &target_object))
             };
@@ -161,6 +190,56 @@ impl HeadObjectChecker {
         Err(anyhow!("head_object() failed. key={}.", key,))
     }
 
+    // with --target-object-key-case and --date-partition, the key of the target object is converted from the source object.
+    fn target_key(&self, source_object: &S3syncObject) -> String {
+        types::convert_object_to_target_key(source_object, &self.config.filter_config)
+    }
+
+    // With --head-not-found-retries, NotFound is retried to tolerate eventual consistency.
+    async fn head_target_object(
+        &self,
+        key: &str,
+        checksum_mode: Option<ChecksumMode>,
+    ) -> Result<HeadObjectOutput> {
+        let mut retry_count = 0;
+        loop {
+            let result = self
+                .target
+                .head_object(
+                    key,
+                    None,
+                    checksum_mode.clone(),
+                    self.config.target_sse_c.clone(),
+                    self.config.target_sse_c_key.clone(),
+                    self.config.target_sse_c_key_md5.clone(),
+                )
+                .await;
+
+            if let Err(e) = &result {
+                if retry_count < self.config.head_not_found_retries
+                    && is_head_object_not_found_error(e)
+                {
+                    retry_count += 1;
+                    debug!(
+                        worker_index = self.worker_index,
+                        key = key,
+                        retry_count = retry_count,
+                        "target object not found. retrying HeadObject for eventual consistency."
+                    );
+
+                    tokio::time::sleep(std::time::Duration::from_millis(
+                        HEAD_NOT_FOUND_RETRY_INTERVAL_MILLISECONDS,
+                    ))
+                    .await;
+
+                    continue;
+                }
+            }
+
+            return result;
+        }
+    }
+
     async fn are_different_e_tags(
         &self,
         key: &str,
@@ -234,7 +313,9 @@ impl HeadObjectChecker {
                 .source
                 .head_object(
                     key,
-                    None,
+                    source_object
+                        .version_id()
+                        .map(|version_id| version_id.to_string()),
                     None,
                     self.config.source_sse_c.clone(),
                     self.config.source_sse_c_key.clone(),
@@ -285,17 +366,22 @@ impl HeadObjectChecker {
     async fn is_source_local_e_tag_different_from_target_s3(
         &self,
         key: &str,
+        source_object: &S3syncObject,
         head_target_object_output: &HeadObjectOutput,
     ) -> Result<bool> {
+        let _checksum_permit = acquire_checksum_permit(&self.checksum_semaphore).await;
         let source_e_tag = if self.source.is_local_storage() {
-            let mut local_path = self.source.get_local_path();
-            local_path.push(key);
+            let local_path = fs_util::key_to_source_file_path(
+                self.source.get_local_path(),
+                key,
+                &self.config.upload_manifest,
+            );
 
             if self.config.transfer_config.auto_chunksize {
                 if let Ok(object_parts) = self
                     .target
                     .get_object_parts(
-                        key,
+                        &self.target_key(source_object),
                         None,
                         self.config.target_sse_c.clone(),
                         self.config.target_sse_c_key.clone(),
@@ -304,10 +390,11 @@ impl HeadObjectChecker {
                     .await
                 {
                     if object_parts.is_empty() {
-                        generate_e_tag_hash_from_path(
+                        generate_e_tag_hash_from_path_or_md5_xattr(
                             &local_path,
                             self.config.transfer_config.multipart_chunksize as usize,
                             self.config.transfer_config.multipart_threshold as usize,
+                            &self.config.md5_xattr,
                         )
                         .await?
                     } else {
@@ -325,10 +412,11 @@ impl HeadObjectChecker {
                     return Err(anyhow!("get_object_parts() failed. key={}.", key,));
                 }
             } else {
-                generate_e_tag_hash_from_path(
+                generate_e_tag_hash_from_path_or_md5_xattr(
                     &local_path,
                     self.config.transfer_config.multipart_chunksize as usize,
                     self.config.transfer_config.multipart_threshold as usize,
+                    &self.config.md5_xattr,
                 )
                 .await?
             }
@@ -438,15 +526,24 @@ impl HeadObjectChecker {
         source_object: &S3syncObject,
     ) -> Result<bool> {
         let source_e_tag = source_object.e_tag();
+        let _checksum_permit = acquire_checksum_permit(&self.checksum_semaphore).await;
         let target_e_tag = if self.target.is_local_storage() {
-            let local_path = fs_util::key_to_file_path(self.target.get_local_path(), key);
+            let local_path = fs_util::key_to_file_path(
+                self.target.get_local_path(),
+                &normalize_key(
+                    &self.target_key(source_object),
+                    self.config.filter_config.normalize_unicode,
+                ),
+            );
 
             if self.config.transfer_config.auto_chunksize {
                 if let Ok(object_parts) = self
                     .source
                     .get_object_parts(
                         key,
-                        None,
+                        source_object
+                            .version_id()
+                            .map(|version_id| version_id.to_string()),
                         self.config.target_sse_c.clone(),
                         self.config.target_sse_c_key.clone(),
                         self.config.target_sse_c_key_md5.clone(),
@@ -454,10 +551,11 @@ impl HeadObjectChecker {
                     .await
                 {
                     if object_parts.is_empty() {
-                        generate_e_tag_hash_from_path(
+                        generate_e_tag_hash_from_path_or_md5_xattr(
                             &local_path,
                             self.config.transfer_config.multipart_chunksize as usize,
                             self.config.transfer_config.multipart_threshold as usize,
+                            &self.config.md5_xattr,
                         )
                         .await?
                     } else {
@@ -475,10 +573,11 @@ impl HeadObjectChecker {
                     return Err(anyhow!("get_object_parts() failed. key={}.", key,));
                 }
             } else {
-                generate_e_tag_hash_from_path(
+                generate_e_tag_hash_from_path_or_md5_xattr(
                     &local_path,
                     self.config.transfer_config.multipart_chunksize as usize,
                     self.config.transfer_config.multipart_threshold as usize,
+                    &self.config.md5_xattr,
                 )
                 .await?
             }
@@ -492,7 +591,7 @@ impl HeadObjectChecker {
         let head_target_object_output = self
             .target
             .head_object(
-                key,
+                &self.target_key(source_object),
                 None,
                 None,
                 self.config.target_sse_c.clone(),
@@ -538,7 +637,9 @@ impl HeadObjectChecker {
                 .source
                 .head_object(
                     key,
-                    None,
+                    source_object
+                        .version_id()
+                        .map(|version_id| version_id.to_string()),
                     None,
                     self.config.source_sse_c.clone(),
                     self.config.source_sse_c_key.clone(),
@@ -735,10 +836,14 @@ impl HeadObjectChecker {
     async fn is_source_local_checksum_different_from_target_s3(
         &self,
         key: &str,
+        source_object: &S3syncObject,
         head_target_object_output: &HeadObjectOutput,
     ) -> Result<bool> {
-        let mut local_path = self.source.get_local_path();
-        local_path.push(key);
+        let local_path = fs_util::key_to_source_file_path(
+            self.source.get_local_path(),
+            key,
+            &self.config.upload_manifest,
+        );
 
         let head_source_object_output = self
             .source
@@ -810,7 +915,7 @@ impl HeadObjectChecker {
         let target_object_parts = if head_target_object_output.checksum_crc64_nvme.is_none() {
             self.target
                 .get_object_parts_attributes(
-                    key,
+                    &self.target_key(source_object),
                     None,
                     self.config.max_keys,
                     self.config.target_sse_c.clone(),
@@ -823,6 +928,7 @@ impl HeadObjectChecker {
         };
 
         let multipart_checksum = !target_object_parts.is_empty();
+        let _checksum_permit = acquire_checksum_permit(&self.checksum_semaphore).await;
         let source_checksum = if multipart_checksum {
             generate_checksum_from_path_for_check(
                 &local_path,
@@ -929,9 +1035,16 @@ impl HeadObjectChecker {
     async fn is_target_local_checksum_different_from_source_s3(
         &self,
         key: &str,
+        source_object: &S3syncObject,
         head_target_object_output: &HeadObjectOutput,
     ) -> Result<bool> {
-        let local_path = fs_util::key_to_file_path(self.target.get_local_path(), key);
+        let local_path = fs_util::key_to_file_path(
+            self.target.get_local_path(),
+            &normalize_key(
+                &self.target_key(source_object),
+                self.config.filter_config.normalize_unicode,
+            ),
+        );
 
         let head_source_object_output = self
             .source
@@ -1015,8 +1128,31 @@ impl HeadObjectChecker {
             vec![]
         };
 
+        let checksum_algorithm = self
+            .config
+            .filter_config
+            .check_checksum_algorithm
+            .as_ref()
+            .unwrap()
+            .to_string();
+        let cached_checksum =
+            if self.config.checksum_on_download_only_changed && !self.config.force_reverify {
+                fs_util::get_cached_checksum(&local_path, &checksum_algorithm)
+            } else {
+                None
+            };
+
         let multipart_checksum = !source_object_parts.is_empty();
-        let target_checksum = if multipart_checksum {
+        let _checksum_permit = acquire_checksum_permit(&self.checksum_semaphore).await;
+        let target_checksum = if let Some(cached_checksum) = cached_checksum {
+            trace!(
+                name = FILTER_NAME,
+                key = key,
+                cached_checksum = cached_checksum,
+                "the local file is unchanged since verified. use the cached checksum."
+            );
+            cached_checksum
+        } else if multipart_checksum {
             generate_checksum_from_path_for_check(
                 &local_path,
                 self.config
@@ -1056,6 +1192,10 @@ impl HeadObjectChecker {
         }
 
         if source_checksum.as_ref().unwrap().as_str() == target_checksum {
+            if self.config.checksum_on_download_only_changed {
+                fs_util::set_cached_checksum(&local_path, &checksum_algorithm, &target_checksum);
+            }
+
             if head_source_object_output.content_length().unwrap()
                 != head_target_object_output.content_length().unwrap()
             {
@@ -1128,7 +1268,58 @@ impl HeadObjectChecker {
         Ok(true)
     }
 
+    async fn is_e_tag_different(
+        &self,
+        key: &str,
+        source_object: &S3syncObject,
+        target_object: &HeadObjectOutput,
+    ) -> Result<bool> {
+        if !self.source.is_local_storage() && !self.target.is_local_storage() {
+            self.are_different_e_tags(key, source_object, target_object)
+                .await
+        } else if self.source.is_local_storage() && !self.target.is_local_storage() {
+            self.is_source_local_e_tag_different_from_target_s3(key, source_object, target_object)
+                .await
+        } else if !self.source.is_local_storage() && self.target.is_local_storage() {
+            self.is_target_local_e_tag_different_from_source_s3(key, source_object)
+                .await
+        } else {
+            panic!("source and target are both local storage.")
+        }
+    }
+
+    async fn is_checksum_different(
+        &self,
+        key: &str,
+        source_object: &S3syncObject,
+        target_object: &HeadObjectOutput,
+    ) -> Result<bool> {
+        if !self.source.is_local_storage() && !self.target.is_local_storage() {
+            self.are_different_checksums(key, target_object).await
+        } else if self.source.is_local_storage() && !self.target.is_local_storage() {
+            self.is_source_local_checksum_different_from_target_s3(
+                key,
+                source_object,
+                target_object,
+            )
+            .await
+        } else if !self.source.is_local_storage() && self.target.is_local_storage() {
+            self.is_target_local_checksum_different_from_source_s3(
+                key,
+                source_object,
+                target_object,
+            )
+            .await
+        } else {
+            panic!("source and target are both local storage.")
+        }
+    }
+
     fn is_head_object_check_required(&self) -> bool {
+        if self.config.target_list_cache_invalidate_on_write {
+            return true;
+        }
+
         if self.config.transfer_config.auto_chunksize && self.config.filter_config.check_etag {
             return true;
         }
@@ -1144,6 +1335,15 @@ impl HeadObjectChecker {
         )
     }
 
+    fn is_new_object_in_target_listing(&self, source_object: &S3syncObject) -> bool {
+        is_new_object_in_target_listing(
+            self.config.skip_head_for_unlisted_objects,
+            source_object,
+            &self.config.filter_config,
+            self.target_key_map.as_ref(),
+        )
+    }
+
     fn check_target_local_storage_allow_overwrite(&self) -> bool {
         check_target_local_storage_allow_overwrite(
             self.target.is_local_storage(),
@@ -1165,6 +1365,66 @@ fn is_head_object_check_required(
     head_each_target || sync_latest_tagging
 }
 
+// With --target-list-cache-invalidate-on-write, the objects that passed the modified filter are
+// checked by HeadObject, because another process may have created them after the listing.
+// With --skip-head-for-unlisted-objects, the objects that do not exist in the listing are synced
+// without HeadObject, because HeadObject of them is usually NotFound.
+fn is_new_object_in_target_listing(
+    skip_head_for_unlisted_objects: bool,
+    source_object: &S3syncObject,
+    filter_config: &FilterConfig,
+    target_key_map: Option<&ObjectKeyMap>,
+) -> bool {
+    if !skip_head_for_unlisted_objects {
+        return false;
+    }
+
+    let Some(target_key_map) = target_key_map else {
+        return false;
+    };
+
+    if is_object_in_target_key_map(source_object, filter_config, target_key_map) {
+        return false;
+    }
+
+    let key = source_object.key();
+    debug!(
+        name = FILTER_NAME,
+        key = key,
+        "object does not exist in the target listing. HeadObject skipped."
+    );
+
+    true
+}
+
+// GetObjectOutput doesn't have nanos, so LastModified is compared in seconds.
+fn is_target_object_newer(source_object: &S3syncObject, target_object: &HeadObjectOutput) -> bool {
+    if target_object.last_modified().unwrap().secs() <= source_object.last_modified().secs() {
+        return false;
+    }
+
+    let source_last_modified = DateTime::to_chrono_utc(&DateTime::from_millis(
+        source_object.last_modified().to_millis().unwrap(),
+    ))
+    .unwrap()
+    .to_rfc3339();
+    let target_last_modified = DateTime::to_chrono_utc(&DateTime::from_millis(
+        target_object.last_modified().unwrap().to_millis().unwrap(),
+    ))
+    .unwrap()
+    .to_rfc3339();
+    let key = source_object.key();
+    info!(
+        name = FILTER_NAME,
+        source_last_modified = source_last_modified,
+        target_last_modified = target_last_modified,
+        key = key,
+        "target object is newer than the source. skipped-newer."
+    );
+
+    true
+}
+
 fn is_object_modified(source_object: &S3syncObject, target_object: &HeadObjectOutput) -> bool {
     if source_object.size() == 0 && target_object.content_length().unwrap() == 0 {
         return false;
@@ -1223,15 +1483,18 @@ fn is_head_object_not_found_error(result: &anyhow::Error) -> bool {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use aws_sdk_s3::operation::head_object;
     use aws_sdk_s3::primitives::DateTime;
     use aws_sdk_s3::types::Object;
     use aws_smithy_runtime_api::http::{Response, StatusCode};
 
     use crate::config::args::parse_from_args;
-    use crate::pipeline::storage_factory::create_storage_pair;
+    use crate::pipeline::storage_factory::{create_storage_limiters, create_storage_pair};
     use crate::storage::StoragePair;
     use crate::types::token::create_pipeline_cancellation_token;
+    use crate::types::{ObjectEntry, ObjectKey};
 
     use super::*;
 
@@ -1260,6 +1523,37 @@ mod tests {
         assert!(!is_object_modified(&source_object, &target_object));
     }
 
+    #[test]
+    fn is_target_object_newer_test() {
+        init_dummy_tracing_subscriber();
+
+        let source_object = S3syncObject::NotVersioning(
+            Object::builder()
+                .key("test")
+                .size(777)
+                .last_modified(DateTime::from_millis(1500))
+                .build(),
+        );
+
+        let target_object = head_object::builders::HeadObjectOutputBuilder::default()
+            .set_content_length(Some(777))
+            .last_modified(DateTime::from_secs(2))
+            .build();
+        assert!(is_target_object_newer(&source_object, &target_object));
+
+        let target_object = head_object::builders::HeadObjectOutputBuilder::default()
+            .set_content_length(Some(777))
+            .last_modified(DateTime::from_secs(1))
+            .build();
+        assert!(!is_target_object_newer(&source_object, &target_object));
+
+        let target_object = head_object::builders::HeadObjectOutputBuilder::default()
+            .set_content_length(Some(777))
+            .last_modified(DateTime::from_secs(0))
+            .build();
+        assert!(!is_target_object_newer(&source_object, &target_object));
+    }
+
     #[test]
     fn is_object_modified_false_size_zero() {
         init_dummy_tracing_subscriber();
@@ -1355,6 +1649,53 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn is_new_object_in_target_listing_test() {
+        init_dummy_tracing_subscriber();
+
+        let target_key_map = ObjectKeyMap::new(Mutex::new(HashMap::new()));
+        target_key_map.lock().unwrap().insert(
+            ObjectKey::KeyString("data1".to_string()),
+            ObjectEntry {
+                last_modified: DateTime::from_secs(1),
+                content_length: 1,
+                e_tag: None,
+            },
+        );
+        let filter_config = FilterConfig::default();
+
+        let listed_object =
+            S3syncObject::NotVersioning(Object::builder().key("data1").size(1).build());
+        let new_object =
+            S3syncObject::NotVersioning(Object::builder().key("data2").size(1).build());
+
+        assert!(is_new_object_in_target_listing(
+            true,
+            &new_object,
+            &filter_config,
+            Some(&target_key_map)
+        ));
+        assert!(!is_new_object_in_target_listing(
+            true,
+            &listed_object,
+            &filter_config,
+            Some(&target_key_map)
+        ));
+
+        assert!(!is_new_object_in_target_listing(
+            false,
+            &new_object,
+            &filter_config,
+            Some(&target_key_map)
+        ));
+        assert!(!is_new_object_in_target_listing(
+            true,
+            &new_object,
+            &filter_config,
+            None
+        ));
+    }
+
     #[test]
     fn is_head_object_not_found_error_test() {
         init_dummy_tracing_subscriber();
@@ -1383,14 +1724,21 @@ mod tests {
         let cancellation_token = create_pipeline_cancellation_token();
         let (stats_sender, _) = async_channel::unbounded();
 
-        let StoragePair { target, source } =
-            create_storage_pair(config.clone(), cancellation_token.clone(), stats_sender).await;
+        let StoragePair { target, source } = create_storage_pair(
+            config.clone(),
+            cancellation_token.clone(),
+            stats_sender,
+            create_storage_limiters(&config),
+        )
+        .await;
 
         let head_object_checker = HeadObjectChecker::new(
             config.clone(),
             dyn_clone::clone_box(&*(source)),
             dyn_clone::clone_box(&*(target)),
             1,
+            None,
+            None,
         );
 
         let source_object =
@@ -1408,6 +1756,53 @@ mod tests {
             .unwrap());
     }
 
+    #[tokio::test]
+    async fn head_object_not_found_retries() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--allow-both-local-storage",
+            "--check-size",
+            "--head-not-found-retries",
+            "2",
+            "./test_data/source/dir1/",
+            "./test_data/target/dir1/",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+        let cancellation_token = create_pipeline_cancellation_token();
+        let (stats_sender, _) = async_channel::unbounded();
+
+        let StoragePair { target, source } = create_storage_pair(
+            config.clone(),
+            cancellation_token.clone(),
+            stats_sender,
+            create_storage_limiters(&config),
+        )
+        .await;
+
+        let head_object_checker = HeadObjectChecker::new(
+            config.clone(),
+            dyn_clone::clone_box(&*(source)),
+            dyn_clone::clone_box(&*(target)),
+            1,
+            None,
+            None,
+        );
+
+        let start = std::time::Instant::now();
+        let source_object =
+            S3syncObject::NotVersioning(Object::builder().key("not_found.dat").size(6).build());
+        assert!(head_object_checker
+            .is_old_object(&source_object)
+            .await
+            .unwrap());
+        assert!(
+            std::time::Duration::from_millis(HEAD_NOT_FOUND_RETRY_INTERVAL_MILLISECONDS * 2)
+                <= start.elapsed()
+        );
+    }
+
     fn build_head_object_service_not_found_error() -> SdkError<HeadObjectError, Response<SdkBody>> {
         let not_found = aws_sdk_s3::types::error::NotFound::builder().build();
         let head_object_error = HeadObjectError::NotFound(not_found);
@@ -2,28 +2,41 @@ use anyhow::Result;
 use log::error;
 use tracing::trace;
 
-use crate::types::{sha1_digest_from_key, ObjectEntry, ObjectKey, ObjectKeyMap, S3syncObject};
+use crate::config::FilterConfig;
+use crate::types::{
+    convert_object_to_target_key, sha1_digest_from_key, ObjectEntry, ObjectKey, ObjectKeyMap,
+    S3syncObject,
+};
 
 use super::stage::Stage;
 
 pub struct KeyAggregator {
     base: Stage,
+    // with --target-object-key-case and --date-partition, the source keys are aggregated as the target keys,
+    // so that they can be compared with the target keys for --delete.
+    to_target_key: bool,
 }
 
 impl KeyAggregator {
-    pub fn new(base: Stage) -> Self {
-        Self { base }
+    pub fn new(base: Stage, to_target_key: bool) -> Self {
+        Self {
+            base,
+            to_target_key,
+        }
     }
 
     pub async fn aggregate(&self, key_map: &ObjectKeyMap) -> Result<()> {
         let sha1_digest_required = self.base.config.is_sha1_digest_listing_required();
+        let filter_config = self
+            .to_target_key
+            .then_some(&self.base.config.filter_config);
 
         loop {
             tokio::select! {
                 result = self.base.receiver.as_ref().unwrap().recv() => {
                     match result {
                         Ok(object) => {
-                            insert_key(&object, key_map, sha1_digest_required);
+                            insert_key(&object, key_map, sha1_digest_required, filter_config);
 
                             if let Err(e) = self.base.send(object).await {
                                 return if !self.base.is_channel_closed() {
@@ -48,11 +61,20 @@ impl KeyAggregator {
     }
 }
 
-fn insert_key(object: &S3syncObject, key_map: &ObjectKeyMap, sha1_digest_required: bool) {
+fn insert_key(
+    object: &S3syncObject,
+    key_map: &ObjectKeyMap,
+    sha1_digest_required: bool,
+    filter_config: Option<&FilterConfig>,
+) {
+    let key = match filter_config {
+        Some(filter_config) => convert_object_to_target_key(object, filter_config),
+        None => object.key().to_string(),
+    };
     let object_key = if sha1_digest_required {
-        ObjectKey::KeySHA1Digest(sha1_digest_from_key(object.key()))
+        ObjectKey::KeySHA1Digest(sha1_digest_from_key(&key))
     } else {
-        ObjectKey::KeyString(object.key().to_string())
+        ObjectKey::KeyString(key)
     };
 
     let previous_value = key_map
@@ -83,12 +105,16 @@ mod tests {
 
     use aws_sdk_s3::primitives::DateTime;
     use aws_sdk_s3::types::Object;
+    use chrono::FixedOffset;
 
     use crate::config::args::parse_from_args;
+    use crate::config::FilterConfig;
     use crate::pipeline::key_aggregator::{build_object_key_entry, insert_key, KeyAggregator};
     use crate::pipeline::stage::Stage;
     use crate::types::token::create_pipeline_cancellation_token;
-    use crate::types::{sha1_digest_from_key, ObjectKey, ObjectKeyMap, S3syncObject};
+    use crate::types::{
+        sha1_digest_from_key, DatePartition, KeyCase, ObjectKey, ObjectKeyMap, S3syncObject,
+    };
     use crate::Config;
 
     #[test]
@@ -120,7 +146,7 @@ mod tests {
         );
         let key_map = ObjectKeyMap::new(Mutex::new(HashMap::new()));
 
-        insert_key(&object, &key_map, true);
+        insert_key(&object, &key_map, true, None);
 
         key_map
             .lock()
@@ -142,7 +168,7 @@ mod tests {
         );
         let key_map = ObjectKeyMap::new(Mutex::new(HashMap::new()));
 
-        insert_key(&object, &key_map, false);
+        insert_key(&object, &key_map, false, None);
 
         key_map
             .lock()
@@ -151,6 +177,63 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn insert_key_with_key_case_test() {
+        init_dummy_tracing_subscriber();
+
+        let object = S3syncObject::NotVersioning(
+            Object::builder()
+                .key("Dir1/Test-Key1")
+                .size(1)
+                .last_modified(DateTime::from_secs(1))
+                .build(),
+        );
+        let key_map = ObjectKeyMap::new(Mutex::new(HashMap::new()));
+
+        let filter_config = FilterConfig {
+            target_key_case: Some(KeyCase::Lower),
+            ..Default::default()
+        };
+        insert_key(&object, &key_map, false, Some(&filter_config));
+
+        key_map
+            .lock()
+            .unwrap()
+            .get(&ObjectKey::KeyString("dir1/test-key1".to_string()))
+            .unwrap();
+    }
+
+    #[test]
+    fn insert_key_with_date_partition_test() {
+        init_dummy_tracing_subscriber();
+
+        let object = S3syncObject::NotVersioning(
+            Object::builder()
+                .key("dir1/test-key1")
+                .size(1)
+                .last_modified(DateTime::from_secs(1_700_000_000))
+                .build(),
+        );
+        let key_map = ObjectKeyMap::new(Mutex::new(HashMap::new()));
+
+        let filter_config = FilterConfig {
+            date_partition: Some(DatePartition {
+                pattern: "%Y/%m/%d".to_string(),
+                offset: FixedOffset::east_opt(0).unwrap(),
+            }),
+            ..Default::default()
+        };
+        insert_key(&object, &key_map, false, Some(&filter_config));
+
+        key_map
+            .lock()
+            .unwrap()
+            .get(&ObjectKey::KeyString(
+                "2023/11/14/dir1/test-key1".to_string(),
+            ))
+            .unwrap();
+    }
+
     #[tokio::test]
     async fn aggregate_sha1_test() {
         init_dummy_tracing_subscriber();
@@ -176,7 +259,10 @@ mod tests {
             cancellation_token,
         };
 
-        let key_aggregator = KeyAggregator { base };
+        let key_aggregator = KeyAggregator {
+            base,
+            to_target_key: false,
+        };
         let key_map = ObjectKeyMap::new(Mutex::new(HashMap::new()));
 
         let object = S3syncObject::NotVersioning(
@@ -226,7 +312,10 @@ mod tests {
             cancellation_token,
         };
 
-        let key_aggregator = KeyAggregator { base };
+        let key_aggregator = KeyAggregator {
+            base,
+            to_target_key: false,
+        };
         let key_map = ObjectKeyMap::new(Mutex::new(HashMap::new()));
 
         let object = S3syncObject::NotVersioning(
@@ -268,7 +357,10 @@ mod tests {
             cancellation_token,
         };
 
-        let key_aggregator = KeyAggregator { base };
+        let key_aggregator = KeyAggregator {
+            base,
+            to_target_key: false,
+        };
         let key_map = ObjectKeyMap::new(Mutex::new(HashMap::new()));
 
         let object = S3syncObject::NotVersioning(
@@ -318,7 +410,10 @@ mod tests {
             cancellation_token: cancellation_token.clone(),
         };
 
-        let key_aggregator = KeyAggregator { base };
+        let key_aggregator = KeyAggregator {
+            base,
+            to_target_key: false,
+        };
         let key_map = ObjectKeyMap::new(Mutex::new(HashMap::new()));
 
         cancellation_token.cancel();
@@ -1,8 +1,19 @@
-use anyhow::Result;
-use tracing::trace;
+use std::collections::HashSet;
 
+use anyhow::{anyhow, Context, Result};
+use async_channel::{Receiver, Sender};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, BufReader};
+use tracing::{info, trace, warn};
+
+use crate::types::SyncStatistics::SyncWarning;
+use crate::types::{convert_object_to_target_key, S3syncObject, SyncStatistics};
+
+use super::scan_progress::ScanProgress;
+use super::source_list_cache::{self, SourceListCacheWriter};
 use super::stage::Stage;
 
+const LISTED_OBJECT_CHANNEL_CAPACITY: usize = 1000;
+
 pub struct ObjectLister {
     base: Stage,
 }
@@ -12,34 +23,215 @@ impl ObjectLister {
         Self { base }
     }
 
+    // source objects are forwarded to the next stage through a channel, so that they are counted
+    // as listed(the known work for the progress indicator).
     pub async fn list_source(&self, max_keys: i32) -> Result<()> {
         trace!("list source objects has started.");
 
+        let (list_sender, list_receiver) =
+            async_channel::bounded::<S3syncObject>(LISTED_OBJECT_CHANNEL_CAPACITY);
+        let scan_progress = ScanProgress::new();
+        let (list_result, forward_result) = tokio::join!(
+            async {
+                let result = self.list_source_objects(&list_sender, max_keys).await;
+                list_sender.close();
+                result
+            },
+            async {
+                let forward = self.forward_listed_objects(list_receiver, &scan_progress);
+                if self.base.config.source_scan_progress {
+                    scan_progress.report_until(forward).await
+                } else {
+                    forward.await
+                }
+            }
+        );
+        list_result?;
+        forward_result?;
+
+        trace!("list source objects has been completed.");
+        Ok(())
+    }
+
+    async fn list_source_objects(
+        &self,
+        sender: &Sender<S3syncObject>,
+        max_keys: i32,
+    ) -> Result<()> {
+        let Some(cache_config) = self.base.config.source_list_cache_config.as_ref() else {
+            return self
+                .list_source_objects_from_storage(sender, max_keys)
+                .await;
+        };
+
+        let header = source_list_cache::build_header(&self.base.config);
+        if let Some(reader) = source_list_cache::open_fresh_cache(cache_config, &header)? {
+            let count = source_list_cache::send_cached_objects(reader, sender).await?;
+            info!(
+                path = cache_config.path.to_string_lossy().to_string(),
+                count = count,
+                "source objects have been read from the source list cache."
+            );
+            return Ok(());
+        }
+
+        let mut writer = SourceListCacheWriter::create(&cache_config.path, &header)?;
+        let (cache_sender, cache_receiver) =
+            async_channel::bounded::<S3syncObject>(LISTED_OBJECT_CHANNEL_CAPACITY);
+        let (list_result, record_result) = tokio::join!(
+            async {
+                let result = self
+                    .list_source_objects_from_storage(&cache_sender, max_keys)
+                    .await;
+                cache_sender.close();
+                result
+            },
+            async {
+                let result = record_listed_objects(&mut writer, &cache_receiver, sender).await;
+                cache_receiver.close();
+                result
+            }
+        );
+        list_result?;
+
+        // an incomplete listing(stopped or cancelled) is not cached.
+        if !record_result? || self.base.cancellation_token.is_cancelled() {
+            return Ok(());
+        }
+
+        let count = writer.persist()?;
+        info!(
+            path = cache_config.path.to_string_lossy().to_string(),
+            count = count,
+            "source list cache has been written."
+        );
+
+        Ok(())
+    }
+
+    async fn list_source_objects_from_storage(
+        &self,
+        sender: &Sender<S3syncObject>,
+        max_keys: i32,
+    ) -> Result<()> {
         if self.base.config.enable_versioning {
             self.base
                 .source
                 .as_ref()
                 .unwrap()
-                .list_object_versions(
-                    self.base.sender.as_ref().unwrap(),
-                    max_keys,
-                    self.base.config.warn_as_error,
-                )
-                .await?;
+                .list_object_versions(sender, max_keys, self.base.config.warn_as_error)
+                .await
+        } else if self.base.config.include_only_prefixes_from_stdin {
+            self.list_source_objects_from_reader(
+                BufReader::new(tokio::io::stdin()),
+                sender,
+                max_keys,
+            )
+            .await
+        } else if let Some(prefixes) = self.base.config.list_parallel_prefixes.as_ref() {
+            self.base
+                .source
+                .as_ref()
+                .unwrap()
+                .list_objects_with_parallel_prefixes(sender, max_keys, prefixes)
+                .await
         } else {
             self.base
                 .source
                 .as_ref()
                 .unwrap()
-                .list_objects(
-                    self.base.sender.as_ref().unwrap(),
+                .list_objects(sender, max_keys, self.base.config.warn_as_error)
+                .await
+        }
+    }
+
+    // with --include-only-prefixes-from-stdin, each prefix is listed before the next line is read.
+    // so stdin is not buffered, and the writer is held back while the pipeline is busy.
+    async fn list_source_objects_from_reader<R: AsyncBufRead + Unpin>(
+        &self,
+        reader: R,
+        sender: &Sender<S3syncObject>,
+        max_keys: i32,
+    ) -> Result<()> {
+        let mut lines = reader.lines();
+        while let Some(line) = lines
+            .next_line()
+            .await
+            .context("tokio::io::Lines::next_line() failed.")?
+        {
+            if self.base.cancellation_token.is_cancelled() || sender.is_closed() {
+                trace!("list() canceled.");
+                break;
+            }
+
+            let sub_prefix = line.trim_end_matches('\r');
+            if sub_prefix.is_empty() {
+                continue;
+            }
+
+            self.base
+                .source
+                .as_ref()
+                .unwrap()
+                .list_objects_under_sub_prefix(
+                    sender,
                     max_keys,
+                    sub_prefix,
                     self.base.config.warn_as_error,
                 )
                 .await?;
         }
 
-        trace!("list source objects has been completed.");
+        Ok(())
+    }
+
+    // with --target-object-key-case, a source object whose target key collides with
+    // that of a previously listed object is skipped, so that it does not overwrite the target object.
+    async fn forward_listed_objects(
+        &self,
+        receiver: Receiver<S3syncObject>,
+        scan_progress: &ScanProgress,
+    ) -> Result<()> {
+        let filter_config = &self.base.config.filter_config;
+        let mut target_keys = HashSet::new();
+
+        while let Ok(object) = receiver.recv().await {
+            if filter_config.target_key_case.is_some() {
+                let target_key = convert_object_to_target_key(&object, filter_config);
+                if !target_keys.insert(target_key.clone()) {
+                    self.base
+                        .send_stats(SyncWarning {
+                            key: object.key().to_string(),
+                        })
+                        .await;
+                    warn!(
+                        key = object.key(),
+                        target_key = target_key,
+                        "another source object has the same target key. skipping."
+                    );
+
+                    if self.base.config.warn_as_error {
+                        receiver.close();
+                        return Err(anyhow!("duplicate target key: {}.", target_key));
+                    }
+                    continue;
+                }
+            }
+
+            scan_progress.add();
+            self.base.send_stats(SyncStatistics::ObjectListed).await;
+
+            if let Err(e) = self.base.send(object).await {
+                // the listing is stopped by closing the receiver.
+                receiver.close();
+                return if !self.base.is_channel_closed() {
+                    Err(e)
+                } else {
+                    Ok(())
+                };
+            }
+        }
+
         Ok(())
     }
 
@@ -59,3 +251,219 @@ impl ObjectLister {
         Ok(())
     }
 }
+
+// returns false if the listing has been stopped by closing the receiver.
+async fn record_listed_objects(
+    writer: &mut SourceListCacheWriter,
+    receiver: &Receiver<S3syncObject>,
+    sender: &Sender<S3syncObject>,
+) -> Result<bool> {
+    while let Ok(object) = receiver.recv().await {
+        writer.write(&object)?;
+        if sender.send(object).await.is_err() {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_s3::types::Object;
+
+    use crate::config::args::parse_from_args;
+    use crate::storage::local::LocalStorageFactory;
+    use crate::storage::{StorageFactory, StorageLimiters};
+    use crate::types::token::create_pipeline_cancellation_token;
+    use crate::Config;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn forward_listed_objects_with_target_key_case() {
+        init_dummy_tracing_subscriber();
+
+        let (object_lister, next_stage_receiver, stats_receiver) =
+            create_object_lister_helper(vec!["--target-object-key-case", "lower"]).await;
+        let (list_sender, list_receiver) = async_channel::bounded::<S3syncObject>(1000);
+
+        for key in ["Dir1/Data1", "dir1/data1", "dir1/Data2"] {
+            list_sender
+                .send(S3syncObject::NotVersioning(
+                    Object::builder().key(key).build(),
+                ))
+                .await
+                .unwrap();
+        }
+        list_sender.close();
+
+        object_lister
+            .forward_listed_objects(list_receiver, &ScanProgress::new())
+            .await
+            .unwrap();
+
+        assert_eq!(next_stage_receiver.len(), 2);
+        assert_eq!(
+            next_stage_receiver.recv().await.unwrap().key(),
+            "Dir1/Data1"
+        );
+        assert_eq!(
+            next_stage_receiver.recv().await.unwrap().key(),
+            "dir1/Data2"
+        );
+
+        let mut warning_count = 0;
+        while let Ok(stats) = stats_receiver.try_recv() {
+            if matches!(stats, SyncWarning { .. }) {
+                warning_count += 1;
+            }
+        }
+        assert_eq!(warning_count, 1);
+    }
+
+    #[tokio::test]
+    async fn forward_listed_objects_with_target_key_case_warn_as_error() {
+        init_dummy_tracing_subscriber();
+
+        let (object_lister, _next_stage_receiver, _stats_receiver) =
+            create_object_lister_helper(vec![
+                "--target-object-key-case",
+                "upper",
+                "--warn-as-error",
+            ])
+            .await;
+        let (list_sender, list_receiver) = async_channel::bounded::<S3syncObject>(1000);
+
+        for key in ["data1", "DATA1"] {
+            list_sender
+                .send(S3syncObject::NotVersioning(
+                    Object::builder().key(key).build(),
+                ))
+                .await
+                .unwrap();
+        }
+        list_sender.close();
+
+        assert!(object_lister
+            .forward_listed_objects(list_receiver, &ScanProgress::new())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn list_source_objects_from_reader() {
+        init_dummy_tracing_subscriber();
+
+        let dir = tempfile::tempdir().unwrap();
+        for key in ["dir1/data1", "dir1/data2", "dir2/data3", "data4"] {
+            let path = dir.path().join(key);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "data").unwrap();
+        }
+
+        let args = vec![
+            "s3sync",
+            "--target-access-key",
+            "target_access_key",
+            "--target-secret-access-key",
+            "target_secret_access_key",
+            "--include-only-prefixes-from-stdin",
+            dir.path().to_str().unwrap(),
+            "s3://target-bucket",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+
+        let (stats_sender, stats_receiver) = async_channel::unbounded();
+        let storage = LocalStorageFactory::create(
+            config.clone(),
+            config.source.clone(),
+            create_pipeline_cancellation_token(),
+            stats_sender,
+            None,
+            StorageLimiters::default(),
+        )
+        .await;
+        let object_lister = ObjectLister::new(Stage {
+            config,
+            source: Some(storage),
+            target: None,
+            receiver: None,
+            sender: None,
+            cancellation_token: create_pipeline_cancellation_token(),
+        });
+
+        let (list_sender, list_receiver) = async_channel::bounded::<S3syncObject>(1000);
+        let reader = std::io::Cursor::new("dir1\n\n./data4\r\nnot_found\n../outside\n");
+        object_lister
+            .list_source_objects_from_reader(reader, &list_sender, 1000)
+            .await
+            .unwrap();
+        list_sender.close();
+
+        let mut keys = Vec::new();
+        while let Ok(object) = list_receiver.recv().await {
+            keys.push(object.key().to_string());
+        }
+        keys.sort();
+        assert_eq!(keys, vec!["data4", "dir1/data1", "dir1/data2"]);
+
+        let mut warning_count = 0;
+        while let Ok(stats) = stats_receiver.try_recv() {
+            if matches!(stats, SyncWarning { .. }) {
+                warning_count += 1;
+            }
+        }
+        assert_eq!(warning_count, 2);
+    }
+
+    async fn create_object_lister_helper(
+        options: Vec<&str>,
+    ) -> (
+        ObjectLister,
+        Receiver<S3syncObject>,
+        Receiver<SyncStatistics>,
+    ) {
+        let mut args = vec![
+            "s3sync",
+            "--source-access-key",
+            "source_access_key",
+            "--source-secret-access-key",
+            "source_secret_access_key",
+        ];
+        args.extend(options);
+        args.extend(["s3://source-bucket", "/target-dir"]);
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+
+        let (stats_sender, stats_receiver) = async_channel::unbounded();
+
+        let storage = LocalStorageFactory::create(
+            config.clone(),
+            config.target.clone(),
+            create_pipeline_cancellation_token(),
+            stats_sender,
+            config.target_client_config.clone(),
+            StorageLimiters::default(),
+        )
+        .await;
+
+        let (sender, next_stage_receiver) = async_channel::bounded::<S3syncObject>(1000);
+
+        let object_lister = ObjectLister::new(Stage {
+            config,
+            source: None,
+            target: Some(storage),
+            receiver: None,
+            sender: Some(sender),
+            cancellation_token: create_pipeline_cancellation_token(),
+        });
+
+        (object_lister, next_stage_receiver, stats_receiver)
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
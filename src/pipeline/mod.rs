@@ -1,36 +1,56 @@
-use std::collections::{HashMap, VecDeque};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 
-use anyhow::{anyhow, Error};
+use anyhow::{anyhow, Error, Result};
 use async_channel::{Receiver, Sender};
+use tokio::sync::Semaphore;
 use tokio::task::JoinHandle;
-use tracing::{error, trace};
+use tracing::{debug, error, info, trace, warn};
 
+use crate::pipeline::concurrency_controller::{initial_auto_concurrency, ConcurrencyController};
+use crate::pipeline::delete_log::DeleteLog;
 use crate::pipeline::deleter::ObjectDeleter;
 use crate::pipeline::diff_lister::DiffLister;
 use crate::pipeline::filter::{ExcludeRegexFilter, IncludeRegexFilter, ObjectFilter};
 use crate::pipeline::key_aggregator::KeyAggregator;
 use crate::pipeline::lister::ObjectLister;
+use crate::pipeline::multipart_upload_aborter::MultipartUploadAborter;
 use crate::pipeline::packer::ObjectVersionsPacker;
+use crate::pipeline::scheduler::{ObjectScheduler, PrefixConcurrencyLimiter};
+use crate::pipeline::sorter::ObjectSorter;
 use crate::pipeline::stage::Stage;
-use crate::pipeline::syncer::ObjectSyncer;
+use crate::pipeline::stdout_writer::StdoutWriter;
+use crate::pipeline::syncer::{ObjectSyncer, SyncerSharedState};
 use crate::pipeline::terminator::Terminator;
-use crate::storage::{Storage, StoragePair};
-use crate::types::token::PipelineCancellationToken;
-use crate::types::{ObjectKeyMap, S3syncObject, SyncStatistics};
+use crate::storage::local::fs_util;
+use crate::storage::request_rate_limiter::RequestRateLimiter;
+use crate::storage::{Storage, StorageLimiters, StoragePair};
+use crate::types::token::{create_pipeline_cancellation_token, PipelineCancellationToken};
+use crate::types::{ObjectKeyMap, S3syncObject, StoragePath, SyncStatistics};
 use crate::Config;
 
 const CHANNEL_CAPACITY: usize = 20000;
 
+mod completion_marker;
+mod concurrency_controller;
+mod delete_log;
+mod delete_manifest;
 mod deleter;
 mod diff_lister;
 mod filter;
 mod head_object_checker;
 mod key_aggregator;
 mod lister;
+mod multipart_upload_aborter;
 mod packer;
+mod preflight;
+mod scan_progress;
+mod scheduler;
+mod sorter;
+mod source_list_cache;
 mod stage;
+mod stdout_writer;
 mod storage_factory;
 mod syncer;
 mod terminator;
@@ -43,19 +63,55 @@ pub struct Pipeline {
     source_key_map: Option<ObjectKeyMap>,
     target_key_map: Option<ObjectKeyMap>,
     cancellation_token: PipelineCancellationToken,
+    drain_token: PipelineCancellationToken,
+    drained_object_count: Arc<AtomicU64>,
+    skipped_error_count: Arc<AtomicU64>,
+    retry_elapsed_milliseconds: Arc<AtomicU64>,
+    transferred_object_count: Arc<AtomicU64>,
+    transferred_bytes: Arc<AtomicU64>,
+    source_object_count: Arc<AtomicU64>,
     stats_receiver: Receiver<SyncStatistics>,
     has_error: Arc<AtomicBool>,
     errors: Arc<Mutex<VecDeque<Error>>>,
+    prefix_concurrency_limiter: Option<Arc<PrefixConcurrencyLimiter>>,
+    concurrency_gate: Option<Arc<Semaphore>>,
+    storage_limiters: StorageLimiters,
+    source_concurrency_semaphore: Option<Arc<Semaphore>>,
+    target_concurrency_semaphore: Option<Arc<Semaphore>>,
+    delete_log: Option<Arc<DeleteLog>>,
+    delete_manifest_keys: Option<Arc<HashSet<String>>>,
     ready: bool,
 }
 
 impl Pipeline {
     pub async fn new(config: Config, cancellation_token: PipelineCancellationToken) -> Self {
+        let mut config = config;
+        let mut errors = VecDeque::<Error>::new();
+        if config.negotiate_additional_checksum_algorithm {
+            match storage_factory::negotiate_additional_checksum_algorithm(&config).await {
+                Ok(checksum_algorithm) => config.additional_checksum_algorithm = checksum_algorithm,
+                Err(e) => {
+                    let error = e.to_string();
+                    let source = e.source();
+
+                    error!(
+                        error = error,
+                        source = source,
+                        "additional checksum algorithm negotiation failed."
+                    );
+
+                    errors.push_back(e);
+                }
+            }
+        }
+
         let (stats_sender, stats_receiver) = async_channel::unbounded();
+        let storage_limiters = storage_factory::create_storage_limiters(&config);
         let StoragePair { source, target } = storage_factory::create_storage_pair(
             config.clone(),
             cancellation_token.clone(),
             stats_sender,
+            storage_limiters.clone(),
         )
         .await;
 
@@ -76,6 +132,23 @@ impl Pipeline {
             None
         };
 
+        let prefix_concurrency_limiter = config
+            .max_concurrent_objects_per_prefix
+            .map(|max| Arc::new(PrefixConcurrencyLimiter::new(max as usize)));
+
+        let concurrency_gate = config.concurrency_auto.then(|| {
+            Arc::new(Semaphore::new(
+                initial_auto_concurrency(config.worker_size) as usize
+            ))
+        });
+
+        let source_concurrency_semaphore = config
+            .source_concurrency_limit
+            .map(|limit| Arc::new(Semaphore::new(limit as usize)));
+        let target_concurrency_semaphore = config
+            .target_concurrency_limit
+            .map(|limit| Arc::new(Semaphore::new(limit as usize)));
+
         Self {
             config,
             source,
@@ -83,9 +156,23 @@ impl Pipeline {
             source_key_map,
             target_key_map,
             cancellation_token,
+            drain_token: create_pipeline_cancellation_token(),
+            drained_object_count: Arc::new(AtomicU64::new(0)),
+            skipped_error_count: Arc::new(AtomicU64::new(0)),
+            retry_elapsed_milliseconds: Arc::new(AtomicU64::new(0)),
+            transferred_object_count: Arc::new(AtomicU64::new(0)),
+            transferred_bytes: Arc::new(AtomicU64::new(0)),
+            source_object_count: Arc::new(AtomicU64::new(0)),
             stats_receiver,
-            has_error: Arc::new(AtomicBool::new(false)),
-            errors: Arc::new(Mutex::new(VecDeque::<Error>::new())),
+            has_error: Arc::new(AtomicBool::new(!errors.is_empty())),
+            errors: Arc::new(Mutex::new(errors)),
+            prefix_concurrency_limiter,
+            concurrency_gate,
+            storage_limiters,
+            source_concurrency_semaphore,
+            target_concurrency_semaphore,
+            delete_log: None,
+            delete_manifest_keys: None,
             ready: true,
         }
     }
@@ -96,12 +183,63 @@ impl Pipeline {
         }
         self.ready = false;
 
+        // e.g. additional checksum algorithm negotiation failed.
+        if self.has_error() {
+            self.shutdown().await;
+
+            return;
+        }
+
+        if self.config.abort_incomplete_multipart_uploads {
+            self.abort_incomplete_multipart_uploads().await;
+            self.shutdown().await;
+
+            return;
+        }
+
+        if self.config.download_to_stdout {
+            self.write_object_to_stdout().await;
+            self.shutdown().await;
+
+            return;
+        }
+
         if !self.check_prerequisites().await {
             self.shutdown().await;
 
             return;
         }
 
+        if !self.open_delete_log().await {
+            self.shutdown().await;
+
+            return;
+        }
+
+        if !self.load_delete_manifest() {
+            self.shutdown().await;
+
+            return;
+        }
+
+        if self.config.partial_suffix.is_some() && self.target.is_local_storage() {
+            self.remove_stale_partial_files();
+            if self.has_error() {
+                self.shutdown().await;
+
+                return;
+            }
+        }
+
+        if self.config.cleanup_temp_on_start && self.target.is_local_storage() {
+            self.remove_stale_temp_files();
+            if self.has_error() {
+                self.shutdown().await;
+
+                return;
+            }
+        }
+
         if self.is_listing_target_required() {
             self.aggregate_target_keys().await;
             if self.has_error() {
@@ -111,6 +249,13 @@ impl Pipeline {
             }
         }
 
+        if self.config.emit_manifest_for_delete.is_some() {
+            self.emit_delete_manifest().await;
+            self.shutdown().await;
+
+            return;
+        }
+
         self.sync().await;
 
         self.shutdown().await;
@@ -136,6 +281,244 @@ impl Pipeline {
         true
     }
 
+    // the preflight check is not a part of run(), so that the library users do not send extra requests
+    // unless they ask for it. the CLI runs it before run().
+    pub async fn preflight(&self) -> bool {
+        if self.config.disable_preflight
+            || self.config.abort_incomplete_multipart_uploads
+            || self.config.download_to_stdout
+        {
+            return true;
+        }
+
+        // the target is not listed in some cases(e.g. --remove-modified-filter without --delete), so
+        // s3:ListBucket may not be granted for it.
+        let storages = [
+            (
+                "source",
+                &self.source,
+                &self.config.source,
+                &self.config.source_client_config,
+                true,
+            ),
+            (
+                "target",
+                &self.target,
+                &self.config.target,
+                &self.config.target_client_config,
+                self.is_listing_target_required(),
+            ),
+        ];
+
+        for (side, storage, storage_path, client_config, listed) in storages {
+            let StoragePath::S3 { bucket, prefix } = storage_path else {
+                continue;
+            };
+            if !listed {
+                continue;
+            }
+
+            let result = preflight::check_bucket_access(
+                side,
+                &storage.get_client().unwrap(),
+                bucket,
+                prefix,
+                client_config.as_ref().unwrap(),
+            )
+            .await;
+            if let Err(e) = result {
+                let error = e.to_string();
+                let source = e.source();
+
+                error!(error = error, source = source, "preflight check failed.");
+
+                self.has_error.store(true, Ordering::SeqCst);
+
+                let mut error_list = self.errors.lock().unwrap();
+                error_list.push_back(e);
+
+                return false;
+            }
+        }
+
+        if !self.check_target_default_retention().await {
+            return false;
+        }
+
+        trace!("preflight check has been passed.");
+
+        true
+    }
+
+    // without --require-no-default-retention, the default retention is only reported, and the
+    // failure of the check(e.g. no s3:GetBucketObjectLockConfiguration permission) is ignored.
+    async fn check_target_default_retention(&self) -> bool {
+        let StoragePath::S3 { bucket, .. } = &self.config.target else {
+            return true;
+        };
+
+        let result = preflight::get_default_retention(
+            &self.target.get_client().unwrap(),
+            bucket,
+            self.config.target_client_config.as_ref().unwrap(),
+        )
+        .await;
+
+        let e = match result {
+            Ok(None) => {
+                trace!(bucket = bucket, "target bucket has no default retention.");
+
+                return true;
+            }
+            Ok(Some(default_retention)) if !self.config.require_no_default_retention => {
+                warn!(
+                    bucket = bucket,
+                    default_retention = default_retention,
+                    "target bucket has the default retention of Object Lock. \
+                    the uploaded objects cannot be deleted or overwritten until the retention expires."
+                );
+
+                return true;
+            }
+            Ok(Some(default_retention)) => anyhow!(
+                "target bucket({}) has the default retention of Object Lock({}). \
+                remove --require-no-default-retention to sync into the bucket.",
+                bucket,
+                default_retention
+            ),
+            Err(e) if !self.config.require_no_default_retention => {
+                debug!(
+                    error = e.to_string(),
+                    "failed to check the default retention of the target bucket. skipping."
+                );
+
+                return true;
+            }
+            Err(e) => e,
+        };
+
+        let error = e.to_string();
+        let source = e.source();
+
+        error!(
+            error = error,
+            source = source,
+            "default retention check failed."
+        );
+
+        self.has_error.store(true, Ordering::SeqCst);
+
+        let mut error_list = self.errors.lock().unwrap();
+        error_list.push_back(e);
+
+        false
+    }
+
+    fn load_delete_manifest(&mut self) -> bool {
+        let Some(path) = self.config.delete_from_manifest.clone() else {
+            return true;
+        };
+
+        match delete_manifest::read_manifest(&path, &delete_manifest::build_header(&self.config)) {
+            Ok(keys) => {
+                self.delete_manifest_keys = Some(Arc::new(keys));
+                true
+            }
+            Err(e) => {
+                let error = e.to_string();
+                let source = e.source();
+
+                error!(
+                    error = error,
+                    source = source,
+                    "failed to read the delete manifest."
+                );
+
+                self.has_error.store(true, Ordering::SeqCst);
+
+                let mut error_list = self.errors.lock().unwrap();
+                error_list.push_back(e);
+
+                false
+            }
+        }
+    }
+
+    // Only the source and the target are listed. nothing is transferred or deleted, so that the
+    // deletion candidates can be reviewed before --delete-from-manifest.
+    async fn emit_delete_manifest(&self) {
+        self.aggregate_source_keys().await;
+
+        if self.has_error() {
+            return;
+        }
+
+        let path = self.config.emit_manifest_for_delete.clone().unwrap();
+        let keys = diff_lister::generate_diff(
+            self.source_key_map.as_ref().unwrap(),
+            self.target_key_map.as_ref().unwrap(),
+        );
+
+        match delete_manifest::write_manifest(
+            &path,
+            &delete_manifest::build_header(&self.config),
+            &keys,
+        ) {
+            Ok(count) => {
+                info!(
+                    count = count,
+                    path = path.to_string_lossy().to_string(),
+                    "delete manifest has been written. no object has been deleted."
+                );
+            }
+            Err(e) => {
+                let error = e.to_string();
+                let source = e.source();
+
+                error!(
+                    error = error,
+                    source = source,
+                    "failed to write the delete manifest."
+                );
+
+                self.has_error.store(true, Ordering::SeqCst);
+
+                let mut error_list = self.errors.lock().unwrap();
+                error_list.push_back(e);
+            }
+        }
+    }
+
+    async fn open_delete_log(&mut self) -> bool {
+        let Some(path) = self.config.delete_log.clone() else {
+            return true;
+        };
+
+        match DeleteLog::open(&path, self.config.dry_run).await {
+            Ok(delete_log) => {
+                self.delete_log = Some(Arc::new(delete_log));
+                true
+            }
+            Err(e) => {
+                let error = e.to_string();
+                let source = e.source();
+
+                error!(
+                    error = error,
+                    source = source,
+                    "failed to open the delete log."
+                );
+
+                self.has_error.store(true, Ordering::SeqCst);
+
+                let mut error_list = self.errors.lock().unwrap();
+                error_list.push_back(e);
+
+                false
+            }
+        }
+    }
+
     async fn is_both_bucket_versioning_enabled(&self) -> bool {
         let source_versioning_enabled = self.source.is_versioning_enabled().await;
         if let Err(e) = source_versioning_enabled {
@@ -172,6 +555,138 @@ impl Pipeline {
         false
     }
 
+    fn remove_stale_partial_files(&self) {
+        let result = fs_util::remove_stale_partial_files(
+            &self.target.get_local_path(),
+            self.config.partial_suffix.as_ref().unwrap(),
+            self.config.partial_cleanup_older_than,
+            self.config.dry_run,
+        );
+
+        match result {
+            Ok(removed) => {
+                trace!(removed = removed, "stale partial files removed.");
+            }
+            Err(e) => {
+                self.has_error.store(true, Ordering::SeqCst);
+
+                let error = e.to_string();
+                let source = e.source();
+
+                error!(
+                    error = error,
+                    source = source,
+                    "failed to remove stale partial files."
+                );
+
+                let mut error_list = self.errors.lock().unwrap();
+                error_list.push_back(e);
+            }
+        }
+    }
+
+    // the temp files are created in the directory of the key, or directly in --local-tmp-dir.
+    fn remove_stale_temp_files(&self) {
+        let result = fs_util::remove_stale_temp_files(
+            &self.target.get_local_path(),
+            usize::MAX,
+            self.config.cleanup_temp_older_than,
+            self.config.dry_run,
+        )
+        .and_then(|removed| {
+            let Some(local_tmp_dir) = &self.config.local_tmp_dir else {
+                return Ok(removed);
+            };
+
+            fs_util::remove_stale_temp_files(
+                local_tmp_dir,
+                1,
+                self.config.cleanup_temp_older_than,
+                self.config.dry_run,
+            )
+            .map(|removed_in_local_tmp_dir| removed + removed_in_local_tmp_dir)
+        });
+
+        match result {
+            Ok(removed) => {
+                trace!(removed = removed, "stale temp files removed.");
+            }
+            Err(e) => {
+                self.has_error.store(true, Ordering::SeqCst);
+
+                let error = e.to_string();
+                let source = e.source();
+
+                error!(
+                    error = error,
+                    source = source,
+                    "failed to remove stale temp files."
+                );
+
+                let mut error_list = self.errors.lock().unwrap();
+                error_list.push_back(e);
+            }
+        }
+    }
+
+    async fn abort_incomplete_multipart_uploads(&self) {
+        let stage = Stage::new(
+            self.config.clone(),
+            None,
+            Some(dyn_clone::clone_box(&*self.target)),
+            None,
+            None,
+            self.cancellation_token.clone(),
+        );
+
+        if let Err(e) = MultipartUploadAborter::new(stage).abort().await {
+            self.has_error.store(true, Ordering::SeqCst);
+
+            let error = e.to_string();
+            let source = e.source();
+
+            error!(
+                error = error,
+                source = source,
+                "abort incomplete multipart uploads failed."
+            );
+
+            let mut error_list = self.errors.lock().unwrap();
+            error_list.push_back(e);
+        }
+    }
+
+    async fn write_object_to_stdout(&self) {
+        let stage = Stage::new(
+            self.config.clone(),
+            Some(dyn_clone::clone_box(&*self.source)),
+            Some(dyn_clone::clone_box(&*self.target)),
+            None,
+            None,
+            self.cancellation_token.clone(),
+        );
+
+        // The source prefix is the key of the object.
+        if let Err(e) = StdoutWriter::new(stage)
+            .write("", &mut tokio::io::stdout())
+            .await
+        {
+            self.has_error.store(true, Ordering::SeqCst);
+
+            let error = e.to_string();
+            let source = e.source();
+
+            error!(
+                error = error,
+                source = source,
+                "write object to stdout failed."
+            );
+
+            let mut error_list = self.errors.lock().unwrap();
+            error_list.push_back(e);
+        }
+    }
+
     fn is_listing_target_required(&self) -> bool {
         is_listing_target_required(
             self.config.enable_versioning,
@@ -185,13 +700,33 @@ impl Pipeline {
         self.terminate(self.aggregate_keys_if_necessary(
             self.list_target(),
             Some(self.target_key_map.clone().unwrap()),
+            false,
+        ))
+        .await
+        .unwrap();
+    }
+
+    async fn aggregate_source_keys(&self) {
+        self.terminate(self.aggregate_keys_if_necessary(
+            self.list_source(),
+            self.source_key_map.as_ref().cloned(),
+            true,
         ))
         .await
         .unwrap();
     }
 
     async fn sync(&mut self) {
-        if self.config.enable_versioning {
+        if self.config.delete_from_manifest.is_some() {
+            // only the reviewed keys are deleted. the source is listed to detect the keys that have
+            // appeared again, but nothing is transferred.
+            self.aggregate_source_keys().await;
+
+            // the user filters are not applied without transfer, so every source key is counted.
+            let source_key_count = self.source_key_map.as_ref().unwrap().lock().unwrap().len();
+            self.source_object_count
+                .store(source_key_count as u64, Ordering::SeqCst);
+        } else if self.config.enable_versioning {
             // To keep versioning order, objects must be packed after filtered
             self.terminate(
                 self.sync_objects(
@@ -201,11 +736,14 @@ impl Pipeline {
             .await
             .unwrap();
         } else {
-            self.terminate(self.sync_objects(self.filter_objects(
-                self.aggregate_keys_if_necessary(
-                    self.list_source(),
-                    self.source_key_map.as_ref().cloned(),
-                ),
+            self.terminate(self.sync_objects(self.schedule_objects_if_necessary(
+                self.sort_objects_if_necessary(self.filter_objects(
+                    self.aggregate_keys_if_necessary(
+                        self.list_source(),
+                        self.source_key_map.as_ref().cloned(),
+                        true,
+                    ),
+                )),
             )))
             .await
             .unwrap();
@@ -215,7 +753,24 @@ impl Pipeline {
             return;
         }
 
+        if self.config.fail_on_empty_source && self.is_source_empty() {
+            error!("no source object has been found. aborting the run without deletion.");
+
+            self.has_error.store(true, Ordering::SeqCst);
+
+            let mut error_list = self.errors.lock().unwrap();
+            error_list.push_back(anyhow!("no source object has been found."));
+
+            return;
+        }
+
         if self.config.sync_with_delete {
+            // the source listing is incomplete after draining, so deleting target objects is not safe.
+            if self.is_draining() {
+                warn!("deletion of target objects has been skipped due to the graceful shutdown.");
+                return;
+            }
+
             self.delete().await;
         }
     }
@@ -288,13 +843,14 @@ impl Pipeline {
         &self,
         objects_list: Receiver<S3syncObject>,
         key_map: Option<ObjectKeyMap>,
+        to_target_key: bool,
     ) -> Receiver<S3syncObject> {
         if key_map.is_none() {
             return objects_list;
         }
 
         let (stage, next_stage_receiver) = self.create_spsc_stage(Some(objects_list));
-        let key_aggregator = KeyAggregator::new(stage);
+        let key_aggregator = KeyAggregator::new(stage, to_target_key);
 
         let has_error = self.has_error.clone();
         let error_list = self.errors.clone();
@@ -326,6 +882,24 @@ impl Pipeline {
     fn filter_objects(&self, objects_list: Receiver<S3syncObject>) -> Receiver<S3syncObject> {
         let mut previous_stage_receiver = objects_list;
 
+        if self.config.filter_config.skip_glacier {
+            let (stage, new_receiver) = self.create_spsc_stage(Some(previous_stage_receiver));
+
+            self.spawn_filter(Box::new(filter::SkipGlacierFilter::new(stage, None)));
+            trace!("SkipGlacierFilter has been started.");
+
+            previous_stage_receiver = new_receiver;
+        }
+
+        if self.config.filter_config.skip_zero_byte_objects {
+            let (stage, new_receiver) = self.create_spsc_stage(Some(previous_stage_receiver));
+
+            self.spawn_filter(Box::new(filter::ZeroByteObjectFilter::new(stage, None)));
+            trace!("ZeroByteObjectFilter has been started.");
+
+            previous_stage_receiver = new_receiver;
+        }
+
         if self.config.filter_config.before_time.is_some() {
             let (stage, new_receiver) = self.create_spsc_stage(Some(previous_stage_receiver));
 
@@ -362,6 +936,15 @@ impl Pipeline {
             previous_stage_receiver = new_receiver;
         }
 
+        if self.config.filter_config.owner.is_some() {
+            let (stage, new_receiver) = self.create_spsc_stage(Some(previous_stage_receiver));
+
+            self.spawn_filter(Box::new(filter::OwnerFilter::new(stage, None)));
+            trace!("OwnerFilter has been started.");
+
+            previous_stage_receiver = new_receiver;
+        }
+
         if self.config.filter_config.include_regex.is_some() {
             let (stage, new_receiver) = self.create_spsc_stage(Some(previous_stage_receiver));
 
@@ -380,6 +963,19 @@ impl Pipeline {
             previous_stage_receiver = new_receiver;
         }
 
+        // the objects skipped by the user filters are not counted, but unmodified objects are.
+        if self.config.fail_on_empty_source {
+            let (stage, new_receiver) = self.create_spsc_stage(Some(previous_stage_receiver));
+
+            self.spawn_filter(Box::new(filter::SourceObjectCounter::new(
+                stage,
+                self.source_object_count.clone(),
+            )));
+            trace!("SourceObjectCounter has been started.");
+
+            previous_stage_receiver = new_receiver;
+        }
+
         if !self.config.enable_versioning && !self.config.filter_config.remove_modified_filter {
             let (stage, new_receiver) = self.create_spsc_stage(Some(previous_stage_receiver));
             self.spawn_filter(Box::new(filter::TargetModifiedFilter::new(
@@ -421,12 +1017,50 @@ impl Pipeline {
         let (sender, next_stage_receiver) =
             async_channel::bounded::<S3syncObject>(CHANNEL_CAPACITY);
 
-        for worker_index in 0..(self.config.worker_size) {
-            let stage = self.create_mpmc_stage(sender.clone(), target_objects.clone());
-            let object_syncer = ObjectSyncer::new(stage, worker_index);
-            let has_error = self.has_error.clone();
-            let error_list = self.errors.clone();
-
+        if let Some(concurrency_gate) = &self.concurrency_gate {
+            let concurrency_controller = ConcurrencyController::new(
+                Stage::new(
+                    self.config.clone(),
+                    None,
+                    Some(dyn_clone::clone_box(&*self.target)),
+                    Some(target_objects.clone()),
+                    None,
+                    self.cancellation_token.clone(),
+                ),
+                concurrency_gate.clone(),
+                self.drain_token.clone(),
+                self.skipped_error_count.clone(),
+                self.retry_elapsed_milliseconds.clone(),
+                self.transferred_object_count.clone(),
+                self.transferred_bytes.clone(),
+            );
+            tokio::spawn(async move {
+                concurrency_controller.control().await;
+            });
+        }
+
+        let shared_state = SyncerSharedState {
+            drain_token: self.drain_token.clone(),
+            drained_object_count: self.drained_object_count.clone(),
+            skipped_error_count: self.skipped_error_count.clone(),
+            retry_elapsed_milliseconds: self.retry_elapsed_milliseconds.clone(),
+            transferred_object_count: self.transferred_object_count.clone(),
+            transferred_bytes: self.transferred_bytes.clone(),
+            prefix_concurrency_limiter: self.prefix_concurrency_limiter.clone(),
+            concurrency_gate: self.concurrency_gate.clone(),
+            delete_log: self.delete_log.clone(),
+            target_key_map: self.target_key_map.clone(),
+            checksum_semaphore: self.storage_limiters.checksum_semaphore.clone(),
+            source_concurrency_semaphore: self.source_concurrency_semaphore.clone(),
+            target_concurrency_semaphore: self.target_concurrency_semaphore.clone(),
+        };
+
+        for worker_index in 0..(self.config.worker_size) {
+            let stage = self.create_mpmc_stage(sender.clone(), target_objects.clone());
+            let object_syncer = ObjectSyncer::new(stage, worker_index, shared_state.clone());
+            let has_error = self.has_error.clone();
+            let error_list = self.errors.clone();
+
             tokio::spawn(async move {
                 let result = object_syncer.sync().await;
                 match result {
@@ -444,6 +1078,75 @@ impl Pipeline {
         next_stage_receiver
     }
 
+    fn sort_objects_if_necessary(&self, objects: Receiver<S3syncObject>) -> Receiver<S3syncObject> {
+        if self.config.transfer_order.is_none() {
+            return objects;
+        }
+
+        let (stage, new_receiver) = self.create_spsc_stage(Some(objects));
+        let sorter = ObjectSorter::new(stage);
+
+        let has_error = self.has_error.clone();
+        let error_list = self.errors.clone();
+        tokio::spawn(async move {
+            let result = sorter.sort().await;
+            match result {
+                Ok(_) => {}
+                Err(e) => {
+                    has_error.store(true, Ordering::SeqCst);
+
+                    let error = e.to_string();
+                    let source = e.source();
+
+                    error!(error = error, source = source, "sort objects failed.");
+
+                    let mut error_list = error_list.lock().unwrap();
+                    error_list.push_back(e);
+                }
+            }
+        });
+
+        new_receiver
+    }
+
+    fn schedule_objects_if_necessary(
+        &self,
+        objects: Receiver<S3syncObject>,
+    ) -> Receiver<S3syncObject> {
+        if self.prefix_concurrency_limiter.is_none() {
+            return objects;
+        }
+
+        let (stage, new_receiver) = self.create_spsc_stage(Some(objects));
+        let scheduler = ObjectScheduler::new(
+            stage,
+            self.prefix_concurrency_limiter.as_ref().unwrap().clone(),
+            self.drain_token.clone(),
+        );
+
+        let has_error = self.has_error.clone();
+        let error_list = self.errors.clone();
+        tokio::spawn(async move {
+            let result = scheduler.schedule().await;
+            match result {
+                Ok(_) => {}
+                Err(e) => {
+                    has_error.store(true, Ordering::SeqCst);
+
+                    let error = e.to_string();
+                    let source = e.source();
+
+                    error!(error = error, source = source, "schedule objects failed.");
+
+                    let mut error_list = error_list.lock().unwrap();
+                    error_list.push_back(e);
+                }
+            }
+        });
+
+        new_receiver
+    }
+
     fn pack_object_versions(
         &self,
         target_objects: Receiver<S3syncObject>,
@@ -490,7 +1193,7 @@ impl Pipeline {
 
     fn list_diff(&self) -> Receiver<S3syncObject> {
         let (stage, next_stage_receiver) = self.create_spsc_stage(None);
-        let diff_lister = DiffLister::new(stage);
+        let diff_lister = DiffLister::new(stage, self.delete_manifest_keys.clone());
         let has_error = self.has_error.clone();
         let error_list = self.errors.clone();
 
@@ -530,7 +1233,7 @@ impl Pipeline {
 
         for worker_index in 0..(self.config.worker_size) {
             let stage = self.create_mpmc_stage(sender.clone(), target_objects.clone());
-            let object_deleter = ObjectDeleter::new(stage, worker_index);
+            let object_deleter = ObjectDeleter::new(stage, worker_index, self.delete_log.clone());
             let has_error = self.has_error.clone();
             let error_list = self.errors.clone();
 
@@ -593,6 +1296,87 @@ impl Pipeline {
         )
     }
 
+    pub fn cancel(&self) {
+        self.cancellation_token.cancel();
+    }
+
+    /// Stop handing new objects to sync workers, but let in-flight objects finish.
+    /// Use `cancel()` afterward to abort the remaining transfers.
+    pub fn drain(&self) {
+        self.drain_token.cancel();
+    }
+
+    // a cancelled listing is not regarded as empty.
+    fn is_source_empty(&self) -> bool {
+        !self.cancellation_token.is_cancelled()
+            && self.source_object_count.load(Ordering::SeqCst) == 0
+    }
+
+    pub fn is_draining(&self) -> bool {
+        self.drain_token.is_cancelled()
+    }
+
+    pub fn get_drain_token(&self) -> PipelineCancellationToken {
+        self.drain_token.clone()
+    }
+
+    pub fn get_drained_object_count(&self) -> u64 {
+        self.drained_object_count.load(Ordering::SeqCst)
+    }
+
+    pub fn get_skipped_error_count(&self) -> u64 {
+        self.skipped_error_count.load(Ordering::SeqCst)
+    }
+
+    pub fn get_retry_elapsed_milliseconds(&self) -> u64 {
+        self.retry_elapsed_milliseconds.load(Ordering::SeqCst)
+    }
+
+    pub fn is_retry_budget_exhausted(&self) -> bool {
+        self.config
+            .force_retry_config
+            .retry_budget
+            .is_some_and(|retry_budget| {
+                retry_budget.as_millis() as u64 <= self.get_retry_elapsed_milliseconds()
+            })
+    }
+
+    pub fn is_aborted_by_fail_fast(&self) -> bool {
+        self.config.fail_fast_after != 0
+            && self.config.fail_fast_after <= self.get_skipped_error_count()
+    }
+
+    pub fn is_quota_reached(&self) -> bool {
+        self.config
+            .max_objects
+            .is_some_and(|max_objects| max_objects <= self.get_transferred_object_count())
+            || self
+                .config
+                .max_bytes
+                .is_some_and(|max_bytes| max_bytes <= self.get_transferred_bytes())
+    }
+
+    pub fn get_transferred_object_count(&self) -> u64 {
+        self.transferred_object_count.load(Ordering::SeqCst)
+    }
+
+    pub fn get_transferred_bytes(&self) -> u64 {
+        self.transferred_bytes.load(Ordering::SeqCst)
+    }
+
+    pub fn get_source_request_rate_limiter(&self) -> Option<Arc<RequestRateLimiter>> {
+        self.storage_limiters.source_request_rate_limiter.clone()
+    }
+
+    pub fn get_target_request_rate_limiter(&self) -> Option<Arc<RequestRateLimiter>> {
+        self.storage_limiters.target_request_rate_limiter.clone()
+    }
+
+    // --write-completion-marker. the caller writes it only after a successful run.
+    pub async fn write_completion_marker(&self, key: &str, summary: Option<String>) -> Result<()> {
+        completion_marker::write_completion_marker(&self.config, &self.target, key, summary).await
+    }
+
     pub fn get_stats_receiver(&self) -> Receiver<SyncStatistics> {
         self.stats_receiver.clone()
     }
@@ -716,6 +1500,49 @@ mod tests {
         assert!(!pipeline.has_error());
     }
 
+    #[tokio::test]
+    async fn cancel_pipeline() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--allow-both-local-storage",
+            "./test_data/source/dir1/",
+            "./test_data/target/dir1/",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+        let cancellation_token = create_pipeline_cancellation_token();
+
+        let pipeline = Pipeline::new(config, cancellation_token.clone()).await;
+        pipeline.cancel();
+
+        assert!(cancellation_token.is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn run_pipeline_drained() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--allow-both-local-storage",
+            "--delete",
+            "./test_data/source/dir1/",
+            "./test_data/target/dir1/",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+
+        let mut pipeline = Pipeline::new(config, create_pipeline_cancellation_token()).await;
+        pipeline.drain();
+        assert!(pipeline.is_draining());
+        assert!(pipeline.get_drain_token().is_cancelled());
+
+        pipeline.run().await;
+
+        assert!(!pipeline.has_error());
+        assert_eq!(pipeline.get_drained_object_count(), 0);
+    }
+
     #[tokio::test]
     #[should_panic]
     async fn run_pipeline_twice() {
@@ -783,6 +1610,117 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn run_pipeline_with_partial_suffix() {
+        init_dummy_tracing_subscriber();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let target = format!("{}/", temp_dir.path().to_string_lossy());
+        // a file that is not created by s3sync is kept even if it has the suffix.
+        let user_partial_path = PathBuf::from(format!("{}report.dat.partial", target));
+        std::fs::write(&user_partial_path, b"user").unwrap();
+
+        let args = vec![
+            "s3sync",
+            "--allow-both-local-storage",
+            "--partial-suffix",
+            ".partial",
+            "./test_data/source/dir1/",
+            &target,
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+
+        let mut pipeline = Pipeline::new(config, create_pipeline_cancellation_token()).await;
+        pipeline.run().await;
+
+        assert!(!pipeline.has_error());
+        assert!(user_partial_path.try_exists().unwrap());
+        assert!(PathBuf::from(format!("{}6byte.dat", target))
+            .try_exists()
+            .unwrap());
+        assert!(!PathBuf::from(format!("{}6byte.dat.partial", target))
+            .try_exists()
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn run_pipeline_with_max_objects() {
+        init_dummy_tracing_subscriber();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let target = format!("{}/", temp_dir.path().to_string_lossy());
+
+        let args = vec![
+            "s3sync",
+            "--allow-both-local-storage",
+            "--worker-size",
+            "1",
+            "--max-objects",
+            "1",
+            "./test_data/source/",
+            &target,
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+
+        let mut pipeline = Pipeline::new(config, create_pipeline_cancellation_token()).await;
+        pipeline.run().await;
+
+        assert!(!pipeline.has_error());
+        assert!(pipeline.is_quota_reached());
+        assert!(pipeline.is_draining());
+        assert_eq!(pipeline.get_transferred_object_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn run_pipeline_with_max_concurrent_objects_per_prefix() {
+        init_dummy_tracing_subscriber();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let target = format!("{}/", temp_dir.path().to_string_lossy());
+
+        let args = vec![
+            "s3sync",
+            "--allow-both-local-storage",
+            "--max-concurrent-objects-per-prefix",
+            "1",
+            "./test_data/source/",
+            &target,
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+
+        let mut pipeline = Pipeline::new(config, create_pipeline_cancellation_token()).await;
+        pipeline.run().await;
+
+        assert!(!pipeline.has_error());
+        assert_eq!(pipeline.get_transferred_object_count(), 6);
+    }
+
+    #[tokio::test]
+    async fn run_pipeline_with_concurrency_limits() {
+        init_dummy_tracing_subscriber();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let target = format!("{}/", temp_dir.path().to_string_lossy());
+
+        let args = vec![
+            "s3sync",
+            "--allow-both-local-storage",
+            "--source-concurrency-limit",
+            "1",
+            "--target-concurrency-limit",
+            "2",
+            "./test_data/source/",
+            &target,
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+
+        let mut pipeline = Pipeline::new(config, create_pipeline_cancellation_token()).await;
+        pipeline.run().await;
+
+        assert!(!pipeline.has_error());
+        assert_eq!(pipeline.get_transferred_object_count(), 6);
+    }
+
     #[tokio::test]
     async fn run_pipeline_with_delete() {
         init_dummy_tracing_subscriber();
@@ -893,6 +1831,400 @@ mod tests {
         )
     }
 
+    #[tokio::test]
+    async fn run_pipeline_with_dry_run_delete_log() {
+        init_dummy_tracing_subscriber();
+
+        let dir = tempfile::tempdir().unwrap();
+        let delete_log_path = dir.path().join("delete.log");
+        let delete_log = delete_log_path.to_str().unwrap();
+        let target_dir = dir.path().join("target");
+        tokio::fs::create_dir(&target_dir).await.unwrap();
+        tokio::fs::File::create(target_dir.join("data1"))
+            .await
+            .unwrap();
+        let target = format!("{}/", target_dir.to_str().unwrap());
+
+        let args = vec![
+            "s3sync",
+            "--allow-both-local-storage",
+            "--dry-run",
+            "--delete",
+            "--delete-log",
+            delete_log,
+            "./test_data/source/dir2/",
+            &target,
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+
+        let mut pipeline = Pipeline::new(config, create_pipeline_cancellation_token()).await;
+        pipeline.run().await;
+
+        assert!(!pipeline.has_error());
+
+        let content = tokio::fs::read_to_string(&delete_log_path).await.unwrap();
+        let entries: Vec<serde_json::Value> = content
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0]["key"], "data1");
+        assert_eq!(entries[0]["dry_run"], true);
+        assert!(target_dir.join("data1").try_exists().unwrap());
+    }
+
+    #[tokio::test]
+    async fn run_pipeline_with_delete_manifest() {
+        init_dummy_tracing_subscriber();
+
+        let dir = tempfile::tempdir().unwrap();
+        let manifest_path = dir.path().join("manifest.jsonl");
+        let manifest = manifest_path.to_str().unwrap();
+        let target_dir = dir.path().join("target");
+        tokio::fs::create_dir(&target_dir).await.unwrap();
+        for key in ["data1", "data2"] {
+            tokio::fs::File::create(target_dir.join(key)).await.unwrap();
+        }
+        let target = format!("{}/", target_dir.to_str().unwrap());
+
+        let args = vec![
+            "s3sync",
+            "--allow-both-local-storage",
+            "--delete",
+            "--emit-manifest-for-delete",
+            manifest,
+            "./test_data/source/dir2/",
+            &target,
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+
+        let mut pipeline = Pipeline::new(config, create_pipeline_cancellation_token()).await;
+        pipeline.run().await;
+
+        assert!(!pipeline.has_error());
+        assert!(target_dir.join("data1").try_exists().unwrap());
+        assert!(target_dir.join("data2").try_exists().unwrap());
+
+        // remove data2 from the reviewed set.
+        let content = tokio::fs::read_to_string(&manifest_path).await.unwrap();
+        let reviewed = content
+            .lines()
+            .filter(|line| !line.contains("\"data2\""))
+            .collect::<Vec<&str>>()
+            .join("\n");
+        assert_eq!(content.lines().count(), 3);
+        tokio::fs::write(&manifest_path, reviewed).await.unwrap();
+
+        let args = vec![
+            "s3sync",
+            "--allow-both-local-storage",
+            "--delete",
+            "--delete-from-manifest",
+            manifest,
+            "./test_data/source/dir2/",
+            &target,
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+
+        let mut pipeline = Pipeline::new(config, create_pipeline_cancellation_token()).await;
+        pipeline.run().await;
+
+        assert!(!pipeline.has_error());
+        assert!(!target_dir.join("data1").try_exists().unwrap());
+        assert!(target_dir.join("data2").try_exists().unwrap());
+        assert!(!target_dir.join("6byte.dat").try_exists().unwrap());
+    }
+
+    #[tokio::test]
+    async fn delete_manifest_matches_delete_with_filter() {
+        init_dummy_tracing_subscriber();
+
+        let dir = tempfile::tempdir().unwrap();
+        let source_dir = dir.path().join("source");
+        tokio::fs::create_dir(&source_dir).await.unwrap();
+        for key in ["keep.dat", "excluded.log"] {
+            tokio::fs::File::create(source_dir.join(key)).await.unwrap();
+        }
+        let target_dir = dir.path().join("target");
+        tokio::fs::create_dir(&target_dir).await.unwrap();
+        for key in ["keep.dat", "excluded.log", "stale.dat", "stale.log"] {
+            tokio::fs::File::create(target_dir.join(key)).await.unwrap();
+        }
+        let source = format!("{}/", source_dir.to_str().unwrap());
+        let target = format!("{}/", target_dir.to_str().unwrap());
+        let manifest_path = dir.path().join("manifest.jsonl");
+        let delete_log_path = dir.path().join("delete.log");
+
+        let args = vec![
+            "s3sync",
+            "--allow-both-local-storage",
+            "--delete",
+            "--filter-exclude-regex",
+            r"\.log$",
+            "--emit-manifest-for-delete",
+            manifest_path.to_str().unwrap(),
+            &source,
+            &target,
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+
+        // the pipeline futures are boxed not to overflow the stack of the test thread.
+        let mut pipeline =
+            Box::pin(Pipeline::new(config, create_pipeline_cancellation_token())).await;
+        Box::pin(pipeline.run()).await;
+        assert!(!pipeline.has_error());
+
+        let args = vec![
+            "s3sync",
+            "--allow-both-local-storage",
+            "--dry-run",
+            "--delete",
+            "--filter-exclude-regex",
+            r"\.log$",
+            "--delete-log",
+            delete_log_path.to_str().unwrap(),
+            &source,
+            &target,
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+
+        let mut pipeline =
+            Box::pin(Pipeline::new(config, create_pipeline_cancellation_token())).await;
+        Box::pin(pipeline.run()).await;
+        assert!(!pipeline.has_error());
+
+        let manifest = tokio::fs::read_to_string(&manifest_path).await.unwrap();
+        let manifest_keys: HashSet<String> = manifest
+            .lines()
+            .skip(1)
+            .map(|line| {
+                let entry: serde_json::Value = serde_json::from_str(line).unwrap();
+                entry["key"].as_str().unwrap().to_string()
+            })
+            .collect();
+
+        let delete_log = tokio::fs::read_to_string(&delete_log_path).await.unwrap();
+        let deleted_keys: HashSet<String> = delete_log
+            .lines()
+            .map(|line| {
+                let entry: serde_json::Value = serde_json::from_str(line).unwrap();
+                entry["key"].as_str().unwrap().to_string()
+            })
+            .collect();
+
+        // the filters do not protect or expose target objects. both see the whole source listing.
+        assert_eq!(
+            manifest_keys,
+            HashSet::from(["stale.dat".to_string(), "stale.log".to_string()])
+        );
+        assert_eq!(manifest_keys, deleted_keys);
+    }
+
+    #[tokio::test]
+    async fn run_pipeline_with_delete_manifest_for_another_target() {
+        init_dummy_tracing_subscriber();
+
+        let dir = tempfile::tempdir().unwrap();
+        let manifest_path = dir.path().join("manifest.jsonl");
+        tokio::fs::write(
+            &manifest_path,
+            "{\"source\":\"./test_data/source/dir2/\",\"target\":\"./other/\"}\n",
+        )
+        .await
+        .unwrap();
+
+        let args = vec![
+            "s3sync",
+            "--allow-both-local-storage",
+            "--dry-run",
+            "--delete",
+            "--delete-from-manifest",
+            manifest_path.to_str().unwrap(),
+            "./test_data/source/dir2/",
+            "./test_data/target/delete_dry_run_test/",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+
+        let mut pipeline = Pipeline::new(config, create_pipeline_cancellation_token()).await;
+        pipeline.run().await;
+
+        assert!(pipeline.has_error());
+    }
+
+    #[tokio::test]
+    async fn run_pipeline_with_delete_log_error() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--allow-both-local-storage",
+            "--dry-run",
+            "--delete",
+            "--delete-log",
+            "./test_data/no_such_dir/delete.log",
+            "./test_data/source/dir2/",
+            "./test_data/target/delete_dry_run_test/",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+
+        let mut pipeline = Pipeline::new(config, create_pipeline_cancellation_token()).await;
+        pipeline.run().await;
+
+        assert!(pipeline.has_error());
+    }
+
+    #[tokio::test]
+    async fn run_pipeline_fail_on_empty_source() {
+        init_dummy_tracing_subscriber();
+
+        let dir = tempfile::tempdir().unwrap();
+        let source_dir = dir.path().join("source");
+        tokio::fs::create_dir(&source_dir).await.unwrap();
+        let target_dir = dir.path().join("target");
+        tokio::fs::create_dir(&target_dir).await.unwrap();
+        tokio::fs::File::create(target_dir.join("data1"))
+            .await
+            .unwrap();
+        let source = format!("{}/", source_dir.to_str().unwrap());
+        let target = format!("{}/", target_dir.to_str().unwrap());
+
+        let args = vec![
+            "s3sync",
+            "--allow-both-local-storage",
+            "--delete",
+            "--fail-on-empty-source",
+            &source,
+            &target,
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+
+        let mut pipeline = Pipeline::new(config, create_pipeline_cancellation_token()).await;
+        pipeline.run().await;
+
+        assert!(pipeline.has_error());
+        assert!(target_dir.join("data1").try_exists().unwrap());
+    }
+
+    #[tokio::test]
+    async fn run_pipeline_fail_on_empty_source_with_filter() {
+        init_dummy_tracing_subscriber();
+
+        let dir = tempfile::tempdir().unwrap();
+        let target = format!("{}/", dir.path().to_str().unwrap());
+
+        let args = vec![
+            "s3sync",
+            "--allow-both-local-storage",
+            "--dry-run",
+            "--fail-on-empty-source",
+            "--filter-include-regex",
+            "^no_such_key$",
+            "./test_data/source/dir2/",
+            &target,
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+
+        let mut pipeline = Pipeline::new(config, create_pipeline_cancellation_token()).await;
+        pipeline.run().await;
+
+        assert!(pipeline.has_error());
+
+        let args = vec![
+            "s3sync",
+            "--allow-both-local-storage",
+            "--dry-run",
+            "--fail-on-empty-source",
+            "./test_data/source/dir2/",
+            &target,
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+
+        let mut pipeline = Pipeline::new(config, create_pipeline_cancellation_token()).await;
+        pipeline.run().await;
+
+        assert!(!pipeline.has_error());
+    }
+
+    #[tokio::test]
+    async fn pipeline_preflight_error() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--aws-max-attempts",
+            "1",
+            "--source-endpoint-url",
+            "https://invalid-s3-endpoint-url.6329313.local:65535",
+            "s3://source-bucket",
+            "s3://target-bucket",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+
+        let pipeline = Pipeline::new(config, create_pipeline_cancellation_token()).await;
+
+        assert!(!pipeline.preflight().await);
+        assert!(pipeline.has_error());
+        let error = pipeline.get_errors_and_consume().unwrap()[0].to_string();
+        assert!(error.contains("--source-force-path-style"));
+    }
+
+    #[tokio::test]
+    async fn pipeline_preflight_disabled() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "--target-profile",
+            "target_profile",
+            "--aws-max-attempts",
+            "1",
+            "--disable-preflight",
+            "--source-endpoint-url",
+            "https://invalid-s3-endpoint-url.6329313.local:65535",
+            "s3://source-bucket",
+            "s3://target-bucket",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+
+        let pipeline = Pipeline::new(config, create_pipeline_cancellation_token()).await;
+
+        assert!(pipeline.preflight().await);
+        assert!(!pipeline.has_error());
+    }
+
+    #[tokio::test]
+    async fn pipeline_preflight_target_not_listed() {
+        init_dummy_tracing_subscriber();
+
+        let source_dir = tempfile::tempdir().unwrap();
+        let source = format!("{}/", source_dir.path().to_str().unwrap());
+
+        let args = vec![
+            "s3sync",
+            "--target-profile",
+            "target_profile",
+            "--aws-max-attempts",
+            "1",
+            "--remove-modified-filter",
+            "--target-endpoint-url",
+            "https://invalid-s3-endpoint-url.6329313.local:65535",
+            &source,
+            "s3://target-bucket",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+
+        let pipeline = Pipeline::new(config, create_pipeline_cancellation_token()).await;
+
+        assert!(pipeline.preflight().await);
+        assert!(!pipeline.has_error());
+    }
+
     #[tokio::test]
     async fn run_pipeline_sync_error() {
         init_dummy_tracing_subscriber();
@@ -905,6 +2237,7 @@ mod tests {
             "target_profile",
             "--aws-max-attempts",
             "1",
+            "--disable-preflight",
             "--source-endpoint-url",
             "https://invalid-s3-endpoint-url.6329313.local:65535",
             "--force-retry-count",
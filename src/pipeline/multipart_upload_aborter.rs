@@ -0,0 +1,139 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use aws_sdk_s3::types::MultipartUpload;
+use aws_smithy_types_convert::date_time::DateTimeExt;
+use chrono::{DateTime, Utc};
+use tracing::{debug, info, trace};
+
+use crate::types::SyncStatistics::{SyncDelete, SyncSkip};
+
+use super::stage::Stage;
+
+pub struct MultipartUploadAborter {
+    base: Stage,
+}
+
+impl MultipartUploadAborter {
+    pub fn new(base: Stage) -> Self {
+        Self { base }
+    }
+
+    pub async fn abort(&self) -> Result<()> {
+        trace!("abort incomplete multipart uploads process started.");
+
+        let target = self.base.target.as_ref().unwrap();
+        let older_than = self
+            .base
+            .config
+            .abort_incomplete_multipart_uploads_older_than;
+        let now = Utc::now();
+
+        let mut aborted_uploads: u64 = 0;
+        let mut aborted_bytes: u64 = 0;
+        for upload in target.list_multipart_uploads().await? {
+            if self.base.cancellation_token.is_cancelled() {
+                info!("abort incomplete multipart uploads process has been cancelled.");
+                return Ok(());
+            }
+
+            let key = upload.key().unwrap_or_default();
+            let upload_id = upload.upload_id().unwrap_or_default();
+
+            if !is_initiated_before(&upload, older_than, now) {
+                debug!(
+                    key = key,
+                    upload_id = upload_id,
+                    "multipart upload is newer than the threshold, skipped."
+                );
+                self.base
+                    .send_stats(SyncSkip {
+                        key: key.to_string(),
+                    })
+                    .await;
+                continue;
+            }
+
+            let size = target.get_multipart_upload_size(key, upload_id).await?;
+            target.abort_multipart_upload(key, upload_id).await?;
+
+            aborted_uploads += 1;
+            aborted_bytes += size;
+            self.base
+                .send_stats(SyncDelete {
+                    key: key.to_string(),
+                })
+                .await;
+        }
+
+        info!(
+            aborted_uploads = aborted_uploads,
+            reclaimed_bytes = aborted_bytes,
+            dry_run = self.base.config.dry_run,
+            "abort incomplete multipart uploads completed."
+        );
+
+        Ok(())
+    }
+}
+
+fn is_initiated_before(
+    upload: &MultipartUpload,
+    older_than: Option<Duration>,
+    now: DateTime<Utc>,
+) -> bool {
+    if older_than.is_none() {
+        return true;
+    }
+
+    // an upload without initiated time cannot be judged, so it is kept.
+    let Some(initiated) = upload.initiated() else {
+        return false;
+    };
+
+    let threshold = now - chrono::Duration::from_std(older_than.unwrap()).unwrap();
+    initiated.to_chrono_utc().unwrap() < threshold
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_initiated_before_without_threshold() {
+        let upload = MultipartUpload::builder().key("test").build();
+
+        assert!(is_initiated_before(&upload, None, Utc::now()));
+    }
+
+    #[test]
+    fn is_initiated_before_with_threshold() {
+        let now = DateTime::parse_from_rfc3339("2024-01-08T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let seven_days = Some(Duration::from_secs(7 * 24 * 60 * 60));
+
+        let old_upload = MultipartUpload::builder()
+            .key("old")
+            .initiated(aws_smithy_types::DateTime::from_secs(
+                DateTime::parse_from_rfc3339("2023-12-31T23:59:59Z")
+                    .unwrap()
+                    .timestamp(),
+            ))
+            .build();
+        assert!(is_initiated_before(&old_upload, seven_days, now));
+
+        let new_upload = MultipartUpload::builder()
+            .key("new")
+            .initiated(aws_smithy_types::DateTime::from_secs(
+                DateTime::parse_from_rfc3339("2024-01-01T00:00:01Z")
+                    .unwrap()
+                    .timestamp(),
+            ))
+            .build();
+        assert!(!is_initiated_before(&new_upload, seven_days, now));
+
+        let unknown_upload = MultipartUpload::builder().key("unknown").build();
+        assert!(!is_initiated_before(&unknown_upload, seven_days, now));
+    }
+}
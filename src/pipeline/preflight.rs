@@ -0,0 +1,265 @@
+use anyhow::{Error, Result};
+use aws_sdk_s3::error::{ProvideErrorMetadata, SdkError};
+use aws_sdk_s3::types::DefaultRetention;
+use aws_sdk_s3::Client;
+
+use crate::config::ClientConfig;
+
+// A cheap request to find auth/region/endpoint problems before the sync starts.
+// it is sent only to the buckets that are listed by the sync, so that the check does not need an extra permission.
+pub async fn check_bucket_access(
+    side: &str,
+    client: &Client,
+    bucket: &str,
+    prefix: &str,
+    client_config: &ClientConfig,
+) -> Result<()> {
+    let result = client
+        .list_objects_v2()
+        .bucket(bucket)
+        .prefix(prefix)
+        .max_keys(1)
+        .set_expected_bucket_owner(client_config.expected_bucket_owner.clone())
+        .send()
+        .await;
+
+    let Err(e) = result else {
+        return Ok(());
+    };
+
+    let error_code = e
+        .as_service_error()
+        .and_then(|e| e.code())
+        .map(String::from);
+    let is_dispatch_failure = matches!(e, SdkError::DispatchFailure(_));
+
+    let mut message = format!("preflight check of the {} bucket({}) failed.", side, bucket);
+    if let Some(hint) = build_hint(
+        side,
+        error_code.as_deref(),
+        is_dispatch_failure,
+        client_config,
+    ) {
+        message = format!("{} {}", message, hint);
+    }
+
+    Err(Error::new(e).context(message))
+}
+
+// The default retention of Object Lock is applied to every uploaded object silently, and the objects
+// cannot be deleted until it expires. returns the description of the default retention if set.
+// s3:GetBucketObjectLockConfiguration is required.
+pub async fn get_default_retention(
+    client: &Client,
+    bucket: &str,
+    client_config: &ClientConfig,
+) -> Result<Option<String>> {
+    let result = client
+        .get_object_lock_configuration()
+        .bucket(bucket)
+        .set_expected_bucket_owner(client_config.expected_bucket_owner.clone())
+        .send()
+        .await;
+
+    let output = match result {
+        Ok(output) => output,
+        Err(e)
+            if e.as_service_error().and_then(|e| e.code())
+                == Some("ObjectLockConfigurationNotFoundError") =>
+        {
+            return Ok(None);
+        }
+        Err(e) => {
+            return Err(Error::new(e).context(format!(
+                "failed to get the object lock configuration of the bucket({}).",
+                bucket
+            )));
+        }
+    };
+
+    Ok(output
+        .object_lock_configuration()
+        .and_then(|configuration| configuration.rule())
+        .and_then(|rule| rule.default_retention())
+        .map(describe_default_retention))
+}
+
+fn describe_default_retention(default_retention: &DefaultRetention) -> String {
+    let mode = default_retention
+        .mode()
+        .map_or("unknown", |mode| mode.as_str());
+
+    match (default_retention.days(), default_retention.years()) {
+        (Some(days), _) => format!("{} mode, {} days", mode, days),
+        (_, Some(years)) => format!("{} mode, {} years", mode, years),
+        _ => format!("{} mode", mode),
+    }
+}
+
+fn build_hint(
+    side: &str,
+    error_code: Option<&str>,
+    is_dispatch_failure: bool,
+    client_config: &ClientConfig,
+) -> Option<String> {
+    // virtual-hosted-style requests to a custom endpoint often fail with a DNS error or a wrong signature.
+    let path_style_suggested =
+        client_config.endpoint_url.is_some() && !client_config.force_path_style;
+
+    if is_dispatch_failure {
+        if path_style_suggested {
+            return Some(format!(
+                "the endpoint may not support virtual-hosted-style requests. try --{}-force-path-style.",
+                side
+            ));
+        }
+        return Some(format!(
+            "check the network and the endpoint(--{}-endpoint-url).",
+            side
+        ));
+    }
+
+    match error_code? {
+        "NoSuchBucket" => Some("check the bucket name.".to_string()),
+        "PermanentRedirect"
+        | "AuthorizationHeaderMalformed"
+        | "IllegalLocationConstraintException" => Some(format!(
+            "the bucket may be in another region. check --{}-region.",
+            side
+        )),
+        "SignatureDoesNotMatch" if path_style_suggested => Some(format!(
+            "check the credentials, or try --{}-force-path-style.",
+            side
+        )),
+        "AccessDenied"
+        | "InvalidAccessKeyId"
+        | "SignatureDoesNotMatch"
+        | "ExpiredToken"
+        | "InvalidToken" => Some(format!(
+            "check the credentials(--{}-profile etc.) and the s3:ListBucket permission.",
+            side
+        )),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_s3::types::ObjectLockRetentionMode;
+
+    use crate::config::args::parse_from_args;
+    use crate::Config;
+
+    use super::*;
+
+    #[test]
+    fn build_hint_for_dispatch_failure() {
+        init_dummy_tracing_subscriber();
+
+        let client_config = build_client_config(&["--target-endpoint-url", "https://localhost"]);
+        assert!(build_hint("target", None, true, &client_config)
+            .unwrap()
+            .contains("--target-force-path-style"));
+
+        let client_config = build_client_config(&[
+            "--target-endpoint-url",
+            "https://localhost",
+            "--target-force-path-style",
+        ]);
+        assert!(build_hint("target", None, true, &client_config)
+            .unwrap()
+            .contains("--target-endpoint-url"));
+
+        let client_config = build_client_config(&[]);
+        assert!(build_hint("target", None, true, &client_config)
+            .unwrap()
+            .contains("--target-endpoint-url"));
+    }
+
+    #[test]
+    fn build_hint_for_service_error() {
+        init_dummy_tracing_subscriber();
+
+        let client_config = build_client_config(&[]);
+        assert_eq!(
+            build_hint("target", Some("NoSuchBucket"), false, &client_config).unwrap(),
+            "check the bucket name."
+        );
+        assert!(
+            build_hint("target", Some("PermanentRedirect"), false, &client_config)
+                .unwrap()
+                .contains("--target-region")
+        );
+        assert!(
+            build_hint("target", Some("AccessDenied"), false, &client_config)
+                .unwrap()
+                .contains("s3:ListBucket")
+        );
+        assert!(build_hint(
+            "target",
+            Some("SignatureDoesNotMatch"),
+            false,
+            &client_config
+        )
+        .unwrap()
+        .contains("s3:ListBucket"));
+        assert!(build_hint("target", Some("InternalError"), false, &client_config).is_none());
+        assert!(build_hint("target", None, false, &client_config).is_none());
+
+        let client_config = build_client_config(&["--target-endpoint-url", "https://localhost"]);
+        assert!(build_hint(
+            "target",
+            Some("SignatureDoesNotMatch"),
+            false,
+            &client_config
+        )
+        .unwrap()
+        .contains("--target-force-path-style"));
+    }
+
+    #[test]
+    fn describe_default_retention_test() {
+        init_dummy_tracing_subscriber();
+
+        let default_retention = DefaultRetention::builder()
+            .mode(ObjectLockRetentionMode::Governance)
+            .days(30)
+            .build();
+        assert_eq!(
+            describe_default_retention(&default_retention),
+            "GOVERNANCE mode, 30 days"
+        );
+
+        let default_retention = DefaultRetention::builder()
+            .mode(ObjectLockRetentionMode::Compliance)
+            .years(1)
+            .build();
+        assert_eq!(
+            describe_default_retention(&default_retention),
+            "COMPLIANCE mode, 1 years"
+        );
+
+        let default_retention = DefaultRetention::builder().build();
+        assert_eq!(
+            describe_default_retention(&default_retention),
+            "unknown mode"
+        );
+    }
+
+    fn build_client_config(options: &[&str]) -> ClientConfig {
+        let mut args = vec!["s3sync", "--target-profile", "target_profile"];
+        args.extend_from_slice(options);
+        args.extend_from_slice(&["./test_data/source/", "s3://target-bucket/target_key"]);
+
+        Config::try_from(parse_from_args(args).unwrap())
+            .unwrap()
+            .target_client_config
+            .unwrap()
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
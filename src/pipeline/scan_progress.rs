@@ -0,0 +1,123 @@
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use tracing::info;
+
+const REPORT_INTERVAL: Duration = Duration::from_secs(10);
+
+// With --source-scan-progress, the listing phase of a huge bucket, which can take minutes before the
+// first transfer, is reported periodically at the info level.
+pub struct ScanProgress {
+    listed_object_count: AtomicU64,
+    start_time: Instant,
+}
+
+impl ScanProgress {
+    pub fn new() -> Self {
+        Self {
+            listed_object_count: AtomicU64::new(0),
+            start_time: Instant::now(),
+        }
+    }
+
+    pub fn add(&self) {
+        self.listed_object_count.fetch_add(1, Ordering::SeqCst);
+    }
+
+    pub fn get_listed_object_count(&self) -> u64 {
+        self.listed_object_count.load(Ordering::SeqCst)
+    }
+
+    // reports the progress until the listing future is completed.
+    pub async fn report_until<F: Future<Output = Result<()>>>(&self, listing: F) -> Result<()> {
+        self.report_until_with_interval(listing, REPORT_INTERVAL)
+            .await
+    }
+
+    async fn report_until_with_interval<F: Future<Output = Result<()>>>(
+        &self,
+        listing: F,
+        interval: Duration,
+    ) -> Result<()> {
+        tokio::pin!(listing);
+
+        let mut last_count = 0;
+        loop {
+            tokio::select! {
+                result = &mut listing => {
+                    if result.is_ok() {
+                        info!(
+                            listed_objects = self.get_listed_object_count(),
+                            elapsed_secs = self.start_time.elapsed().as_secs(),
+                            "source listing has been completed."
+                        );
+                    }
+                    return result;
+                }
+                _ = tokio::time::sleep(interval) => {
+                    let count = self.get_listed_object_count();
+                    info!(
+                        listed_objects = count,
+                        listed_objects_per_sec = calculate_rate(count - last_count, interval),
+                        elapsed_secs = self.start_time.elapsed().as_secs(),
+                        "source listing is in progress."
+                    );
+                    last_count = count;
+                }
+            }
+        }
+    }
+}
+
+fn calculate_rate(count: u64, interval: Duration) -> u64 {
+    (count as f64 / interval.as_secs_f64()) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+
+    use super::*;
+
+    #[test]
+    fn calculate_rate_test() {
+        init_dummy_tracing_subscriber();
+
+        assert_eq!(calculate_rate(0, Duration::from_secs(10)), 0);
+        assert_eq!(calculate_rate(1000, Duration::from_secs(10)), 100);
+        assert_eq!(calculate_rate(5, Duration::from_millis(500)), 10);
+    }
+
+    #[tokio::test]
+    async fn report_until_completed() {
+        init_dummy_tracing_subscriber();
+
+        let scan_progress = ScanProgress::new();
+        let listing = async {
+            for _ in 0..5 {
+                scan_progress.add();
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+            Ok(())
+        };
+
+        scan_progress
+            .report_until_with_interval(listing, Duration::from_millis(15))
+            .await
+            .unwrap();
+        assert_eq!(scan_progress.get_listed_object_count(), 5);
+
+        let result = scan_progress
+            .report_until_with_interval(async { Err(anyhow!("test")) }, Duration::from_millis(15))
+            .await;
+        assert!(result.is_err());
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
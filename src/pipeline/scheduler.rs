@@ -0,0 +1,238 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use tokio::sync::Notify;
+use tracing::trace;
+
+use crate::types::token::PipelineCancellationToken;
+use crate::types::S3syncObject;
+
+use super::stage::Stage;
+
+// Limits the number of in-flight objects per top-level prefix.
+// An object is in flight from being dispatched by ObjectScheduler until a sync worker releases it.
+pub struct PrefixConcurrencyLimiter {
+    max_concurrent_objects_per_prefix: usize,
+    in_flight: Mutex<HashMap<String, usize>>,
+    released: Notify,
+}
+
+impl PrefixConcurrencyLimiter {
+    pub fn new(max_concurrent_objects_per_prefix: usize) -> Self {
+        Self {
+            max_concurrent_objects_per_prefix,
+            in_flight: Mutex::new(HashMap::new()),
+            released: Notify::new(),
+        }
+    }
+
+    fn try_acquire(&self, prefix: &str) -> bool {
+        let mut in_flight = self.in_flight.lock().unwrap();
+        let count = in_flight.entry(prefix.to_string()).or_insert(0);
+        if *count >= self.max_concurrent_objects_per_prefix {
+            return false;
+        }
+
+        *count += 1;
+        true
+    }
+
+    pub fn release(&self, key: &str) {
+        let prefix = get_prefix(key);
+
+        let mut in_flight = self.in_flight.lock().unwrap();
+        if let Some(count) = in_flight.get_mut(prefix) {
+            *count -= 1;
+            if *count == 0 {
+                in_flight.remove(prefix);
+            }
+        }
+
+        // the permit is stored if the scheduler is not waiting, so no release is missed.
+        self.released.notify_one();
+    }
+
+    async fn wait_for_release(&self) {
+        self.released.notified().await
+    }
+}
+
+pub struct ObjectScheduler {
+    base: Stage,
+    limiter: Arc<PrefixConcurrencyLimiter>,
+    drain_token: PipelineCancellationToken,
+}
+
+impl ObjectScheduler {
+    pub fn new(
+        base: Stage,
+        limiter: Arc<PrefixConcurrencyLimiter>,
+        drain_token: PipelineCancellationToken,
+    ) -> Self {
+        Self {
+            base,
+            limiter,
+            drain_token,
+        }
+    }
+
+    // Objects are buffered per top-level prefix, and dispatched round-robin across the prefixes
+    // as long as the prefix has fewer in-flight objects than --max-concurrent-objects-per-prefix.
+    pub async fn schedule(&self) -> Result<()> {
+        trace!("object scheduler has started.");
+
+        let mut queues = PrefixQueues::default();
+        let mut listing_completed = false;
+
+        loop {
+            while let Some(object) = queues.pop_next(&self.limiter) {
+                if let Err(e) = self.base.send(object).await {
+                    return if !self.base.is_channel_closed() {
+                        Err(e)
+                    } else {
+                        Ok(())
+                    };
+                }
+            }
+
+            if listing_completed && queues.is_empty() {
+                trace!("object scheduler has been completed.");
+                return Ok(());
+            }
+
+            tokio::select! {
+                result = self.base.receiver.as_ref().unwrap().recv(), if !listing_completed => {
+                    match result {
+                        Ok(object) => queues.push(object),
+                        Err(_) => listing_completed = true,
+                    }
+                },
+                _ = self.limiter.wait_for_release() => {},
+                _ = self.base.cancellation_token.cancelled() => {
+                    trace!("object scheduler has been cancelled.");
+                    return Ok(());
+                }
+                _ = self.drain_token.cancelled() => {
+                    trace!("object scheduler has been drained.");
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct PrefixQueues {
+    order: VecDeque<String>,
+    queues: HashMap<String, VecDeque<S3syncObject>>,
+}
+
+impl PrefixQueues {
+    fn push(&mut self, object: S3syncObject) {
+        let prefix = get_prefix(object.key()).to_string();
+        if !self.queues.contains_key(&prefix) {
+            self.order.push_back(prefix.clone());
+        }
+
+        self.queues.entry(prefix).or_default().push_back(object);
+    }
+
+    fn pop_next(&mut self, limiter: &PrefixConcurrencyLimiter) -> Option<S3syncObject> {
+        for _ in 0..self.order.len() {
+            let prefix = self.order.pop_front().unwrap();
+            if !limiter.try_acquire(&prefix) {
+                self.order.push_back(prefix);
+                continue;
+            }
+
+            let queue = self.queues.get_mut(&prefix).unwrap();
+            let object = queue.pop_front().unwrap();
+            if queue.is_empty() {
+                self.queues.remove(&prefix);
+            } else {
+                self.order.push_back(prefix);
+            }
+
+            return Some(object);
+        }
+
+        None
+    }
+
+    fn is_empty(&self) -> bool {
+        self.queues.is_empty()
+    }
+}
+
+// The top-level prefix of the key including the trailing delimiter. Keys without a delimiter share "".
+fn get_prefix(key: &str) -> &str {
+    match key.find('/') {
+        Some(index) => &key[..=index],
+        None => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_s3::types::Object;
+
+    use super::*;
+
+    #[test]
+    fn get_prefix_test() {
+        init_dummy_tracing_subscriber();
+
+        assert_eq!(get_prefix("dir1/dir2/key"), "dir1/");
+        assert_eq!(get_prefix("dir1/"), "dir1/");
+        assert_eq!(get_prefix("key"), "");
+    }
+
+    #[test]
+    fn round_robin_across_prefixes() {
+        init_dummy_tracing_subscriber();
+
+        let limiter = PrefixConcurrencyLimiter::new(10);
+        let mut queues = PrefixQueues::default();
+        for key in ["big/1", "big/2", "big/3", "small/1", "key"] {
+            queues.push(build_object(key));
+        }
+
+        let mut keys = vec![];
+        while let Some(object) = queues.pop_next(&limiter) {
+            keys.push(object.key().to_string());
+        }
+
+        assert_eq!(keys, vec!["big/1", "small/1", "key", "big/2", "big/3"]);
+        assert!(queues.is_empty());
+    }
+
+    #[test]
+    fn limit_concurrent_objects_per_prefix() {
+        init_dummy_tracing_subscriber();
+
+        let limiter = PrefixConcurrencyLimiter::new(1);
+        let mut queues = PrefixQueues::default();
+        for key in ["big/1", "big/2", "small/1"] {
+            queues.push(build_object(key));
+        }
+
+        assert_eq!(queues.pop_next(&limiter).unwrap().key(), "big/1");
+        assert_eq!(queues.pop_next(&limiter).unwrap().key(), "small/1");
+        assert!(queues.pop_next(&limiter).is_none());
+
+        limiter.release("big/1");
+        assert_eq!(queues.pop_next(&limiter).unwrap().key(), "big/2");
+        assert!(queues.is_empty());
+    }
+
+    fn build_object(key: &str) -> S3syncObject {
+        S3syncObject::NotVersioning(Object::builder().key(key).size(1).build())
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
@@ -0,0 +1,140 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use tracing::trace;
+
+use crate::types::{S3syncObject, TransferOrder};
+
+use super::stage::Stage;
+
+pub struct ObjectSorter {
+    base: Stage,
+}
+
+impl ObjectSorter {
+    pub fn new(base: Stage) -> Self {
+        Self { base }
+    }
+
+    // Objects are buffered up to --transfer-order-buffer-size, and each buffer is sorted before dispatch.
+    pub async fn sort(&self) -> Result<()> {
+        trace!("object sorter has started.");
+
+        let transfer_order = self.base.config.transfer_order.unwrap();
+        let buffer_size = self.base.config.transfer_order_buffer_size;
+
+        let mut buffer = Vec::<S3syncObject>::new();
+
+        loop {
+            tokio::select! {
+                result = self.base.receiver.as_ref().unwrap().recv() => {
+                    match result {
+                        Ok(object) => {
+                            buffer.push(object);
+                            if buffer.len() >= buffer_size {
+                                sort_objects(&mut buffer, transfer_order);
+                                if !self.send_objects(&mut buffer).await? {
+                                    return Ok(());
+                                }
+                            }
+                        },
+                        Err(_) => {
+                            sort_objects(&mut buffer, transfer_order);
+                            self.send_objects(&mut buffer).await?;
+
+                            trace!("object sorter has been completed.");
+                            return Ok(());
+                        }
+                    }
+                },
+                _ = self.base.cancellation_token.cancelled() => {
+                    trace!("object sorter has been cancelled.");
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    // Returns false if the next stage has been closed.
+    async fn send_objects(&self, buffer: &mut Vec<S3syncObject>) -> Result<bool> {
+        for object in buffer.drain(..) {
+            if let Err(e) = self.base.send(object).await {
+                return if !self.base.is_channel_closed() {
+                    Err(e)
+                } else {
+                    Ok(false)
+                };
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+fn sort_objects(objects: &mut [S3syncObject], transfer_order: TransferOrder) {
+    match transfer_order {
+        TransferOrder::SizeAsc => objects.sort_by_key(|object| object.size()),
+        TransferOrder::SizeDesc => objects.sort_by_key(|object| std::cmp::Reverse(object.size())),
+        TransferOrder::Key => objects.sort_by(|a, b| a.key().cmp(b.key())),
+        TransferOrder::Random => shuffle(objects),
+    }
+}
+
+// Fisher-Yates shuffle with xorshift64. The order does not need to be cryptographically random.
+fn shuffle(objects: &mut [S3syncObject]) {
+    let mut state = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos() as u64
+        | 1;
+
+    for i in (1..objects.len()).rev() {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+
+        objects.swap(i, (state % (i as u64 + 1)) as usize);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_s3::types::Object;
+
+    use super::*;
+
+    #[test]
+    fn sort_objects_test() {
+        init_dummy_tracing_subscriber();
+
+        let mut objects = vec![
+            S3syncObject::NotVersioning(Object::builder().key("b").size(1).build()),
+            S3syncObject::NotVersioning(Object::builder().key("c").size(3).build()),
+            S3syncObject::NotVersioning(Object::builder().key("a").size(2).build()),
+        ];
+
+        sort_objects(&mut objects, TransferOrder::SizeAsc);
+        assert_eq!(keys(&objects), vec!["b", "a", "c"]);
+
+        sort_objects(&mut objects, TransferOrder::SizeDesc);
+        assert_eq!(keys(&objects), vec!["c", "a", "b"]);
+
+        sort_objects(&mut objects, TransferOrder::Key);
+        assert_eq!(keys(&objects), vec!["a", "b", "c"]);
+
+        sort_objects(&mut objects, TransferOrder::Random);
+        let mut shuffled_keys = keys(&objects);
+        shuffled_keys.sort();
+        assert_eq!(shuffled_keys, vec!["a", "b", "c"]);
+    }
+
+    fn keys(objects: &[S3syncObject]) -> Vec<&str> {
+        objects.iter().map(|object| object.key()).collect()
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
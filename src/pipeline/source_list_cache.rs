@@ -0,0 +1,280 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::{anyhow, Context, Result};
+use async_channel::Sender;
+use aws_sdk_s3::types::{ChecksumAlgorithm, Object, ObjectStorageClass};
+use aws_smithy_types::date_time::Format as DateTimeFormat;
+use aws_smithy_types::DateTime;
+use serde_json::{json, Value};
+use tempfile::NamedTempFile;
+
+use crate::config::SourceListCacheConfig;
+use crate::types::{S3syncObject, StoragePath};
+use crate::Config;
+
+// The first line of the cache is a header of the listing conditions. the cache is not used
+// if they are different from the current ones. each following line is a listed object.
+pub fn build_header(config: &Config) -> Value {
+    let source = match &config.source {
+        StoragePath::S3 { bucket, prefix } => format!("s3://{}/{}", bucket, prefix),
+        StoragePath::Local(path) => path.to_string_lossy().to_string(),
+    };
+
+    json!({
+        "source": source,
+        "non_recursive": config.non_recursive,
+        "start_after": config.start_after,
+        "stop_at": config.stop_at,
+    })
+}
+
+// returns None if the cache does not exist, is older than --list-cache-max-age, or was written
+// under the different listing conditions.
+pub fn open_fresh_cache(
+    cache_config: &SourceListCacheConfig,
+    header: &Value,
+) -> Result<Option<BufReader<File>>> {
+    let Ok(metadata) = std::fs::metadata(&cache_config.path) else {
+        return Ok(None);
+    };
+
+    let age = SystemTime::now()
+        .duration_since(metadata.modified()?)
+        .unwrap_or_default();
+    if cache_config.max_age < age {
+        return Ok(None);
+    }
+
+    let mut reader =
+        BufReader::new(File::open(&cache_config.path).context("std::fs::File::open() failed.")?);
+    let mut header_line = String::new();
+    reader.read_line(&mut header_line)?;
+    if serde_json::from_str::<Value>(&header_line).ok().as_ref() != Some(header) {
+        return Ok(None);
+    }
+
+    Ok(Some(reader))
+}
+
+pub async fn send_cached_objects(
+    reader: BufReader<File>,
+    sender: &Sender<S3syncObject>,
+) -> Result<u64> {
+    let mut count = 0;
+    for line in reader.lines() {
+        let object = entry_to_object(&line?)?;
+
+        // the listing is stopped by closing the receiver.
+        if sender
+            .send(S3syncObject::NotVersioning(object))
+            .await
+            .is_err()
+        {
+            break;
+        }
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+// The objects are written to a temporary file, and it replaces the cache only when the listing
+// has been completed, so that an incomplete listing is never reused.
+pub struct SourceListCacheWriter {
+    path: PathBuf,
+    writer: BufWriter<NamedTempFile>,
+    count: u64,
+}
+
+impl SourceListCacheWriter {
+    pub fn create(path: &Path, header: &Value) -> Result<Self> {
+        let dir = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        let temp_file =
+            NamedTempFile::new_in(dir).context("tempfile::NamedTempFile::new_in() failed.")?;
+
+        let mut writer = BufWriter::new(temp_file);
+        writeln!(writer, "{}", header)?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            writer,
+            count: 0,
+        })
+    }
+
+    pub fn write(&mut self, object: &S3syncObject) -> Result<()> {
+        let S3syncObject::NotVersioning(object) = object else {
+            return Err(anyhow!("only the latest objects can be cached."));
+        };
+
+        writeln!(self.writer, "{}", object_to_entry(object))?;
+        self.count += 1;
+
+        Ok(())
+    }
+
+    pub fn persist(self) -> Result<u64> {
+        let temp_file = self
+            .writer
+            .into_inner()
+            .context("std::io::BufWriter::into_inner() failed.")?;
+        temp_file
+            .persist(&self.path)
+            .context("tempfile::NamedTempFile::persist() failed.")?;
+
+        Ok(self.count)
+    }
+}
+
+fn object_to_entry(object: &Object) -> Value {
+    json!({
+        "key": object.key(),
+        "size": object.size(),
+        "last_modified": object
+            .last_modified()
+            .map(|last_modified| last_modified.fmt(DateTimeFormat::DateTime).unwrap()),
+        "e_tag": object.e_tag(),
+        "storage_class": object.storage_class().map(|storage_class| storage_class.as_str()),
+        "checksum_algorithm": object
+            .checksum_algorithm()
+            .iter()
+            .map(|algorithm| algorithm.as_str())
+            .collect::<Vec<&str>>(),
+    })
+}
+
+fn entry_to_object(line: &str) -> Result<Object> {
+    let entry: Value = serde_json::from_str(line).context("serde_json::from_str() failed.")?;
+
+    let last_modified = entry["last_modified"]
+        .as_str()
+        .map(|last_modified| DateTime::from_str(last_modified, DateTimeFormat::DateTime))
+        .transpose()?;
+    let checksum_algorithm = entry["checksum_algorithm"].as_array().map(|algorithms| {
+        algorithms
+            .iter()
+            .filter_map(|algorithm| algorithm.as_str().map(ChecksumAlgorithm::from))
+            .collect::<Vec<ChecksumAlgorithm>>()
+    });
+
+    Ok(Object::builder()
+        .set_key(entry["key"].as_str().map(String::from))
+        .set_size(entry["size"].as_i64())
+        .set_last_modified(last_modified)
+        .set_e_tag(entry["e_tag"].as_str().map(String::from))
+        .set_storage_class(
+            entry["storage_class"]
+                .as_str()
+                .map(ObjectStorageClass::from),
+        )
+        .set_checksum_algorithm(checksum_algorithm.filter(|algorithms| !algorithms.is_empty()))
+        .build())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::config::args::parse_from_args;
+
+    use super::*;
+
+    #[test]
+    fn entry_round_trip() {
+        init_dummy_tracing_subscriber();
+
+        let object = Object::builder()
+            .key("dir1/data1")
+            .size(6)
+            .last_modified(
+                DateTime::from_str("2023-01-20T00:00:00.002Z", DateTimeFormat::DateTime).unwrap(),
+            )
+            .e_tag("\"d41d8cd98f00b204e9800998ecf8427e\"")
+            .storage_class(ObjectStorageClass::Glacier)
+            .checksum_algorithm(ChecksumAlgorithm::Sha256)
+            .build();
+
+        let restored = entry_to_object(&object_to_entry(&object).to_string()).unwrap();
+        assert_eq!(restored, object);
+
+        let object = Object::builder().key("data1").size(0).build();
+        let restored = entry_to_object(&object_to_entry(&object).to_string()).unwrap();
+        assert_eq!(restored, object);
+    }
+
+    #[tokio::test]
+    async fn write_and_read_cache() {
+        init_dummy_tracing_subscriber();
+
+        let dir = tempfile::tempdir().unwrap();
+        let cache_config = SourceListCacheConfig {
+            path: dir.path().join("source_list.cache"),
+            max_age: Duration::from_secs(3600),
+        };
+        let config = build_config();
+        let header = build_header(&config);
+
+        assert!(open_fresh_cache(&cache_config, &header).unwrap().is_none());
+
+        let mut writer = SourceListCacheWriter::create(&cache_config.path, &header).unwrap();
+        for key in ["data1", "dir1/data2"] {
+            writer
+                .write(&S3syncObject::NotVersioning(
+                    Object::builder().key(key).size(1).build(),
+                ))
+                .unwrap();
+        }
+        // not persisted yet.
+        assert!(open_fresh_cache(&cache_config, &header).unwrap().is_none());
+        assert_eq!(writer.persist().unwrap(), 2);
+
+        let reader = open_fresh_cache(&cache_config, &header).unwrap().unwrap();
+        let (sender, receiver) = async_channel::unbounded();
+        assert_eq!(send_cached_objects(reader, &sender).await.unwrap(), 2);
+        assert_eq!(receiver.recv().await.unwrap().key(), "data1");
+        assert_eq!(receiver.recv().await.unwrap().key(), "dir1/data2");
+
+        // the listing conditions are different.
+        let mut other_config = build_config();
+        other_config.non_recursive = true;
+        assert!(
+            open_fresh_cache(&cache_config, &build_header(&other_config))
+                .unwrap()
+                .is_none()
+        );
+
+        // expired.
+        let expired_cache_config = SourceListCacheConfig {
+            path: cache_config.path.clone(),
+            max_age: Duration::ZERO,
+        };
+        std::thread::sleep(Duration::from_millis(10));
+        assert!(open_fresh_cache(&expired_cache_config, &header)
+            .unwrap()
+            .is_none());
+    }
+
+    fn build_config() -> Config {
+        let args = vec![
+            "s3sync",
+            "--source-profile",
+            "source_profile",
+            "s3://source-bucket/prefix/",
+            "./test_data/target/",
+        ];
+
+        Config::try_from(parse_from_args(args).unwrap()).unwrap()
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
@@ -0,0 +1,174 @@
+use anyhow::{anyhow, Result};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tracing::{debug, trace, warn};
+
+use crate::storage::e_tag_verify::{is_multipart_upload_e_tag, normalize_e_tag};
+use crate::types::SyncStatistics::{ETagVerified, SyncBytes, SyncComplete, SyncWarning};
+
+use super::stage::Stage;
+
+pub struct StdoutWriter {
+    base: Stage,
+}
+
+impl StdoutWriter {
+    pub fn new(base: Stage) -> Self {
+        Self { base }
+    }
+
+    // Streams the object body to the writer. The source must resolve to a single object.
+    // The ETag is verified if it is an MD5 digest, but the written data cannot be rolled back.
+    pub async fn write<W: AsyncWrite + Unpin>(&self, key: &str, writer: &mut W) -> Result<()> {
+        trace!(key = key, "write object to stdout process started.");
+
+        let source = self.base.source.as_ref().unwrap();
+        let mut get_object_output = source
+            .get_object(
+                key,
+                None,
+                None,
+                self.base.config.source_sse_c.clone(),
+                self.base.config.source_sse_c_key.clone(),
+                self.base.config.source_sse_c_key_md5.clone(),
+            )
+            .await?;
+
+        let content_length = get_object_output.content_length().unwrap_or_default() as u64;
+        let e_tag = normalize_e_tag(&get_object_output.e_tag().map(|e_tag| e_tag.to_string()));
+        let verify_e_tag = !self.base.config.disable_etag_verify
+            && e_tag.is_some()
+            && !is_multipart_upload_e_tag(&e_tag)
+            && get_object_output.server_side_encryption().is_none()
+            && get_object_output.sse_customer_algorithm().is_none();
+
+        let mut md5_context = md5::Context::new();
+        let mut written_length: u64 = 0;
+        while let Some(bytes) = get_object_output.body.try_next().await? {
+            if self.base.cancellation_token.is_cancelled() {
+                warn!(key = key, "write object to stdout cancelled.");
+                return Ok(());
+            }
+
+            writer.write_all(&bytes).await?;
+            if verify_e_tag {
+                md5_context.consume(&bytes);
+            }
+            written_length += bytes.len() as u64;
+        }
+        writer.flush().await?;
+
+        self.base.send_stats(SyncBytes(written_length)).await;
+
+        if written_length != content_length {
+            self.base
+                .send_stats(SyncWarning {
+                    key: key.to_string(),
+                })
+                .await;
+
+            return Err(anyhow!(
+                "content length mismatch. key={key}, content_length={content_length}, written_length={written_length}."
+            ));
+        }
+
+        if verify_e_tag {
+            let md5_digest = format!("{:x}", md5_context.compute());
+            if Some(&md5_digest) != e_tag.as_ref() {
+                self.base
+                    .send_stats(SyncWarning {
+                        key: key.to_string(),
+                    })
+                    .await;
+                warn!(
+                    key = key,
+                    source_e_tag = e_tag,
+                    md5_digest = md5_digest,
+                    "ETag mismatch. the data written to stdout may be corrupted."
+                );
+            } else {
+                self.base
+                    .send_stats(ETagVerified {
+                        key: key.to_string(),
+                    })
+                    .await;
+                debug!(key = key, "ETag verified.");
+            }
+        }
+
+        self.base
+            .send_stats(SyncComplete {
+                key: key.to_string(),
+            })
+            .await;
+
+        trace!(key = key, "write object to stdout process completed.");
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::args::parse_from_args;
+    use crate::pipeline::storage_factory::{create_storage_limiters, create_storage_pair};
+    use crate::storage::StoragePair;
+    use crate::types::token::create_pipeline_cancellation_token;
+    use crate::types::SyncStatistics;
+    use crate::Config;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn write_object() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--allow-both-local-storage",
+            "./test_data/source/dir1/",
+            "./test_data/target/dir1/",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+        let cancellation_token = create_pipeline_cancellation_token();
+        let (stats_sender, stats_receiver) = async_channel::unbounded();
+
+        let StoragePair { source, target } = create_storage_pair(
+            config.clone(),
+            cancellation_token.clone(),
+            stats_sender,
+            create_storage_limiters(&config),
+        )
+        .await;
+
+        let stage = Stage::new(
+            config,
+            Some(source),
+            Some(target),
+            None,
+            None,
+            cancellation_token,
+        );
+
+        let mut buffer = Vec::new();
+        StdoutWriter::new(stage)
+            .write("6byte.dat", &mut buffer)
+            .await
+            .unwrap();
+
+        assert_eq!(buffer, b"123456");
+        assert!(matches!(
+            stats_receiver.recv().await.unwrap(),
+            SyncStatistics::SyncBytes(6)
+        ));
+        assert!(matches!(
+            stats_receiver.recv().await.unwrap(),
+            SyncStatistics::SyncComplete { .. }
+        ));
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
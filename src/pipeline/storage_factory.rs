@@ -1,12 +1,17 @@
 use std::sync::Arc;
 
+use anyhow::Result;
 use async_channel::Sender;
+use aws_sdk_s3::types::ChecksumAlgorithm;
 use leaky_bucket::RateLimiter;
+use tokio::sync::Semaphore;
+use tracing::{debug, info, warn};
 
 use crate::config::ClientConfig;
 use crate::storage::local::LocalStorageFactory;
+use crate::storage::request_rate_limiter::RequestRateLimiter;
 use crate::storage::s3::S3StorageFactory;
-use crate::storage::{Storage, StorageFactory, StoragePair};
+use crate::storage::{Storage, StorageFactory, StorageLimiters, StoragePair};
 use crate::types::token::PipelineCancellationToken;
 use crate::types::{StoragePath, SyncStatistics};
 use crate::Config;
@@ -14,11 +19,53 @@ use crate::Config;
 // default refill interval 100ms
 const REFILL_PER_INTERVAL_DIVIDER: usize = 10;
 
+// the strongest algorithm first.
+const NEGOTIATION_CHECKSUM_ALGORITHMS: [ChecksumAlgorithm; 5] = [
+    ChecksumAlgorithm::Sha256,
+    ChecksumAlgorithm::Sha1,
+    ChecksumAlgorithm::Crc64Nvme,
+    ChecksumAlgorithm::Crc32C,
+    ChecksumAlgorithm::Crc32,
+];
+const CHECKSUM_PROBE_KEY_SUFFIX: &str = ".s3sync-checksum-probe";
+
 pub async fn create_storage_pair(
     config: Config,
     cancellation_token: PipelineCancellationToken,
     stats_sender: Sender<SyncStatistics>,
+    limiters: StorageLimiters,
 ) -> StoragePair {
+    let source_client_config = if config.source_region_auto_detect {
+        detect_source_region(&config).await
+    } else {
+        config.source_client_config.clone()
+    };
+
+    let source = create_storage(
+        config.clone(),
+        source_client_config,
+        config.source.clone(),
+        cancellation_token.clone(),
+        stats_sender.clone(),
+        limiters.clone(),
+    )
+    .await;
+
+    let target = create_storage(
+        config.clone(),
+        config.target_client_config,
+        config.target,
+        cancellation_token,
+        stats_sender.clone(),
+        limiters,
+    )
+    .await;
+
+    StoragePair { source, target }
+}
+
+// the limiters are shared by the source and the target, and by all the workers of the pipeline.
+pub fn create_storage_limiters(config: &Config) -> StorageLimiters {
     let rate_limit_objects_per_sec = if config.rate_limit_objects.is_some() {
         let rate_limit_value = config.rate_limit_objects.unwrap();
         let refill = if rate_limit_value <= REFILL_PER_INTERVAL_DIVIDER as u32 {
@@ -54,29 +101,89 @@ pub async fn create_storage_pair(
         None
     };
 
-    let source = create_storage(
-        config.clone(),
-        config.source_client_config.clone(),
-        config.source.clone(),
-        cancellation_token.clone(),
-        stats_sender.clone(),
-        rate_limit_objects_per_sec.clone(),
-        rate_limit_bandwidth.clone(),
-    )
-    .await;
+    StorageLimiters {
+        rate_limit_objects_per_sec,
+        rate_limit_bandwidth,
+        checksum_semaphore: config
+            .checksum_parallelism
+            .map(|parallelism| Arc::new(Semaphore::new(parallelism as usize))),
+        source_request_rate_limiter: config
+            .source_request_rate_limit
+            .map(|limit| Arc::new(RequestRateLimiter::new(limit))),
+        target_request_rate_limiter: config
+            .target_request_rate_limit
+            .map(|limit| Arc::new(RequestRateLimiter::new(limit))),
+    }
+}
 
-    let target = create_storage(
-        config.clone(),
-        config.target_client_config,
-        config.target,
-        cancellation_token,
-        stats_sender.clone(),
-        rate_limit_objects_per_sec.clone(),
-        rate_limit_bandwidth.clone(),
-    )
-    .await;
+// the detected region is used for all requests to the source, so that the detection is done only once.
+async fn detect_source_region(config: &Config) -> Option<ClientConfig> {
+    let mut client_config = config.source_client_config.clone()?;
+    let StoragePath::S3 { bucket, .. } = &config.source else {
+        return Some(client_config);
+    };
 
-    StoragePair { source, target }
+    if let Some(region) = client_config.detect_bucket_region(bucket).await {
+        info!(
+            bucket = bucket,
+            region = region,
+            "the source region has been detected."
+        );
+        client_config.region = Some(region);
+    } else {
+        warn!(
+            bucket = bucket,
+            "failed to detect the source region. the default region is used."
+        );
+    }
+
+    Some(client_config)
+}
+
+// the probe is done only once, and the result is used for all uploads.
+// an error that is not a matter of the algorithm(e.g. AccessDenied) is returned, not regarded as unsupported.
+pub async fn negotiate_additional_checksum_algorithm(
+    config: &Config,
+) -> Result<Option<ChecksumAlgorithm>> {
+    let Some(client_config) = config.target_client_config.as_ref() else {
+        return Ok(None);
+    };
+    let StoragePath::S3 { bucket, prefix } = &config.target else {
+        return Ok(None);
+    };
+
+    if config.dry_run {
+        info!("additional checksum algorithm negotiation is skipped in dry-run mode.");
+        return Ok(None);
+    }
+
+    let probe_key = format!("{}{}", prefix, CHECKSUM_PROBE_KEY_SUFFIX);
+    for checksum_algorithm in NEGOTIATION_CHECKSUM_ALGORITHMS {
+        if client_config
+            .probe_checksum_algorithm(bucket, &probe_key, &checksum_algorithm)
+            .await?
+        {
+            info!(
+                bucket = bucket,
+                additional_checksum_algorithm = checksum_algorithm.as_str(),
+                "the additional checksum algorithm has been negotiated."
+            );
+            return Ok(Some(checksum_algorithm));
+        }
+
+        debug!(
+            bucket = bucket,
+            additional_checksum_algorithm = checksum_algorithm.as_str(),
+            "the additional checksum algorithm is not supported by the target."
+        );
+    }
+
+    warn!(
+        bucket = bucket,
+        "no additional checksum algorithm is supported by the target. only ETag is used."
+    );
+
+    Ok(None)
 }
 
 async fn create_storage(
@@ -85,8 +192,7 @@ async fn create_storage(
     storage_path: StoragePath,
     cancellation_token: PipelineCancellationToken,
     stats_sender: Sender<SyncStatistics>,
-    rate_limit_objects_per_sec: Option<Arc<RateLimiter>>,
-    rate_limit_bandwidth: Option<Arc<RateLimiter>>,
+    limiters: StorageLimiters,
 ) -> Storage {
     let factory_fn = match storage_path {
         StoragePath::S3 { .. } => S3StorageFactory::create,
@@ -99,8 +205,7 @@ async fn create_storage(
         cancellation_token,
         stats_sender,
         client_config,
-        rate_limit_objects_per_sec,
-        rate_limit_bandwidth,
+        limiters,
     )
     .await
 }
@@ -132,7 +237,13 @@ mod tests {
         let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
         let (stats_sender, _) = async_channel::unbounded();
 
-        create_storage_pair(config, create_pipeline_cancellation_token(), stats_sender).await;
+        create_storage_pair(
+            config.clone(),
+            create_pipeline_cancellation_token(),
+            stats_sender,
+            create_storage_limiters(&config),
+        )
+        .await;
     }
 
     #[tokio::test]
@@ -151,8 +262,13 @@ mod tests {
         let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
         let (stats_sender, _) = async_channel::unbounded();
 
-        let storage_pair =
-            create_storage_pair(config, create_pipeline_cancellation_token(), stats_sender).await;
+        let storage_pair = create_storage_pair(
+            config.clone(),
+            create_pipeline_cancellation_token(),
+            stats_sender,
+            create_storage_limiters(&config),
+        )
+        .await;
 
         assert!(storage_pair.source.get_client().is_none());
     }
@@ -173,8 +289,13 @@ mod tests {
         let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
         let (stats_sender, _) = async_channel::unbounded();
 
-        let storage_pair =
-            create_storage_pair(config, create_pipeline_cancellation_token(), stats_sender).await;
+        let storage_pair = create_storage_pair(
+            config.clone(),
+            create_pipeline_cancellation_token(),
+            stats_sender,
+            create_storage_limiters(&config),
+        )
+        .await;
 
         assert!(storage_pair.target.get_client().is_none());
     }
@@ -199,12 +320,80 @@ mod tests {
         let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
         let (stats_sender, _) = async_channel::unbounded();
 
-        let storage_pair =
-            create_storage_pair(config, create_pipeline_cancellation_token(), stats_sender).await;
+        let storage_pair = create_storage_pair(
+            config.clone(),
+            create_pipeline_cancellation_token(),
+            stats_sender,
+            create_storage_limiters(&config),
+        )
+        .await;
 
         assert!(storage_pair.target.get_client().is_none());
     }
 
+    #[test]
+    fn create_storage_limiters_from_config() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-access-key",
+            "source_access_key",
+            "--source-secret-access-key",
+            "source_secret_access_key",
+            "--target-access-key",
+            "target_access_key",
+            "--target-secret-access-key",
+            "target_secret_access_key",
+            "--checksum-parallelism",
+            "2",
+            "--source-request-rate-limit",
+            "5",
+            "s3://source-bucket",
+            "s3://target-bucket",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+
+        let limiters = create_storage_limiters(&config);
+
+        assert!(limiters.rate_limit_objects_per_sec.is_none());
+        assert!(limiters.rate_limit_bandwidth.is_none());
+        assert_eq!(limiters.checksum_semaphore.unwrap().available_permits(), 2);
+        assert_eq!(
+            limiters
+                .source_request_rate_limiter
+                .unwrap()
+                .get_requests_per_sec(),
+            5
+        );
+        assert!(limiters.target_request_rate_limiter.is_none());
+    }
+
+    #[tokio::test]
+    async fn negotiate_additional_checksum_algorithm_dry_run() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--target-access-key",
+            "target_access_key",
+            "--target-secret-access-key",
+            "target_secret_access_key",
+            "--additional-checksum-algorithm",
+            "negotiate",
+            "--dry-run",
+            "./test_data/source",
+            "s3://target-bucket",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+
+        assert!(config.negotiate_additional_checksum_algorithm);
+        assert!(negotiate_additional_checksum_algorithm(&config)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
     fn init_dummy_tracing_subscriber() {
         let _ = tracing_subscriber::fmt()
             .with_env_filter("dummy=trace")
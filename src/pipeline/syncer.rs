@@ -1,5 +1,8 @@
 use std::collections::HashMap;
 use std::ops::Add;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
 
 use anyhow::{anyhow, Context, Error, Result};
 use aws_sdk_s3::operation::delete_object::{DeleteObjectError, DeleteObjectOutput};
@@ -11,30 +14,106 @@ use aws_sdk_s3::operation::head_object::HeadObjectError;
 use aws_sdk_s3::operation::list_object_versions::ListObjectVersionsError;
 use aws_sdk_s3::operation::put_object::{PutObjectError, PutObjectOutput};
 use aws_sdk_s3::operation::put_object_tagging::PutObjectTaggingError;
+use aws_sdk_s3::primitives::DateTime;
 use aws_sdk_s3::types::{ChecksumAlgorithm, ChecksumMode, ChecksumType, ObjectPart, Tag, Tagging};
 use aws_smithy_runtime_api::client::result::SdkError;
 use aws_smithy_runtime_api::http::Response;
 use aws_smithy_types::body::SdkBody;
-use tracing::{error, info, trace, warn};
+use tokio::sync::Semaphore;
+use tracing::{debug, error, error_span, info, trace, warn, Instrument};
 
+use crate::pipeline::concurrency_controller::acquire_concurrency_permit;
+use crate::pipeline::delete_log::{append_delete_log, DeleteLog};
 use crate::pipeline::head_object_checker::HeadObjectChecker;
+use crate::pipeline::scheduler::PrefixConcurrencyLimiter;
 use crate::pipeline::versioning_info_collector::VersioningInfoCollector;
 use crate::storage::e_tag_verify;
 use crate::types;
 use crate::types::error::S3syncError;
-use crate::types::SyncStatistics::{SyncComplete, SyncDelete, SyncError, SyncSkip, SyncWarning};
-use crate::types::{ObjectChecksum, S3syncObject, SseCustomerKey};
+use crate::types::token::PipelineCancellationToken;
+use crate::types::SyncStatistics::{
+    RedownloadHealed, RedownloadStillCorrupted, RetryExhausted, SyncComplete, SyncDelete,
+    SyncError, SyncSkip, SyncWarning, TransferredObjectSize,
+};
+use crate::types::{ObjectChecksum, ObjectKeyMap, S3syncObject, SseCustomerKey, TagGuardMode};
 
 use super::stage::Stage;
 
+// correlation id to associate all logs of an object sync.
+static SYNC_CORRELATION_ID: AtomicU64 = AtomicU64::new(1);
+
 pub struct ObjectSyncer {
     worker_index: u16,
     base: Stage,
+    drain_token: PipelineCancellationToken,
+    drained_object_count: Arc<AtomicU64>,
+    skipped_error_count: Arc<AtomicU64>,
+    retry_elapsed_milliseconds: Arc<AtomicU64>,
+    transferred_object_count: Arc<AtomicU64>,
+    transferred_bytes: Arc<AtomicU64>,
+    prefix_concurrency_limiter: Option<Arc<PrefixConcurrencyLimiter>>,
+    concurrency_gate: Option<Arc<Semaphore>>,
+    delete_log: Option<Arc<DeleteLog>>,
+    target_key_map: Option<ObjectKeyMap>,
+    checksum_semaphore: Option<Arc<Semaphore>>,
+    source_concurrency_semaphore: Option<Arc<Semaphore>>,
+    target_concurrency_semaphore: Option<Arc<Semaphore>>,
+}
+
+/// State shared by all sync workers of a pipeline.
+/// The counters are aggregated by the pipeline after the workers finish.
+#[derive(Clone, Default)]
+pub struct SyncerSharedState {
+    pub drain_token: PipelineCancellationToken,
+    pub drained_object_count: Arc<AtomicU64>,
+    pub skipped_error_count: Arc<AtomicU64>,
+    pub retry_elapsed_milliseconds: Arc<AtomicU64>,
+    pub transferred_object_count: Arc<AtomicU64>,
+    pub transferred_bytes: Arc<AtomicU64>,
+    pub prefix_concurrency_limiter: Option<Arc<PrefixConcurrencyLimiter>>,
+    pub concurrency_gate: Option<Arc<Semaphore>>,
+    pub delete_log: Option<Arc<DeleteLog>>,
+    pub target_key_map: Option<ObjectKeyMap>,
+    pub checksum_semaphore: Option<Arc<Semaphore>>,
+    pub source_concurrency_semaphore: Option<Arc<Semaphore>>,
+    pub target_concurrency_semaphore: Option<Arc<Semaphore>>,
 }
 
 impl ObjectSyncer {
-    pub fn new(base: Stage, worker_index: u16) -> Self {
-        Self { worker_index, base }
+    pub fn new(base: Stage, worker_index: u16, shared_state: SyncerSharedState) -> Self {
+        let SyncerSharedState {
+            drain_token,
+            drained_object_count,
+            skipped_error_count,
+            retry_elapsed_milliseconds,
+            transferred_object_count,
+            transferred_bytes,
+            prefix_concurrency_limiter,
+            concurrency_gate,
+            delete_log,
+            target_key_map,
+            checksum_semaphore,
+            source_concurrency_semaphore,
+            target_concurrency_semaphore,
+        } = shared_state;
+
+        Self {
+            worker_index,
+            base,
+            drain_token,
+            drained_object_count,
+            skipped_error_count,
+            retry_elapsed_milliseconds,
+            transferred_object_count,
+            transferred_bytes,
+            prefix_concurrency_limiter,
+            concurrency_gate,
+            delete_log,
+            target_key_map,
+            checksum_semaphore,
+            source_concurrency_semaphore,
+            target_concurrency_semaphore,
+        }
     }
 
     pub async fn sync(&self) -> Result<()> {
@@ -45,14 +124,47 @@ impl ObjectSyncer {
     async fn receive_and_sync(&self) -> Result<()> {
         loop {
             tokio::select! {
+                // cancellation and drain must take precedence over receiving a new object.
+                biased;
+
+                _ = self.base.cancellation_token.cancelled() => {
+                    info!(worker_index = self.worker_index, "sync worker has been cancelled.");
+                    return Ok(());
+                }
+                _ = self.drain_token.cancelled() => {
+                    info!(worker_index = self.worker_index, "sync worker has been drained.");
+                    return Ok(());
+                }
                 recv_result = self.base.receiver.as_ref().unwrap().recv() => {
                     match recv_result {
                         Ok(object) => {
-                            if self.sync_object_with_force_retry(object).await.is_err() {
+                            let key = object.key().to_string();
+
+                            // error level span is always enabled, so every log of this object carries these fields.
+                            let span = error_span!(
+                                "sync_object",
+                                worker_index = self.worker_index,
+                                key = object.key(),
+                                correlation_id = SYNC_CORRELATION_ID.fetch_add(1, Ordering::Relaxed),
+                            );
+                            // with --concurrency-auto, the number of workers syncing objects at the same time is limited.
+                            let concurrency_permit = acquire_concurrency_permit(&self.concurrency_gate).await;
+                            let result = self.sync_object_with_force_retry(object).instrument(span).await;
+                            drop(concurrency_permit);
+                            if let Some(limiter) = &self.prefix_concurrency_limiter {
+                                limiter.release(&key);
+                            }
+
+                            if result.is_err() {
                                 self.base.cancellation_token.cancel();
                                 error!(worker_index = self.worker_index, "sync worker has been cancelled with error.");
                                 return Err(anyhow!("sync worker has been cancelled with error."));
                             }
+
+                            // objects which were in flight when the graceful shutdown started.
+                            if self.drain_token.is_cancelled() && !self.base.cancellation_token.is_cancelled() {
+                                self.drained_object_count.fetch_add(1, Ordering::SeqCst);
+                            }
                         },
                         Err(_) => {
                             // normal shutdown
@@ -61,10 +173,6 @@ impl ObjectSyncer {
                         }
                     }
                 },
-                _ = self.base.cancellation_token.cancelled() => {
-                    info!(worker_index = self.worker_index, "sync worker has been cancelled.");
-                    return Ok(());
-                }
             }
         }
 
@@ -74,6 +182,10 @@ impl ObjectSyncer {
     async fn sync_object_with_force_retry(&self, object: S3syncObject) -> Result<()> {
         let key = object.key();
 
+        // the start of the current force retry(sleep and re-sync), to be charged to --retry-budget.
+        let mut retry_start_time: Option<Instant> = None;
+        let mut retry_count: u32 = 0;
+
         for _ in 0..=self.base.config.force_retry_config.force_retry_count {
             let result = if self.base.config.enable_versioning {
                 self.sync_object_versions(object.clone()).await
@@ -81,6 +193,11 @@ impl ObjectSyncer {
                 self.sync_object(object.clone()).await
             };
 
+            if let Some(start_time) = retry_start_time.take() {
+                self.retry_elapsed_milliseconds
+                    .fetch_add(start_time.elapsed().as_millis() as u64, Ordering::SeqCst);
+            }
+
             return if result.is_ok() {
                 Ok(())
             } else {
@@ -97,7 +214,14 @@ impl ObjectSyncer {
                 let e = result.unwrap_err();
                 let error = e.to_string();
 
-                if is_force_retryable_error(&e) {
+                if is_force_retryable_error(&e) && !self.is_retry_budget_exhausted() {
+                    if self.is_max_retries_per_object_reached(retry_count) {
+                        return self.give_up_object(key, e).await;
+                    }
+                    retry_count += 1;
+
+                    retry_start_time = Some(Instant::now());
+
                     self.base
                         .send_stats(SyncWarning {
                             key: key.to_string(),
@@ -111,13 +235,8 @@ impl ObjectSyncer {
                         "force retryable error has occurred."
                     );
 
-                    tokio::time::sleep(std::time::Duration::from_millis(
-                        self.base
-                            .config
-                            .force_retry_config
-                            .force_retry_interval_milliseconds,
-                    ))
-                    .await;
+                    tokio::time::sleep(self.base.config.force_retry_config.force_retry_interval())
+                        .await;
 
                     continue;
                 }
@@ -136,13 +255,30 @@ impl ObjectSyncer {
                         "object not found. skipping."
                     );
 
-                    if self.base.config.warn_as_error {
+                    if self.base.config.warn_as_error || self.is_fail_fast_threshold_reached() {
                         return Err(e);
                     }
 
                     return Ok(());
                 }
 
+                if is_access_denied_error(&e) && self.is_expected_bucket_owner_specified() {
+                    self.base
+                        .send_stats(SyncError {
+                            key: key.to_string(),
+                        })
+                        .await;
+                    error!(
+                        worker_index = self.worker_index,
+                        key = key,
+                        error = error,
+                        source = e.source(),
+                        "access denied. the bucket may not be owned by the expected bucket owner."
+                    );
+
+                    return Err(e);
+                }
+
                 if is_access_denied_error(&e) {
                     self.base
                         .send_stats(SyncWarning {
@@ -157,7 +293,7 @@ impl ObjectSyncer {
                         "access denied. skipping."
                     );
 
-                    if self.base.config.warn_as_error {
+                    if self.base.config.warn_as_error || self.is_fail_fast_threshold_reached() {
                         return Err(e);
                     }
 
@@ -195,6 +331,91 @@ impl ObjectSyncer {
         Err(anyhow!("force retry count exceeded. key={}.", key,))
     }
 
+    fn is_max_retries_per_object_reached(&self, retry_count: u32) -> bool {
+        self.base
+            .config
+            .force_retry_config
+            .max_retries_per_object
+            .is_some_and(|max_retries_per_object| max_retries_per_object <= retry_count)
+    }
+
+    // marks the object as failed without cancelling the pipeline, unless --fail-fast-after is reached.
+    async fn give_up_object(&self, key: &str, e: Error) -> Result<()> {
+        self.base
+            .send_stats(SyncError {
+                key: key.to_string(),
+            })
+            .await;
+        self.base
+            .send_stats(RetryExhausted {
+                key: key.to_string(),
+            })
+            .await;
+        error!(
+            worker_index = self.worker_index,
+            key = key,
+            error = e.to_string(),
+            source = e.source(),
+            "retry limit per object(--max-retries-per-object) has been exceeded. the object has been marked as failed."
+        );
+
+        if self.is_fail_fast_threshold_reached() {
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    fn is_expected_bucket_owner_specified(&self) -> bool {
+        let config = &self.base.config;
+        [&config.source_client_config, &config.target_client_config]
+            .iter()
+            .any(|client_config| {
+                client_config
+                    .as_ref()
+                    .is_some_and(|client_config| client_config.expected_bucket_owner.is_some())
+            })
+    }
+
+    // returns true if the time spent in force retries has reached --retry-budget.
+    fn is_retry_budget_exhausted(&self) -> bool {
+        let Some(retry_budget) = self.base.config.force_retry_config.retry_budget else {
+            return false;
+        };
+
+        let retry_elapsed_milliseconds = self.retry_elapsed_milliseconds.load(Ordering::SeqCst);
+        if retry_elapsed_milliseconds < retry_budget.as_millis() as u64 {
+            return false;
+        }
+
+        warn!(
+            worker_index = self.worker_index,
+            retry_elapsed_milliseconds = retry_elapsed_milliseconds,
+            "retry budget(--retry-budget) has been exhausted. force retry is disabled."
+        );
+
+        true
+    }
+
+    // counts a skipped per-key error, and returns true if the count has reached --fail-fast-after.
+    fn is_fail_fast_threshold_reached(&self) -> bool {
+        let skipped_error_count = self.skipped_error_count.fetch_add(1, Ordering::SeqCst) + 1;
+
+        let fail_fast_after = self.base.config.fail_fast_after;
+        if fail_fast_after == 0 || skipped_error_count < fail_fast_after {
+            return false;
+        }
+
+        error!(
+            worker_index = self.worker_index,
+            skipped_error_count = skipped_error_count,
+            fail_fast_after = fail_fast_after,
+            "the number of errors has reached --fail-fast-after. aborting."
+        );
+
+        true
+    }
+
     async fn sync_object(&self, object: S3syncObject) -> Result<()> {
         let key = object.key();
 
@@ -220,13 +441,26 @@ impl ObjectSyncer {
             dyn_clone::clone_box(&*(*self.base.source.as_ref().unwrap())),
             dyn_clone::clone_box(&*(*self.base.target.as_ref().unwrap())),
             self.worker_index,
+            self.target_key_map.clone(),
+            self.checksum_semaphore.clone(),
         );
 
         if head_object_checker.is_sync_required(&object).await? {
-            return self.sync_or_delete_object(object).await;
+            let if_modified_since = if self.base.config.source_if_modified_since {
+                head_object_checker.get_target_last_modified()
+            } else {
+                None
+            };
+
+            return self
+                .sync_or_delete_object_with_redownload(object, if_modified_since)
+                .await;
         }
 
-        if self.base.config.sync_latest_tagging && self.sync_tagging(key).await? {
+        if self.base.config.sync_latest_tagging
+            && !head_object_checker.is_target_newer_than_source(&object)
+            && self.sync_tagging(&object).await?
+        {
             self.base
                 .send_stats(SyncComplete {
                     key: key.to_string(),
@@ -245,6 +479,60 @@ impl ObjectSyncer {
         Ok(())
     }
 
+    // with --verify-only-on-mismatch-redownload, an object whose local file fails the verification
+    // is re-downloaded only once, so that a persistently corrupted object does not loop.
+    async fn sync_or_delete_object_with_redownload(
+        &self,
+        object: S3syncObject,
+        if_modified_since: Option<DateTime>,
+    ) -> Result<()> {
+        let result = self
+            .sync_or_delete_object(object.clone(), if_modified_since)
+            .await;
+
+        if !self.base.config.verify_only_on_mismatch_redownload
+            || !result
+                .as_ref()
+                .is_err_and(is_local_verification_mismatch_error)
+        {
+            return result;
+        }
+
+        let key = object.key().to_string();
+        warn!(
+            worker_index = self.worker_index,
+            key = key,
+            "local file verification failed. re-downloading the object."
+        );
+
+        // the mismatched download has been discarded. the condition of the first download is not used,
+        // so that the object is downloaded again regardless of the existing file.
+        let result = self.sync_or_delete_object(object, None).await;
+        match &result {
+            Ok(_) => {
+                info!(
+                    worker_index = self.worker_index,
+                    key = key,
+                    "the object has been healed by re-download."
+                );
+
+                self.base.send_stats(RedownloadHealed { key }).await;
+            }
+            Err(e) if is_local_verification_mismatch_error(e) => {
+                error!(
+                    worker_index = self.worker_index,
+                    key = key,
+                    "the object is still corrupted after re-download."
+                );
+
+                self.base.send_stats(RedownloadStillCorrupted { key }).await;
+            }
+            Err(_) => {}
+        }
+
+        result
+    }
+
     fn is_incompatible_object_with_local_storage(&self, object: &S3syncObject) -> bool {
         self.base.target.as_ref().unwrap().is_local_storage()
             && is_object_with_directory_name_suffix_and_none_zero_size(object)
@@ -257,22 +545,57 @@ impl ObjectSyncer {
             self.worker_index,
         );
 
-        let objects_to_sync = versioning_info_collector
+        let (objects_to_sync, removed_target_version_ids) = versioning_info_collector
             .collect_object_versions_to_sync(&object)
             .await?;
 
         for object in objects_to_sync {
-            self.sync_or_delete_object(object).await?;
+            self.sync_or_delete_object(object, None).await?;
+        }
+
+        for target_version_id in removed_target_version_ids {
+            self.delete_removed_version(object.key(), target_version_id)
+                .await?;
         }
 
         Ok(())
     }
 
-    async fn sync_or_delete_object(&self, object: S3syncObject) -> Result<()> {
+    async fn delete_removed_version(&self, key: &str, target_version_id: String) -> Result<()> {
+        info!(
+            worker_index = self.worker_index,
+            key = key,
+            target_version_id = target_version_id,
+            "version has been removed from the source storage. delete it."
+        );
+
+        self.base
+            .target
+            .as_ref()
+            .unwrap()
+            .delete_object(key, Some(target_version_id.clone()))
+            .await
+            .context("pipeline::syncer::delete_removed_version() failed.")?;
+        append_delete_log(&self.delete_log, key, Some(&target_version_id)).await?;
+
+        self.base
+            .send_stats(SyncDelete {
+                key: key.to_string(),
+            })
+            .await;
+
+        Ok(())
+    }
+
+    async fn sync_or_delete_object(
+        &self,
+        object: S3syncObject,
+        if_modified_since: Option<DateTime>,
+    ) -> Result<()> {
         let key = object.key();
 
         if object.is_delete_marker() {
-            self.delete_object(key).await?;
+            self.delete_object(&object).await?;
 
             self.base
                 .send_stats(SyncDelete {
@@ -283,8 +606,13 @@ impl ObjectSyncer {
             return Ok(());
         }
 
-        let get_object_output = self
-            .get_object(
+        // the source permit is held until the body has been read by put_object().
+        let _source_permit = acquire_concurrency_permit(&self.source_concurrency_semaphore).await;
+        let get_object_output = if let Some(if_modified_since) = if_modified_since {
+            self.get_object_if_modified_since(key, if_modified_since)
+                .await
+        } else {
+            self.get_object(
                 key,
                 object.version_id().map(|version_id| version_id.to_string()),
                 self.base.config.additional_checksum_mode.clone(),
@@ -292,7 +620,8 @@ impl ObjectSyncer {
                 self.base.config.source_sse_c_key.clone(),
                 self.base.config.source_sse_c_key_md5.clone(),
             )
-            .await;
+            .await
+        };
 
         if self.base.cancellation_token.is_cancelled() {
             info!(
@@ -304,6 +633,22 @@ impl ObjectSyncer {
             return Ok(());
         }
 
+        if get_object_output.as_ref().is_err_and(is_not_modified_error) {
+            self.base
+                .send_stats(SyncSkip {
+                    key: key.to_string(),
+                })
+                .await;
+
+            debug!(
+                worker_index = self.worker_index,
+                key = key,
+                "source object has not been modified since the target object. skipping."
+            );
+
+            return Ok(());
+        }
+
         match get_object_output {
             Ok(get_object_output) => {
                 let tagging = if self.base.config.disable_tagging {
@@ -313,13 +658,21 @@ impl ObjectSyncer {
                 } else {
                     let get_object_tagging_output =
                         self.get_object_tagging(key, &get_object_output).await?;
-                    if get_object_tagging_output.is_some() {
+                    if let Some(source_tagging) = &get_object_tagging_output {
                         trace!(
                             worker_index = self.worker_index,
                             key = key,
                             "tagging = {:?}.",
-                            get_object_tagging_output.as_ref().unwrap().tag_set()
+                            source_tagging.tag_set()
                         );
+
+                        if !self
+                            .check_object_tag_limits(key, source_tagging.tag_set())
+                            .await?
+                        {
+                            return Ok(());
+                        }
+
                         generate_tagging_string(&get_object_tagging_output)
                     } else {
                         None
@@ -330,8 +683,10 @@ impl ObjectSyncer {
                     .build_object_checksum(key, &get_object_output, object.checksum_algorithm())
                     .await?;
 
+                let _target_permit =
+                    acquire_concurrency_permit(&self.target_concurrency_semaphore).await;
                 let put_object_output = self
-                    .put_object(key, get_object_output, tagging, object_checksum)
+                    .put_object(&object, get_object_output, tagging, object_checksum)
                     .await;
                 if let Err(e) = put_object_output {
                     return self.handle_put_object_error(key, e).await;
@@ -347,24 +702,64 @@ impl ObjectSyncer {
                 key: key.to_string(),
             })
             .await;
+        if self.base.config.object_size_histogram {
+            self.base
+                .send_stats(TransferredObjectSize(object.size() as u64))
+                .await;
+        }
+
+        self.drain_if_quota_reached(object.size());
 
         self.base.send(object).await
     }
 
-    async fn sync_tagging(&self, key: &str) -> Result<bool> {
+    // in-flight objects are still completed, so the quota may be exceeded by them.
+    fn drain_if_quota_reached(&self, size: i64) {
+        let transferred_object_count =
+            self.transferred_object_count.fetch_add(1, Ordering::SeqCst) + 1;
+        let transferred_bytes = self
+            .transferred_bytes
+            .fetch_add(size as u64, Ordering::SeqCst)
+            + size as u64;
+
+        let max_objects_reached = self
+            .base
+            .config
+            .max_objects
+            .is_some_and(|max_objects| max_objects <= transferred_object_count);
+        let max_bytes_reached = self
+            .base
+            .config
+            .max_bytes
+            .is_some_and(|max_bytes| max_bytes <= transferred_bytes);
+
+        if (max_objects_reached || max_bytes_reached) && !self.drain_token.is_cancelled() {
+            info!(
+                worker_index = self.worker_index,
+                transferred_objects = transferred_object_count,
+                transferred_bytes = transferred_bytes,
+                "quota has been reached. stop starting new objects."
+            );
+
+            self.drain_token.cancel();
+        }
+    }
+
+    async fn sync_tagging(&self, object: &S3syncObject) -> Result<bool> {
         let source_tagging = self
             .base
             .source
             .as_ref()
             .unwrap()
-            .get_object_tagging(key, None)
+            .get_object_tagging(object.key(), None)
             .await?;
+        let target_key = self.target_key(object);
         let target_tagging = self
             .base
             .target
             .as_ref()
             .unwrap()
-            .get_object_tagging(key, None)
+            .get_object_tagging(&target_key, None)
             .await?;
 
         let source_tagging_map = tag_set_to_map(source_tagging.tag_set());
@@ -373,24 +768,31 @@ impl ObjectSyncer {
         if source_tagging_map != target_tagging_map {
             trace!(
                 worker_index = self.worker_index,
-                key = key,
+                key = object.key(),
                 "new tagging = {:?}.",
                 source_tagging.tag_set()
             );
 
+            if !self
+                .check_object_tag_limits(object.key(), source_tagging.tag_set())
+                .await?
+            {
+                return Ok(false);
+            }
+
             if source_tagging.tag_set().as_ref().is_empty() {
                 self.base
                     .target
                     .as_ref()
                     .unwrap()
-                    .delete_object_tagging(key, None)
+                    .delete_object_tagging(&target_key, None)
                     .await?;
             } else {
                 self.base
                     .target
                     .as_ref()
                     .unwrap()
-                    .put_object_tagging(key, None, build_tagging(source_tagging.tag_set()))
+                    .put_object_tagging(&target_key, None, build_tagging(source_tagging.tag_set()))
                     .await?;
             }
 
@@ -400,6 +802,59 @@ impl ObjectSyncer {
         Ok(false)
     }
 
+    // --object-tag-count-guard: the source tags may come from a storage with looser limits than S3.
+    // returns false if the object should be skipped.
+    async fn check_object_tag_limits(&self, key: &str, tag_set: &[Tag]) -> Result<bool> {
+        let Some(tag_guard_mode) = self.base.config.object_tag_count_guard else {
+            return Ok(true);
+        };
+
+        let Err(message) =
+            types::check_tag_limits(tag_set.iter().map(|tag| (tag.key(), tag.value())))
+        else {
+            return Ok(true);
+        };
+
+        // the error is counted by sync_object_with_force_retry().
+        if tag_guard_mode == TagGuardMode::Error {
+            error!(
+                worker_index = self.worker_index,
+                key = key,
+                message = message,
+                "the tags exceed the S3 limits."
+            );
+
+            return Err(anyhow!(
+                "the tags of {} exceed the S3 limits: {}",
+                key,
+                message
+            ));
+        }
+
+        self.base
+            .send_stats(SyncWarning {
+                key: key.to_string(),
+            })
+            .await;
+
+        warn!(
+            worker_index = self.worker_index,
+            key = key,
+            message = message,
+            "the tags exceed the S3 limits. skipping."
+        );
+
+        if self.base.config.warn_as_error {
+            return Err(anyhow!(
+                "the tags of {} exceed the S3 limits: {}",
+                key,
+                message
+            ));
+        }
+
+        Ok(false)
+    }
+
     async fn handle_put_object_error(&self, key: &str, e: Error) -> Result<()> {
         self.base
             .send_stats(SyncWarning {
@@ -431,6 +886,20 @@ impl ObjectSyncer {
             return Ok(());
         }
 
+        if is_path_conflict_error(&e) {
+            warn!(
+                worker_index = self.worker_index,
+                key = key,
+                "object path conflicts with an existing path. skipping."
+            );
+
+            if self.base.config.warn_as_error {
+                return Err(e);
+            }
+
+            return Ok(());
+        }
+
         warn!(
             worker_index = self.worker_index,
             key = key,
@@ -467,9 +936,35 @@ impl ObjectSyncer {
             .context("pipeline::syncer::get_object() failed.")
     }
 
-    async fn put_object(
+    async fn get_object_if_modified_since(
         &self,
         key: &str,
+        if_modified_since: DateTime,
+    ) -> Result<GetObjectOutput> {
+        self.base
+            .source
+            .as_ref()
+            .unwrap()
+            .get_object_if_modified_since(
+                key,
+                if_modified_since,
+                self.base.config.additional_checksum_mode.clone(),
+                self.base.config.source_sse_c.clone(),
+                self.base.config.source_sse_c_key.clone(),
+                self.base.config.source_sse_c_key_md5.clone(),
+            )
+            .await
+            .context("pipeline::syncer::get_object_if_modified_since() failed.")
+    }
+
+    // with --target-object-key-case and --date-partition, the key of the target object is converted from the source key.
+    fn target_key(&self, object: &S3syncObject) -> String {
+        types::convert_object_to_target_key(object, &self.base.config.filter_config)
+    }
+
+    async fn put_object(
+        &self,
+        object: &S3syncObject,
         get_object_output: GetObjectOutput,
         tagging: Option<String>,
         object_checksum: Option<ObjectChecksum>,
@@ -478,19 +973,29 @@ impl ObjectSyncer {
             .target
             .as_ref()
             .unwrap()
-            .put_object(key, get_object_output, tagging, object_checksum)
+            .put_object(
+                &self.target_key(object),
+                get_object_output,
+                tagging,
+                object_checksum,
+            )
             .await
             .context("pipeline::syncer::put_object() failed.")
     }
 
-    async fn delete_object(&self, key: &str) -> Result<DeleteObjectOutput> {
-        self.base
+    async fn delete_object(&self, object: &S3syncObject) -> Result<DeleteObjectOutput> {
+        let target_key = self.target_key(object);
+        let result = self
+            .base
             .target
             .as_ref()
             .unwrap()
-            .delete_object(key, None)
+            .delete_object(&target_key, None)
             .await
-            .context("pipeline::syncer::delete_object() failed.")
+            .context("pipeline::syncer::delete_object() failed.")?;
+        append_delete_log(&self.delete_log, &target_key, None).await?;
+
+        Ok(result)
     }
 
     async fn get_object_tagging(
@@ -712,6 +1217,16 @@ fn is_not_found_error(result: &Error) -> bool {
     false
 }
 
+fn is_not_modified_error(result: &Error) -> bool {
+    if let Some(SdkError::ServiceError(e)) =
+        result.downcast_ref::<SdkError<GetObjectError, Response<SdkBody>>>()
+    {
+        return e.raw().status().as_u16() == 304;
+    }
+
+    false
+}
+
 fn is_access_denied_error(result: &Error) -> bool {
     if let Some(SdkError::ServiceError(e)) =
         result.downcast_ref::<SdkError<GetObjectError, Response<SdkBody>>>()
@@ -756,9 +1271,25 @@ fn is_directory_traversal_error(e: &Error) -> bool {
     false
 }
 
-fn is_cancelled_error(e: &Error) -> bool {
+fn is_path_conflict_error(e: &Error) -> bool {
     if let Some(err) = e.downcast_ref::<S3syncError>() {
-        return *err == S3syncError::Cancelled;
+        return *err == S3syncError::PathConflictError;
+    }
+
+    false
+}
+
+fn is_local_verification_mismatch_error(e: &Error) -> bool {
+    if let Some(err) = e.downcast_ref::<S3syncError>() {
+        return *err == S3syncError::LocalVerificationMismatch;
+    }
+
+    false
+}
+
+fn is_cancelled_error(e: &Error) -> bool {
+    if let Some(err) = e.downcast_ref::<S3syncError>() {
+        return *err == S3syncError::Cancelled;
     }
 
     false
@@ -817,7 +1348,7 @@ mod tests {
     use aws_smithy_runtime_api::http::{Response, StatusCode};
 
     use crate::config::args::parse_from_args;
-    use crate::pipeline::storage_factory::create_storage_pair;
+    use crate::pipeline::storage_factory::{create_storage_limiters, create_storage_pair};
     use crate::storage::StoragePair;
     use crate::types::token::create_pipeline_cancellation_token;
     use crate::Config;
@@ -879,6 +1410,19 @@ mod tests {
         assert!(!is_force_retryable_error(&anyhow!("error")));
     }
 
+    #[test]
+    fn is_path_conflict_error_test() {
+        init_dummy_tracing_subscriber();
+
+        assert!(is_path_conflict_error(&anyhow!(
+            S3syncError::PathConflictError
+        )));
+        assert!(!is_path_conflict_error(&anyhow!(
+            S3syncError::DirectoryTraversalError
+        )));
+        assert!(!is_path_conflict_error(&anyhow!("Error")));
+    }
+
     #[test]
     fn is_directory_traversal_error_test() {
         init_dummy_tracing_subscriber();
@@ -892,6 +1436,23 @@ mod tests {
         )));
     }
 
+    #[test]
+    fn is_local_verification_mismatch_error_test() {
+        init_dummy_tracing_subscriber();
+
+        assert!(is_local_verification_mismatch_error(&anyhow!(
+            S3syncError::LocalVerificationMismatch
+        )));
+        assert!(is_local_verification_mismatch_error(
+            &anyhow!(S3syncError::LocalVerificationMismatch)
+                .context("pipeline::syncer::put_object() failed.")
+        ));
+        assert!(!is_local_verification_mismatch_error(&anyhow!(
+            S3syncError::Cancelled
+        )));
+        assert!(!is_local_verification_mismatch_error(&anyhow!("Error")));
+    }
+
     #[test]
     fn is_cancelled_error_test() {
         init_dummy_tracing_subscriber();
@@ -921,8 +1482,13 @@ mod tests {
         let cancellation_token = create_pipeline_cancellation_token();
         let (stats_sender, stats_receiver) = async_channel::unbounded();
 
-        let StoragePair { source, target } =
-            create_storage_pair(config.clone(), cancellation_token.clone(), stats_sender).await;
+        let StoragePair { source, target } = create_storage_pair(
+            config.clone(),
+            cancellation_token.clone(),
+            stats_sender,
+            create_storage_limiters(&config),
+        )
+        .await;
         let (sender, receiver) = async_channel::bounded::<S3syncObject>(1000);
 
         sender
@@ -947,6 +1513,76 @@ mod tests {
                 cancellation_token.clone(),
             ),
             0,
+            SyncerSharedState::default(),
+        )
+        .sync()
+        .await;
+
+        assert!(result.is_ok());
+
+        let stats = stats_receiver.recv().await.unwrap();
+        match stats {
+            SyncSkip { .. } => {}
+            _ => panic!("skip object not found"),
+        }
+    }
+
+    #[tokio::test]
+    async fn sync_object_skip_if_target_newer() {
+        init_dummy_tracing_subscriber();
+
+        let dir = tempfile::tempdir().unwrap();
+        tokio::fs::write(dir.path().join("6byte.dat"), b"123456")
+            .await
+            .unwrap();
+        let target = format!("{}/", dir.path().to_str().unwrap());
+
+        let args = vec![
+            "s3sync",
+            "--allow-both-local-storage",
+            "--head-each-target",
+            "--check-size",
+            "--skip-if-target-newer",
+            "./test_data/source/dir1/",
+            &target,
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+        let cancellation_token = create_pipeline_cancellation_token();
+        let (stats_sender, stats_receiver) = async_channel::unbounded();
+
+        let StoragePair { source, target } = create_storage_pair(
+            config.clone(),
+            cancellation_token.clone(),
+            stats_sender,
+            create_storage_limiters(&config),
+        )
+        .await;
+        let (sender, receiver) = async_channel::bounded::<S3syncObject>(1000);
+
+        // the size is different, but the target object is newer.
+        sender
+            .send(S3syncObject::NotVersioning(
+                Object::builder()
+                    .key("6byte.dat")
+                    .size(7)
+                    .last_modified(DateTime::from_secs(0))
+                    .build(),
+            ))
+            .await
+            .unwrap();
+        sender.close();
+
+        let result = ObjectSyncer::new(
+            Stage::new(
+                config.clone(),
+                Some(dyn_clone::clone_box(&*source)),
+                Some(dyn_clone::clone_box(&*target)),
+                Some(receiver),
+                None,
+                cancellation_token.clone(),
+            ),
+            0,
+            SyncerSharedState::default(),
         )
         .sync()
         .await;
@@ -975,8 +1611,13 @@ mod tests {
         let cancellation_token = create_pipeline_cancellation_token();
         let (stats_sender, stats_receiver) = async_channel::unbounded();
 
-        let StoragePair { source, target } =
-            create_storage_pair(config.clone(), cancellation_token.clone(), stats_sender).await;
+        let StoragePair { source, target } = create_storage_pair(
+            config.clone(),
+            cancellation_token.clone(),
+            stats_sender,
+            create_storage_limiters(&config),
+        )
+        .await;
         let (sender, receiver) = async_channel::bounded::<S3syncObject>(1000);
         let (next_sender, _next_receiver) = async_channel::bounded::<S3syncObject>(1000);
 
@@ -1002,6 +1643,7 @@ mod tests {
                 cancellation_token.clone(),
             ),
             0,
+            SyncerSharedState::default(),
         )
         .sync()
         .await;
@@ -1014,6 +1656,232 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn sync_object_drained() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--allow-both-local-storage",
+            "./test_data/source/dir1/",
+            "./test_data/target/dir1/",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+        let cancellation_token = create_pipeline_cancellation_token();
+        let drain_token = create_pipeline_cancellation_token();
+        let drained_object_count = Arc::new(AtomicU64::new(0));
+        let (stats_sender, stats_receiver) = async_channel::unbounded();
+
+        let StoragePair { source, target } = create_storage_pair(
+            config.clone(),
+            cancellation_token.clone(),
+            stats_sender,
+            create_storage_limiters(&config),
+        )
+        .await;
+        let (sender, receiver) = async_channel::bounded::<S3syncObject>(1000);
+
+        sender
+            .send(S3syncObject::NotVersioning(
+                Object::builder()
+                    .key("6byte.dat")
+                    .size(6)
+                    .last_modified(DateTime::from_secs(0))
+                    .build(),
+            ))
+            .await
+            .unwrap();
+
+        drain_token.cancel();
+
+        let result = ObjectSyncer::new(
+            Stage::new(
+                config.clone(),
+                Some(dyn_clone::clone_box(&*source)),
+                Some(dyn_clone::clone_box(&*target)),
+                Some(receiver),
+                None,
+                cancellation_token.clone(),
+            ),
+            0,
+            SyncerSharedState {
+                drain_token,
+                drained_object_count: drained_object_count.clone(),
+                ..Default::default()
+            },
+        )
+        .sync()
+        .await;
+
+        assert!(result.is_ok());
+        assert!(stats_receiver.is_empty());
+        assert_eq!(sender.len(), 1);
+        assert_eq!(drained_object_count.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn fail_fast_threshold() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--allow-both-local-storage",
+            "--fail-fast-after",
+            "2",
+            "./test_data/source/dir1/",
+            "./test_data/target/dir1/",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+        let cancellation_token = create_pipeline_cancellation_token();
+        let skipped_error_count = Arc::new(AtomicU64::new(0));
+        let (stats_sender, _) = async_channel::unbounded();
+
+        let StoragePair { source, target } = create_storage_pair(
+            config.clone(),
+            cancellation_token.clone(),
+            stats_sender,
+            create_storage_limiters(&config),
+        )
+        .await;
+        let (_, receiver) = async_channel::bounded::<S3syncObject>(1000);
+
+        let object_syncer = ObjectSyncer::new(
+            Stage::new(
+                config.clone(),
+                Some(dyn_clone::clone_box(&*source)),
+                Some(dyn_clone::clone_box(&*target)),
+                Some(receiver),
+                None,
+                cancellation_token.clone(),
+            ),
+            0,
+            SyncerSharedState {
+                skipped_error_count: skipped_error_count.clone(),
+                ..Default::default()
+            },
+        );
+
+        assert!(!object_syncer.is_fail_fast_threshold_reached());
+        assert!(object_syncer.is_fail_fast_threshold_reached());
+        assert_eq!(skipped_error_count.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn retry_budget_exhausted() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--allow-both-local-storage",
+            "--retry-budget",
+            "1m",
+            "./test_data/source/dir1/",
+            "./test_data/target/dir1/",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+        let cancellation_token = create_pipeline_cancellation_token();
+        let retry_elapsed_milliseconds = Arc::new(AtomicU64::new(0));
+        let (stats_sender, _) = async_channel::unbounded();
+
+        let StoragePair { source, target } = create_storage_pair(
+            config.clone(),
+            cancellation_token.clone(),
+            stats_sender,
+            create_storage_limiters(&config),
+        )
+        .await;
+        let (_, receiver) = async_channel::bounded::<S3syncObject>(1000);
+
+        let object_syncer = ObjectSyncer::new(
+            Stage::new(
+                config.clone(),
+                Some(dyn_clone::clone_box(&*source)),
+                Some(dyn_clone::clone_box(&*target)),
+                Some(receiver),
+                None,
+                cancellation_token.clone(),
+            ),
+            0,
+            SyncerSharedState {
+                retry_elapsed_milliseconds: retry_elapsed_milliseconds.clone(),
+                ..Default::default()
+            },
+        );
+
+        retry_elapsed_milliseconds.store(59_999, Ordering::SeqCst);
+        assert!(!object_syncer.is_retry_budget_exhausted());
+
+        retry_elapsed_milliseconds.store(60_000, Ordering::SeqCst);
+        assert!(object_syncer.is_retry_budget_exhausted());
+    }
+
+    #[tokio::test]
+    async fn max_retries_per_object_exceeded() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--allow-both-local-storage",
+            "--max-retries-per-object",
+            "2",
+            "--fail-fast-after",
+            "2",
+            "./test_data/source/dir1/",
+            "./test_data/target/dir1/",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+        let cancellation_token = create_pipeline_cancellation_token();
+        let (stats_sender, stats_receiver) = async_channel::unbounded();
+
+        let StoragePair { source, target } = create_storage_pair(
+            config.clone(),
+            cancellation_token.clone(),
+            stats_sender,
+            create_storage_limiters(&config),
+        )
+        .await;
+        let (_, receiver) = async_channel::bounded::<S3syncObject>(1000);
+
+        let object_syncer = ObjectSyncer::new(
+            Stage::new(
+                config.clone(),
+                Some(dyn_clone::clone_box(&*source)),
+                Some(dyn_clone::clone_box(&*target)),
+                Some(receiver),
+                None,
+                cancellation_token.clone(),
+            ),
+            0,
+            SyncerSharedState::default(),
+        );
+
+        assert!(!object_syncer.is_max_retries_per_object_reached(1));
+        assert!(object_syncer.is_max_retries_per_object_reached(2));
+
+        // the first exhausted object does not stop the pipeline.
+        assert!(object_syncer
+            .give_up_object("key1", anyhow!("error"))
+            .await
+            .is_ok());
+        assert_eq!(
+            stats_receiver.recv().await.unwrap(),
+            SyncError {
+                key: "key1".to_string()
+            }
+        );
+        assert_eq!(
+            stats_receiver.recv().await.unwrap(),
+            RetryExhausted {
+                key: "key1".to_string()
+            }
+        );
+
+        assert!(object_syncer
+            .give_up_object("key2", anyhow!("error"))
+            .await
+            .is_err());
+    }
+
     #[tokio::test]
     #[cfg(target_family = "unix")]
     async fn sync_object_head_object_error() {
@@ -1043,8 +1911,13 @@ mod tests {
         let cancellation_token = create_pipeline_cancellation_token();
         let (stats_sender, _) = async_channel::unbounded();
 
-        let StoragePair { source, target } =
-            create_storage_pair(config.clone(), cancellation_token.clone(), stats_sender).await;
+        let StoragePair { source, target } = create_storage_pair(
+            config.clone(),
+            cancellation_token.clone(),
+            stats_sender,
+            create_storage_limiters(&config),
+        )
+        .await;
         let (sender, receiver) = async_channel::bounded::<S3syncObject>(1000);
         let (next_sender, _next_receiver) = async_channel::bounded::<S3syncObject>(1000);
 
@@ -1070,6 +1943,7 @@ mod tests {
                 cancellation_token.clone(),
             ),
             0,
+            SyncerSharedState::default(),
         )
         .sync()
         .await;
@@ -1108,8 +1982,13 @@ mod tests {
         let cancellation_token = create_pipeline_cancellation_token();
         let (stats_sender, _) = async_channel::unbounded();
 
-        let StoragePair { source, target } =
-            create_storage_pair(config.clone(), cancellation_token.clone(), stats_sender).await;
+        let StoragePair { source, target } = create_storage_pair(
+            config.clone(),
+            cancellation_token.clone(),
+            stats_sender,
+            create_storage_limiters(&config),
+        )
+        .await;
         let (sender, receiver) = async_channel::bounded::<S3syncObject>(1000);
 
         sender
@@ -1132,6 +2011,7 @@ mod tests {
                 cancellation_token.clone(),
             ),
             0,
+            SyncerSharedState::default(),
         )
         .sync()
         .await;
@@ -1164,8 +2044,13 @@ mod tests {
         let cancellation_token = create_pipeline_cancellation_token();
         let (stats_sender, _) = async_channel::unbounded();
 
-        let StoragePair { source, target } =
-            create_storage_pair(config.clone(), cancellation_token.clone(), stats_sender).await;
+        let StoragePair { source, target } = create_storage_pair(
+            config.clone(),
+            cancellation_token.clone(),
+            stats_sender,
+            create_storage_limiters(&config),
+        )
+        .await;
         let (sender, receiver) = async_channel::bounded::<S3syncObject>(1000);
 
         sender
@@ -1186,6 +2071,7 @@ mod tests {
                 cancellation_token.clone(),
             ),
             0,
+            SyncerSharedState::default(),
         )
         .sync()
         .await;
@@ -1218,8 +2104,13 @@ mod tests {
         let cancellation_token = create_pipeline_cancellation_token();
         let (stats_sender, _) = async_channel::unbounded();
 
-        let StoragePair { source, target } =
-            create_storage_pair(config.clone(), cancellation_token.clone(), stats_sender).await;
+        let StoragePair { source, target } = create_storage_pair(
+            config.clone(),
+            cancellation_token.clone(),
+            stats_sender,
+            create_storage_limiters(&config),
+        )
+        .await;
         let (_, receiver) = async_channel::bounded::<S3syncObject>(1000);
 
         cancellation_token.cancel();
@@ -1234,6 +2125,7 @@ mod tests {
                 cancellation_token.clone(),
             ),
             0,
+            SyncerSharedState::default(),
         )
         .sync()
         .await;
@@ -1259,6 +2151,20 @@ mod tests {
         assert!(!is_not_found_error(&anyhow!("test error")));
     }
 
+    #[test]
+    fn is_not_modified_error_test() {
+        init_dummy_tracing_subscriber();
+
+        assert!(is_not_modified_error(&anyhow!(
+            build_get_object_not_modified_error()
+        )));
+
+        assert!(!is_not_modified_error(&anyhow!(
+            build_get_object_no_such_key_error()
+        )));
+        assert!(!is_not_modified_error(&anyhow!("test error")));
+    }
+
     #[test]
     fn is_access_denied_error_test() {
         init_dummy_tracing_subscriber();
@@ -1452,6 +2358,18 @@ mod tests {
         SdkError::service_error(get_object_error, response)
     }
 
+    fn build_get_object_not_modified_error() -> SdkError<GetObjectError, Response<SdkBody>> {
+        let unhandled_error = GetObjectError::generic(
+            aws_sdk_s3::error::ErrorMetadata::builder()
+                .code("NotModified")
+                .build(),
+        );
+
+        let response = Response::new(StatusCode::try_from(304).unwrap(), SdkBody::from(r#""#));
+
+        SdkError::service_error(unhandled_error, response)
+    }
+
     fn build_get_object_access_denied_error() -> SdkError<GetObjectError, Response<SdkBody>> {
         let unhandled_error = GetObjectError::generic(
             aws_sdk_s3::error::ErrorMetadata::builder()
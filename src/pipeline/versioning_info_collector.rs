@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use anyhow::{Context, Result};
 use aws_sdk_s3::operation::head_object::HeadObjectOutput;
@@ -29,10 +29,12 @@ impl VersioningInfoCollector {
         }
     }
 
+    // Returns the source versions to sync, and the target version ids whose source versions have been removed.
+    // The latter is always empty without --delete-removed-versions.
     pub async fn collect_object_versions_to_sync(
         &self,
         source_packed_object_versions: &S3syncObject,
-    ) -> Result<ObjectVersions> {
+    ) -> Result<(ObjectVersions, Vec<String>)> {
         let source_object_versions = types::unpack_object_versions(source_packed_object_versions);
 
         let key = source_packed_object_versions.key();
@@ -47,10 +49,17 @@ impl VersioningInfoCollector {
             .build_head_object_output_map(&target_object_versions)
             .await?;
 
+        let removed_target_version_ids = if self.config.delete_removed_versions {
+            find_removed_target_version_ids(&source_object_versions, &target_head_object_output_map)
+        } else {
+            vec![]
+        };
+
         let mut object_versions_to_sync = ObjectVersions::new();
         for source_object in source_object_versions {
             if let S3syncObject::DeleteMarker(marker) = &source_object {
-                // delete marker is always at the end of the array
+                // delete marker is always at the end of the array, even with --versions-order newest-first.
+                // it is not sent again if the latest version in the target has already been deleted.
                 if !target_latest_version_deleted || !object_versions_to_sync.is_empty() {
                     object_versions_to_sync.push(source_object);
                 } else {
@@ -99,7 +108,7 @@ impl VersioningInfoCollector {
             }
         }
 
-        Ok(object_versions_to_sync)
+        Ok((object_versions_to_sync, removed_target_version_ids))
     }
 
     async fn build_head_object_output_map(
@@ -144,6 +153,29 @@ fn is_latest_version_deleted(object_versions: &Vec<ObjectVersion>) -> bool {
     true
 }
 
+// Only the target versions synced by s3sync(i.e. with the origin version id metadata) are candidates.
+// Delete markers are not compared, because they do not have metadata.
+fn find_removed_target_version_ids(
+    source_object_versions: &ObjectVersions,
+    head_object_output_map: &HeadObjectOutputMap,
+) -> Vec<String> {
+    let source_version_ids: HashSet<&str> = source_object_versions
+        .iter()
+        .filter(|object| !object.is_delete_marker())
+        .filter_map(|object| object.version_id())
+        .collect();
+
+    let mut removed_target_version_ids: Vec<String> = head_object_output_map
+        .iter()
+        .filter(|(source_version_id, _)| !source_version_ids.contains(source_version_id.as_str()))
+        .filter_map(|(_, head_object_output)| head_object_output.version_id())
+        .map(|version_id| version_id.to_string())
+        .collect();
+    removed_target_version_ids.sort();
+
+    removed_target_version_ids
+}
+
 fn does_not_contain_version_id(
     head_object_output_map: &HeadObjectOutputMap,
     version_id: &str,
@@ -185,6 +217,51 @@ mod tests {
         assert!(!is_latest_version_deleted(&object_versions));
     }
 
+    #[test]
+    fn find_removed_target_version_ids_test() {
+        init_dummy_tracing_subscriber();
+
+        let source_object_versions = vec![
+            S3syncObject::Versioning(ObjectVersion::builder().version_id("source1").build()),
+            S3syncObject::Versioning(ObjectVersion::builder().version_id("source3").build()),
+            S3syncObject::DeleteMarker(
+                aws_sdk_s3::types::DeleteMarkerEntry::builder()
+                    .version_id("source2")
+                    .build(),
+            ),
+        ];
+
+        let mut head_object_output_map = HeadObjectOutputMap::new();
+        head_object_output_map.insert(
+            "source1".to_string(),
+            HeadObjectOutput::builder().version_id("target1").build(),
+        );
+        head_object_output_map.insert(
+            "source2".to_string(),
+            HeadObjectOutput::builder().version_id("target2").build(),
+        );
+        head_object_output_map.insert(
+            "source3".to_string(),
+            HeadObjectOutput::builder().version_id("target3").build(),
+        );
+        head_object_output_map.insert(
+            "source4".to_string(),
+            HeadObjectOutput::builder().version_id("target4").build(),
+        );
+
+        // source2 is a delete marker in the source, so the data version synced from it has been removed.
+        assert_eq!(
+            find_removed_target_version_ids(&source_object_versions, &head_object_output_map),
+            vec!["target2".to_string(), "target4".to_string()]
+        );
+
+        assert!(find_removed_target_version_ids(
+            &source_object_versions,
+            &HeadObjectOutputMap::new()
+        )
+        .is_empty());
+    }
+
     #[test]
     fn does_not_contain_version_id_true() {
         init_dummy_tracing_subscriber();
@@ -9,6 +9,15 @@ use crate::storage::checksum::AdditionalChecksum;
 
 const UNKNOWN_CHECKSUM_VALUE: &str = "UNKNOWN";
 
+// The sampling decision is deterministic by the key, so that re-runs verify the same objects.
+pub fn is_checksum_verify_sampled(key: &str, sampling_percent: Option<u8>) -> bool {
+    let Some(sampling_percent) = sampling_percent else {
+        return true;
+    };
+
+    crc32fast::hash(key.as_bytes()) % 100 < sampling_percent as u32
+}
+
 pub async fn generate_checksum_from_path(
     path: &Path,
     checksum_algorithm: ChecksumAlgorithm,
@@ -158,7 +167,8 @@ mod tests {
 
     use crate::storage::additional_checksum_verify::{
         generate_checksum_from_path, generate_checksum_from_path_for_check,
-        generate_checksum_from_path_with_chunksize, UNKNOWN_CHECKSUM_VALUE,
+        generate_checksum_from_path_with_chunksize, is_checksum_verify_sampled,
+        UNKNOWN_CHECKSUM_VALUE,
     };
 
     const TEST_SHA256_BASE64_DIGEST: &str = "WZRHGrsBESr8wYFZ9sx0tPURuZgG2lmzyvWpwXPKz8U=";
@@ -172,6 +182,22 @@ mod tests {
     const THRESHOLD_SHA256_BASE64_FINAL_DIGEST: &str =
         "CPUzmvYGEjhKR5UARYyMgSkXZMUvVQ2BMxs1zpgY97g=-1";
 
+    #[test]
+    fn is_checksum_verify_sampled_test() {
+        assert!(is_checksum_verify_sampled("key", None));
+        assert!(is_checksum_verify_sampled("key", Some(100)));
+
+        let sampled_count = (0..10000)
+            .filter(|i| is_checksum_verify_sampled(&format!("dir/{i}.dat"), Some(5)))
+            .count();
+        assert!((300..700).contains(&sampled_count));
+
+        assert_eq!(
+            is_checksum_verify_sampled("dir/1.dat", Some(50)),
+            is_checksum_verify_sampled("dir/1.dat", Some(50))
+        );
+    }
+
     #[tokio::test]
     async fn generate_checksum_from_path_test() {
         init_dummy_tracing_subscriber();
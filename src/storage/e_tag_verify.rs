@@ -5,6 +5,8 @@ use aws_sdk_s3::types::ServerSideEncryption;
 use tokio::fs::File;
 use tokio::io::AsyncReadExt;
 
+use crate::storage::local::fs_util;
+
 const UNKNOWN_E_TAG_VALUE: &str = "UNKNOWN";
 
 pub fn verify_e_tag(
@@ -104,6 +106,24 @@ pub async fn generate_e_tag_hash_from_path(
     Ok(generate_e_tag_hash(&concatnated_md5_hash, parts_count))
 }
 
+// If md5_xattr is specified and the file is uploaded with a single part, a precomputed MD5 digest is used.
+pub async fn generate_e_tag_hash_from_path_or_md5_xattr(
+    path: &Path,
+    multipart_chunksize: usize,
+    multipart_threshold: usize,
+    md5_xattr: &Option<String>,
+) -> Result<String> {
+    if let Some(xattr_name) = md5_xattr {
+        if tokio::fs::metadata(path).await?.len() < multipart_threshold as u64 {
+            if let Some(md5) = fs_util::get_md5_from_xattr(path, xattr_name) {
+                return Ok(format!("\"{}\"", md5));
+            }
+        }
+    }
+
+    generate_e_tag_hash_from_path(path, multipart_chunksize, multipart_threshold).await
+}
+
 pub async fn generate_e_tag_hash_from_path_with_auto_chunksize(
     path: &Path,
     object_parts: Vec<i64>,
@@ -605,6 +625,26 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn generate_e_tag_hash_from_path_or_md5_xattr_fallback() {
+        init_dummy_tracing_subscriber();
+
+        let path = PathBuf::from("test_data/5byte.dat");
+        assert_eq!(
+            generate_e_tag_hash_from_path_or_md5_xattr(
+                &path,
+                8 * 1024 * 1024,
+                8 * 1024 * 1024,
+                &Some("user.s3sync.md5".to_string())
+            )
+            .await
+            .unwrap(),
+            generate_e_tag_hash_from_path(&path, 8 * 1024 * 1024, 8 * 1024 * 1024)
+                .await
+                .unwrap()
+        );
+    }
+
     #[tokio::test]
     async fn generate_e_tag_hash_from_path_auto_chunksize_test() {
         init_dummy_tracing_subscriber();
@@ -0,0 +1,146 @@
+use aws_sdk_s3::types::ServerSideEncryption;
+
+const KMS_KEY_ARN_PREFIX: &str = "arn:";
+const KMS_KEY_ID_ARN_SEPARATOR: &str = ":key/";
+
+// S3 always returns the KMS key as the key ARN, so a key ID is compared with the last part of the ARN.
+// an alias can not be resolved without KMS, so only the algorithm is verified for an alias.
+pub fn verify_encryption(
+    expected_sse: &ServerSideEncryption,
+    expected_kms_key_id: Option<&str>,
+    target_sse: Option<&ServerSideEncryption>,
+    target_kms_key_id: Option<&str>,
+) -> bool {
+    if target_sse != Some(expected_sse) {
+        return false;
+    }
+
+    let Some(expected_kms_key_id) = expected_kms_key_id else {
+        return true;
+    };
+    if is_kms_key_alias(expected_kms_key_id) {
+        return true;
+    }
+
+    let Some(target_kms_key_id) = target_kms_key_id else {
+        return false;
+    };
+
+    if expected_kms_key_id.starts_with(KMS_KEY_ARN_PREFIX) {
+        return target_kms_key_id == expected_kms_key_id;
+    }
+
+    target_kms_key_id == expected_kms_key_id
+        || target_kms_key_id.ends_with(&format!("{KMS_KEY_ID_ARN_SEPARATOR}{expected_kms_key_id}"))
+}
+
+fn is_kms_key_alias(key_id: &str) -> bool {
+    key_id.starts_with("alias/") || key_id.contains(":alias/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY_ID: &str = "1234abcd-12ab-34cd-56ef-1234567890ab";
+    const KEY_ARN: &str =
+        "arn:aws:kms:us-east-1:123456789012:key/1234abcd-12ab-34cd-56ef-1234567890ab";
+    const OTHER_KEY_ARN: &str =
+        "arn:aws:kms:us-east-1:123456789012:key/0000abcd-12ab-34cd-56ef-1234567890ab";
+
+    #[test]
+    fn verify_encryption_algorithm() {
+        init_dummy_tracing_subscriber();
+
+        assert!(verify_encryption(
+            &ServerSideEncryption::Aes256,
+            None,
+            Some(&ServerSideEncryption::Aes256),
+            None
+        ));
+        assert!(!verify_encryption(
+            &ServerSideEncryption::Aes256,
+            None,
+            Some(&ServerSideEncryption::AwsKms),
+            Some(KEY_ARN)
+        ));
+        assert!(!verify_encryption(
+            &ServerSideEncryption::AwsKms,
+            None,
+            None,
+            None
+        ));
+        assert!(verify_encryption(
+            &ServerSideEncryption::AwsKms,
+            None,
+            Some(&ServerSideEncryption::AwsKms),
+            Some(KEY_ARN)
+        ));
+    }
+
+    #[test]
+    fn verify_encryption_kms_key_id() {
+        init_dummy_tracing_subscriber();
+
+        assert!(verify_encryption(
+            &ServerSideEncryption::AwsKms,
+            Some(KEY_ID),
+            Some(&ServerSideEncryption::AwsKms),
+            Some(KEY_ARN)
+        ));
+        assert!(verify_encryption(
+            &ServerSideEncryption::AwsKms,
+            Some(KEY_ARN),
+            Some(&ServerSideEncryption::AwsKms),
+            Some(KEY_ARN)
+        ));
+        assert!(!verify_encryption(
+            &ServerSideEncryption::AwsKms,
+            Some(KEY_ID),
+            Some(&ServerSideEncryption::AwsKms),
+            Some(OTHER_KEY_ARN)
+        ));
+        assert!(!verify_encryption(
+            &ServerSideEncryption::AwsKms,
+            Some(KEY_ARN),
+            Some(&ServerSideEncryption::AwsKms),
+            Some(OTHER_KEY_ARN)
+        ));
+        assert!(!verify_encryption(
+            &ServerSideEncryption::AwsKms,
+            Some(KEY_ID),
+            Some(&ServerSideEncryption::AwsKms),
+            None
+        ));
+    }
+
+    #[test]
+    fn verify_encryption_kms_key_alias() {
+        init_dummy_tracing_subscriber();
+
+        assert!(verify_encryption(
+            &ServerSideEncryption::AwsKms,
+            Some("alias/my-key"),
+            Some(&ServerSideEncryption::AwsKms),
+            Some(KEY_ARN)
+        ));
+        assert!(verify_encryption(
+            &ServerSideEncryption::AwsKms,
+            Some("arn:aws:kms:us-east-1:123456789012:alias/my-key"),
+            Some(&ServerSideEncryption::AwsKms),
+            Some(KEY_ARN)
+        ));
+        assert!(!verify_encryption(
+            &ServerSideEncryption::AwsKms,
+            Some("alias/my-key"),
+            Some(&ServerSideEncryption::Aes256),
+            None
+        ));
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
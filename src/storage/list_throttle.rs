@@ -0,0 +1,195 @@
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use aws_sdk_s3::config::interceptors::FinalizerInterceptorContextRef;
+use aws_sdk_s3::config::{ConfigBag, Intercept, RuntimeComponents};
+use aws_sdk_s3::error::BoxError;
+use tokio::time::Instant;
+use tracing::warn;
+
+const MIN_REQUESTS_PER_SEC: f64 = 1.0;
+const ADDITIVE_INCREASE_PER_SUCCESS: f64 = 1.0;
+const MULTIPLICATIVE_DECREASE_FACTOR: f64 = 0.5;
+
+// Adaptive throttle of the listing requests of one side(--list-max-rps).
+// the rate starts at the maximum, is halved on a throttling response, and is increased by one
+// request per second on each successful response up to the maximum(AIMD).
+// it is shared by the parallel listing workers, and is independent of --rate-limit-objects.
+pub struct ListThrottle {
+    max_requests_per_sec: f64,
+    state: Mutex<ListThrottleState>,
+}
+
+struct ListThrottleState {
+    requests_per_sec: f64,
+    next_request_time: Instant,
+}
+
+impl ListThrottle {
+    pub fn new(max_requests_per_sec: u32) -> Self {
+        Self {
+            max_requests_per_sec: max_requests_per_sec as f64,
+            state: Mutex::new(ListThrottleState {
+                requests_per_sec: max_requests_per_sec as f64,
+                next_request_time: Instant::now(),
+            }),
+        }
+    }
+
+    // the requests are spaced evenly at the current rate.
+    pub async fn acquire(&self) {
+        let request_time = {
+            let mut state = self.state.lock().unwrap();
+
+            let request_time = state.next_request_time.max(Instant::now());
+            state.next_request_time =
+                request_time + Duration::from_secs_f64(1.0 / state.requests_per_sec);
+
+            request_time
+        };
+
+        tokio::time::sleep_until(request_time).await;
+    }
+
+    pub fn on_success(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.requests_per_sec =
+            (state.requests_per_sec + ADDITIVE_INCREASE_PER_SUCCESS).min(self.max_requests_per_sec);
+    }
+
+    // returns the decreased rate.
+    pub fn on_throttled(&self) -> f64 {
+        let mut state = self.state.lock().unwrap();
+        state.requests_per_sec =
+            (state.requests_per_sec * MULTIPLICATIVE_DECREASE_FACTOR).max(MIN_REQUESTS_PER_SEC);
+
+        state.requests_per_sec
+    }
+
+    pub fn get_requests_per_sec(&self) -> f64 {
+        self.state.lock().unwrap().requests_per_sec
+    }
+}
+
+impl fmt::Debug for ListThrottle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ListThrottle")
+            .field("max_requests_per_sec", &self.max_requests_per_sec)
+            .field("requests_per_sec", &self.get_requests_per_sec())
+            .finish()
+    }
+}
+
+pub async fn acquire_list_permit(list_throttle: &Option<Arc<ListThrottle>>) {
+    if let Some(list_throttle) = list_throttle {
+        list_throttle.acquire().await;
+    }
+}
+
+// Reports the throttling responses of the listing requests to the throttle.
+// it is attached to each listing request, so the attempts retried by the SDK(--aws-max-attempts)
+// decrease the rate as well.
+#[derive(Debug)]
+pub struct ListThrottleInterceptor {
+    list_throttle: Arc<ListThrottle>,
+}
+
+impl ListThrottleInterceptor {
+    pub fn new(list_throttle: Arc<ListThrottle>) -> Self {
+        Self { list_throttle }
+    }
+}
+
+impl Intercept for ListThrottleInterceptor {
+    fn name(&self) -> &'static str {
+        "ListThrottleInterceptor"
+    }
+
+    fn read_after_attempt(
+        &self,
+        context: &FinalizerInterceptorContextRef<'_>,
+        _runtime_components: &RuntimeComponents,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let Some(response) = context.response() else {
+            return Ok(());
+        };
+
+        if is_throttling_status(response.status().as_u16()) {
+            let requests_per_sec = self.list_throttle.on_throttled();
+            warn!(
+                requests_per_sec = requests_per_sec,
+                "listing request has been throttled. decreasing the listing rate."
+            );
+        }
+
+        Ok(())
+    }
+}
+
+// S3 responds with 503 Slow Down on the request rate limit, and S3 compatible storages may
+// respond with 429 Too Many Requests.
+fn is_throttling_status(status: u16) -> bool {
+    matches!(status, 429 | 503)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn additive_increase_and_multiplicative_decrease() {
+        init_dummy_tracing_subscriber();
+
+        let list_throttle = ListThrottle::new(10);
+        assert_eq!(list_throttle.get_requests_per_sec(), 10.0);
+
+        list_throttle.on_success();
+        assert_eq!(list_throttle.get_requests_per_sec(), 10.0);
+
+        assert_eq!(list_throttle.on_throttled(), 5.0);
+        assert_eq!(list_throttle.on_throttled(), 2.5);
+        assert_eq!(list_throttle.on_throttled(), 1.25);
+        assert_eq!(list_throttle.on_throttled(), 1.0);
+        assert_eq!(list_throttle.on_throttled(), 1.0);
+
+        list_throttle.on_success();
+        list_throttle.on_success();
+        assert_eq!(list_throttle.get_requests_per_sec(), 3.0);
+    }
+
+    #[tokio::test]
+    async fn acquire_at_the_current_rate() {
+        init_dummy_tracing_subscriber();
+
+        let list_throttle = Some(Arc::new(ListThrottle::new(20)));
+
+        let start_time = Instant::now();
+        for _ in 0..5 {
+            acquire_list_permit(&list_throttle).await;
+        }
+        acquire_list_permit(&None).await;
+
+        // the first request is sent immediately, and the following ones every 50ms.
+        assert!(Duration::from_millis(200) <= start_time.elapsed());
+    }
+
+    #[test]
+    fn is_throttling_status_test() {
+        init_dummy_tracing_subscriber();
+
+        assert!(is_throttling_status(503));
+        assert!(is_throttling_status(429));
+
+        assert!(!is_throttling_status(200));
+        assert!(!is_throttling_status(403));
+        assert!(!is_throttling_status(500));
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
@@ -1,4 +1,6 @@
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 
 use anyhow::{Context, Result};
 use aws_sdk_s3::primitives::DateTime;
@@ -6,7 +8,24 @@ use filetime::{set_file_mtime, FileTime};
 use regex::Regex;
 use tempfile::NamedTempFile;
 use tokio::fs::File;
-use tracing::trace;
+use tracing::{info, trace};
+use walkdir::WalkDir;
+
+use crate::types::UploadManifest;
+
+const MD5_HEX_DIGEST_LENGTH: usize = 32;
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+const MAX_XATTR_VALUE_SIZE: usize = 256;
+const CHECKSUM_CACHE_XATTR_NAME: &str = "user.s3sync.checksum";
+// the partial files(--partial-suffix) are marked with this extended attribute while they are in progress,
+// so that a file of the user that happens to have the suffix is never removed as a stale partial file.
+const PARTIAL_FILE_XATTR_NAME: &str = "user.s3sync.partial";
+// the temp files for download are named `.s3sync-tmp-<random>` and marked with this extended attribute,
+// so that a file of the user that happens to have the prefix is never removed(--cleanup-temp-on-start).
+pub const TEMP_FILE_PREFIX: &str = ".s3sync-tmp-";
+const TEMP_FILE_XATTR_NAME: &str = "user.s3sync.temp";
+// rwx for user, group and others. setuid/setgid/sticky are excluded(--preserve-file-mode).
+pub const FILE_PERMISSION_BITS: u32 = 0o777;
 
 pub fn check_directory_traversal(key: &str) -> bool {
     let re = Regex::new(r"\.\.[/\\]").unwrap();
@@ -46,10 +65,11 @@ pub fn set_last_modified(
     seconds: i64,
     nanos: u32,
 ) -> std::io::Result<()> {
-    set_file_mtime(
-        key_to_file_path(path, key),
-        FileTime::from_unix_time(seconds, nanos),
-    )
+    set_file_last_modified(&key_to_file_path(path, key), seconds, nanos)
+}
+
+pub fn set_file_last_modified(path: &Path, seconds: i64, nanos: u32) -> std::io::Result<()> {
+    set_file_mtime(path, FileTime::from_unix_time(seconds, nanos))
 }
 
 pub fn is_key_a_directory(key: &str) -> bool {
@@ -60,15 +80,249 @@ pub fn is_key_a_directory(key: &str) -> bool {
     key.ends_with('/')
 }
 
-pub async fn create_temp_file_from_key(path: &Path, key: &str) -> Result<NamedTempFile> {
+// With local_tmp_dir, the temp file is created in it instead of the directory of the key.
+pub async fn create_temp_file_from_key(
+    path: &Path,
+    key: &str,
+    local_tmp_dir: Option<&Path>,
+) -> Result<NamedTempFile> {
     create_directory_if_necessary(path, key).await?;
 
-    let temp_directory_path = key_to_directory_without_filename(path.to_path_buf(), key);
-    let file =
-        NamedTempFile::new_in(temp_directory_path).context("NamedTempFile::new_in failed.")?;
+    let temp_directory_path = if let Some(local_tmp_dir) = local_tmp_dir {
+        local_tmp_dir.to_path_buf()
+    } else {
+        key_to_directory_without_filename(path.to_path_buf(), key)
+    };
+    create_marked_temp_file_in(&temp_directory_path)
+}
+
+fn create_marked_temp_file_in(directory_path: &Path) -> Result<NamedTempFile> {
+    let file = tempfile::Builder::new()
+        .prefix(TEMP_FILE_PREFIX)
+        .tempfile_in(directory_path)
+        .context("tempfile::Builder::tempfile_in() failed.")?;
+
+    if !set_xattr(file.path(), TEMP_FILE_XATTR_NAME, b"1") {
+        trace!("the temp file cannot be marked. it will not be removed as a stale temp file.");
+    }
+
     Ok(file)
 }
 
+// If the temp file is on another file system, it cannot be renamed.
+// In that case, it is copied to the directory of the real path, synced and renamed.
+// The mark is removed before the temp file is renamed to the real path.
+pub fn persist_temp_file(temp_file: NamedTempFile, real_path: &Path) -> Result<()> {
+    remove_xattr(temp_file.path(), TEMP_FILE_XATTR_NAME);
+    let temp_file = match temp_file.persist(real_path) {
+        Ok(_) => return Ok(()),
+        Err(e) if is_cross_device_error(&e.error) => e.file,
+        Err(e) => return Err(e).context("tempfile::NamedTempFile::persist() failed."),
+    };
+
+    let copied_file = create_marked_temp_file_in(real_path.parent().unwrap())?;
+    std::fs::copy(temp_file.path(), copied_file.path()).context("std::fs::copy() failed.")?;
+    remove_xattr(copied_file.path(), TEMP_FILE_XATTR_NAME);
+    copied_file
+        .as_file()
+        .sync_all()
+        .context("std::fs::File::sync_all() failed.")?;
+    copied_file
+        .persist(real_path)
+        .context("tempfile::NamedTempFile::persist() failed.")?;
+
+    Ok(())
+}
+
+// A renamed file is durable only after the directory entry is flushed.
+#[cfg(unix)]
+pub fn sync_parent_directory(path: &Path) -> Result<()> {
+    let parent = path.parent().unwrap();
+    std::fs::File::open(parent)
+        .context("std::fs::File::open() failed.")?
+        .sync_all()
+        .context("std::fs::File::sync_all() failed.")?;
+
+    Ok(())
+}
+
+// Directories cannot be opened as files on this platform.
+#[cfg(not(unix))]
+pub fn sync_parent_directory(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(unix)]
+fn is_cross_device_error(e: &std::io::Error) -> bool {
+    e.raw_os_error() == Some(libc::EXDEV)
+}
+
+#[cfg(windows)]
+fn is_cross_device_error(e: &std::io::Error) -> bool {
+    const ERROR_NOT_SAME_DEVICE: i32 = 17;
+    e.raw_os_error() == Some(ERROR_NOT_SAME_DEVICE)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn is_cross_device_error(_e: &std::io::Error) -> bool {
+    false
+}
+
+// The partial file is deleted when dropped, unless it is persisted or kept.
+// A stale partial file of the same key is replaced.
+pub async fn create_partial_file_from_key(
+    path: &Path,
+    key: &str,
+    partial_suffix: &str,
+) -> Result<NamedTempFile> {
+    create_directory_if_necessary(path, key).await?;
+
+    let partial_path = key_to_partial_file_path(path.to_path_buf(), key, partial_suffix);
+    if let Err(e) = std::fs::remove_file(&partial_path) {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            return Err(e).context("std::fs::remove_file() failed.");
+        }
+    }
+
+    let file_path = key_to_file_path(path.to_path_buf(), key);
+    let file = tempfile::Builder::new()
+        .prefix(file_path.file_name().unwrap())
+        .suffix(partial_suffix)
+        .rand_bytes(0)
+        .tempfile_in(partial_path.parent().unwrap())
+        .context("tempfile::Builder::tempfile_in() failed.")?;
+
+    if !set_xattr(file.path(), PARTIAL_FILE_XATTR_NAME, b"1") {
+        trace!(
+            key = key,
+            "the partial file cannot be marked. it will not be removed as a stale partial file."
+        );
+    }
+
+    Ok(file)
+}
+
+// The mark is removed before the partial file is renamed to the final name.
+pub fn unmark_partial_file(path: &Path) {
+    remove_xattr(path, PARTIAL_FILE_XATTR_NAME);
+}
+
+fn is_marked_partial_file(path: &Path) -> bool {
+    get_xattr(path, PARTIAL_FILE_XATTR_NAME).is_some()
+}
+
+pub fn key_to_partial_file_path(path: PathBuf, key: &str, partial_suffix: &str) -> PathBuf {
+    let mut partial_path = key_to_file_path(path, key).into_os_string();
+    partial_path.push(partial_suffix);
+    partial_path.into()
+}
+
+pub fn is_partial_file(path: &Path, partial_suffix: &str) -> bool {
+    path.to_string_lossy().ends_with(partial_suffix)
+}
+
+// Remove partial files left by an interrupted run. Only the files marked by s3sync are removed.
+// The partial files modified within older_than are kept, because they may be used by another running s3sync.
+// Returns the number of removed files.
+pub fn remove_stale_partial_files(
+    path: &Path,
+    partial_suffix: &str,
+    older_than: Duration,
+    dry_run: bool,
+) -> Result<u64> {
+    let mut removed = 0;
+    for entry in WalkDir::new(path)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+    {
+        if !entry.file_type().is_file()
+            || !is_partial_file(entry.path(), partial_suffix)
+            || !is_marked_partial_file(entry.path())
+        {
+            continue;
+        }
+
+        let modified = entry
+            .metadata()
+            .context("walkdir::DirEntry::metadata() failed.")?
+            .modified()
+            .context("std::fs::Metadata::modified() failed.")?;
+        let age = SystemTime::now()
+            .duration_since(modified)
+            .unwrap_or_default();
+        if age < older_than {
+            continue;
+        }
+
+        let partial_path = entry.path().to_string_lossy().to_string();
+        if dry_run {
+            info!(path = partial_path, "[dry-run] stale partial file removed.");
+        } else {
+            std::fs::remove_file(entry.path()).context("std::fs::remove_file() failed.")?;
+            info!(path = partial_path, "stale partial file removed.");
+        }
+        removed += 1;
+    }
+
+    Ok(removed)
+}
+
+pub fn is_temp_file(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|file_name| file_name.to_string_lossy().starts_with(TEMP_FILE_PREFIX))
+}
+
+fn is_marked_temp_file(path: &Path) -> bool {
+    get_xattr(path, TEMP_FILE_XATTR_NAME).is_some()
+}
+
+// Remove temp files left by a crashed run. Only the files marked by s3sync are removed.
+// The temp files modified within older_than are kept,
+// because they may be used by another running s3sync. Returns the number of removed files.
+pub fn remove_stale_temp_files(
+    path: &Path,
+    max_depth: usize,
+    older_than: Duration,
+    dry_run: bool,
+) -> Result<u64> {
+    let mut removed = 0;
+    for entry in WalkDir::new(path)
+        .max_depth(max_depth)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+    {
+        if !entry.file_type().is_file()
+            || !is_temp_file(entry.path())
+            || !is_marked_temp_file(entry.path())
+        {
+            continue;
+        }
+
+        let modified = entry
+            .metadata()
+            .context("walkdir::DirEntry::metadata() failed.")?
+            .modified()
+            .context("std::fs::Metadata::modified() failed.")?;
+        let age = SystemTime::now()
+            .duration_since(modified)
+            .unwrap_or_default();
+        if age < older_than {
+            continue;
+        }
+
+        let temp_path = entry.path().to_string_lossy().to_string();
+        if dry_run {
+            info!(path = temp_path, "[dry-run] stale temp file removed.");
+        } else {
+            std::fs::remove_file(entry.path()).context("std::fs::remove_file() failed.")?;
+            info!(path = temp_path, "stale temp file removed.");
+        }
+        removed += 1;
+    }
+
+    Ok(removed)
+}
+
 pub async fn create_directory_hierarchy_from_key(path: PathBuf, key: &str) -> Result<bool> {
     let directory_path = key_to_directory_without_filename(path, key);
 
@@ -87,6 +341,51 @@ pub async fn create_directory_hierarchy_from_key(path: PathBuf, key: &str) -> Re
     Ok(true)
 }
 
+// A path conflicts if a file exists where a directory is needed(e.g. `foo` for `foo/bar`),
+// or a directory exists where a file is needed. The conflicting component is suffixed with ` (n)`.
+// Returns None if the key does not conflict.
+pub fn resolve_path_conflict(path: &Path, key: &str) -> Option<String> {
+    let key = remove_root_slash(key);
+    let is_directory_key = key.ends_with('/');
+    let components: Vec<&str> = key.trim_end_matches('/').split('/').collect();
+
+    let mut resolved_key = String::new();
+    let mut conflicted = false;
+    for (index, component) in components.iter().enumerate() {
+        let directory_required = is_directory_key || index < components.len() - 1;
+
+        let mut candidate = component.to_string();
+        let mut suffix = 0;
+        while is_conflicting_path(
+            &key_to_file_path(path.to_path_buf(), &format!("{resolved_key}{candidate}")),
+            directory_required,
+        ) {
+            suffix += 1;
+            candidate = format!("{component} ({suffix})");
+            conflicted = true;
+        }
+
+        resolved_key.push_str(&candidate);
+        if directory_required {
+            resolved_key.push('/');
+        }
+    }
+
+    if !conflicted {
+        return None;
+    }
+
+    Some(resolved_key)
+}
+
+fn is_conflicting_path(path: &Path, directory_required: bool) -> bool {
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return false;
+    };
+
+    metadata.is_dir() != directory_required
+}
+
 pub fn remove_root_slash(key: &str) -> String {
     let re = Regex::new(r"^/+").unwrap();
     re.replace(key, "").to_string()
@@ -99,6 +398,22 @@ pub fn key_to_file_path(path: PathBuf, key: &str) -> PathBuf {
     format!("{lossy_path}{file}").into()
 }
 
+// with --upload-manifest, the file path of the key is taken from the manifest.
+pub fn key_to_source_file_path(
+    path: PathBuf,
+    key: &str,
+    upload_manifest: &Option<Arc<UploadManifest>>,
+) -> PathBuf {
+    if let Some(local_path) = upload_manifest
+        .as_ref()
+        .and_then(|upload_manifest| upload_manifest.local_paths.get(key))
+    {
+        return path.join(local_path);
+    }
+
+    key_to_file_path(path, key)
+}
+
 async fn create_directory_if_necessary(path: &Path, key: &str) -> Result<bool> {
     create_directory_hierarchy_from_key(path.to_path_buf(), key).await?;
 
@@ -129,6 +444,270 @@ fn convert_os_specific_directory_char(key: &str) -> String {
     key.replace('/', std::path::MAIN_SEPARATOR_STR)
 }
 
+// The value of the extended attribute is `<MD5 hex digest>:<size>:<mtime(unix seconds)>`.
+// It is used only when the size and mtime match the file. a bare MD5 digest is not trusted,
+// because it cannot be detected as stale after the file is modified.
+pub fn get_md5_from_xattr(path: &Path, name: &str) -> Option<String> {
+    let value = String::from_utf8(get_xattr(path, name)?).ok()?;
+    let (size, mtime) = get_size_and_mtime(path)?;
+
+    parse_md5_xattr_value(&value, size, mtime)
+}
+
+// The checksum cache is `<checksum algorithm>:<checksum>:<size>:<mtime(unix seconds)>`.
+// It is used only when the size and mtime match the file, i.e. the file has not been changed since verified.
+pub fn get_cached_checksum(path: &Path, checksum_algorithm: &str) -> Option<String> {
+    let value = String::from_utf8(get_xattr(path, CHECKSUM_CACHE_XATTR_NAME)?).ok()?;
+    let (size, mtime) = get_size_and_mtime(path)?;
+
+    parse_checksum_cache_value(&value, checksum_algorithm, size, mtime)
+}
+
+// Caching is best effort. If the file system does not support extended attributes, nothing is cached.
+pub fn set_cached_checksum(path: &Path, checksum_algorithm: &str, checksum: &str) -> bool {
+    let Some((size, mtime)) = get_size_and_mtime(path) else {
+        return false;
+    };
+
+    let value = format!("{}:{}:{}:{}", checksum_algorithm, checksum, size, mtime);
+    set_xattr(path, CHECKSUM_CACHE_XATTR_NAME, value.as_bytes())
+}
+
+fn get_size_and_mtime(path: &Path) -> Option<(u64, i64)> {
+    let metadata = std::fs::metadata(path).ok()?;
+    let mtime = metadata
+        .modified()
+        .ok()?
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()?
+        .as_secs() as i64;
+
+    Some((metadata.len(), mtime))
+}
+
+fn parse_checksum_cache_value(
+    value: &str,
+    checksum_algorithm: &str,
+    size: u64,
+    mtime: i64,
+) -> Option<String> {
+    let fields: Vec<&str> = value.trim().split(':').collect();
+    if fields.len() != 4 || fields[0] != checksum_algorithm || fields[1].is_empty() {
+        return None;
+    }
+
+    if fields[2].parse::<u64>().ok()? != size || fields[3].parse::<i64>().ok()? != mtime {
+        trace!(
+            checksum = fields[1],
+            "checksum in the extended attribute is stale."
+        );
+        return None;
+    }
+
+    Some(fields[1].to_string())
+}
+
+fn parse_md5_xattr_value(value: &str, size: u64, mtime: i64) -> Option<String> {
+    let fields: Vec<&str> = value.trim().split(':').collect();
+
+    let md5 = fields[0].to_ascii_lowercase();
+    if md5.len() != MD5_HEX_DIGEST_LENGTH || !md5.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    if fields.len() != 3 {
+        return None;
+    }
+
+    if fields[1].parse::<u64>().ok()? != size || fields[2].parse::<i64>().ok()? != mtime {
+        trace!(md5 = md5, "md5 in the extended attribute is stale.");
+        return None;
+    }
+
+    Some(md5)
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn get_xattr(path: &Path, name: &str) -> Option<Vec<u8>> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    let name = CString::new(name).ok()?;
+    let mut buffer = vec![0u8; MAX_XATTR_VALUE_SIZE];
+
+    // SAFETY: path and name are NUL-terminated, and the buffer is valid for buffer.len() bytes.
+    #[cfg(target_os = "linux")]
+    let size = unsafe {
+        libc::getxattr(
+            path.as_ptr(),
+            name.as_ptr(),
+            buffer.as_mut_ptr() as *mut libc::c_void,
+            buffer.len(),
+        )
+    };
+    // SAFETY: path and name are NUL-terminated, and the buffer is valid for buffer.len() bytes.
+    #[cfg(target_os = "macos")]
+    let size = unsafe {
+        libc::getxattr(
+            path.as_ptr(),
+            name.as_ptr(),
+            buffer.as_mut_ptr() as *mut libc::c_void,
+            buffer.len(),
+            0,
+            0,
+        )
+    };
+
+    if size < 0 {
+        return None;
+    }
+
+    buffer.truncate(size as usize);
+    Some(buffer)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn get_xattr(_path: &Path, _name: &str) -> Option<Vec<u8>> {
+    None
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn set_xattr(path: &Path, name: &str, value: &[u8]) -> bool {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let Ok(path) = CString::new(path.as_os_str().as_bytes()) else {
+        return false;
+    };
+    let Ok(name) = CString::new(name) else {
+        return false;
+    };
+
+    // SAFETY: path and name are NUL-terminated, and the value is valid for value.len() bytes.
+    #[cfg(target_os = "linux")]
+    let result = unsafe {
+        libc::setxattr(
+            path.as_ptr(),
+            name.as_ptr(),
+            value.as_ptr() as *const libc::c_void,
+            value.len(),
+            0,
+        )
+    };
+    // SAFETY: path and name are NUL-terminated, and the value is valid for value.len() bytes.
+    #[cfg(target_os = "macos")]
+    let result = unsafe {
+        libc::setxattr(
+            path.as_ptr(),
+            name.as_ptr(),
+            value.as_ptr() as *const libc::c_void,
+            value.len(),
+            0,
+            0,
+        )
+    };
+
+    result == 0
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn set_xattr(_path: &Path, _name: &str, _value: &[u8]) -> bool {
+    false
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn remove_xattr(path: &Path, name: &str) -> bool {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let Ok(path) = CString::new(path.as_os_str().as_bytes()) else {
+        return false;
+    };
+    let Ok(name) = CString::new(name) else {
+        return false;
+    };
+
+    // SAFETY: path and name are NUL-terminated.
+    #[cfg(target_os = "linux")]
+    let result = unsafe { libc::removexattr(path.as_ptr(), name.as_ptr()) };
+    // SAFETY: path and name are NUL-terminated.
+    #[cfg(target_os = "macos")]
+    let result = unsafe { libc::removexattr(path.as_ptr(), name.as_ptr(), 0) };
+
+    result == 0
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn remove_xattr(_path: &Path, _name: &str) -> bool {
+    false
+}
+
+// Preallocation is best effort. Ok(false) is returned if the platform or the file system does not support it.
+// The other errors(e.g. ENOSPC) are returned as is, so that they can be detected before the download.
+#[cfg(target_os = "linux")]
+pub fn preallocate_file(file: &std::fs::File, size: u64) -> std::io::Result<bool> {
+    use std::os::unix::io::AsRawFd;
+
+    // SAFETY: the file descriptor is valid while the file is borrowed.
+    let result = unsafe { libc::posix_fallocate(file.as_raw_fd(), 0, size as libc::off_t) };
+
+    match result {
+        0 => Ok(true),
+        libc::EOPNOTSUPP | libc::EINVAL | libc::ENOSYS => Ok(false),
+        errno => Err(std::io::Error::from_raw_os_error(errno)),
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn preallocate_file(_file: &std::fs::File, _size: u64) -> std::io::Result<bool> {
+    Ok(false)
+}
+
+// returns (dev, ino) of the file if it has other hard links.
+#[cfg(unix)]
+pub fn get_hardlink_id(metadata: &std::fs::Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+
+    if metadata.nlink() <= 1 {
+        return None;
+    }
+
+    Some((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+pub fn get_hardlink_id(_metadata: &std::fs::Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+// the permission bits of the file in octal, e.g. "644". setuid/setgid/sticky are not included.
+#[cfg(unix)]
+pub fn get_file_mode(path: &Path) -> Option<String> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = std::fs::metadata(path).ok()?.permissions().mode();
+    Some(format!("{:o}", mode & FILE_PERMISSION_BITS))
+}
+
+#[cfg(not(unix))]
+pub fn get_file_mode(_path: &Path) -> Option<String> {
+    None
+}
+
+#[cfg(unix)]
+pub fn set_file_mode(path: &Path, mode: u32) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode))
+        .context("std::fs::set_permissions() failed.")
+}
+
+#[cfg(not(unix))]
+pub fn set_file_mode(_path: &Path, _mode: u32) -> Result<()> {
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -181,6 +760,299 @@ mod tests {
         get_last_modified(&PathBuf::from("test_data/5byte.dat")).await;
     }
 
+    #[test]
+    fn parse_md5_xattr_value_test() {
+        init_dummy_tracing_subscriber();
+
+        assert_eq!(
+            parse_md5_xattr_value("D41D8CD98F00B204E9800998ECF8427E:0:0", 0, 0).unwrap(),
+            "d41d8cd98f00b204e9800998ecf8427e"
+        );
+        assert_eq!(
+            parse_md5_xattr_value("d41d8cd98f00b204e9800998ecf8427e:5:100\n", 5, 100).unwrap(),
+            "d41d8cd98f00b204e9800998ecf8427e"
+        );
+
+        assert!(parse_md5_xattr_value("d41d8cd98f00b204e9800998ecf8427e:5:100", 6, 100).is_none());
+        assert!(parse_md5_xattr_value("d41d8cd98f00b204e9800998ecf8427e:5:100", 5, 101).is_none());
+        assert!(parse_md5_xattr_value("d41d8cd98f00b204e9800998ecf8427e:5", 5, 100).is_none());
+        // a bare MD5 digest is not trusted.
+        assert!(parse_md5_xattr_value("d41d8cd98f00b204e9800998ecf8427e", 0, 0).is_none());
+        assert!(parse_md5_xattr_value("d41d8cd98f00b204e9800998ecf842:0:0", 0, 0).is_none());
+        assert!(parse_md5_xattr_value("z41d8cd98f00b204e9800998ecf8427e:0:0", 0, 0).is_none());
+        assert!(parse_md5_xattr_value("", 0, 0).is_none());
+    }
+
+    #[tokio::test]
+    async fn create_partial_file_and_remove_stale() {
+        init_dummy_tracing_subscriber();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = PathBuf::from(format!("{}/", temp_dir.path().to_string_lossy()));
+
+        let partial_file = create_partial_file_from_key(&path, "dir1/data1", ".partial")
+            .await
+            .unwrap();
+        let partial_path = partial_file.path().to_path_buf();
+        assert_eq!(
+            partial_path,
+            key_to_partial_file_path(path.clone(), "dir1/data1", ".partial")
+        );
+        assert!(is_partial_file(&partial_path, ".partial"));
+
+        partial_file.keep().unwrap();
+        std::fs::write(key_to_file_path(path.clone(), "dir1/data2"), b"data").unwrap();
+
+        // a file of the user that has the suffix is not marked.
+        let user_file_path = key_to_file_path(path.clone(), "dir1/report.partial");
+        std::fs::write(&user_file_path, b"data").unwrap();
+
+        let old = FileTime::from_system_time(SystemTime::now() - Duration::from_secs(7200));
+        set_file_mtime(&partial_path, old).unwrap();
+        set_file_mtime(&user_file_path, old).unwrap();
+
+        // some file systems(e.g. tmpfs without user xattr) do not support extended attributes.
+        if !is_marked_partial_file(&partial_path) {
+            assert_eq!(
+                remove_stale_partial_files(&path, ".partial", Duration::from_secs(3600), false)
+                    .unwrap(),
+                0
+            );
+            return;
+        }
+
+        // a partial file of another running s3sync is kept.
+        assert_eq!(
+            remove_stale_partial_files(&path, ".partial", Duration::from_secs(86400), false)
+                .unwrap(),
+            0
+        );
+        assert!(partial_path.exists());
+
+        assert_eq!(
+            remove_stale_partial_files(&path, ".partial", Duration::from_secs(3600), true).unwrap(),
+            1
+        );
+        assert!(partial_path.exists());
+
+        assert_eq!(
+            remove_stale_partial_files(&path, ".partial", Duration::from_secs(3600), false)
+                .unwrap(),
+            1
+        );
+        assert!(!partial_path.exists());
+        assert!(key_to_file_path(path, "dir1/data2").exists());
+        assert!(user_file_path.exists());
+    }
+
+    #[tokio::test]
+    #[cfg(target_os = "linux")]
+    async fn unmark_partial_file_test() {
+        init_dummy_tracing_subscriber();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = PathBuf::from(format!("{}/", temp_dir.path().to_string_lossy()));
+
+        let partial_file = create_partial_file_from_key(&path, "data1", ".partial")
+            .await
+            .unwrap();
+        if !is_marked_partial_file(partial_file.path()) {
+            return;
+        }
+
+        unmark_partial_file(partial_file.path());
+        assert!(!is_marked_partial_file(partial_file.path()));
+    }
+
+    #[tokio::test]
+    async fn remove_stale_temp_files_test() {
+        init_dummy_tracing_subscriber();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = PathBuf::from(format!("{}/", temp_dir.path().to_string_lossy()));
+
+        let stale_temp_file = create_temp_file_from_key(&path, "dir1/data1", None)
+            .await
+            .unwrap();
+        let (_, stale_temp_path) = stale_temp_file.keep().unwrap();
+        assert!(is_temp_file(&stale_temp_path));
+        set_file_last_modified(&stale_temp_path, 1_700_000_000, 0).unwrap();
+
+        let fresh_temp_file = create_temp_file_from_key(&path, "dir1/data2", None)
+            .await
+            .unwrap();
+        let (_, fresh_temp_path) = fresh_temp_file.keep().unwrap();
+
+        std::fs::write(key_to_file_path(path.clone(), "dir1/data3"), b"data").unwrap();
+        set_file_last_modified(
+            &key_to_file_path(path.clone(), "dir1/data3"),
+            1_700_000_000,
+            0,
+        )
+        .unwrap();
+
+        // a file of the user that has the prefix is not marked.
+        let user_file_path = key_to_file_path(path.clone(), "dir1/.s3sync-tmp-user");
+        std::fs::write(&user_file_path, b"data").unwrap();
+        set_file_last_modified(&user_file_path, 1_700_000_000, 0).unwrap();
+
+        let older_than = Duration::from_secs(3600);
+
+        // some file systems(e.g. tmpfs without user xattr) do not support extended attributes.
+        if !is_marked_temp_file(&stale_temp_path) {
+            assert_eq!(
+                remove_stale_temp_files(&path, usize::MAX, older_than, false).unwrap(),
+                0
+            );
+            return;
+        }
+
+        // the temp files are not directly under the path.
+        assert_eq!(
+            remove_stale_temp_files(&path, 1, older_than, false).unwrap(),
+            0
+        );
+
+        assert_eq!(
+            remove_stale_temp_files(&path, usize::MAX, older_than, true).unwrap(),
+            1
+        );
+        assert!(stale_temp_path.exists());
+
+        assert_eq!(
+            remove_stale_temp_files(&path, usize::MAX, older_than, false).unwrap(),
+            1
+        );
+        assert!(!stale_temp_path.exists());
+        assert!(fresh_temp_path.exists());
+        assert!(key_to_file_path(path, "dir1/data3").exists());
+        assert!(user_file_path.exists());
+    }
+
+    #[tokio::test]
+    async fn partial_file_removed_when_dropped() {
+        init_dummy_tracing_subscriber();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = PathBuf::from(format!("{}/", temp_dir.path().to_string_lossy()));
+
+        let partial_file = create_partial_file_from_key(&path, "data1", ".partial")
+            .await
+            .unwrap();
+        let partial_path = partial_file.path().to_path_buf();
+        assert!(partial_path.exists());
+
+        drop(partial_file);
+        assert!(!partial_path.exists());
+    }
+
+    #[test]
+    fn parse_checksum_cache_value_test() {
+        init_dummy_tracing_subscriber();
+
+        assert_eq!(
+            parse_checksum_cache_value("SHA256:abc+/=-2:5:100\n", "SHA256", 5, 100).unwrap(),
+            "abc+/=-2"
+        );
+
+        assert!(parse_checksum_cache_value("SHA256:abc:5:100", "CRC32", 5, 100).is_none());
+        assert!(parse_checksum_cache_value("SHA256:abc:5:100", "SHA256", 6, 100).is_none());
+        assert!(parse_checksum_cache_value("SHA256:abc:5:100", "SHA256", 5, 101).is_none());
+        assert!(parse_checksum_cache_value("SHA256::5:100", "SHA256", 5, 100).is_none());
+        assert!(parse_checksum_cache_value("SHA256:abc:5", "SHA256", 5, 100).is_none());
+        assert!(parse_checksum_cache_value("", "SHA256", 5, 100).is_none());
+    }
+
+    #[test]
+    fn preallocate_file_test() {
+        init_dummy_tracing_subscriber();
+
+        let temp_file = NamedTempFile::new().unwrap();
+
+        // some file systems do not support preallocation.
+        if !preallocate_file(temp_file.as_file(), 1024).unwrap() {
+            return;
+        }
+
+        assert_eq!(temp_file.as_file().metadata().unwrap().len(), 1024);
+    }
+
+    #[test]
+    fn sync_parent_directory_test() {
+        init_dummy_tracing_subscriber();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("data1");
+        std::fs::write(&file_path, b"12345").unwrap();
+
+        sync_parent_directory(&file_path).unwrap();
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn sync_parent_directory_not_found() {
+        init_dummy_tracing_subscriber();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("no_such_dir").join("data1");
+
+        assert!(sync_parent_directory(&file_path).is_err());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn set_and_get_file_mode() {
+        init_dummy_tracing_subscriber();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("data1");
+        std::fs::write(&file_path, b"12345").unwrap();
+
+        set_file_mode(&file_path, 0o640).unwrap();
+        assert_eq!(get_file_mode(&file_path).unwrap(), "640");
+
+        set_file_mode(&file_path, 0o4755).unwrap();
+        assert_eq!(get_file_mode(&file_path).unwrap(), "755");
+
+        set_file_mode(&file_path, 0o755).unwrap();
+        assert_eq!(get_file_mode(&file_path).unwrap(), "755");
+
+        assert!(get_file_mode(&temp_dir.path().join("no_such_file")).is_none());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn set_and_get_cached_checksum() {
+        init_dummy_tracing_subscriber();
+
+        let temp_file = NamedTempFile::new().unwrap();
+        std::fs::write(temp_file.path(), b"12345").unwrap();
+
+        // some file systems(e.g. tmpfs without user xattr) do not support extended attributes.
+        if !set_cached_checksum(temp_file.path(), "SHA256", "checksum") {
+            return;
+        }
+
+        assert_eq!(
+            get_cached_checksum(temp_file.path(), "SHA256").unwrap(),
+            "checksum"
+        );
+        assert!(get_cached_checksum(temp_file.path(), "CRC32").is_none());
+
+        std::fs::write(temp_file.path(), b"123456").unwrap();
+        assert!(get_cached_checksum(temp_file.path(), "SHA256").is_none());
+    }
+
+    #[test]
+    fn get_md5_from_xattr_not_found() {
+        init_dummy_tracing_subscriber();
+
+        assert!(get_md5_from_xattr(Path::new("test_data/5byte.dat"), "user.s3sync.md5").is_none());
+        assert!(
+            get_md5_from_xattr(Path::new("test_data/no_such_file"), "user.s3sync.md5").is_none()
+        );
+    }
+
     #[test]
     fn is_key_directory_test() {
         init_dummy_tracing_subscriber();
@@ -213,11 +1085,49 @@ mod tests {
     async fn create_temp_file_from_key_test() {
         init_dummy_tracing_subscriber();
 
-        create_temp_file_from_key(Path::new("playground/"), "tempdir/filename")
+        create_temp_file_from_key(Path::new("playground/"), "tempdir/filename", None)
             .await
             .unwrap();
     }
 
+    #[tokio::test]
+    async fn create_temp_file_in_local_tmp_dir() {
+        init_dummy_tracing_subscriber();
+
+        let target_dir = tempfile::tempdir().unwrap();
+        let local_tmp_dir = tempfile::tempdir().unwrap();
+
+        let target_path = PathBuf::from(format!("{}/", target_dir.path().to_string_lossy()));
+
+        let temp_file =
+            create_temp_file_from_key(&target_path, "dir/filename", Some(local_tmp_dir.path()))
+                .await
+                .unwrap();
+        assert_eq!(temp_file.path().parent().unwrap(), local_tmp_dir.path());
+        assert!(target_dir.path().join("dir").is_dir());
+
+        std::fs::write(temp_file.path(), b"data").unwrap();
+        let real_path = target_dir.path().join("dir/filename");
+        persist_temp_file(temp_file, &real_path).unwrap();
+
+        assert_eq!(std::fs::read(&real_path).unwrap(), b"data");
+        assert!(!is_marked_temp_file(&real_path));
+        assert_eq!(std::fs::read_dir(local_tmp_dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn is_cross_device_error_test() {
+        init_dummy_tracing_subscriber();
+
+        assert!(is_cross_device_error(&std::io::Error::from_raw_os_error(
+            libc::EXDEV
+        )));
+        assert!(!is_cross_device_error(&std::io::Error::from_raw_os_error(
+            libc::ENOENT
+        )));
+    }
+
     #[tokio::test]
     #[cfg(target_family = "unix")]
     async fn create_directory_hierarchy_from_key_unix() {
@@ -790,6 +1700,54 @@ mod tests {
         assert_eq!(mtime.secs(), 777);
     }
 
+    #[test]
+    fn resolve_path_conflict_test() {
+        init_dummy_tracing_subscriber();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = PathBuf::from(format!("{}/", temp_dir.path().to_string_lossy()));
+        std::fs::write(temp_dir.path().join("foo"), b"file").unwrap();
+        std::fs::create_dir(temp_dir.path().join("dir")).unwrap();
+
+        assert_eq!(resolve_path_conflict(&path, "bar/baz"), None);
+        assert_eq!(resolve_path_conflict(&path, "foo"), None);
+        assert_eq!(resolve_path_conflict(&path, "dir/"), None);
+        assert_eq!(resolve_path_conflict(&path, "dir/foo"), None);
+
+        // file exists where a directory is needed.
+        assert_eq!(
+            resolve_path_conflict(&path, "foo/bar"),
+            Some("foo (1)/bar".to_string())
+        );
+        assert_eq!(
+            resolve_path_conflict(&path, "/foo/"),
+            Some("foo (1)/".to_string())
+        );
+
+        // directory exists where a file is needed.
+        assert_eq!(
+            resolve_path_conflict(&path, "dir"),
+            Some("dir (1)".to_string())
+        );
+
+        // the renamed directory is reused, and a conflicting renamed file is skipped.
+        std::fs::create_dir(temp_dir.path().join("foo (1)")).unwrap();
+        std::fs::write(temp_dir.path().join("dir (1)"), b"file").unwrap();
+        std::fs::create_dir(temp_dir.path().join("dir (2)")).unwrap();
+        assert_eq!(
+            resolve_path_conflict(&path, "foo/bar"),
+            Some("foo (1)/bar".to_string())
+        );
+        assert_eq!(
+            resolve_path_conflict(&path, "dir (1)/bar"),
+            Some("dir (1) (1)/bar".to_string())
+        );
+        assert_eq!(
+            resolve_path_conflict(&path, "dir"),
+            Some("dir (1)".to_string())
+        );
+    }
+
     #[test]
     fn remove_root_slash_test() {
         init_dummy_tracing_subscriber();
@@ -1,8 +1,10 @@
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
 use std::io;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use std::str::FromStr;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use anyhow::{anyhow, Context, Result};
 use async_channel::Sender;
@@ -19,8 +21,8 @@ use aws_sdk_s3::operation::put_object_tagging::PutObjectTaggingOutput;
 use aws_sdk_s3::primitives::ByteStream;
 use aws_sdk_s3::primitives::DateTime;
 use aws_sdk_s3::types::{
-    ChecksumAlgorithm, ChecksumMode, Object, ObjectPart, ObjectVersion, ServerSideEncryption,
-    StorageClass, Tagging,
+    ChecksumAlgorithm, ChecksumMode, MultipartUpload, Object, ObjectPart, ObjectVersion,
+    ServerSideEncryption, StorageClass, Tagging,
 };
 use aws_sdk_s3::Client;
 use aws_smithy_runtime_api::client::result::SdkError;
@@ -30,25 +32,34 @@ use aws_smithy_types_convert::date_time::DateTimeExt;
 use leaky_bucket::RateLimiter;
 use tokio::io::BufReader;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt};
+use tokio::sync::Semaphore;
 use tracing::{debug, info, trace, warn};
 use walkdir::{DirEntry, WalkDir};
 
 use crate::config::ClientConfig;
 use crate::storage::additional_checksum_verify::{
     generate_checksum_from_path, generate_checksum_from_path_with_chunksize,
+    is_checksum_verify_sampled,
 };
 use crate::storage::e_tag_verify::{
-    generate_e_tag_hash_from_path, generate_e_tag_hash_from_path_with_auto_chunksize,
-    is_multipart_upload_e_tag, verify_e_tag,
+    generate_e_tag_hash_from_path, generate_e_tag_hash_from_path_or_md5_xattr,
+    generate_e_tag_hash_from_path_with_auto_chunksize, is_multipart_upload_e_tag, verify_e_tag,
 };
 use crate::storage::{
-    convert_to_buf_byte_stream_with_callback, get_size_string_from_content_range, Storage,
-    StorageFactory, StorageTrait,
+    acquire_checksum_permit, convert_to_buf_byte_stream_with_callback, display_checksum,
+    get_size_string_from_content_range, Storage, StorageFactory, StorageLimiters, StorageTrait,
 };
 use crate::types::error::S3syncError;
 use crate::types::token::PipelineCancellationToken;
-use crate::types::SyncStatistics::{ChecksumVerified, ETagVerified, SyncBytes, SyncWarning};
-use crate::types::{ObjectChecksum, S3syncObject, SseCustomerKey, StoragePath, SyncStatistics};
+use crate::types::SyncStatistics::{
+    ChecksumVerified, ChecksumVerifyNotSampled, ContentLengthVerified, ETagVerified, SyncBytes,
+    SyncWarning,
+};
+use crate::types::{
+    normalize_key, parse_file_mode, HardlinkMode, ObjectChecksum, S3syncObject, SseCustomerKey,
+    StoragePath, SyncStatistics, UploadManifest, S3SYNC_FILE_MODE_METADATA_KEY,
+    S3SYNC_HARDLINK_TARGET_METADATA_KEY,
+};
 use crate::Config;
 
 pub mod fs_util;
@@ -58,6 +69,9 @@ const MISMATCH_WARNING_WITH_HELP: &str = "mismatch. object in the local storage
  To suppress this warning, please add --disable-multipart-verify command line option. \
  To resolve this issue, please add --auto-chunksize command line option(but extra API overheads).";
 
+const HARDLINK_TARGET_WAIT_INTERVAL_MILLISECONDS: u64 = 100;
+const HARDLINK_TARGET_WAIT_TIMEOUT_MILLISECONDS: u64 = 60 * 1000;
+
 pub struct LocalStorageFactory {}
 
 #[async_trait]
@@ -68,18 +82,9 @@ impl StorageFactory for LocalStorageFactory {
         cancellation_token: PipelineCancellationToken,
         stats_sender: Sender<SyncStatistics>,
         _client_config: Option<ClientConfig>,
-        rate_limit_objects_per_sec: Option<Arc<RateLimiter>>,
-        rate_limit_bandwidth: Option<Arc<RateLimiter>>,
+        limiters: StorageLimiters,
     ) -> Storage {
-        LocalStorage::create(
-            config,
-            path,
-            cancellation_token,
-            stats_sender,
-            rate_limit_objects_per_sec,
-            rate_limit_bandwidth,
-        )
-        .await
+        LocalStorage::create(config, path, cancellation_token, stats_sender, limiters).await
     }
 }
 
@@ -91,6 +96,11 @@ struct LocalStorage {
     stats_sender: Sender<SyncStatistics>,
     rate_limit_objects_per_sec: Option<Arc<RateLimiter>>,
     rate_limit_bandwidth: Option<Arc<RateLimiter>>,
+    checksum_semaphore: Option<Arc<Semaphore>>,
+    // with --normalize-unicode, normalized key -> real key of the listed files whose names are not normalized.
+    real_keys: Arc<Mutex<HashMap<String, String>>>,
+    // with --hardlink-mode dedup-metadata, key of the subsequent link -> key of the first listed link.
+    hardlink_targets: Arc<Mutex<HashMap<String, String>>>,
 }
 
 impl LocalStorage {
@@ -99,8 +109,7 @@ impl LocalStorage {
         path: StoragePath,
         cancellation_token: PipelineCancellationToken,
         stats_sender: Sender<SyncStatistics>,
-        rate_limit_objects_per_sec: Option<Arc<RateLimiter>>,
-        rate_limit_bandwidth: Option<Arc<RateLimiter>>,
+        limiters: StorageLimiters,
     ) -> Storage {
         let local_path = if let StoragePath::Local(local_path) = path {
             local_path
@@ -113,13 +122,363 @@ impl LocalStorage {
             path: local_path,
             cancellation_token,
             stats_sender,
-            rate_limit_objects_per_sec,
-            rate_limit_bandwidth,
+            rate_limit_objects_per_sec: limiters.rate_limit_objects_per_sec,
+            rate_limit_bandwidth: limiters.rate_limit_bandwidth,
+            checksum_semaphore: limiters.checksum_semaphore,
+            real_keys: Arc::new(Mutex::new(HashMap::new())),
+            hardlink_targets: Arc::new(Mutex::new(HashMap::new())),
         };
 
         Box::new(storage)
     }
 
+    // with --normalize-unicode, the key is normalized and resolved to the real key of the listed file, if any.
+    fn resolve_key(&self, key: &str) -> String {
+        let normalization = self.config.filter_config.normalize_unicode;
+        if normalization.is_none() {
+            return key.to_string();
+        }
+
+        let normalized_key = normalize_key(key, normalization);
+        self.real_keys
+            .lock()
+            .unwrap()
+            .get(&normalized_key)
+            .cloned()
+            .unwrap_or(normalized_key)
+    }
+
+    // the first link may be being downloaded by another worker, so the link is deferred until it
+    // has been downloaded. if it has not been downloaded in time, the link is reported as an error.
+    async fn wait_for_hardlink_target(
+        &self,
+        key: &str,
+        hardlink_target: &str,
+        target_path: &Path,
+    ) -> Result<()> {
+        let start = tokio::time::Instant::now();
+        while !target_path.is_file() {
+            if self.cancellation_token.is_cancelled() {
+                return Err(anyhow!(S3syncError::Cancelled));
+            }
+
+            if Duration::from_millis(HARDLINK_TARGET_WAIT_TIMEOUT_MILLISECONDS) <= start.elapsed() {
+                return Err(anyhow!(
+                    "the target of the hard link has not been downloaded. key={}, hardlink_target={}.",
+                    key,
+                    hardlink_target
+                ));
+            }
+
+            tokio::time::sleep(Duration::from_millis(
+                HARDLINK_TARGET_WAIT_INTERVAL_MILLISECONDS,
+            ))
+            .await;
+        }
+
+        Ok(())
+    }
+
+    async fn create_hardlink(&self, key: &str, hardlink_target: &str) -> Result<PutObjectOutput> {
+        if fs_util::check_directory_traversal(hardlink_target) {
+            return Err(anyhow!(S3syncError::DirectoryTraversalError));
+        }
+
+        let target_path =
+            fs_util::key_to_file_path(self.path.to_path_buf(), &self.resolve_key(hardlink_target));
+        let link_path = fs_util::key_to_file_path(self.path.to_path_buf(), key);
+
+        self.wait_for_hardlink_target(key, hardlink_target, &target_path)
+            .await?;
+
+        if let Some(parent) = link_path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        // the existing file is replaced with the hard link.
+        if let Err(e) = tokio::fs::remove_file(&link_path).await {
+            if e.kind() != io::ErrorKind::NotFound {
+                return Err(e.into());
+            }
+        }
+        tokio::fs::hard_link(&target_path, &link_path)
+            .await
+            .context("tokio::fs::hard_link() failed.")?;
+
+        debug!(
+            key = key,
+            hardlink_target = hardlink_target,
+            "hard link has been created."
+        );
+
+        Ok(PutObjectOutput::builder().build())
+    }
+
+    // with --upload-manifest, objects are listed from the manifest instead of walking the directory.
+    async fn list_objects_from_upload_manifest(
+        &self,
+        upload_manifest: &UploadManifest,
+        sender: &Sender<S3syncObject>,
+        warn_as_error: bool,
+    ) -> Result<()> {
+        for key in &upload_manifest.keys {
+            if self.cancellation_token.is_cancelled() {
+                trace!("list() canceled.");
+                break;
+            }
+
+            let path = fs_util::key_to_source_file_path(
+                self.path.clone(),
+                key,
+                &self.config.upload_manifest,
+            );
+            let lossy_path = path.to_string_lossy().to_string();
+
+            let metadata = match tokio::fs::metadata(&path).await {
+                Ok(metadata) if metadata.is_file() => metadata,
+                result => {
+                    self.send_stats(SyncWarning { key: key.clone() }).await;
+
+                    let error = result
+                        .err()
+                        .map(|e| e.to_string())
+                        .unwrap_or_else(|| "not a file.".to_string());
+                    warn!(
+                        key = key,
+                        path = lossy_path,
+                        error = error,
+                        "failed to access the file in the upload manifest. skipping."
+                    );
+
+                    if warn_as_error {
+                        return Err(anyhow!(
+                            "failed to access the file in the upload manifest: {}.",
+                            lossy_path
+                        ));
+                    }
+                    continue;
+                }
+            };
+
+            let _checksum_permit = acquire_checksum_permit(&self.checksum_semaphore).await;
+            let e_tag = if self.config.filter_config.check_etag
+                && !self.config.transfer_config.auto_chunksize
+                && !self.config.filter_config.remove_modified_filter
+                && self.config.filter_config.check_checksum_algorithm.is_none()
+            {
+                Some(
+                    generate_e_tag_hash_from_path_or_md5_xattr(
+                        &path,
+                        self.config.transfer_config.multipart_chunksize as usize,
+                        self.config.transfer_config.multipart_threshold as usize,
+                        &self.config.md5_xattr,
+                    )
+                    .await?,
+                )
+            } else {
+                None
+            };
+
+            let object = S3syncObject::NotVersioning(
+                Object::builder()
+                    .key(key)
+                    .size(i64::try_from(metadata.len()).unwrap())
+                    .last_modified(DateTime::from(metadata.modified().unwrap()))
+                    .set_e_tag(e_tag)
+                    .set_checksum_algorithm(
+                        self.config
+                            .additional_checksum_algorithm
+                            .clone()
+                            .map(|algorithm| vec![algorithm]),
+                    )
+                    .build(),
+            );
+            if let Err(e) = sender
+                .send(object)
+                .await
+                .context("async_channel::Sender::send() failed.")
+            {
+                return if !sender.is_closed() { Err(e) } else { Ok(()) };
+            }
+        }
+
+        Ok(())
+    }
+
+    // the keys are relative to the source directory, even if the walk starts from its sub directory.
+    async fn walk_and_send_objects(
+        &self,
+        root: &Path,
+        sender: &Sender<S3syncObject>,
+        warn_as_error: bool,
+    ) -> Result<()> {
+        let normalization = self.config.filter_config.normalize_unicode;
+        let mut listed_keys = HashSet::new();
+        // (dev, ino) -> key of the first listed link
+        let mut hardlink_first_keys: HashMap<(u64, u64), String> = HashMap::new();
+
+        let mut walk_dir = WalkDir::new(root).follow_links(self.config.follow_symlinks);
+        if self.config.non_recursive {
+            // only the files directly under the directory.
+            walk_dir = walk_dir.max_depth(1);
+        }
+
+        for entry in walk_dir {
+            if let Err(e) = entry {
+                if let Some(inner) = e.io_error() {
+                    if inner.kind() == io::ErrorKind::NotFound {
+                        continue;
+                    }
+                }
+
+                let path = e
+                    .path()
+                    .unwrap_or_else(|| Path::new(""))
+                    .to_string_lossy()
+                    .to_string();
+                self.send_stats(SyncWarning {
+                    key: path.to_string(),
+                })
+                .await;
+
+                let error = e.to_string();
+                warn!(path = path, error = error, "failed to list local files.");
+
+                if warn_as_error {
+                    return Err(anyhow!("failed to list(): {:?}.", e));
+                }
+                continue;
+            }
+
+            if !self
+                .check_dir_entry(entry.as_ref().unwrap(), warn_as_error)
+                .await?
+            {
+                continue;
+            }
+
+            if let Some(partial_suffix) = &self.config.partial_suffix {
+                if fs_util::is_partial_file(entry.as_ref().unwrap().path(), partial_suffix) {
+                    let path = entry.as_ref().unwrap().path().to_string_lossy().to_string();
+                    debug!(path = path, "partial file is skipped.");
+                    continue;
+                }
+            }
+
+            if self.cancellation_token.is_cancelled() {
+                trace!("list() canceled.");
+                break;
+            }
+
+            let mut path = remove_local_path_prefix(
+                entry.as_ref().unwrap().path().to_str().unwrap(),
+                self.path.to_str().unwrap(),
+            );
+
+            if cfg!(windows) {
+                path = convert_windows_directory_char_to_slash(&path);
+            }
+
+            if normalization.is_some() {
+                let normalized_key = normalize_key(&path, normalization);
+                if !listed_keys.insert(normalized_key.clone()) {
+                    self.send_stats(SyncWarning { key: path.clone() }).await;
+                    warn!(
+                        path = path,
+                        key = normalized_key,
+                        "another file has the same normalized key. skipping."
+                    );
+
+                    if warn_as_error {
+                        return Err(anyhow!("duplicate normalized key: {}.", normalized_key));
+                    }
+                    continue;
+                }
+
+                if normalized_key != path {
+                    self.real_keys
+                        .lock()
+                        .unwrap()
+                        .insert(normalized_key.clone(), path);
+                    path = normalized_key;
+                }
+            }
+
+            let mut is_subsequent_hardlink = false;
+            if self.config.hardlink_mode != HardlinkMode::Copy {
+                let hardlink_id = entry
+                    .as_ref()
+                    .unwrap()
+                    .metadata()
+                    .ok()
+                    .and_then(|metadata| fs_util::get_hardlink_id(&metadata));
+
+                if let Some(hardlink_id) = hardlink_id {
+                    if let Some(first_key) = hardlink_first_keys.get(&hardlink_id) {
+                        if self.config.hardlink_mode == HardlinkMode::Skip {
+                            self.send_stats(SyncWarning { key: path.clone() }).await;
+                            warn!(
+                                key = path,
+                                hardlink_target = first_key,
+                                "hard link of an already listed file. skipping."
+                            );
+                            continue;
+                        }
+
+                        self.hardlink_targets
+                            .lock()
+                            .unwrap()
+                            .insert(path.clone(), first_key.clone());
+                        is_subsequent_hardlink = true;
+                    } else {
+                        hardlink_first_keys.insert(hardlink_id, path.clone());
+                    }
+                }
+            }
+
+            let _checksum_permit = acquire_checksum_permit(&self.checksum_semaphore).await;
+            let e_tag = if self.config.filter_config.check_etag
+                && !self.config.transfer_config.auto_chunksize
+                && !self.config.filter_config.remove_modified_filter
+                && self.config.filter_config.check_checksum_algorithm.is_none()
+            {
+                Some(
+                    generate_e_tag_hash_from_path_or_md5_xattr(
+                        &PathBuf::from(entry.as_ref().unwrap().path()),
+                        self.config.transfer_config.multipart_chunksize as usize,
+                        self.config.transfer_config.multipart_threshold as usize,
+                        &self.config.md5_xattr,
+                    )
+                    .await?,
+                )
+            } else {
+                None
+            };
+
+            let mut object = build_object_from_dir_entry(
+                entry.as_ref().unwrap(),
+                &path,
+                e_tag,
+                self.config.additional_checksum_algorithm.clone(),
+            );
+            if is_subsequent_hardlink {
+                // uploaded as an empty object with the metadata pointer to the first link.
+                object.size = Some(0);
+            }
+
+            let object = S3syncObject::NotVersioning(object);
+            if let Err(e) = sender
+                .send(object)
+                .await
+                .context("async_channel::Sender::send() failed.")
+            {
+                return if !sender.is_closed() { Err(e) } else { Ok(()) };
+            }
+        }
+
+        Ok(())
+    }
+
     async fn check_dir_entry(&self, entry: &DirEntry, warn_as_error: bool) -> Result<bool> {
         if entry.file_type().is_dir() || entry.file_type().is_symlink() {
             return Ok(false);
@@ -172,6 +531,69 @@ impl LocalStorage {
         }
     }
 
+    fn get_overridden_content_type(&self, path: &Path) -> Option<String> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+
+        self.config
+            .content_type_override_map
+            .as_ref()?
+            .get(&extension)
+            .cloned()
+    }
+
+    fn get_mapped_content_encoding(&self, path: &Path) -> Option<String> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+
+        self.config
+            .content_encoding_map
+            .as_ref()?
+            .get(&extension)
+            .cloned()
+    }
+
+    // with --put-file-mode-metadata, the permission bits of the file are put to the metadata.
+    fn get_file_mode_metadata(&self, path: &Path) -> Option<HashMap<String, String>> {
+        if !self.config.put_file_mode_metadata {
+            return None;
+        }
+
+        let file_mode = fs_util::get_file_mode(path)?;
+        Some(HashMap::from([(
+            S3SYNC_FILE_MODE_METADATA_KEY.to_string(),
+            file_mode,
+        )]))
+    }
+
+    // --download-file-mode takes a fixed mode. with --preserve-file-mode, the mode is taken from
+    // the metadata, and the file without it is created with the default permission.
+    fn get_download_file_mode(
+        &self,
+        key: &str,
+        get_object_output: &GetObjectOutput,
+    ) -> Option<u32> {
+        if self.config.download_file_mode.is_some() {
+            return self.config.download_file_mode;
+        }
+        if !self.config.preserve_file_mode {
+            return None;
+        }
+
+        let file_mode = get_object_output
+            .metadata()
+            .and_then(|metadata| metadata.get(S3SYNC_FILE_MODE_METADATA_KEY))?;
+        let Some(parsed_file_mode) = parse_file_mode(file_mode) else {
+            warn!(
+                key = key,
+                file_mode = file_mode,
+                "invalid file mode in the metadata. the default permission is used."
+            );
+            return None;
+        };
+
+        // anyone who can write the bucket controls the metadata, so setuid/setgid/sticky are not restored.
+        Some(parsed_file_mode & fs_util::FILE_PERMISSION_BITS)
+    }
+
     // I can't find a way to simplify this function.
     #[allow(clippy::too_many_arguments)]
     async fn verify_local_file(
@@ -187,7 +609,11 @@ impl LocalStorage {
         target_object_parts: Option<Vec<ObjectPart>>,
         target_content_length: u64,
         source_express_onezone_storage: bool,
-    ) -> Result<()> {
+    ) -> Result<bool> {
+        let mut verified = true;
+        let mut computed_e_tag = None;
+        let mut computed_checksum = None;
+
         if !self.config.disable_etag_verify && !source_express_onezone_storage {
             trace!(
                 key = key,
@@ -196,6 +622,7 @@ impl LocalStorage {
             );
 
             let target_sse = None;
+            let _checksum_permit = acquire_checksum_permit(&self.checksum_semaphore).await;
             let target_e_tag = if let Some(parts) = target_object_parts.as_ref() {
                 Some(
                     generate_e_tag_hash_from_path_with_auto_chunksize(
@@ -238,6 +665,8 @@ impl LocalStorage {
                             "skip e_tag verification."
                         );
                     } else {
+                        verified = false;
+
                         self.send_stats(SyncWarning {
                             key: key.to_string(),
                         })
@@ -283,7 +712,11 @@ impl LocalStorage {
                     );
                 }
             }
+
+            computed_e_tag = target_e_tag;
         } else if source_content_length != target_content_length {
+            verified = false;
+
             self.send_stats(SyncWarning {
                 key: key.to_string(),
             })
@@ -295,8 +728,37 @@ impl LocalStorage {
                 target_content_length = target_content_length,
                 "content length mismatch. file in the local storage may be corrupted."
             );
+        } else if self.config.verify_content_length_only {
+            self.send_stats(ContentLengthVerified {
+                key: key.to_string(),
+            })
+            .await;
+
+            trace!(
+                key = key,
+                source_content_length = source_content_length,
+                target_content_length = target_content_length,
+                "content length verified."
+            );
         }
 
+        let source_final_checksum = match source_final_checksum {
+            Some(_) if !is_checksum_verify_sampled(key, self.config.checksum_verify_sampling) => {
+                trace!(
+                    key = key,
+                    "additional checksum verification is not sampled."
+                );
+
+                self.send_stats(ChecksumVerifyNotSampled {
+                    key: key.to_string(),
+                })
+                .await;
+
+                None
+            }
+            source_final_checksum => source_final_checksum,
+        };
+
         if let Some(source_final_checksum) = source_final_checksum {
             trace!(
                 key = key,
@@ -313,6 +775,7 @@ impl LocalStorage {
                 vec![source_content_length as i64]
             };
 
+            let _checksum_permit = acquire_checksum_permit(&self.checksum_semaphore).await;
             let target_final_checksum = generate_checksum_from_path(
                 real_path,
                 source_checksum_algorithm.as_ref().unwrap().clone(),
@@ -325,6 +788,8 @@ impl LocalStorage {
                 source_checksum_algorithm.as_ref().unwrap().as_str();
 
             if source_final_checksum != target_final_checksum {
+                verified = false;
+
                 self.send_stats(SyncWarning {
                     key: key.to_string(),
                 })
@@ -350,9 +815,33 @@ impl LocalStorage {
                     target_final_checksum = target_final_checksum,
                     "additional checksum verified."
                 );
+
+                if self.config.checksum_on_download_only_changed
+                    && !fs_util::set_cached_checksum(
+                        real_path,
+                        additional_checksum_algorithm,
+                        &target_final_checksum,
+                    )
+                {
+                    debug!(key = key, "failed to cache the verified checksum.");
+                }
             }
+
+            computed_checksum = Some(target_final_checksum);
         }
-        Ok(())
+
+        if self.config.checksum_display {
+            display_checksum(
+                key,
+                computed_e_tag.as_deref(),
+                source_checksum_algorithm
+                    .as_ref()
+                    .map(|algorithm| algorithm.as_str()),
+                computed_checksum.as_deref(),
+            );
+        }
+
+        Ok(verified)
     }
 }
 
@@ -372,87 +861,72 @@ impl StorageTrait for LocalStorage {
         _max_keys: i32,
         warn_as_error: bool,
     ) -> Result<()> {
-        for entry in WalkDir::new(&self.path).follow_links(self.config.follow_symlinks) {
-            if let Err(e) = entry {
-                if let Some(inner) = e.io_error() {
-                    if inner.kind() == io::ErrorKind::NotFound {
-                        continue;
-                    }
-                }
-
-                let path = e
-                    .path()
-                    .unwrap_or_else(|| Path::new(""))
-                    .to_string_lossy()
-                    .to_string();
-                self.send_stats(SyncWarning {
-                    key: path.to_string(),
-                })
+        if let Some(upload_manifest) = &self.config.upload_manifest {
+            return self
+                .list_objects_from_upload_manifest(upload_manifest, sender, warn_as_error)
                 .await;
+        }
 
-                let error = e.to_string();
-                warn!(path = path, error = error, "failed to list local files.");
-
-                if warn_as_error {
-                    return Err(anyhow!("failed to list(): {:?}.", e));
-                }
-                continue;
-            }
+        self.walk_and_send_objects(&self.path, sender, warn_as_error)
+            .await
+    }
 
-            if !self
-                .check_dir_entry(entry.as_ref().unwrap(), warn_as_error)
-                .await?
-            {
-                continue;
-            }
+    #[cfg(not(tarpaulin_include))]
+    async fn list_objects_with_parallel_prefixes(
+        &self,
+        _sender: &Sender<S3syncObject>,
+        _max_keys: i32,
+        _prefixes: &[String],
+    ) -> Result<()> {
+        panic!("not implemented");
+    }
 
-            if self.cancellation_token.is_cancelled() {
-                trace!("list() canceled.");
-                break;
-            }
+    // the sub prefix is a file or a directory relative to the source directory(--include-only-prefixes-from-stdin).
+    async fn list_objects_under_sub_prefix(
+        &self,
+        sender: &Sender<S3syncObject>,
+        _max_keys: i32,
+        sub_prefix: &str,
+        warn_as_error: bool,
+    ) -> Result<()> {
+        let sub_path = Path::new(sub_prefix);
+        let is_inside_source = sub_path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+        let path = self.path.join(
+            sub_path
+                .components()
+                .filter(|component| matches!(component, Component::Normal(_)))
+                .collect::<PathBuf>(),
+        );
 
-            let mut path = remove_local_path_prefix(
-                entry.as_ref().unwrap().path().to_str().unwrap(),
-                self.path.to_str().unwrap(),
+        let result = if is_inside_source {
+            tokio::fs::metadata(&path).await.map_err(|e| e.to_string())
+        } else {
+            Err("outside of the source directory.".to_string())
+        };
+        if let Err(error) = result {
+            self.send_stats(SyncWarning {
+                key: sub_prefix.to_string(),
+            })
+            .await;
+            warn!(
+                prefix = sub_prefix,
+                error = error,
+                "failed to access the prefix read from stdin. skipping."
             );
 
-            if cfg!(windows) {
-                path = convert_windows_directory_char_to_slash(&path);
-            }
-
-            let e_tag = if self.config.filter_config.check_etag
-                && !self.config.transfer_config.auto_chunksize
-                && !self.config.filter_config.remove_modified_filter
-                && self.config.filter_config.check_checksum_algorithm.is_none()
-            {
-                Some(
-                    generate_e_tag_hash_from_path(
-                        &PathBuf::from(entry.as_ref().unwrap().path()),
-                        self.config.transfer_config.multipart_chunksize as usize,
-                        self.config.transfer_config.multipart_threshold as usize,
-                    )
-                    .await?,
-                )
-            } else {
-                None
-            };
-
-            let object = S3syncObject::NotVersioning(build_object_from_dir_entry(
-                entry.as_ref().unwrap(),
-                &path,
-                e_tag,
-                self.config.additional_checksum_algorithm.clone(),
-            ));
-            if let Err(e) = sender
-                .send(object)
-                .await
-                .context("async_channel::Sender::send() failed.")
-            {
-                return if !sender.is_closed() { Err(e) } else { Ok(()) };
+            if warn_as_error {
+                return Err(anyhow!(
+                    "failed to access the prefix read from stdin: {}.",
+                    sub_prefix
+                ));
             }
+            return Ok(());
         }
 
-        Ok(())
+        self.walk_and_send_objects(&path, sender, warn_as_error)
+            .await
     }
 
     #[cfg(not(tarpaulin_include))]
@@ -474,19 +948,62 @@ impl StorageTrait for LocalStorage {
         _sse_c_key: SseCustomerKey,
         _sse_c_key_md5: Option<String>,
     ) -> Result<GetObjectOutput> {
-        let mut path = self.path.clone();
-        path.push(key);
+        let hardlink_target = self.hardlink_targets.lock().unwrap().get(key).cloned();
 
-        let content_type = if self.config.no_guess_mime_type {
-            None
+        let real_key = self.resolve_key(key);
+        let key = real_key.as_str();
+
+        let path = if self.config.upload_manifest.is_some() {
+            fs_util::key_to_source_file_path(self.path.clone(), key, &self.config.upload_manifest)
         } else {
-            Some(
-                mime_guess::from_path(&path)
-                    .first_or_octet_stream()
-                    .to_string(),
-            )
+            let mut path = self.path.clone();
+            path.push(key);
+            path
+        };
+
+        if let Some(hardlink_target) = hardlink_target {
+            return Ok(GetObjectOutputBuilder::default()
+                .content_length(0)
+                .last_modified(fs_util::get_last_modified(&path).await)
+                .metadata(S3SYNC_HARDLINK_TARGET_METADATA_KEY, hardlink_target)
+                .body(ByteStream::from_static(b""))
+                .build());
+        }
+
+        // a pre-compressed file(e.g. index.html.gz) is typed by the file name without the encoding extension.
+        let content_encoding = self.get_mapped_content_encoding(&path);
+        let content_type_path = if content_encoding.is_some() {
+            path.with_extension("")
+        } else {
+            path.clone()
         };
 
+        let content_type =
+            if let Some(content_type) = self.get_overridden_content_type(&content_type_path) {
+                Some(content_type)
+            } else if self.config.no_guess_mime_type {
+                None
+            } else {
+                Some(
+                    mime_guess::from_path(&content_type_path)
+                        .first_or_octet_stream()
+                        .to_string(),
+                )
+            };
+
+        // with --fast-empty-files, an empty file is neither opened nor hashed.
+        if self.config.fast_empty_files && fs_util::get_file_size(&path).await == 0 {
+            return Ok(GetObjectOutputBuilder::default()
+                .content_length(0)
+                .set_content_type(content_type)
+                .set_content_encoding(content_encoding)
+                .last_modified(fs_util::get_last_modified(&path).await)
+                .set_metadata(self.get_file_mode_metadata(&path))
+                .body(ByteStream::from_static(b""))
+                .build());
+        }
+
+        let _checksum_permit = acquire_checksum_permit(&self.checksum_semaphore).await;
         let checksum = if self.config.additional_checksum_algorithm.is_some() {
             Some(
                 generate_checksum_from_path_with_chunksize(
@@ -558,7 +1075,9 @@ impl StorageTrait for LocalStorage {
         Ok(GetObjectOutputBuilder::default()
             .set_content_length(Some(fs_util::get_file_size(&path).await as i64))
             .set_content_type(content_type)
+            .set_content_encoding(content_encoding)
             .last_modified(fs_util::get_last_modified(&path).await)
+            .set_metadata(self.get_file_mode_metadata(&path))
             .set_body(Some(ByteStream::from_path(path).await?))
             .set_checksum_sha256(checksum_sha256)
             .set_checksum_sha1(checksum_sha1)
@@ -569,6 +1088,18 @@ impl StorageTrait for LocalStorage {
     }
 
     #[cfg(not(tarpaulin_include))]
+    async fn get_object_if_modified_since(
+        &self,
+        _key: &str,
+        _if_modified_since: DateTime,
+        _checksum_mode: Option<ChecksumMode>,
+        _sse_c: Option<String>,
+        _sse_c_key: SseCustomerKey,
+        _sse_c_key_md5: Option<String>,
+    ) -> Result<GetObjectOutput> {
+        panic!("not implemented");
+    }
+
     async fn get_object_versions(&self, _key: &str, _max_keys: i32) -> Result<Vec<ObjectVersion>> {
         panic!("not implemented");
     }
@@ -591,7 +1122,14 @@ impl StorageTrait for LocalStorage {
         _sse_c_key: SseCustomerKey,
         _sse_c_key_md5: Option<String>,
     ) -> Result<HeadObjectOutput> {
-        let path = fs_util::key_to_file_path(self.path.to_path_buf(), key);
+        let real_key = self.resolve_key(key);
+        let key = real_key.as_str();
+
+        let path = fs_util::key_to_source_file_path(
+            self.path.to_path_buf(),
+            key,
+            &self.config.upload_manifest,
+        );
 
         let result = path.try_exists();
         if let Err(e) = result {
@@ -660,6 +1198,9 @@ impl StorageTrait for LocalStorage {
         _tagging: Option<String>,
         object_checksum: Option<ObjectChecksum>,
     ) -> Result<PutObjectOutput> {
+        let real_key = self.resolve_key(key);
+        let key = real_key.as_str();
+
         let source_sse = get_object_output.server_side_encryption().cloned();
         let source_e_tag = get_object_output.e_tag().map(|e_tag| e_tag.to_string());
         let source_content_length = get_object_output.content_length().unwrap() as u64;
@@ -690,6 +1231,28 @@ impl StorageTrait for LocalStorage {
             return Err(anyhow!(S3syncError::DirectoryTraversalError));
         }
 
+        let resolved_key;
+        let key = if self.config.rename_on_conflict || self.config.skip_on_conflict {
+            match fs_util::resolve_path_conflict(&self.path, key) {
+                Some(_) if self.config.skip_on_conflict => {
+                    return Err(anyhow!(S3syncError::PathConflictError));
+                }
+                Some(renamed_key) => {
+                    warn!(
+                        key = key,
+                        renamed_key = renamed_key,
+                        "object path conflicts with an existing path. renamed."
+                    );
+
+                    resolved_key = renamed_key;
+                    resolved_key.as_str()
+                }
+                None => key,
+            }
+        } else {
+            key
+        };
+
         if self.config.dry_run {
             // In a dry run, content-range is set.
             let content_length_string = get_size_string_from_content_range(&get_object_output);
@@ -719,7 +1282,40 @@ impl StorageTrait for LocalStorage {
             return Ok(PutObjectOutput::builder().build());
         }
 
-        let mut temp_file = fs_util::create_temp_file_from_key(&self.path, key).await?;
+        if self.config.hardlink_mode == HardlinkMode::DedupMetadata {
+            if let Some(hardlink_target) = get_object_output
+                .metadata()
+                .and_then(|metadata| metadata.get(S3SYNC_HARDLINK_TARGET_METADATA_KEY))
+            {
+                return self.create_hardlink(key, hardlink_target).await;
+            }
+        }
+
+        let file_mode = self.get_download_file_mode(key, &get_object_output);
+
+        let mut temp_file = if let Some(partial_suffix) = &self.config.partial_suffix {
+            fs_util::create_partial_file_from_key(&self.path, key, partial_suffix).await?
+        } else {
+            fs_util::create_temp_file_from_key(
+                &self.path,
+                key,
+                self.config.local_tmp_dir.as_deref(),
+            )
+            .await?
+        };
+
+        let preallocated = if self.config.preallocate && 0 < source_content_length {
+            let preallocated =
+                fs_util::preallocate_file(temp_file.as_file(), source_content_length)
+                    .context("fs_util::preallocate_file() failed.")?;
+            if !preallocated {
+                debug!(key = key, "preallocation is not supported. skip it.");
+            }
+            preallocated
+        } else {
+            false
+        };
+
         let mut file = tokio::fs::File::from_std(temp_file.as_file_mut().try_clone().unwrap());
 
         let seconds = get_object_output.last_modified().as_ref().unwrap().secs();
@@ -742,6 +1338,7 @@ impl StorageTrait for LocalStorage {
         let mut buf_reader = BufReader::new(byte_stream.into_async_read());
 
         let mut chunked_remaining: u64 = 0;
+        let mut written_length: u64 = 0;
         loop {
             let buffer = buf_reader.fill_buf().await?;
             if buffer.is_empty() {
@@ -751,6 +1348,7 @@ impl StorageTrait for LocalStorage {
             let buffer_len = buffer.len();
             file.write_all(buffer).await?;
             buf_reader.consume(buffer_len);
+            written_length += buffer_len as u64;
 
             // make it easy to cancel
             chunked_remaining += buffer_len as u64;
@@ -765,12 +1363,45 @@ impl StorageTrait for LocalStorage {
         }
 
         file.flush().await?;
+        // The preallocated file may be larger than the received data.
+        if preallocated {
+            file.set_len(written_length).await?;
+        }
+        if self.config.fsync_on_complete {
+            file.sync_all().await?;
+        }
         drop(file);
 
         let real_path = fs_util::key_to_file_path(self.path.to_path_buf(), key);
-        temp_file.persist(&real_path).unwrap();
 
-        fs_util::set_last_modified(self.path.to_path_buf(), key, seconds, nanos).unwrap();
+        // With --partial-suffix, the partial file is renamed to the final name after verification.
+        // With --verify-only-on-mismatch-redownload and --strict-verify, the temp file is verified before it replaces
+        // the existing file, so that a mismatched download never overwrites it.
+        let (partial_file, unverified_temp_file, verify_path) =
+            if self.config.partial_suffix.is_some() {
+                let partial_path = temp_file.path().to_path_buf();
+                fs_util::set_file_last_modified(&partial_path, seconds, nanos).unwrap();
+                if let Some(file_mode) = file_mode {
+                    fs_util::set_file_mode(&partial_path, file_mode)?;
+                }
+
+                (Some(temp_file), None, partial_path)
+            } else if self.config.verify_only_on_mismatch_redownload || self.config.strict_verify {
+                let temp_path = temp_file.path().to_path_buf();
+
+                (None, Some(temp_file), temp_path)
+            } else {
+                fs_util::persist_temp_file(temp_file, &real_path)?;
+                fs_util::set_last_modified(self.path.to_path_buf(), key, seconds, nanos).unwrap();
+                if let Some(file_mode) = file_mode {
+                    fs_util::set_file_mode(&real_path, file_mode)?;
+                }
+                if self.config.fsync_on_complete {
+                    fs_util::sync_parent_directory(&real_path)?;
+                }
+
+                (None, None, real_path.clone())
+            };
 
         let target_object_parts = if let Some(object_checksum) = &object_checksum {
             object_checksum.object_parts.clone()
@@ -778,22 +1409,72 @@ impl StorageTrait for LocalStorage {
             None
         };
 
-        let target_content_length = fs_util::get_file_size(&real_path).await;
+        let target_content_length = fs_util::get_file_size(&verify_path).await;
+
+        let verified = self
+            .verify_local_file(
+                key,
+                object_checksum,
+                &source_sse,
+                &source_e_tag,
+                source_content_length,
+                source_final_checksum,
+                source_checksum_algorithm,
+                &verify_path,
+                target_object_parts,
+                target_content_length,
+                source_storage_class == Some(StorageClass::ExpressOnezone),
+            )
+            .await?;
+
+        // the unverified file(partial or temp) is removed on drop without touching the existing file,
+        // and the object is re-downloaded by the syncer.
+        if !verified && self.config.verify_only_on_mismatch_redownload {
+            return Err(anyhow!(S3syncError::LocalVerificationMismatch));
+        }
 
-        self.verify_local_file(
-            key,
-            object_checksum,
-            &source_sse,
-            &source_e_tag,
-            source_content_length,
-            source_final_checksum,
-            source_checksum_algorithm,
-            &real_path,
-            target_object_parts,
-            target_content_length,
-            source_storage_class == Some(StorageClass::ExpressOnezone),
-        )
-        .await?;
+        // with --strict-verify, a mismatch fails the object instead of being a warning. the temp file is
+        // removed on drop, so that the next run does not regard the corrupt file as up to date.
+        if !verified && self.config.strict_verify && unverified_temp_file.is_some() {
+            return Err(anyhow!(S3syncError::VerificationMismatch));
+        }
+
+        if let Some(temp_file) = unverified_temp_file {
+            fs_util::persist_temp_file(temp_file, &real_path)?;
+            fs_util::set_last_modified(self.path.to_path_buf(), key, seconds, nanos).unwrap();
+            if let Some(file_mode) = file_mode {
+                fs_util::set_file_mode(&real_path, file_mode)?;
+            }
+            if self.config.fsync_on_complete {
+                fs_util::sync_parent_directory(&real_path)?;
+            }
+        }
+
+        if let Some(partial_file) = partial_file {
+            if !verified {
+                partial_file
+                    .keep()
+                    .context("tempfile::NamedTempFile::keep() failed.")?;
+
+                let partial_path = verify_path.to_string_lossy().to_string();
+                warn!(
+                    key = key,
+                    partial_path = partial_path,
+                    "verification failed. the partial file is not renamed to the final name."
+                );
+
+                // the object is not in the final name, so it is not regarded as synced.
+                return Err(anyhow!(S3syncError::VerificationMismatch));
+            }
+
+            fs_util::unmark_partial_file(&verify_path);
+            partial_file
+                .persist(&real_path)
+                .context("tempfile::NamedTempFile::persist() failed.")?;
+            if self.config.fsync_on_complete {
+                fs_util::sync_parent_directory(&real_path)?;
+            }
+        }
 
         let lossy_path = real_path.to_string_lossy().to_string();
         info!(
@@ -821,6 +1502,9 @@ impl StorageTrait for LocalStorage {
         key: &str,
         _version_id: Option<String>,
     ) -> Result<DeleteObjectOutput> {
+        let real_key = self.resolve_key(key);
+        let key = real_key.as_str();
+
         let file_to_delete = fs_util::key_to_file_path(self.path.to_path_buf(), key);
         let lossy_path = file_to_delete.to_string_lossy().to_string();
 
@@ -855,6 +1539,21 @@ impl StorageTrait for LocalStorage {
         panic!("not implemented");
     }
 
+    #[cfg(not(tarpaulin_include))]
+    async fn list_multipart_uploads(&self) -> Result<Vec<MultipartUpload>> {
+        panic!("not implemented");
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    async fn get_multipart_upload_size(&self, _key: &str, _upload_id: &str) -> Result<u64> {
+        panic!("not implemented");
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    async fn abort_multipart_upload(&self, _key: &str, _upload_id: &str) -> Result<()> {
+        panic!("not implemented");
+    }
+
     fn get_client(&self) -> Option<Arc<Client>> {
         None
     }
@@ -1049,8 +1748,7 @@ mod tests {
             create_pipeline_cancellation_token(),
             stats_sender,
             config.target_client_config.clone(),
-            None,
-            None,
+            StorageLimiters::default(),
         )
         .await;
     }
@@ -1077,8 +1775,7 @@ mod tests {
             create_pipeline_cancellation_token(),
             stats_sender,
             config.target_client_config.clone(),
-            None,
-            None,
+            StorageLimiters::default(),
         )
         .await;
 
@@ -1112,8 +1809,7 @@ mod tests {
             create_pipeline_cancellation_token(),
             stats_sender,
             config.target_client_config.clone(),
-            None,
-            None,
+            StorageLimiters::default(),
         )
         .await;
     }
@@ -1140,8 +1836,7 @@ mod tests {
             create_pipeline_cancellation_token(),
             stats_sender,
             config.target_client_config.clone(),
-            None,
-            None,
+            StorageLimiters::default(),
         )
         .await;
 
@@ -1173,8 +1868,7 @@ mod tests {
             create_pipeline_cancellation_token(),
             stats_sender,
             config.target_client_config.clone(),
-            None,
-            None,
+            StorageLimiters::default(),
         )
         .await;
 
@@ -1191,6 +1885,46 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn list_storage_non_recursive() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-access-key",
+            "dummy_access_key",
+            "--source-secret-access-key",
+            "dummy_secret_access_key",
+            "--non-recursive",
+            "s3://dummy-bucket",
+            "./test_data/source/",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+        let (stats_sender, _) = async_channel::unbounded();
+
+        let storage = LocalStorageFactory::create(
+            config.clone(),
+            config.target.clone(),
+            create_pipeline_cancellation_token(),
+            stats_sender,
+            config.target_client_config.clone(),
+            StorageLimiters::default(),
+        )
+        .await;
+
+        let (sender, receiver) = async_channel::bounded::<S3syncObject>(1000);
+        storage.list_objects(&sender, 1000, false).await.unwrap();
+        sender.close();
+
+        let mut keys = Vec::new();
+        while let Ok(object) = receiver.recv().await {
+            keys.push(object.key().to_string());
+        }
+        keys.sort();
+
+        assert_eq!(keys, vec!["data1", "data2", "data3"]);
+    }
+
     #[tokio::test]
     async fn list_storage_not_found() {
         init_dummy_tracing_subscriber();
@@ -1213,8 +1947,7 @@ mod tests {
             create_pipeline_cancellation_token(),
             stats_sender,
             config.target_client_config.clone(),
-            None,
-            None,
+            StorageLimiters::default(),
         )
         .await;
 
@@ -1245,8 +1978,7 @@ mod tests {
             create_pipeline_cancellation_token(),
             stats_sender,
             config.target_client_config.clone(),
-            None,
-            None,
+            StorageLimiters::default(),
         )
         .await;
 
@@ -1277,8 +2009,7 @@ mod tests {
             create_pipeline_cancellation_token(),
             stats_sender,
             config.target_client_config.clone(),
-            None,
-            None,
+            StorageLimiters::default(),
         )
         .await;
 
@@ -1309,8 +2040,7 @@ mod tests {
             create_pipeline_cancellation_token(),
             stats_sender,
             config.target_client_config.clone(),
-            None,
-            None,
+            StorageLimiters::default(),
         )
         .await;
 
@@ -1348,8 +2078,7 @@ mod tests {
             cancellation_token.clone(),
             stats_sender,
             config.target_client_config.clone(),
-            None,
-            None,
+            StorageLimiters::default(),
         )
         .await;
 
@@ -1397,8 +2126,7 @@ mod tests {
             create_pipeline_cancellation_token(),
             stats_sender,
             config.target_client_config.clone(),
-            None,
-            None,
+            StorageLimiters::default(),
         )
         .await;
 
@@ -1434,46 +2162,1054 @@ mod tests {
             "--source-secret-access-key",
             "dummy_secret_access_key",
             "s3://dummy-bucket",
-            "./test_data/denied_dir4",
+            "./test_data/denied_dir4",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+        let (stats_sender, _) = async_channel::unbounded();
+
+        let storage = LocalStorageFactory::create(
+            config.clone(),
+            config.target.clone(),
+            create_pipeline_cancellation_token(),
+            stats_sender,
+            config.target_client_config.clone(),
+            StorageLimiters::default(),
+        )
+        .await;
+
+        let (sender, _) = async_channel::bounded::<S3syncObject>(1000);
+
+        permissions = fs::metadata("./test_data/denied_dir4")
+            .unwrap()
+            .permissions();
+        permissions.set_mode(0o755);
+        fs::set_permissions("./test_data/denied_dir4", permissions).unwrap();
+
+        assert!(storage.list_objects(&sender, 1000, false).await.is_ok());
+    }
+
+    #[tokio::test]
+    #[cfg(target_family = "unix")]
+    async fn list_storage_permission_file_warning() {
+        init_dummy_tracing_subscriber();
+
+        use std::fs;
+        use std::os::unix::fs::PermissionsExt;
+
+        let mut permissions = fs::metadata("./test_data/denied/denied")
+            .unwrap()
+            .permissions();
+        permissions.set_mode(0o000);
+        fs::set_permissions("./test_data/denied/denied", permissions.clone()).unwrap();
+
+        let args = vec![
+            "s3sync",
+            "--source-access-key",
+            "dummy_access_key",
+            "--source-secret-access-key",
+            "dummy_secret_access_key",
+            "s3://dummy-bucket",
+            "./test_data/denied",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+        let (stats_sender, _) = async_channel::unbounded();
+
+        let storage = LocalStorageFactory::create(
+            config.clone(),
+            config.target.clone(),
+            create_pipeline_cancellation_token(),
+            stats_sender,
+            config.target_client_config.clone(),
+            StorageLimiters::default(),
+        )
+        .await;
+
+        let (sender, _) = async_channel::bounded::<S3syncObject>(1000);
+
+        permissions.set_mode(0o644);
+        fs::set_permissions("./test_data/denied/denied", permissions).unwrap();
+
+        assert!(storage.list_objects(&sender, 1000, false).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn get_object() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-access-key",
+            "dummy_access_key",
+            "--source-secret-access-key",
+            "dummy_secret_access_key",
+            "s3://dummy-bucket",
+            "./test_data/",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+        let (stats_sender, _) = async_channel::unbounded();
+
+        let storage = LocalStorageFactory::create(
+            config.clone(),
+            config.target.clone(),
+            create_pipeline_cancellation_token(),
+            stats_sender,
+            config.target_client_config.clone(),
+            StorageLimiters::default(),
+        )
+        .await;
+
+        storage
+            .get_object(
+                "source/data1",
+                None,
+                None,
+                None,
+                SseCustomerKey { key: None },
+                None,
+            )
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn get_object_with_fast_empty_files() {
+        init_dummy_tracing_subscriber();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::write(temp_dir.path().join("empty"), b"").unwrap();
+        let source = format!("{}/", temp_dir.path().to_string_lossy());
+
+        let args = vec![
+            "s3sync",
+            "--target-access-key",
+            "dummy_access_key",
+            "--target-secret-access-key",
+            "dummy_secret_access_key",
+            "--additional-checksum-algorithm",
+            "SHA256",
+            "--fast-empty-files",
+            &source,
+            "s3://dummy-bucket",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+        let (stats_sender, _) = async_channel::unbounded();
+
+        let storage = LocalStorageFactory::create(
+            config.clone(),
+            config.source.clone(),
+            create_pipeline_cancellation_token(),
+            stats_sender,
+            config.source_client_config.clone(),
+            StorageLimiters::default(),
+        )
+        .await;
+
+        let get_object_output = storage
+            .get_object(
+                "empty",
+                None,
+                Some(ChecksumMode::Enabled),
+                None,
+                SseCustomerKey { key: None },
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(get_object_output.content_length(), Some(0));
+        assert!(get_object_output.checksum_sha256().is_none());
+        assert!(get_object_output.e_tag().is_none());
+    }
+
+    #[tokio::test]
+    async fn list_and_get_object_with_upload_manifest() {
+        init_dummy_tracing_subscriber();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source = format!("{}/", temp_dir.path().to_string_lossy());
+        std::fs::create_dir(temp_dir.path().join("dir1")).unwrap();
+        std::fs::write(temp_dir.path().join("dir1").join("data1"), b"test").unwrap();
+        std::fs::write(temp_dir.path().join("data2"), b"unlisted").unwrap();
+
+        let manifest_file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(
+            manifest_file.path(),
+            "local_path,target_key\ndir1/data1,flat/one\nnot_found,flat/two\n",
+        )
+        .unwrap();
+
+        let args = vec![
+            "s3sync",
+            "--target-access-key",
+            "dummy_access_key",
+            "--target-secret-access-key",
+            "dummy_secret_access_key",
+            "--upload-manifest",
+            manifest_file.path().to_str().unwrap(),
+            &source,
+            "s3://dummy-bucket",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+        let (stats_sender, stats_receiver) = async_channel::unbounded();
+
+        let storage = LocalStorageFactory::create(
+            config.clone(),
+            config.source.clone(),
+            create_pipeline_cancellation_token(),
+            stats_sender,
+            config.source_client_config.clone(),
+            StorageLimiters::default(),
+        )
+        .await;
+
+        let (sender, receiver) = async_channel::bounded::<S3syncObject>(1000);
+        storage.list_objects(&sender, 1000, false).await.unwrap();
+
+        assert_eq!(receiver.len(), 1);
+        assert_eq!(stats_receiver.len(), 1);
+
+        let object = receiver.recv().await.unwrap();
+        assert_eq!(object.key(), "flat/one");
+        assert_eq!(object.size(), 4);
+
+        let get_object_output = storage
+            .get_object(
+                object.key(),
+                None,
+                None,
+                None,
+                SseCustomerKey { key: None },
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(get_object_output.content_length(), Some(4));
+
+        storage
+            .head_object(
+                object.key(),
+                None,
+                None,
+                None,
+                SseCustomerKey { key: None },
+                None,
+            )
+            .await
+            .unwrap();
+
+        // the missing file is an error with --warn-as-error.
+        let (sender, _receiver) = async_channel::bounded::<S3syncObject>(1000);
+        assert!(storage.list_objects(&sender, 1000, true).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn list_and_get_object_with_normalize_unicode() {
+        init_dummy_tracing_subscriber();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source = format!("{}/", temp_dir.path().to_string_lossy());
+        // decomposed "é"(U+0065 U+0301), as macOS file names are
+        std::fs::write(temp_dir.path().join("cafe\u{0301}.txt"), b"test").unwrap();
+
+        let args = vec![
+            "s3sync",
+            "--target-access-key",
+            "dummy_access_key",
+            "--target-secret-access-key",
+            "dummy_secret_access_key",
+            "--normalize-unicode",
+            "NFC",
+            &source,
+            "s3://dummy-bucket",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+        let (stats_sender, _) = async_channel::unbounded();
+
+        let storage = LocalStorageFactory::create(
+            config.clone(),
+            config.source.clone(),
+            create_pipeline_cancellation_token(),
+            stats_sender,
+            config.source_client_config.clone(),
+            StorageLimiters::default(),
+        )
+        .await;
+
+        let (sender, receiver) = async_channel::bounded::<S3syncObject>(1000);
+        storage.list_objects(&sender, 1000, false).await.unwrap();
+
+        assert_eq!(receiver.len(), 1);
+        let object = receiver.recv().await.unwrap();
+        assert_eq!(object.key(), "caf\u{00e9}.txt");
+
+        let get_object_output = storage
+            .get_object(
+                object.key(),
+                None,
+                None,
+                None,
+                SseCustomerKey { key: None },
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(get_object_output.content_length(), Some(4));
+
+        storage
+            .head_object(
+                object.key(),
+                None,
+                None,
+                None,
+                SseCustomerKey { key: None },
+                None,
+            )
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn get_object_with_content_type_override_map() {
+        init_dummy_tracing_subscriber();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source = format!("{}/", temp_dir.path().to_string_lossy());
+        std::fs::write(temp_dir.path().join("app.WASM"), b"wasm").unwrap();
+        std::fs::write(temp_dir.path().join("data.json"), b"{}").unwrap();
+        std::fs::write(temp_dir.path().join("readme.txt"), b"text").unwrap();
+
+        let args = vec![
+            "s3sync",
+            "--target-access-key",
+            "dummy_access_key",
+            "--target-secret-access-key",
+            "dummy_secret_access_key",
+            "--content-type-override-map",
+            "wasm=application/wasm,.JSON=application/vnd.test+json",
+            &source,
+            "s3://dummy-bucket",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+        let (stats_sender, _) = async_channel::unbounded();
+
+        let storage = LocalStorageFactory::create(
+            config.clone(),
+            config.source.clone(),
+            create_pipeline_cancellation_token(),
+            stats_sender,
+            config.source_client_config.clone(),
+            StorageLimiters::default(),
+        )
+        .await;
+
+        let get_content_type = |key: &'static str| {
+            let storage = dyn_clone::clone_box(&*storage);
+            async move {
+                storage
+                    .get_object(key, None, None, None, SseCustomerKey { key: None }, None)
+                    .await
+                    .unwrap()
+                    .content_type()
+                    .unwrap()
+                    .to_string()
+            }
+        };
+
+        assert_eq!(get_content_type("app.WASM").await, "application/wasm");
+        assert_eq!(
+            get_content_type("data.json").await,
+            "application/vnd.test+json"
+        );
+        assert_eq!(get_content_type("readme.txt").await, "text/plain");
+    }
+
+    #[tokio::test]
+    async fn get_object_with_content_encoding_map() {
+        init_dummy_tracing_subscriber();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source = format!("{}/", temp_dir.path().to_string_lossy());
+        std::fs::write(temp_dir.path().join("index.html.GZ"), b"gzip").unwrap();
+        std::fs::write(temp_dir.path().join("data.json.br"), b"br").unwrap();
+        std::fs::write(temp_dir.path().join("readme.txt"), b"text").unwrap();
+
+        let args = vec![
+            "s3sync",
+            "--target-access-key",
+            "dummy_access_key",
+            "--target-secret-access-key",
+            "dummy_secret_access_key",
+            "--content-encoding-map",
+            "gz=gzip,br=br",
+            &source,
+            "s3://dummy-bucket",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+        let (stats_sender, _) = async_channel::unbounded();
+
+        let storage = LocalStorageFactory::create(
+            config.clone(),
+            config.source.clone(),
+            create_pipeline_cancellation_token(),
+            stats_sender,
+            config.source_client_config.clone(),
+            StorageLimiters::default(),
+        )
+        .await;
+
+        let get_object_output = storage
+            .get_object(
+                "index.html.GZ",
+                None,
+                None,
+                None,
+                SseCustomerKey { key: None },
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(get_object_output.content_encoding().unwrap(), "gzip");
+        assert_eq!(get_object_output.content_type().unwrap(), "text/html");
+
+        let get_object_output = storage
+            .get_object(
+                "data.json.br",
+                None,
+                None,
+                None,
+                SseCustomerKey { key: None },
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(get_object_output.content_encoding().unwrap(), "br");
+        assert_eq!(
+            get_object_output.content_type().unwrap(),
+            "application/json"
+        );
+
+        let get_object_output = storage
+            .get_object(
+                "readme.txt",
+                None,
+                None,
+                None,
+                SseCustomerKey { key: None },
+                None,
+            )
+            .await
+            .unwrap();
+        assert!(get_object_output.content_encoding().is_none());
+        assert_eq!(get_object_output.content_type().unwrap(), "text/plain");
+    }
+
+    #[tokio::test]
+    async fn head_object() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-access-key",
+            "dummy_access_key",
+            "--source-secret-access-key",
+            "dummy_secret_access_key",
+            "s3://dummy-bucket",
+            "./test_data/",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+        let (stats_sender, _) = async_channel::unbounded();
+
+        let storage = LocalStorageFactory::create(
+            config.clone(),
+            config.target.clone(),
+            create_pipeline_cancellation_token(),
+            stats_sender,
+            config.target_client_config.clone(),
+            StorageLimiters::default(),
+        )
+        .await;
+
+        let head_object_output = storage
+            .head_object(
+                "source/dir1/6byte.dat",
+                None,
+                None,
+                None,
+                SseCustomerKey { key: None },
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(head_object_output.content_length().unwrap(), 6);
+        assert!(head_object_output.last_modified().is_some());
+    }
+
+    #[tokio::test]
+    async fn head_object_not_found() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-access-key",
+            "dummy_access_key",
+            "--source-secret-access-key",
+            "dummy_secret_access_key",
+            "s3://dummy-bucket",
+            "./test_data/",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+        let (stats_sender, _) = async_channel::unbounded();
+
+        let storage = LocalStorageFactory::create(
+            config.clone(),
+            config.target.clone(),
+            create_pipeline_cancellation_token(),
+            stats_sender,
+            config.target_client_config.clone(),
+            StorageLimiters::default(),
+        )
+        .await;
+
+        let head_object_output = storage
+            .head_object(
+                "source/dir1/no_data",
+                None,
+                None,
+                None,
+                SseCustomerKey { key: None },
+                None,
+            )
+            .await;
+        assert!(head_object_output.is_err());
+
+        if let Some(SdkError::ServiceError(e)) = head_object_output
+            .err()
+            .unwrap()
+            .downcast_ref::<SdkError<HeadObjectError, Response<SdkBody>>>()
+        {
+            assert!(e.err().is_not_found());
+            return;
+        }
+
+        panic!("no 404 error occurred")
+    }
+
+    #[tokio::test]
+    #[cfg(target_family = "unix")]
+    async fn head_object_error() {
+        init_dummy_tracing_subscriber();
+
+        use std::fs;
+        use std::os::unix::fs::PermissionsExt;
+
+        if nix::unistd::geteuid().is_root() {
+            panic!("run tests from root");
+        }
+
+        let mut permissions = fs::metadata("./test_data/denied_dir5")
+            .unwrap()
+            .permissions();
+        permissions.set_mode(0o000);
+        fs::set_permissions("./test_data/denied_dir5", permissions).unwrap();
+
+        let args = vec![
+            "s3sync",
+            "--source-access-key",
+            "dummy_access_key",
+            "--source-secret-access-key",
+            "dummy_secret_access_key",
+            "s3://dummy-bucket",
+            "./test_data/",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+        let (stats_sender, _) = async_channel::unbounded();
+
+        let storage = LocalStorageFactory::create(
+            config.clone(),
+            config.target.clone(),
+            create_pipeline_cancellation_token(),
+            stats_sender,
+            config.target_client_config.clone(),
+            StorageLimiters::default(),
+        )
+        .await;
+
+        let head_object_output = storage
+            .head_object(
+                "denied_dir5/data",
+                None,
+                None,
+                None,
+                SseCustomerKey { key: None },
+                None,
+            )
+            .await;
+
+        permissions = fs::metadata("./test_data/denied_dir5")
+            .unwrap()
+            .permissions();
+        permissions.set_mode(0o755);
+        fs::set_permissions("./test_data/denied_dir5", permissions).unwrap();
+
+        assert!(head_object_output.is_err());
+
+        if matches!(
+            head_object_output
+                .err()
+                .unwrap()
+                .downcast_ref::<SdkError<HeadObjectError, Response<SdkBody>>>(),
+            Some(SdkError::ServiceError(_))
+        ) {
+            panic!("ServiceError occurred");
+        }
+    }
+
+    #[tokio::test]
+    async fn put_object() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-access-key",
+            "dummy_access_key",
+            "--source-secret-access-key",
+            "dummy_secret_access_key",
+            "s3://dummy-bucket",
+            "./test_data/",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+        let (stats_sender, _) = async_channel::unbounded();
+
+        let storage = LocalStorageFactory::create(
+            config.clone(),
+            config.target.clone(),
+            create_pipeline_cancellation_token(),
+            stats_sender,
+            config.target_client_config.clone(),
+            StorageLimiters::default(),
+        )
+        .await;
+
+        let get_object_output = storage
+            .get_object(
+                "source/data1",
+                None,
+                None,
+                None,
+                SseCustomerKey { key: None },
+                None,
+            )
+            .await
+            .unwrap();
+
+        storage
+            .put_object("target/data1", get_object_output, None, None)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn put_object_directory() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-access-key",
+            "dummy_access_key",
+            "--source-secret-access-key",
+            "dummy_secret_access_key",
+            "s3://dummy-bucket",
+            "./test_data/",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+        let (stats_sender, _) = async_channel::unbounded();
+
+        let storage = LocalStorageFactory::create(
+            config.clone(),
+            config.target.clone(),
+            create_pipeline_cancellation_token(),
+            stats_sender,
+            config.target_client_config.clone(),
+            StorageLimiters::default(),
+        )
+        .await;
+
+        storage
+            .put_object(
+                "target/",
+                GetObjectOutputBuilder::default()
+                    .set_content_length(Some(0))
+                    .last_modified(DateTime::from_secs(1))
+                    .build(),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn put_object_with_directory_traversal() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-access-key",
+            "dummy_access_key",
+            "--source-secret-access-key",
+            "dummy_secret_access_key",
+            "s3://dummy-bucket",
+            "./test_data/",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+        let (stats_sender, _) = async_channel::unbounded();
+
+        let storage = LocalStorageFactory::create(
+            config.clone(),
+            config.target.clone(),
+            create_pipeline_cancellation_token(),
+            stats_sender,
+            config.target_client_config.clone(),
+            StorageLimiters::default(),
+        )
+        .await;
+
+        let e = storage
+            .put_object(
+                "target/../../etc/passwd",
+                GetObjectOutputBuilder::default()
+                    .set_content_length(Some(1))
+                    .last_modified(DateTime::from_secs(1))
+                    .build(),
+                None,
+                None,
+            )
+            .await
+            .err()
+            .unwrap();
+
+        assert_eq!(
+            *e.downcast_ref::<S3syncError>().unwrap(),
+            S3syncError::DirectoryTraversalError
+        );
+    }
+
+    #[tokio::test]
+    async fn put_object_with_rename_on_conflict() {
+        init_dummy_tracing_subscriber();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let target = format!("{}/", temp_dir.path().to_string_lossy());
+        std::fs::write(temp_dir.path().join("foo"), b"file").unwrap();
+
+        let args = vec![
+            "s3sync",
+            "--source-access-key",
+            "dummy_access_key",
+            "--source-secret-access-key",
+            "dummy_secret_access_key",
+            "--rename-on-conflict",
+            "s3://dummy-bucket",
+            &target,
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+        let (stats_sender, _) = async_channel::unbounded();
+
+        let storage = LocalStorageFactory::create(
+            config.clone(),
+            config.target.clone(),
+            create_pipeline_cancellation_token(),
+            stats_sender,
+            config.target_client_config.clone(),
+            StorageLimiters::default(),
+        )
+        .await;
+
+        storage
+            .put_object(
+                "foo/bar",
+                GetObjectOutputBuilder::default()
+                    .set_content_length(Some(3))
+                    .last_modified(DateTime::from_secs(1))
+                    .body(ByteStream::from_static(b"bar"))
+                    .build(),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            std::fs::read(temp_dir.path().join("foo (1)").join("bar")).unwrap(),
+            b"bar"
+        );
+        assert_eq!(std::fs::read(temp_dir.path().join("foo")).unwrap(), b"file");
+    }
+
+    #[tokio::test]
+    #[cfg(target_family = "unix")]
+    async fn list_and_get_object_with_hardlink_mode_dedup_metadata() {
+        init_dummy_tracing_subscriber();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source = format!("{}/", temp_dir.path().to_string_lossy());
+        std::fs::create_dir(temp_dir.path().join("dir1")).unwrap();
+        std::fs::create_dir(temp_dir.path().join("dir2")).unwrap();
+        std::fs::write(temp_dir.path().join("dir1").join("data"), b"test").unwrap();
+        std::fs::hard_link(
+            temp_dir.path().join("dir1").join("data"),
+            temp_dir.path().join("dir2").join("data"),
+        )
+        .unwrap();
+
+        let args = vec![
+            "s3sync",
+            "--target-access-key",
+            "dummy_access_key",
+            "--target-secret-access-key",
+            "dummy_secret_access_key",
+            "--hardlink-mode",
+            "dedup-metadata",
+            &source,
+            "s3://dummy-bucket",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+        let (stats_sender, _) = async_channel::unbounded();
+
+        let storage = LocalStorageFactory::create(
+            config.clone(),
+            config.source.clone(),
+            create_pipeline_cancellation_token(),
+            stats_sender,
+            config.source_client_config.clone(),
+            StorageLimiters::default(),
+        )
+        .await;
+
+        let (sender, receiver) = async_channel::bounded::<S3syncObject>(1000);
+        storage.list_objects(&sender, 1000, false).await.unwrap();
+
+        assert_eq!(receiver.len(), 2);
+        let first = receiver.recv().await.unwrap();
+        let subsequent = receiver.recv().await.unwrap();
+        assert_eq!(first.size(), 4);
+        assert_eq!(subsequent.size(), 0);
+
+        let get_object_output = storage
+            .get_object(
+                subsequent.key(),
+                None,
+                None,
+                None,
+                SseCustomerKey { key: None },
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(get_object_output.content_length(), Some(0));
+        assert_eq!(
+            get_object_output
+                .metadata()
+                .unwrap()
+                .get(S3SYNC_HARDLINK_TARGET_METADATA_KEY)
+                .unwrap(),
+            first.key()
+        );
+    }
+
+    #[tokio::test]
+    #[cfg(target_family = "unix")]
+    async fn list_objects_with_hardlink_mode_skip() {
+        init_dummy_tracing_subscriber();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source = format!("{}/", temp_dir.path().to_string_lossy());
+        std::fs::write(temp_dir.path().join("data1"), b"test").unwrap();
+        std::fs::hard_link(temp_dir.path().join("data1"), temp_dir.path().join("data2")).unwrap();
+
+        let args = vec![
+            "s3sync",
+            "--target-access-key",
+            "dummy_access_key",
+            "--target-secret-access-key",
+            "dummy_secret_access_key",
+            "--hardlink-mode",
+            "skip",
+            &source,
+            "s3://dummy-bucket",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+        let (stats_sender, stats_receiver) = async_channel::unbounded();
+
+        let storage = LocalStorageFactory::create(
+            config.clone(),
+            config.source.clone(),
+            create_pipeline_cancellation_token(),
+            stats_sender,
+            config.source_client_config.clone(),
+            StorageLimiters::default(),
+        )
+        .await;
+
+        let (sender, receiver) = async_channel::bounded::<S3syncObject>(1000);
+        storage.list_objects(&sender, 1000, false).await.unwrap();
+
+        assert_eq!(receiver.len(), 1);
+        assert_eq!(stats_receiver.len(), 1);
+    }
+
+    #[tokio::test]
+    #[cfg(target_family = "unix")]
+    async fn put_object_with_hardlink_mode_dedup_metadata() {
+        init_dummy_tracing_subscriber();
+
+        use std::os::unix::fs::MetadataExt;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let target = format!("{}/", temp_dir.path().to_string_lossy());
+        std::fs::write(temp_dir.path().join("data1"), b"test").unwrap();
+
+        let args = vec![
+            "s3sync",
+            "--source-access-key",
+            "dummy_access_key",
+            "--source-secret-access-key",
+            "dummy_secret_access_key",
+            "--hardlink-mode",
+            "dedup-metadata",
+            "s3://dummy-bucket",
+            &target,
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+        let (stats_sender, stats_receiver) = async_channel::unbounded();
+
+        let storage = LocalStorageFactory::create(
+            config.clone(),
+            config.target.clone(),
+            create_pipeline_cancellation_token(),
+            stats_sender,
+            config.target_client_config.clone(),
+            StorageLimiters::default(),
+        )
+        .await;
+
+        storage
+            .put_object(
+                "dir1/data2",
+                GetObjectOutputBuilder::default()
+                    .set_content_length(Some(0))
+                    .last_modified(DateTime::from_secs(1))
+                    .metadata(S3SYNC_HARDLINK_TARGET_METADATA_KEY, "data1")
+                    .body(ByteStream::from_static(b""))
+                    .build(),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            std::fs::metadata(temp_dir.path().join("dir1").join("data2"))
+                .unwrap()
+                .ino(),
+            std::fs::metadata(temp_dir.path().join("data1"))
+                .unwrap()
+                .ino()
+        );
+
+        // the link is deferred until the target of the hard link has been downloaded.
+        let data4_path = temp_dir.path().join("data4");
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            std::fs::write(data4_path, b"test").unwrap();
+        });
+        storage
+            .put_object(
+                "data3",
+                GetObjectOutputBuilder::default()
+                    .set_content_length(Some(0))
+                    .last_modified(DateTime::from_secs(1))
+                    .metadata(S3SYNC_HARDLINK_TARGET_METADATA_KEY, "data4")
+                    .body(ByteStream::from_static(b""))
+                    .build(),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            std::fs::metadata(temp_dir.path().join("data3"))
+                .unwrap()
+                .ino(),
+            std::fs::metadata(temp_dir.path().join("data4"))
+                .unwrap()
+                .ino()
+        );
+        assert_eq!(stats_receiver.len(), 0);
+    }
+
+    #[tokio::test]
+    #[cfg(target_family = "unix")]
+    async fn put_object_with_hardlink_mode_dedup_metadata_target_not_downloaded() {
+        init_dummy_tracing_subscriber();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let target = format!("{}/", temp_dir.path().to_string_lossy());
+
+        let args = vec![
+            "s3sync",
+            "--source-access-key",
+            "dummy_access_key",
+            "--source-secret-access-key",
+            "dummy_secret_access_key",
+            "--hardlink-mode",
+            "dedup-metadata",
+            "s3://dummy-bucket",
+            &target,
         ];
         let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
         let (stats_sender, _) = async_channel::unbounded();
+        let cancellation_token = create_pipeline_cancellation_token();
 
         let storage = LocalStorageFactory::create(
             config.clone(),
             config.target.clone(),
-            create_pipeline_cancellation_token(),
+            cancellation_token.clone(),
             stats_sender,
             config.target_client_config.clone(),
-            None,
-            None,
+            StorageLimiters::default(),
         )
         .await;
 
-        let (sender, _) = async_channel::bounded::<S3syncObject>(1000);
-
-        permissions = fs::metadata("./test_data/denied_dir4")
-            .unwrap()
-            .permissions();
-        permissions.set_mode(0o755);
-        fs::set_permissions("./test_data/denied_dir4", permissions).unwrap();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            cancellation_token.cancel();
+        });
+        let result = storage
+            .put_object(
+                "data3",
+                GetObjectOutputBuilder::default()
+                    .set_content_length(Some(0))
+                    .last_modified(DateTime::from_secs(1))
+                    .metadata(S3SYNC_HARDLINK_TARGET_METADATA_KEY, "data4")
+                    .body(ByteStream::from_static(b""))
+                    .build(),
+                None,
+                None,
+            )
+            .await;
 
-        assert!(storage.list_objects(&sender, 1000, false).await.is_ok());
+        assert!(result.is_err());
+        assert!(!temp_dir.path().join("data3").exists());
     }
 
     #[tokio::test]
-    #[cfg(target_family = "unix")]
-    async fn list_storage_permission_file_warning() {
+    async fn put_object_with_preallocate() {
         init_dummy_tracing_subscriber();
 
-        use std::fs;
-        use std::os::unix::fs::PermissionsExt;
-
-        let mut permissions = fs::metadata("./test_data/denied/denied")
-            .unwrap()
-            .permissions();
-        permissions.set_mode(0o000);
-        fs::set_permissions("./test_data/denied/denied", permissions.clone()).unwrap();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let target = format!("{}/", temp_dir.path().to_string_lossy());
 
         let args = vec![
             "s3sync",
@@ -1481,8 +3217,9 @@ mod tests {
             "dummy_access_key",
             "--source-secret-access-key",
             "dummy_secret_access_key",
+            "--preallocate",
             "s3://dummy-bucket",
-            "./test_data/denied",
+            &target,
         ];
         let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
         let (stats_sender, _) = async_channel::unbounded();
@@ -1493,31 +3230,46 @@ mod tests {
             create_pipeline_cancellation_token(),
             stats_sender,
             config.target_client_config.clone(),
-            None,
-            None,
+            StorageLimiters::default(),
         )
         .await;
 
-        let (sender, _) = async_channel::bounded::<S3syncObject>(1000);
-
-        permissions.set_mode(0o644);
-        fs::set_permissions("./test_data/denied/denied", permissions).unwrap();
+        // the preallocated file is truncated to the received data.
+        storage
+            .put_object(
+                "foo",
+                GetObjectOutputBuilder::default()
+                    .set_content_length(Some(1024))
+                    .last_modified(DateTime::from_secs(1))
+                    .body(ByteStream::from_static(b"foo"))
+                    .build(),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
 
-        assert!(storage.list_objects(&sender, 1000, false).await.is_ok());
+        assert_eq!(std::fs::read(temp_dir.path().join("foo")).unwrap(), b"foo");
     }
 
     #[tokio::test]
-    async fn get_object() {
+    async fn put_object_with_fsync_on_complete() {
         init_dummy_tracing_subscriber();
 
+        let temp_dir = tempfile::tempdir().unwrap();
+        let target = format!("{}/", temp_dir.path().to_string_lossy());
+
         let args = vec![
             "s3sync",
             "--source-access-key",
             "dummy_access_key",
             "--source-secret-access-key",
             "dummy_secret_access_key",
+            "--fsync-on-complete",
+            "--partial-suffix",
+            ".partial",
             "s3://dummy-bucket",
-            "./test_data/",
+            &target,
         ];
         let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
         let (stats_sender, _) = async_channel::unbounded();
@@ -1528,54 +3280,94 @@ mod tests {
             create_pipeline_cancellation_token(),
             stats_sender,
             config.target_client_config.clone(),
-            None,
-            None,
+            StorageLimiters::default(),
         )
         .await;
 
-        storage
-            .get_object(
-                "source/data1",
-                None,
-                None,
+        for key in ["foo", "dir1/bar"] {
+            storage
+                .put_object(
+                    key,
+                    GetObjectOutputBuilder::default()
+                        .set_content_length(Some(3))
+                        .last_modified(DateTime::from_secs(1))
+                        .body(ByteStream::from_static(b"foo"))
+                        .build(),
+                    None,
+                    None,
+                )
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(std::fs::read(temp_dir.path().join("foo")).unwrap(), b"foo");
+        assert_eq!(
+            std::fs::read(temp_dir.path().join("dir1/bar")).unwrap(),
+            b"foo"
+        );
+        assert!(!temp_dir.path().join("foo.partial").exists());
+
+        // md5 of an empty object. the partial file is kept, and the object is not regarded as synced.
+        let e = storage
+            .put_object(
+                "baz",
+                GetObjectOutputBuilder::default()
+                    .set_content_length(Some(3))
+                    .e_tag("\"d41d8cd98f00b204e9800998ecf8427e\"")
+                    .last_modified(DateTime::from_secs(1))
+                    .body(ByteStream::from_static(b"foo"))
+                    .build(),
                 None,
-                SseCustomerKey { key: None },
                 None,
             )
             .await
+            .err()
             .unwrap();
+
+        assert_eq!(
+            *e.downcast_ref::<S3syncError>().unwrap(),
+            S3syncError::VerificationMismatch
+        );
+        assert!(!temp_dir.path().join("baz").exists());
+        assert!(temp_dir.path().join("baz.partial").exists());
     }
 
     #[tokio::test]
-    async fn head_object() {
+    #[cfg(target_family = "unix")]
+    async fn get_object_with_put_file_mode_metadata() {
         init_dummy_tracing_subscriber();
 
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::write(temp_dir.path().join("data1"), b"data1").unwrap();
+        fs_util::set_file_mode(&temp_dir.path().join("data1"), 0o751).unwrap();
+        let source = format!("{}/", temp_dir.path().to_string_lossy());
+
         let args = vec![
             "s3sync",
-            "--source-access-key",
+            "--target-access-key",
             "dummy_access_key",
-            "--source-secret-access-key",
+            "--target-secret-access-key",
             "dummy_secret_access_key",
+            "--put-file-mode-metadata",
+            &source,
             "s3://dummy-bucket",
-            "./test_data/",
         ];
         let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
         let (stats_sender, _) = async_channel::unbounded();
 
         let storage = LocalStorageFactory::create(
             config.clone(),
-            config.target.clone(),
+            config.source.clone(),
             create_pipeline_cancellation_token(),
             stats_sender,
-            config.target_client_config.clone(),
-            None,
-            None,
+            config.source_client_config.clone(),
+            StorageLimiters::default(),
         )
         .await;
 
-        let head_object_output = storage
-            .head_object(
-                "source/dir1/6byte.dat",
+        let get_object_output = storage
+            .get_object(
+                "data1",
                 None,
                 None,
                 None,
@@ -1584,22 +3376,86 @@ mod tests {
             )
             .await
             .unwrap();
-        assert_eq!(head_object_output.content_length().unwrap(), 6);
-        assert!(head_object_output.last_modified().is_some());
+
+        assert_eq!(
+            get_object_output
+                .metadata()
+                .unwrap()
+                .get(S3SYNC_FILE_MODE_METADATA_KEY)
+                .unwrap(),
+            "751"
+        );
     }
 
     #[tokio::test]
-    async fn head_object_not_found() {
+    #[cfg(target_family = "unix")]
+    async fn put_object_with_file_mode() {
         init_dummy_tracing_subscriber();
 
+        let temp_dir = tempfile::tempdir().unwrap();
+        let target = format!("{}/", temp_dir.path().to_string_lossy());
+
+        for (option, expected_mode) in [
+            (vec!["--download-file-mode", "0640"], "640"),
+            (vec!["--preserve-file-mode"], "751"),
+            (
+                vec!["--preserve-file-mode", "--partial-suffix", ".partial"],
+                "751",
+            ),
+        ] {
+            let mut args = vec![
+                "s3sync",
+                "--source-access-key",
+                "dummy_access_key",
+                "--source-secret-access-key",
+                "dummy_secret_access_key",
+            ];
+            args.extend_from_slice(&option);
+            args.extend_from_slice(&["s3://dummy-bucket", &target]);
+            let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+            let (stats_sender, _) = async_channel::unbounded();
+
+            let storage = LocalStorageFactory::create(
+                config.clone(),
+                config.target.clone(),
+                create_pipeline_cancellation_token(),
+                stats_sender,
+                config.target_client_config.clone(),
+                StorageLimiters::default(),
+            )
+            .await;
+
+            storage
+                .put_object(
+                    "data1",
+                    GetObjectOutputBuilder::default()
+                        .set_content_length(Some(3))
+                        .last_modified(DateTime::from_secs(1))
+                        .metadata(S3SYNC_FILE_MODE_METADATA_KEY, "4751")
+                        .body(ByteStream::from_static(b"foo"))
+                        .build(),
+                    None,
+                    None,
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(
+                fs_util::get_file_mode(&temp_dir.path().join("data1")).unwrap(),
+                expected_mode
+            );
+        }
+
+        // the file without the metadata or with an invalid one is created with the default permission.
         let args = vec![
             "s3sync",
             "--source-access-key",
             "dummy_access_key",
             "--source-secret-access-key",
             "dummy_secret_access_key",
+            "--preserve-file-mode",
             "s3://dummy-bucket",
-            "./test_data/",
+            &target,
         ];
         let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
         let (stats_sender, _) = async_channel::unbounded();
@@ -1610,52 +3466,44 @@ mod tests {
             create_pipeline_cancellation_token(),
             stats_sender,
             config.target_client_config.clone(),
-            None,
-            None,
+            StorageLimiters::default(),
         )
         .await;
 
-        let head_object_output = storage
-            .head_object(
-                "source/dir1/no_data",
-                None,
-                None,
-                None,
-                SseCustomerKey { key: None },
-                None,
-            )
-            .await;
-        assert!(head_object_output.is_err());
+        for (key, file_mode) in [("data2", None), ("data3", Some("rwxr-xr-x"))] {
+            storage
+                .put_object(
+                    key,
+                    GetObjectOutputBuilder::default()
+                        .set_content_length(Some(3))
+                        .last_modified(DateTime::from_secs(1))
+                        .set_metadata(file_mode.map(|file_mode| {
+                            HashMap::from([(
+                                S3SYNC_FILE_MODE_METADATA_KEY.to_string(),
+                                file_mode.to_string(),
+                            )])
+                        }))
+                        .body(ByteStream::from_static(b"foo"))
+                        .build(),
+                    None,
+                    None,
+                )
+                .await
+                .unwrap();
 
-        if let Some(SdkError::ServiceError(e)) = head_object_output
-            .err()
-            .unwrap()
-            .downcast_ref::<SdkError<HeadObjectError, Response<SdkBody>>>()
-        {
-            assert!(e.err().is_not_found());
-            return;
+            assert_ne!(
+                fs_util::get_file_mode(&temp_dir.path().join(key)).unwrap(),
+                "751"
+            );
         }
-
-        panic!("no 404 error occurred")
     }
 
     #[tokio::test]
-    #[cfg(target_family = "unix")]
-    async fn head_object_error() {
+    async fn put_object_with_verify_only_on_mismatch_redownload() {
         init_dummy_tracing_subscriber();
 
-        use std::fs;
-        use std::os::unix::fs::PermissionsExt;
-
-        if nix::unistd::geteuid().is_root() {
-            panic!("run tests from root");
-        }
-
-        let mut permissions = fs::metadata("./test_data/denied_dir5")
-            .unwrap()
-            .permissions();
-        permissions.set_mode(0o000);
-        fs::set_permissions("./test_data/denied_dir5", permissions).unwrap();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let target = format!("{}/", temp_dir.path().to_string_lossy());
 
         let args = vec![
             "s3sync",
@@ -1663,8 +3511,9 @@ mod tests {
             "dummy_access_key",
             "--source-secret-access-key",
             "dummy_secret_access_key",
+            "--verify-only-on-mismatch-redownload",
             "s3://dummy-bucket",
-            "./test_data/",
+            &target,
         ];
         let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
         let (stats_sender, _) = async_channel::unbounded();
@@ -1675,53 +3524,72 @@ mod tests {
             create_pipeline_cancellation_token(),
             stats_sender,
             config.target_client_config.clone(),
-            None,
-            None,
+            StorageLimiters::default(),
         )
         .await;
 
-        let head_object_output = storage
-            .head_object(
-                "denied_dir5/data",
-                None,
-                None,
+        std::fs::write(temp_dir.path().join("foo"), b"old").unwrap();
+
+        // md5 of an empty object.
+        let e = storage
+            .put_object(
+                "foo",
+                GetObjectOutputBuilder::default()
+                    .set_content_length(Some(3))
+                    .e_tag("\"d41d8cd98f00b204e9800998ecf8427e\"")
+                    .last_modified(DateTime::from_secs(1))
+                    .body(ByteStream::from_static(b"foo"))
+                    .build(),
                 None,
-                SseCustomerKey { key: None },
                 None,
             )
-            .await;
+            .await
+            .err()
+            .unwrap();
 
-        permissions = fs::metadata("./test_data/denied_dir5")
-            .unwrap()
-            .permissions();
-        permissions.set_mode(0o755);
-        fs::set_permissions("./test_data/denied_dir5", permissions).unwrap();
+        assert_eq!(
+            *e.downcast_ref::<S3syncError>().unwrap(),
+            S3syncError::LocalVerificationMismatch
+        );
 
-        assert!(head_object_output.is_err());
+        // the mismatched download does not overwrite the existing file, and no temp file is left.
+        assert_eq!(std::fs::read(temp_dir.path().join("foo")).unwrap(), b"old");
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 1);
 
-        if matches!(
-            head_object_output
-                .err()
-                .unwrap()
-                .downcast_ref::<SdkError<HeadObjectError, Response<SdkBody>>>(),
-            Some(SdkError::ServiceError(_))
-        ) {
-            panic!("ServiceError occurred");
-        }
+        storage
+            .put_object(
+                "foo",
+                GetObjectOutputBuilder::default()
+                    .set_content_length(Some(3))
+                    .e_tag("\"acbd18db4cc2f85cedef654fccc4a4d8\"")
+                    .last_modified(DateTime::from_secs(1))
+                    .body(ByteStream::from_static(b"foo"))
+                    .build(),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(std::fs::read(temp_dir.path().join("foo")).unwrap(), b"foo");
     }
 
     #[tokio::test]
-    async fn put_object() {
+    async fn put_object_with_strict_verify() {
         init_dummy_tracing_subscriber();
 
+        let temp_dir = tempfile::tempdir().unwrap();
+        let target = format!("{}/", temp_dir.path().to_string_lossy());
+
         let args = vec![
             "s3sync",
             "--source-access-key",
             "dummy_access_key",
             "--source-secret-access-key",
             "dummy_secret_access_key",
+            "--strict-verify",
             "s3://dummy-bucket",
-            "./test_data/",
+            &target,
         ];
         let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
         let (stats_sender, _) = async_channel::unbounded();
@@ -1732,41 +3600,69 @@ mod tests {
             create_pipeline_cancellation_token(),
             stats_sender,
             config.target_client_config.clone(),
-            None,
-            None,
+            StorageLimiters::default(),
         )
         .await;
 
-        let get_object_output = storage
-            .get_object(
-                "source/data1",
-                None,
-                None,
+        // md5 of an empty object.
+        let e = storage
+            .put_object(
+                "foo",
+                GetObjectOutputBuilder::default()
+                    .set_content_length(Some(3))
+                    .e_tag("\"d41d8cd98f00b204e9800998ecf8427e\"")
+                    .last_modified(DateTime::from_secs(1))
+                    .body(ByteStream::from_static(b"foo"))
+                    .build(),
                 None,
-                SseCustomerKey { key: None },
                 None,
             )
             .await
+            .err()
             .unwrap();
 
+        assert_eq!(
+            *e.downcast_ref::<S3syncError>().unwrap(),
+            S3syncError::VerificationMismatch
+        );
+        // the mismatched file is not left to be regarded as up to date in the next run.
+        assert!(!temp_dir.path().join("foo").exists());
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 0);
+
         storage
-            .put_object("target/data1", get_object_output, None, None)
+            .put_object(
+                "bar",
+                GetObjectOutputBuilder::default()
+                    .set_content_length(Some(3))
+                    .e_tag("\"acbd18db4cc2f85cedef654fccc4a4d8\"")
+                    .last_modified(DateTime::from_secs(1))
+                    .body(ByteStream::from_static(b"foo"))
+                    .build(),
+                None,
+                None,
+            )
             .await
             .unwrap();
     }
 
     #[tokio::test]
-    async fn put_object_directory() {
+    async fn put_object_with_local_tmp_dir() {
         init_dummy_tracing_subscriber();
 
+        let temp_dir = tempfile::tempdir().unwrap();
+        let target = format!("{}/", temp_dir.path().to_string_lossy());
+        let local_tmp_dir = tempfile::tempdir().unwrap();
+
         let args = vec![
             "s3sync",
             "--source-access-key",
             "dummy_access_key",
             "--source-secret-access-key",
             "dummy_secret_access_key",
+            "--local-tmp-dir",
+            local_tmp_dir.path().to_str().unwrap(),
             "s3://dummy-bucket",
-            "./test_data/",
+            &target,
         ];
         let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
         let (stats_sender, _) = async_channel::unbounded();
@@ -1777,37 +3673,48 @@ mod tests {
             create_pipeline_cancellation_token(),
             stats_sender,
             config.target_client_config.clone(),
-            None,
-            None,
+            StorageLimiters::default(),
         )
         .await;
 
         storage
             .put_object(
-                "target/",
+                "dir/foo",
                 GetObjectOutputBuilder::default()
-                    .set_content_length(Some(0))
+                    .set_content_length(Some(3))
                     .last_modified(DateTime::from_secs(1))
+                    .body(ByteStream::from_static(b"foo"))
                     .build(),
                 None,
                 None,
             )
             .await
             .unwrap();
+
+        assert_eq!(
+            std::fs::read(temp_dir.path().join("dir").join("foo")).unwrap(),
+            b"foo"
+        );
+        assert_eq!(std::fs::read_dir(local_tmp_dir.path()).unwrap().count(), 0);
     }
 
     #[tokio::test]
-    async fn put_object_with_directory_traversal() {
+    async fn put_object_with_skip_on_conflict() {
         init_dummy_tracing_subscriber();
 
+        let temp_dir = tempfile::tempdir().unwrap();
+        let target = format!("{}/", temp_dir.path().to_string_lossy());
+        std::fs::write(temp_dir.path().join("foo"), b"file").unwrap();
+
         let args = vec![
             "s3sync",
             "--source-access-key",
             "dummy_access_key",
             "--source-secret-access-key",
             "dummy_secret_access_key",
+            "--skip-on-conflict",
             "s3://dummy-bucket",
-            "./test_data/",
+            &target,
         ];
         let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
         let (stats_sender, _) = async_channel::unbounded();
@@ -1818,17 +3725,17 @@ mod tests {
             create_pipeline_cancellation_token(),
             stats_sender,
             config.target_client_config.clone(),
-            None,
-            None,
+            StorageLimiters::default(),
         )
         .await;
 
         let e = storage
             .put_object(
-                "target/../../etc/passwd",
+                "foo/bar",
                 GetObjectOutputBuilder::default()
-                    .set_content_length(Some(1))
+                    .set_content_length(Some(3))
                     .last_modified(DateTime::from_secs(1))
+                    .body(ByteStream::from_static(b"bar"))
                     .build(),
                 None,
                 None,
@@ -1839,8 +3746,9 @@ mod tests {
 
         assert_eq!(
             *e.downcast_ref::<S3syncError>().unwrap(),
-            S3syncError::DirectoryTraversalError
+            S3syncError::PathConflictError
         );
+        assert!(!temp_dir.path().join("foo (1)").try_exists().unwrap());
     }
 
     #[test]
@@ -1888,8 +3796,7 @@ mod tests {
             create_pipeline_cancellation_token(),
             stats_sender,
             config.target_client_config.clone(),
-            None,
-            None,
+            StorageLimiters::default(),
         )
         .await;
 
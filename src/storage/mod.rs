@@ -11,18 +11,21 @@ use aws_sdk_s3::operation::get_object_tagging::GetObjectTaggingOutput;
 use aws_sdk_s3::operation::head_object::HeadObjectOutput;
 use aws_sdk_s3::operation::put_object::PutObjectOutput;
 use aws_sdk_s3::operation::put_object_tagging::PutObjectTaggingOutput;
-use aws_sdk_s3::primitives::ByteStream;
-use aws_sdk_s3::types::{ChecksumMode, ObjectPart, ObjectVersion, Tagging};
+use aws_sdk_s3::primitives::{ByteStream, DateTime};
+use aws_sdk_s3::types::{ChecksumMode, MultipartUpload, ObjectPart, ObjectVersion, Tagging};
 use aws_sdk_s3::Client;
 use aws_smithy_types::body::SdkBody;
 use dyn_clone::DynClone;
 use hyper::Body;
 use leaky_bucket::RateLimiter;
 use tokio::io::{AsyncRead, BufReader};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 use tokio_util::io::ReaderStream;
+use tracing::info;
 
 use crate::config::ClientConfig;
 use crate::storage::checksum::AdditionalChecksum;
+use crate::storage::request_rate_limiter::RequestRateLimiter;
 use crate::types::async_callback::AsyncReadWithCallback;
 use crate::types::token::PipelineCancellationToken;
 use crate::types::{ObjectChecksum, S3syncObject, SseCustomerKey, StoragePath, SyncStatistics};
@@ -31,7 +34,10 @@ use crate::Config;
 pub mod additional_checksum_verify;
 pub mod checksum;
 pub mod e_tag_verify;
+pub mod encryption_verify;
+pub mod list_throttle;
 pub mod local;
+pub mod request_rate_limiter;
 pub mod s3;
 
 pub type Storage = Box<dyn StorageTrait + Send + Sync>;
@@ -41,6 +47,16 @@ pub struct StoragePair {
     pub target: Storage,
 }
 
+// Config holds only the plain limits. The limiters are created once per pipeline and shared by both storages.
+#[derive(Clone, Default)]
+pub struct StorageLimiters {
+    pub rate_limit_objects_per_sec: Option<Arc<RateLimiter>>,
+    pub rate_limit_bandwidth: Option<Arc<RateLimiter>>,
+    pub checksum_semaphore: Option<Arc<Semaphore>>,
+    pub source_request_rate_limiter: Option<Arc<RequestRateLimiter>>,
+    pub target_request_rate_limiter: Option<Arc<RequestRateLimiter>>,
+}
+
 #[async_trait]
 pub trait StorageFactory {
     async fn create(
@@ -49,8 +65,7 @@ pub trait StorageFactory {
         cancellation_token: PipelineCancellationToken,
         stats_sender: Sender<SyncStatistics>,
         client_config: Option<ClientConfig>,
-        rate_limit_objects_per_sec: Option<Arc<RateLimiter>>,
-        rate_limit_bandwidth: Option<Arc<RateLimiter>>,
+        limiters: StorageLimiters,
     ) -> Storage;
 }
 
@@ -64,6 +79,19 @@ pub trait StorageTrait: DynClone {
         max_keys: i32,
         warn_as_error: bool,
     ) -> Result<()>;
+    async fn list_objects_with_parallel_prefixes(
+        &self,
+        sender: &Sender<S3syncObject>,
+        max_keys: i32,
+        prefixes: &[String],
+    ) -> Result<()>;
+    async fn list_objects_under_sub_prefix(
+        &self,
+        sender: &Sender<S3syncObject>,
+        max_keys: i32,
+        sub_prefix: &str,
+        warn_as_error: bool,
+    ) -> Result<()>;
     async fn list_object_versions(
         &self,
         sender: &Sender<S3syncObject>,
@@ -79,6 +107,15 @@ pub trait StorageTrait: DynClone {
         sse_c_key: SseCustomerKey,
         sse_c_key_md5: Option<String>,
     ) -> Result<GetObjectOutput>;
+    async fn get_object_if_modified_since(
+        &self,
+        key: &str,
+        if_modified_since: DateTime,
+        checksum_mode: Option<ChecksumMode>,
+        sse_c: Option<String>,
+        sse_c_key: SseCustomerKey,
+        sse_c_key_md5: Option<String>,
+    ) -> Result<GetObjectOutput>;
     async fn get_object_versions(&self, key: &str, max_keys: i32) -> Result<Vec<ObjectVersion>>;
     async fn get_object_tagging(
         &self,
@@ -135,6 +172,10 @@ pub trait StorageTrait: DynClone {
         version_id: Option<String>,
     ) -> Result<DeleteObjectTaggingOutput>;
     async fn is_versioning_enabled(&self) -> Result<bool>;
+    // keys of multipart uploads are full keys(including prefix).
+    async fn list_multipart_uploads(&self) -> Result<Vec<MultipartUpload>>;
+    async fn get_multipart_upload_size(&self, key: &str, upload_id: &str) -> Result<u64>;
+    async fn abort_multipart_upload(&self, key: &str, upload_id: &str) -> Result<()>;
     fn get_client(&self) -> Option<Arc<Client>>;
     fn get_stats_sender(&self) -> Sender<SyncStatistics>;
     async fn send_stats(&self, stats: SyncStatistics);
@@ -162,6 +203,37 @@ where
     )))
 }
 
+// Limits the number of concurrent checksum/ETag computations of local files(--checksum-parallelism).
+pub async fn acquire_checksum_permit(
+    checksum_semaphore: &Option<Arc<Semaphore>>,
+) -> Option<OwnedSemaphorePermit> {
+    match checksum_semaphore {
+        Some(semaphore) => Some(semaphore.clone().acquire_owned().await.unwrap()),
+        None => None,
+    }
+}
+
+// The ETag/additional checksum of the target object after verification(--checksum-display).
+// with --json-tracing, each object is output as a JSON line.
+pub fn display_checksum(
+    key: &str,
+    e_tag: Option<&str>,
+    checksum_algorithm: Option<&str>,
+    checksum: Option<&str>,
+) {
+    if e_tag.is_none() && checksum.is_none() {
+        return;
+    }
+
+    info!(
+        key = key,
+        e_tag = e_tag,
+        checksum_algorithm = checksum.and(checksum_algorithm),
+        checksum = checksum,
+        "checksum of the target object."
+    );
+}
+
 pub fn get_size_string_from_content_range(get_object_output: &GetObjectOutput) -> String {
     let content_length_str = get_object_output.content_length().unwrap().to_string();
     let size = if get_object_output.content_range().is_some() {
@@ -178,10 +250,123 @@ pub fn get_size_string_from_content_range(get_object_output: &GetObjectOutput) -
     size.to_string()
 }
 
+// --verify-range-reads: a misbehaving backend may return the bytes of another range.
+// requested_range is the inclusive byte range of the Range header, or None for the whole object.
+pub fn verify_content_range(
+    get_object_output: &GetObjectOutput,
+    requested_range: Option<(u64, u64)>,
+) -> std::result::Result<(), String> {
+    let content_length = get_object_output.content_length().unwrap_or_default() as u64;
+
+    let Some(content_range) = get_object_output.content_range() else {
+        if requested_range.is_some() {
+            return Err("Content-Range is not returned for the range request.".to_string());
+        }
+        return Ok(());
+    };
+
+    let (first, last, total) = parse_content_range(content_range)
+        .ok_or_else(|| format!("invalid Content-Range: {}", content_range))?;
+
+    let (expected_first, expected_last) = match requested_range {
+        Some(requested_range) => requested_range,
+        // the whole object is requested, so the range must cover it.
+        None => {
+            if total != Some(content_length) {
+                return Err(format!(
+                    "Content-Range({}) does not cover the whole object of {} bytes.",
+                    content_range, content_length
+                ));
+            }
+            (0, content_length.saturating_sub(1))
+        }
+    };
+
+    if first != expected_first || last != expected_last {
+        return Err(format!(
+            "Content-Range({}) does not match the requested range(bytes={}-{}).",
+            content_range, expected_first, expected_last
+        ));
+    }
+    if last - first + 1 != content_length {
+        return Err(format!(
+            "Content-Length({}) does not match Content-Range({}).",
+            content_length, content_range
+        ));
+    }
+
+    Ok(())
+}
+
+// example: bytes 200-1000/67589 -> (200, 1000, Some(67589)). the total may be `*`.
+fn parse_content_range(content_range: &str) -> Option<(u64, u64, Option<u64>)> {
+    let (range, total) = content_range.strip_prefix("bytes ")?.split_once('/')?;
+    let (first, last) = range.split_once('-')?;
+    let (first, last) = (first.parse::<u64>().ok()?, last.parse::<u64>().ok()?);
+    if last < first {
+        return None;
+    }
+
+    let total = if total == "*" {
+        None
+    } else {
+        Some(total.parse::<u64>().ok()?)
+    };
+
+    Some((first, last, total))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn verify_content_range_test() {
+        init_dummy_tracing_subscriber();
+
+        let build_output = |content_length: i64, content_range: Option<&str>| {
+            GetObjectOutput::builder()
+                .set_content_length(Some(content_length))
+                .set_content_range(content_range.map(|content_range| content_range.to_string()))
+                .build()
+        };
+
+        // the whole object
+        verify_content_range(&build_output(100, None), None).unwrap();
+        verify_content_range(&build_output(100, Some("bytes 0-99/100")), None).unwrap();
+        assert!(verify_content_range(&build_output(100, Some("bytes 100-199/300")), None).is_err());
+        assert!(verify_content_range(&build_output(100, Some("bytes 0-99/*")), None).is_err());
+
+        // the range request
+        verify_content_range(&build_output(1, Some("bytes 0-0/100")), Some((0, 0))).unwrap();
+        verify_content_range(&build_output(1, Some("bytes 0-0/*")), Some((0, 0))).unwrap();
+        assert!(verify_content_range(&build_output(1, None), Some((0, 0))).is_err());
+        assert!(
+            verify_content_range(&build_output(1, Some("bytes 1-1/100")), Some((0, 0))).is_err()
+        );
+        assert!(
+            verify_content_range(&build_output(2, Some("bytes 0-0/100")), Some((0, 0))).is_err()
+        );
+        assert!(
+            verify_content_range(&build_output(1, Some("items 0-0/100")), Some((0, 0))).is_err()
+        );
+    }
+
+    #[test]
+    fn parse_content_range_test() {
+        init_dummy_tracing_subscriber();
+
+        assert_eq!(
+            parse_content_range("bytes 200-1000/67589"),
+            Some((200, 1000, Some(67589)))
+        );
+        assert_eq!(parse_content_range("bytes 0-0/*"), Some((0, 0, None)));
+        assert!(parse_content_range("bytes 10-0/100").is_none());
+        assert!(parse_content_range("bytes */100").is_none());
+        assert!(parse_content_range("bytes 0-a/100").is_none());
+        assert!(parse_content_range("").is_none());
+    }
+
     #[test]
     fn get_size_string_from_content_range_test() {
         init_dummy_tracing_subscriber();
@@ -205,6 +390,40 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn acquire_checksum_permit_test() {
+        init_dummy_tracing_subscriber();
+
+        assert!(acquire_checksum_permit(&None).await.is_none());
+
+        let semaphore = Some(Arc::new(Semaphore::new(1)));
+        let permit = acquire_checksum_permit(&semaphore).await;
+        assert!(permit.is_some());
+        assert_eq!(semaphore.as_ref().unwrap().available_permits(), 0);
+
+        drop(permit);
+        assert_eq!(semaphore.as_ref().unwrap().available_permits(), 1);
+    }
+
+    #[test]
+    fn display_checksum_test() {
+        init_dummy_tracing_subscriber();
+
+        display_checksum(
+            "data1",
+            Some("\"d41d8cd98f00b204e9800998ecf8427e\""),
+            Some("SHA256"),
+            Some("47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU="),
+        );
+        display_checksum(
+            "data1",
+            Some("\"d41d8cd98f00b204e9800998ecf8427e\""),
+            None,
+            None,
+        );
+        display_checksum("data1", None, Some("SHA256"), None);
+    }
+
     fn init_dummy_tracing_subscriber() {
         let _ = tracing_subscriber::fmt()
             .with_env_filter("dummy=trace")
@@ -0,0 +1,127 @@
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use leaky_bucket::RateLimiter;
+
+// default refill interval 100ms
+const REFILL_PER_INTERVAL_DIVIDER: u32 = 10;
+
+// Limits the S3 API requests of one side(--source-request-rate-limit/--target-request-rate-limit).
+// unlike --rate-limit-objects, every request is counted, including the list, head, tagging and
+// multipart upload requests.
+pub struct RequestRateLimiter {
+    rate_limiter: RateLimiter,
+    requests_per_sec: u32,
+    request_count: AtomicU64,
+    start_time: Instant,
+}
+
+impl RequestRateLimiter {
+    pub fn new(requests_per_sec: u32) -> Self {
+        Self {
+            rate_limiter: build_rate_limiter(requests_per_sec),
+            requests_per_sec,
+            request_count: AtomicU64::new(0),
+            start_time: Instant::now(),
+        }
+    }
+
+    pub async fn acquire(&self) {
+        self.rate_limiter.acquire(1).await;
+        self.request_count.fetch_add(1, Ordering::SeqCst);
+    }
+
+    pub fn get_requests_per_sec(&self) -> u32 {
+        self.requests_per_sec
+    }
+
+    pub fn get_request_count(&self) -> u64 {
+        self.request_count.load(Ordering::SeqCst)
+    }
+
+    pub fn get_effective_rate(&self) -> f64 {
+        let elapsed = self.start_time.elapsed().as_secs_f64();
+        if elapsed == 0.0 {
+            return 0.0;
+        }
+
+        self.get_request_count() as f64 / elapsed
+    }
+}
+
+impl fmt::Debug for RequestRateLimiter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestRateLimiter")
+            .field("requests_per_sec", &self.requests_per_sec)
+            .field("request_count", &self.get_request_count())
+            .finish()
+    }
+}
+
+pub async fn acquire_request_permit(request_rate_limiter: &Option<Arc<RequestRateLimiter>>) {
+    if let Some(request_rate_limiter) = request_rate_limiter {
+        request_rate_limiter.acquire().await;
+    }
+}
+
+// the refill interval is stretched for a small limit, so that the limit is never exceeded.
+fn build_rate_limiter(requests_per_sec: u32) -> RateLimiter {
+    let (refill, interval) = refill_and_interval(requests_per_sec);
+
+    RateLimiter::builder()
+        .max(requests_per_sec as usize)
+        .initial(requests_per_sec as usize)
+        .refill(refill)
+        .interval(interval)
+        .fair(false)
+        .build()
+}
+
+fn refill_and_interval(requests_per_sec: u32) -> (usize, Duration) {
+    if requests_per_sec < REFILL_PER_INTERVAL_DIVIDER {
+        (1, Duration::from_millis(1000 / requests_per_sec as u64))
+    } else {
+        (
+            (requests_per_sec / REFILL_PER_INTERVAL_DIVIDER) as usize,
+            Duration::from_millis(100),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refill_and_interval_test() {
+        init_dummy_tracing_subscriber();
+
+        assert_eq!(refill_and_interval(1), (1, Duration::from_millis(1000)));
+        assert_eq!(refill_and_interval(4), (1, Duration::from_millis(250)));
+        assert_eq!(refill_and_interval(10), (1, Duration::from_millis(100)));
+        assert_eq!(refill_and_interval(25), (2, Duration::from_millis(100)));
+        assert_eq!(refill_and_interval(1000), (100, Duration::from_millis(100)));
+    }
+
+    #[tokio::test]
+    async fn acquire_and_count() {
+        init_dummy_tracing_subscriber();
+
+        let request_rate_limiter = Arc::new(RequestRateLimiter::new(100));
+        for _ in 0..5 {
+            acquire_request_permit(&Some(request_rate_limiter.clone())).await;
+        }
+        acquire_request_permit(&None).await;
+
+        assert_eq!(request_rate_limiter.get_request_count(), 5);
+        assert!(0.0 < request_rate_limiter.get_effective_rate());
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
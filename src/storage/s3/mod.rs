@@ -1,7 +1,9 @@
 use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::future::Future;
 use std::path::PathBuf;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
 use std::sync::Arc;
 
 use anyhow::{anyhow, Context, Result};
@@ -10,39 +12,56 @@ use async_trait::async_trait;
 use aws_sdk_s3::error::ProvideErrorMetadata;
 use aws_sdk_s3::operation::delete_object::DeleteObjectOutput;
 use aws_sdk_s3::operation::delete_object_tagging::DeleteObjectTaggingOutput;
-use aws_sdk_s3::operation::get_object::GetObjectOutput;
+use aws_sdk_s3::operation::get_object::{GetObjectError, GetObjectOutput};
+use aws_sdk_s3::operation::get_object_attributes::GetObjectAttributesError;
 use aws_sdk_s3::operation::get_object_tagging::GetObjectTaggingOutput;
-use aws_sdk_s3::operation::head_object::HeadObjectOutput;
+use aws_sdk_s3::operation::head_object::{HeadObjectError, HeadObjectOutput};
 use aws_sdk_s3::operation::put_object::PutObjectOutput;
 use aws_sdk_s3::operation::put_object_tagging::PutObjectTaggingOutput;
+use aws_sdk_s3::primitives::DateTime;
 use aws_sdk_s3::types::builders::ObjectPartBuilder;
+#[cfg(feature = "s3_select")]
+use aws_sdk_s3::types::ExpressionType;
 use aws_sdk_s3::types::{
-    BucketVersioningStatus, ChecksumMode, DeleteMarkerEntry, ObjectAttributes, ObjectPart,
-    ObjectVersion, Tagging,
+    BucketVersioningStatus, ChecksumAlgorithm, ChecksumMode, DeleteMarkerEntry, MultipartUpload,
+    ObjectAttributes, ObjectPart, ObjectVersion, ObjectVersionStorageClass,
+    OptionalObjectAttributes, RestoreStatus, Tagging,
 };
 use aws_sdk_s3::Client;
+use aws_smithy_runtime_api::client::result::SdkError;
+use aws_smithy_runtime_api::http::Response;
+use aws_smithy_types::body::SdkBody;
 use aws_smithy_types_convert::date_time::DateTimeExt;
 use leaky_bucket::RateLimiter;
-use tracing::{debug, info, trace};
+use tokio::sync::Semaphore;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, trace, warn};
 
 use crate::config::ClientConfig;
 use crate::storage::checksum::AdditionalChecksum;
+use crate::storage::list_throttle::{acquire_list_permit, ListThrottle, ListThrottleInterceptor};
+use crate::storage::request_rate_limiter::{acquire_request_permit, RequestRateLimiter};
 use crate::storage::s3::upload_manager::UploadManager;
 use crate::storage::{
-    convert_to_buf_byte_stream_with_callback, get_size_string_from_content_range, Storage,
-    StorageFactory, StorageTrait,
+    convert_to_buf_byte_stream_with_callback, get_size_string_from_content_range,
+    verify_content_range, Storage, StorageFactory, StorageLimiters, StorageTrait,
 };
 use crate::types::token::PipelineCancellationToken;
-use crate::types::SyncStatistics::{SyncBytes, SyncSkip};
+#[cfg(feature = "s3_select")]
+use crate::types::SourceSelectConfig;
+use crate::types::SyncStatistics::{SyncBytes, SyncSkip, SyncWarning};
 use crate::types::{
-    clone_object_version_with_key, ObjectChecksum, ObjectVersions, S3syncObject, SseCustomerKey,
-    StoragePath, SyncStatistics,
+    clone_object_version_with_key, ObjectChecksum, ObjectRestoreStatus, ObjectVersions,
+    S3syncObject, SourceVersionMap, SseCustomerKey, StoragePath, SyncStatistics,
 };
 use crate::Config;
 
 const EXPRESS_ONEZONE_STORAGE_SUFFIX: &str = "--x-s3";
+const NON_RECURSIVE_DELIMITER: &str = "/";
 
 mod client_builder;
+#[cfg(feature = "s3_select")]
+mod select;
 mod upload_manager;
 
 pub struct S3StorageFactory {}
@@ -55,8 +74,7 @@ impl StorageFactory for S3StorageFactory {
         cancellation_token: PipelineCancellationToken,
         stats_sender: Sender<SyncStatistics>,
         client_config: Option<ClientConfig>,
-        rate_limit_objects_per_sec: Option<Arc<RateLimiter>>,
-        rate_limit_bandwidth: Option<Arc<RateLimiter>>,
+        limiters: StorageLimiters,
     ) -> Storage {
         S3Storage::boxed_new(
             config,
@@ -66,8 +84,12 @@ impl StorageFactory for S3StorageFactory {
             Some(Arc::new(
                 client_config.as_ref().unwrap().create_client().await,
             )),
-            rate_limit_objects_per_sec,
-            rate_limit_bandwidth,
+            client_config
+                .as_ref()
+                .unwrap()
+                .expected_bucket_owner
+                .clone(),
+            limiters,
         )
         .await
     }
@@ -80,9 +102,19 @@ struct S3Storage {
     prefix: String,
     cancellation_token: PipelineCancellationToken,
     client: Option<Arc<Client>>,
+    expected_bucket_owner: Option<String>,
+    include_restore_status: bool,
+    include_owner: bool,
+    source_version_map: Option<Arc<SourceVersionMap>>,
+    #[cfg(feature = "s3_select")]
+    source_select: Option<SourceSelectConfig>,
     stats_sender: Sender<SyncStatistics>,
     rate_limit_objects_per_sec: Option<Arc<RateLimiter>>,
     rate_limit_bandwidth: Option<Arc<RateLimiter>>,
+    // with --source-sse-c-probe, whether the last probed source object has required SSE-C.
+    sse_c_required: Option<Arc<AtomicBool>>,
+    request_rate_limiter: Option<Arc<RequestRateLimiter>>,
+    list_throttle: Option<Arc<ListThrottle>>,
 }
 
 impl S3Storage {
@@ -92,8 +124,8 @@ impl S3Storage {
         cancellation_token: PipelineCancellationToken,
         stats_sender: Sender<SyncStatistics>,
         client: Option<Arc<Client>>,
-        rate_limit_objects_per_sec: Option<Arc<RateLimiter>>,
-        rate_limit_bandwidth: Option<Arc<RateLimiter>>,
+        expected_bucket_owner: Option<String>,
+        limiters: StorageLimiters,
     ) -> Storage {
         let (bucket, prefix) = if let StoragePath::S3 { bucket, prefix } = path {
             (bucket, prefix)
@@ -101,20 +133,206 @@ impl S3Storage {
             panic!("s3 path no found")
         };
 
+        // the restore status, the pinned versions and S3 Select are only for the source storage.
+        let is_source = match &config.source {
+            StoragePath::S3 {
+                bucket: source_bucket,
+                prefix: source_prefix,
+            } => *source_bucket == bucket && *source_prefix == prefix,
+            StoragePath::Local(_) => false,
+        };
+        let include_restore_status = config.source_list_include_restore_status && is_source;
+        let include_owner = config.source_list_include_owner && is_source;
+        let source_version_map = if is_source {
+            config.source_version_map.clone()
+        } else {
+            None
+        };
+        #[cfg(feature = "s3_select")]
+        let source_select = if is_source {
+            config.source_select.clone()
+        } else {
+            None
+        };
+        let sse_c_required = if config.source_sse_c_probe && is_source {
+            Some(Arc::new(AtomicBool::new(false)))
+        } else {
+            None
+        };
+        let request_rate_limiter = if is_source {
+            limiters.source_request_rate_limiter
+        } else {
+            limiters.target_request_rate_limiter
+        };
+        let list_throttle = config
+            .list_max_rps
+            .map(|list_max_rps| Arc::new(ListThrottle::new(list_max_rps)));
+
         let storage = S3Storage {
             config,
             bucket,
             prefix,
             cancellation_token,
             client,
+            expected_bucket_owner,
+            include_restore_status,
+            include_owner,
+            source_version_map,
+            #[cfg(feature = "s3_select")]
+            source_select,
             stats_sender,
-            rate_limit_objects_per_sec,
-            rate_limit_bandwidth,
+            rate_limit_objects_per_sec: limiters.rate_limit_objects_per_sec,
+            rate_limit_bandwidth: limiters.rate_limit_bandwidth,
+            sse_c_required,
+            request_rate_limiter,
+            list_throttle,
         };
 
         Box::new(storage)
     }
 
+    fn is_after_stop_at(&self, key: &str) -> bool {
+        is_after_stop_at(key, &self.prefix, &self.config.stop_at)
+    }
+
+    // the number of the prefixes listed at the same time is bounded by the semaphore.
+    fn spawn_prefix_lister(
+        &self,
+        sender: &Sender<S3syncObject>,
+        max_keys: i32,
+        list_prefix: &str,
+        semaphore: Arc<Semaphore>,
+    ) -> JoinHandle<Result<()>> {
+        let storage = self.clone();
+        let sender = sender.clone();
+        let list_prefix = list_prefix.to_string();
+        tokio::spawn(async move {
+            let _permit = semaphore
+                .acquire_owned()
+                .await
+                .context("tokio::sync::Semaphore::acquire_owned() failed.")?;
+
+            storage
+                .list_objects_with_prefix(&sender, max_keys, &list_prefix, None)
+                .await?;
+            Ok(())
+        })
+    }
+
+    async fn list_objects_with_prefix(
+        &self,
+        sender: &Sender<S3syncObject>,
+        max_keys: i32,
+        list_prefix: &str,
+        delimiter: Option<&str>,
+    ) -> Result<Vec<String>> {
+        let mut common_prefixes = Vec::new();
+        let mut continuation_token = "".to_string();
+        loop {
+            let mut list_object_v2 = self
+                .client
+                .as_ref()
+                .unwrap()
+                .list_objects_v2()
+                .bucket(&self.bucket)
+                .set_expected_bucket_owner(self.expected_bucket_owner.clone())
+                .prefix(list_prefix)
+                .set_delimiter(delimiter.map(|delimiter| delimiter.to_string()))
+                .set_start_after(
+                    self.config
+                        .start_after
+                        .as_ref()
+                        .map(|start_after| generate_full_key(&self.prefix, start_after)),
+                )
+                .max_keys(max_keys);
+            if self.include_restore_status {
+                list_object_v2 = list_object_v2
+                    .optional_object_attributes(OptionalObjectAttributes::RestoreStatus)
+            }
+            if self.include_owner {
+                list_object_v2 = list_object_v2.fetch_owner(true)
+            }
+            if !continuation_token.is_empty() {
+                list_object_v2 = list_object_v2.continuation_token(continuation_token.to_string())
+            }
+
+            if self.cancellation_token.is_cancelled() {
+                trace!("list_objects() canceled.");
+                break;
+            }
+
+            let list_objects_output = self
+                .send_list_request(|config_override| {
+                    list_object_v2
+                        .clone()
+                        .customize()
+                        .config_override(config_override)
+                        .send()
+                })
+                .await
+                .context("aws_sdk_s3::client::list_objects_v2() failed.")?;
+
+            for object in list_objects_output.contents() {
+                if self.is_after_stop_at(object.key().unwrap()) {
+                    trace!(key = object.key().unwrap(), "listing reached --stop-at.");
+                    return Ok(common_prefixes);
+                }
+
+                let key_without_prefix = remove_s3_prefix(object.key().unwrap(), &self.prefix);
+                if key_without_prefix.is_empty() {
+                    self.send_stats(SyncSkip {
+                        key: object.key().unwrap().to_string(),
+                    })
+                    .await;
+
+                    let key = object.key().unwrap();
+                    debug!(key = key, "key that is same as prefix is skipped.");
+
+                    continue;
+                }
+
+                if self.include_restore_status {
+                    self.send_restore_status(object.key().unwrap(), object.restore_status())
+                        .await;
+                }
+
+                let non_versioning_object =
+                    S3syncObject::clone_non_versioning_object_with_key(object, &key_without_prefix);
+
+                if let Err(e) = sender
+                    .send(non_versioning_object.clone())
+                    .await
+                    .context("async_channel::Sender::send() failed.")
+                {
+                    return if !sender.is_closed() {
+                        Err(e)
+                    } else {
+                        Ok(common_prefixes)
+                    };
+                }
+            }
+
+            common_prefixes.extend(
+                list_objects_output
+                    .common_prefixes()
+                    .iter()
+                    .filter_map(|common_prefix| common_prefix.prefix().map(|p| p.to_string()))
+                    .filter(|common_prefix| !self.is_after_stop_at(common_prefix)),
+            );
+
+            if !list_objects_output.is_truncated().unwrap() {
+                break;
+            }
+
+            continuation_token = list_objects_output
+                .next_continuation_token()
+                .unwrap()
+                .to_string();
+        }
+
+        Ok(common_prefixes)
+    }
+
     async fn aggregate_delete_markers(
         &self,
         delete_marker_entries: &[DeleteMarkerEntry],
@@ -151,6 +369,123 @@ impl S3Storage {
         }
     }
 
+    // HeadObject has no response body, so the cause of 400 is unknown. it is read from GetObject of
+    // the first byte of the version.
+    async fn is_version_not_found(&self, key: &str, version_id: &str, e: &anyhow::Error) -> bool {
+        if is_version_not_found_error(e) {
+            return true;
+        }
+
+        if !is_bad_request_error(e) {
+            return false;
+        }
+
+        self.get_object_first_byte(
+            key,
+            Some(version_id.to_string()),
+            self.config.source_sse_c.clone(),
+            self.config.source_sse_c_key.clone(),
+            self.config.source_sse_c_key_md5.clone(),
+        )
+        .await
+        .is_err_and(|e| is_invalid_version_id_error(&e))
+    }
+
+    // with --source-versions-at, the pinned versions are sent instead of listing the source.
+    async fn list_objects_from_source_version_map(
+        &self,
+        source_version_map: &SourceVersionMap,
+        sender: &Sender<S3syncObject>,
+        warn_as_error: bool,
+    ) -> Result<()> {
+        for (key, version_id) in &source_version_map.entries {
+            if self.cancellation_token.is_cancelled() {
+                trace!("list_objects() canceled.");
+                break;
+            }
+
+            let result = self
+                .head_object(
+                    key,
+                    Some(version_id.to_string()),
+                    Some(ChecksumMode::Enabled),
+                    self.config.source_sse_c.clone(),
+                    self.config.source_sse_c_key.clone(),
+                    self.config.source_sse_c_key_md5.clone(),
+                )
+                .await;
+
+            let head_object_output = match result {
+                Ok(head_object_output) => head_object_output,
+                Err(e) if self.is_version_not_found(key, version_id, &e).await => {
+                    self.send_stats(SyncWarning { key: key.clone() }).await;
+
+                    let error = e.root_cause().to_string();
+                    warn!(
+                        key = key,
+                        version_id = version_id,
+                        error = error,
+                        "the version in the source version map is not found. skipping."
+                    );
+
+                    if warn_as_error {
+                        return Err(anyhow!(
+                            "the version in the source version map is not found: {} {}.",
+                            key,
+                            version_id
+                        ));
+                    }
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+
+            let object = S3syncObject::Versioning(build_object_version_from_head_object(
+                key,
+                version_id,
+                &head_object_output,
+            ));
+            if let Err(e) = sender
+                .send(object)
+                .await
+                .context("async_channel::Sender::send() failed.")
+            {
+                return if !sender.is_closed() { Err(e) } else { Ok(()) };
+            }
+        }
+
+        Ok(())
+    }
+
+    // RestoreStatus is returned only for the archived objects that have been restored or are being restored.
+    async fn send_restore_status(&self, key: &str, restore_status: Option<&RestoreStatus>) {
+        let Some(restore_status) = restore_status else {
+            return;
+        };
+
+        let in_progress = restore_status.is_restore_in_progress().unwrap_or(false);
+        let expiry_date = restore_status
+            .restore_expiry_date()
+            .map(|expiry_date| expiry_date.to_chrono_utc().unwrap().to_rfc3339());
+
+        if in_progress {
+            info!(key = key, "restore is in progress.");
+        } else {
+            info!(
+                key = key,
+                restore_expiry_date = expiry_date.as_deref(),
+                "object has been restored."
+            );
+        }
+
+        self.send_stats(SyncStatistics::RestoreStatus(ObjectRestoreStatus {
+            key: key.to_string(),
+            in_progress,
+            expiry_date,
+        }))
+        .await;
+    }
+
     async fn aggregate_object_versions_and_send(
         &self,
         sender: &Sender<S3syncObject>,
@@ -173,13 +508,18 @@ impl S3Storage {
             }
 
             if !previous_key.is_empty() && previous_key != key_without_prefix {
-                Self::send_object_versions_with_sort(
+                self.send_object_versions_with_sort(
                     sender,
                     &mut s3sync_object_map.remove(&previous_key).unwrap(),
                 )
                 .await?;
             }
 
+            if self.include_restore_status {
+                self.send_restore_status(object.key().unwrap(), object.restore_status())
+                    .await;
+            }
+
             let versioning_object =
                 S3syncObject::clone_versioning_object_with_key(object, &key_without_prefix);
 
@@ -198,6 +538,7 @@ impl S3Storage {
     }
 
     async fn send_object_versions_with_sort(
+        &self,
         sender: &Sender<S3syncObject>,
         object_versions: &mut ObjectVersions,
     ) -> Result<()> {
@@ -214,6 +555,27 @@ impl S3Storage {
                 .cmp(&b.last_modified().as_nanos())
         });
 
+        let trimmed_object_versions =
+            trim_object_versions(object_versions, self.config.versions_limit);
+        for object in trimmed_object_versions {
+            let key = object.key();
+            let version_id = object.version_id();
+            debug!(
+                key = key,
+                version_id = version_id,
+                "version that exceeds --versions-limit is skipped."
+            );
+
+            self.send_stats(SyncSkip {
+                key: key.to_string(),
+            })
+            .await;
+        }
+
+        if self.config.versions_newest_first {
+            reverse_object_versions(object_versions);
+        }
+
         for object in object_versions {
             if let Err(e) = sender
                 .send(object.clone())
@@ -235,12 +597,15 @@ impl S3Storage {
         sse_c_key: SseCustomerKey,
         sse_c_key_md5: Option<String>,
     ) -> Result<GetObjectOutput> {
+        self.exec_request_rate_limit().await;
+
         let result = self
             .client
             .as_ref()
             .unwrap()
             .get_object()
             .bucket(&self.bucket)
+            .set_expected_bucket_owner(self.expected_bucket_owner.clone())
             .key(generate_full_key(&self.prefix, key))
             .set_version_id(version_id.clone())
             .set_sse_customer_algorithm(sse_c.clone())
@@ -251,6 +616,8 @@ impl S3Storage {
             .await;
 
         if let Ok(get_object_output) = result {
+            self.verify_range_read(key, &get_object_output, Some((0, 0)))?;
+
             return Ok(get_object_output);
         }
 
@@ -258,12 +625,15 @@ impl S3Storage {
         if let Some(code) = service_error.code() {
             // Use normal request for empty object. content-range is not set.
             if code == "InvalidRange" {
+                self.exec_request_rate_limit().await;
+
                 return self
                     .client
                     .as_ref()
                     .unwrap()
                     .get_object()
                     .bucket(&self.bucket)
+                    .set_expected_bucket_owner(self.expected_bucket_owner.clone())
                     .key(generate_full_key(&self.prefix, key))
                     .set_version_id(version_id)
                     .set_sse_customer_algorithm(sse_c)
@@ -278,93 +648,529 @@ impl S3Storage {
         Err(anyhow!(service_error))
     }
 
-    async fn exec_rate_limit_objects_per_sec(&self) {
-        if self.rate_limit_objects_per_sec.is_some() {
-            self.rate_limit_objects_per_sec
-                .as_ref()
-                .unwrap()
-                .acquire(1)
-                .await;
-        }
-    }
-}
-
-#[async_trait]
-impl StorageTrait for S3Storage {
-    fn is_local_storage(&self) -> bool {
-        false
-    }
+    // The attributes of the source object are taken from the first byte of it.
+    #[cfg(feature = "s3_select")]
+    async fn select_object(
+        &self,
+        key: &str,
+        source_select: &SourceSelectConfig,
+        sse_c: Option<String>,
+        sse_c_key: SseCustomerKey,
+        sse_c_key_md5: Option<String>,
+    ) -> Result<GetObjectOutput> {
+        let get_object_output = self
+            .get_object_first_byte(
+                key,
+                None,
+                sse_c.clone(),
+                sse_c_key.clone(),
+                sse_c_key_md5.clone(),
+            )
+            .await?;
 
-    fn is_express_onezone_storage(&self) -> bool {
-        is_express_onezone_storage(&self.bucket)
-    }
+        self.exec_request_rate_limit().await;
 
-    async fn list_objects(
-        &self,
-        sender: &Sender<S3syncObject>,
-        max_keys: i32,
-        _warn_as_error: bool,
-    ) -> Result<()> {
-        let mut continuation_token = "".to_string();
-        loop {
-            let mut list_object_v2 = self
-                .client
-                .as_ref()
-                .unwrap()
-                .list_objects_v2()
-                .bucket(&self.bucket)
-                .prefix(&self.prefix)
-                .max_keys(max_keys);
-            if !continuation_token.is_empty() {
-                list_object_v2 = list_object_v2.continuation_token(continuation_token.to_string())
-            }
+        let select_output = self
+            .client
+            .as_ref()
+            .unwrap()
+            .select_object_content()
+            .bucket(&self.bucket)
+            .set_expected_bucket_owner(self.expected_bucket_owner.clone())
+            .key(generate_full_key(&self.prefix, key))
+            .expression(&source_select.expression)
+            .expression_type(ExpressionType::Sql)
+            .input_serialization(select::build_input_serialization(
+                source_select.input_format,
+            ))
+            .output_serialization(select::build_output_serialization(
+                source_select.output_format,
+            ))
+            .set_sse_customer_algorithm(sse_c)
+            .set_sse_customer_key(sse_c_key.key.clone())
+            .set_sse_customer_key_md5(sse_c_key_md5)
+            .send()
+            .await
+            .context("aws_sdk_s3::client::select_object_content() failed.")?;
 
-            if self.cancellation_token.is_cancelled() {
-                trace!("list_objects() canceled.");
-                break;
-            }
+        let (records, records_size) =
+            select::spool_records(select_output, self.config.local_tmp_dir.as_deref()).await?;
 
-            let list_objects_output = list_object_v2
-                .send()
-                .await
-                .context("aws_sdk_s3::client::list_objects_v2() failed.")?;
+        debug!(
+            key = key,
+            source_size = get_object_output.content_length(),
+            selected_size = records_size,
+            "source object has been filtered by S3 Select."
+        );
 
-            for object in list_objects_output.contents() {
-                let key_without_prefix = remove_s3_prefix(object.key().unwrap(), &self.prefix);
-                if key_without_prefix.is_empty() {
-                    self.send_stats(SyncSkip {
-                        key: object.key().unwrap().to_string(),
-                    })
-                    .await;
+        Ok(select::replace_body_with_records(
+            get_object_output,
+            records,
+            records_size,
+            source_select.output_format,
+        ))
+    }
 
-                    let key = object.key().unwrap();
-                    debug!(key = key, "key that is same as prefix is skipped.");
+    async fn send_get_object(
+        &self,
+        key: &str,
+        version_id: Option<String>,
+        checksum_mode: Option<ChecksumMode>,
+        sse_c: Option<String>,
+        sse_c_key: SseCustomerKey,
+        sse_c_key_md5: Option<String>,
+    ) -> Result<GetObjectOutput> {
+        if self.config.dry_run {
+            return self
+                .get_object_first_byte(key, version_id, sse_c, sse_c_key, sse_c_key_md5)
+                .await;
+        }
 
-                    continue;
-                }
+        #[cfg(feature = "s3_select")]
+        if let Some(source_select) = &self.source_select {
+            return self
+                .select_object(key, source_select, sse_c, sse_c_key, sse_c_key_md5)
+                .await;
+        }
 
-                let non_versioning_object =
-                    S3syncObject::clone_non_versioning_object_with_key(object, &key_without_prefix);
+        self.exec_request_rate_limit().await;
 
-                if let Err(e) = sender
-                    .send(non_versioning_object.clone())
-                    .await
-                    .context("async_channel::Sender::send() failed.")
-                {
-                    return if !sender.is_closed() { Err(e) } else { Ok(()) };
-                }
+        let result = self
+            .client
+            .as_ref()
+            .unwrap()
+            .get_object()
+            .bucket(&self.bucket)
+            .set_expected_bucket_owner(self.expected_bucket_owner.clone())
+            .key(generate_full_key(&self.prefix, key))
+            .set_version_id(version_id)
+            .set_checksum_mode(checksum_mode)
+            .set_sse_customer_algorithm(sse_c)
+            .set_sse_customer_key(sse_c_key.key.clone())
+            .set_sse_customer_key_md5(sse_c_key_md5)
+            .send()
+            .await
+            .context("aws_sdk_s3::client::get_object() failed.")?;
+        self.verify_range_read(key, &result, None)?;
+
+        Ok(result)
+    }
+
+    async fn send_get_object_if_modified_since(
+        &self,
+        key: &str,
+        if_modified_since: DateTime,
+        checksum_mode: Option<ChecksumMode>,
+        sse_c: Option<String>,
+        sse_c_key: SseCustomerKey,
+        sse_c_key_md5: Option<String>,
+    ) -> Result<GetObjectOutput> {
+        if self.config.dry_run {
+            return self
+                .get_object_first_byte(key, None, sse_c, sse_c_key, sse_c_key_md5)
+                .await;
+        }
+
+        self.exec_request_rate_limit().await;
+
+        let result = self
+            .client
+            .as_ref()
+            .unwrap()
+            .get_object()
+            .bucket(&self.bucket)
+            .set_expected_bucket_owner(self.expected_bucket_owner.clone())
+            .key(generate_full_key(&self.prefix, key))
+            .if_modified_since(if_modified_since)
+            .set_checksum_mode(checksum_mode)
+            .set_sse_customer_algorithm(sse_c)
+            .set_sse_customer_key(sse_c_key.key.clone())
+            .set_sse_customer_key_md5(sse_c_key_md5)
+            .send()
+            .await
+            .context("aws_sdk_s3::client::get_object() failed.")?;
+        self.verify_range_read(key, &result, None)?;
+
+        Ok(result)
+    }
+
+    async fn send_head_object(
+        &self,
+        key: &str,
+        version_id: Option<String>,
+        checksum_mode: Option<ChecksumMode>,
+        sse_c: Option<String>,
+        sse_c_key: SseCustomerKey,
+        sse_c_key_md5: Option<String>,
+    ) -> Result<HeadObjectOutput> {
+        self.exec_request_rate_limit().await;
+
+        let result = self
+            .client
+            .as_ref()
+            .unwrap()
+            .head_object()
+            .bucket(&self.bucket)
+            .set_expected_bucket_owner(self.expected_bucket_owner.clone())
+            .key(generate_full_key(&self.prefix, key))
+            .set_version_id(version_id)
+            .set_checksum_mode(checksum_mode)
+            .set_sse_customer_algorithm(sse_c)
+            .set_sse_customer_key(sse_c_key.key.clone())
+            .set_sse_customer_key_md5(sse_c_key_md5)
+            .send()
+            .await
+            .context("aws_sdk_s3::client::head_object() failed.")?;
+
+        Ok(result)
+    }
+
+    // with --source-sse-c-probe, the request is sent with(or without) the SSE-C parameters in the same way as
+    // the last probed object. if S3 rejects the SSE-C parameters(400), it is sent again in the other way.
+    async fn probe_sse_c<T, F, Fut>(
+        &self,
+        sse_c: Option<String>,
+        sse_c_key: SseCustomerKey,
+        sse_c_key_md5: Option<String>,
+        request: F,
+    ) -> Result<T>
+    where
+        F: Fn(Option<String>, SseCustomerKey, Option<String>) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let Some(sse_c_required) = &self.sse_c_required else {
+            return request(sse_c, sse_c_key, sse_c_key_md5).await;
+        };
+
+        let send_request = |with_sse_c: bool| {
+            if with_sse_c {
+                request(sse_c.clone(), sse_c_key.clone(), sse_c_key_md5.clone())
+            } else {
+                request(None, SseCustomerKey { key: None }, None)
             }
+        };
 
-            if !list_objects_output.is_truncated().unwrap() {
-                break;
+        let with_sse_c = sse_c_required.load(AtomicOrdering::SeqCst);
+        match send_request(with_sse_c).await {
+            Err(e) if is_sse_c_mismatch_error(&e) => {
+                debug!(
+                    with_sse_c = !with_sse_c,
+                    "SSE-C parameters have been rejected. retrying."
+                );
+
+                let result = send_request(!with_sse_c).await;
+                if result.is_ok() {
+                    sse_c_required.store(!with_sse_c, AtomicOrdering::SeqCst);
+                }
+
+                result
             }
+            result => result,
+        }
+    }
 
-            continuation_token = list_objects_output
-                .next_continuation_token()
+    async fn exec_rate_limit_objects_per_sec(&self) {
+        if self.rate_limit_objects_per_sec.is_some() {
+            self.rate_limit_objects_per_sec
+                .as_ref()
                 .unwrap()
-                .to_string();
+                .acquire(1)
+                .await;
         }
+    }
+
+    async fn exec_request_rate_limit(&self) {
+        acquire_request_permit(&self.request_rate_limiter).await;
+    }
+
+    fn verify_range_read(
+        &self,
+        key: &str,
+        get_object_output: &GetObjectOutput,
+        requested_range: Option<(u64, u64)>,
+    ) -> Result<()> {
+        if !self.config.verify_range_reads {
+            return Ok(());
+        }
+
+        verify_content_range(get_object_output, requested_range)
+            .map_err(|e| anyhow!("range read verification of {} failed. {}", key, e))
+    }
+
+    // with --list-max-rps, the listing request is throttled adaptively. the throttling responses are
+    // reported by the interceptor on each attempt, including the ones retried by the SDK.
+    async fn send_list_request<T, E, F, Fut>(
+        &self,
+        send: F,
+    ) -> Result<T, SdkError<E, Response<SdkBody>>>
+    where
+        F: FnOnce(aws_sdk_s3::config::Builder) -> Fut,
+        Fut: Future<Output = Result<T, SdkError<E, Response<SdkBody>>>>,
+    {
+        acquire_list_permit(&self.list_throttle).await;
+        self.exec_request_rate_limit().await;
+
+        let Some(list_throttle) = &self.list_throttle else {
+            return send(aws_sdk_s3::config::Builder::default()).await;
+        };
+
+        let result = send(
+            aws_sdk_s3::config::Builder::default()
+                .interceptor(ListThrottleInterceptor::new(list_throttle.clone())),
+        )
+        .await;
+        if result.is_ok() {
+            list_throttle.on_success();
+        }
+
+        result
+    }
+
+    // HeadObject with partNumber returns the size of the part, so it takes 1 API call per part.
+    async fn get_object_parts_by_head_object(
+        &self,
+        key: &str,
+        version_id: Option<String>,
+        sse_c: Option<String>,
+        sse_c_key: SseCustomerKey,
+        sse_c_key_md5: Option<String>,
+    ) -> Result<Vec<ObjectPart>> {
+        self.exec_request_rate_limit().await;
+
+        let object = self
+            .client
+            .as_ref()
+            .unwrap()
+            .head_object()
+            .bucket(&self.bucket)
+            .set_expected_bucket_owner(self.expected_bucket_owner.clone())
+            .key(generate_full_key(&self.prefix, key))
+            .set_version_id(version_id.clone())
+            .part_number(1)
+            .set_sse_customer_algorithm(sse_c.clone())
+            .set_sse_customer_key(sse_c_key.key.clone())
+            .set_sse_customer_key_md5(sse_c_key_md5.clone())
+            .send()
+            .await
+            .context("aws_sdk_s3::client::head_object() failed.")?;
 
+        let mut object_parts = vec![];
+
+        let parts_count = object.parts_count().unwrap_or_default();
+        if parts_count == 0 {
+            return Ok(vec![]);
+        }
+
+        object_parts.push(
+            ObjectPartBuilder::default()
+                .size(object.content_length().unwrap())
+                .build(),
+        );
+
+        for part_number in 2..=parts_count {
+            self.exec_request_rate_limit().await;
+
+            let object = self
+                .client
+                .as_ref()
+                .unwrap()
+                .head_object()
+                .bucket(&self.bucket)
+                .set_expected_bucket_owner(self.expected_bucket_owner.clone())
+                .key(generate_full_key(&self.prefix, key))
+                .set_version_id(version_id.clone())
+                .part_number(part_number)
+                .set_sse_customer_algorithm(sse_c.clone())
+                .set_sse_customer_key(sse_c_key.key.clone())
+                .set_sse_customer_key_md5(sse_c_key_md5.clone())
+                .send()
+                .await
+                .context("aws_sdk_s3::client::head_object() failed.")?;
+
+            object_parts.push(
+                ObjectPartBuilder::default()
+                    .size(object.content_length().unwrap())
+                    .build(),
+            );
+        }
+
+        Ok(object_parts)
+    }
+
+    // with --chunk-size-from-target, the part sizes of the existing target object are reused, so that
+    // the re-uploaded object has the same ETag. returns None if the target object does not exist,
+    // is not multipart, or its size is different from the source.
+    async fn get_target_part_layout(
+        &self,
+        key: &str,
+        content_length: i64,
+    ) -> Result<Option<Vec<ObjectPart>>> {
+        let result = self
+            .get_object_parts_attributes(
+                key,
+                None,
+                self.config.max_keys,
+                self.config.target_sse_c.clone(),
+                self.config.target_sse_c_key.clone(),
+                self.config.target_sse_c_key_md5.clone(),
+            )
+            .await;
+        let object_parts = match result {
+            Ok(object_parts) if !object_parts.is_empty() => object_parts,
+            // GetObjectAttributes returns the parts only for the objects with an additional checksum.
+            Ok(_) => {
+                self.get_object_parts_by_head_object(
+                    key,
+                    None,
+                    self.config.target_sse_c.clone(),
+                    self.config.target_sse_c_key.clone(),
+                    self.config.target_sse_c_key_md5.clone(),
+                )
+                .await?
+            }
+            Err(e) if is_object_attributes_not_found_error(&e) => {
+                debug!(
+                    key = key,
+                    "target object does not exist. the part layout is not reused."
+                );
+                return Ok(None);
+            }
+            Err(e) => return Err(e),
+        };
+
+        if object_parts.is_empty() {
+            debug!(
+                key = key,
+                "target object is not multipart. the part layout is not reused."
+            );
+            return Ok(None);
+        }
+
+        let target_content_length = object_parts
+            .iter()
+            .map(|part| part.size().unwrap_or_default())
+            .sum::<i64>();
+        if target_content_length != content_length {
+            debug!(
+                key = key,
+                content_length = content_length,
+                target_content_length = target_content_length,
+                "size of the target object is different. the part layout is not reused."
+            );
+            return Ok(None);
+        }
+
+        Ok(Some(object_parts))
+    }
+}
+
+#[async_trait]
+impl StorageTrait for S3Storage {
+    fn is_local_storage(&self) -> bool {
+        false
+    }
+
+    fn is_express_onezone_storage(&self) -> bool {
+        is_express_onezone_storage(&self.bucket)
+    }
+
+    async fn list_objects(
+        &self,
+        sender: &Sender<S3syncObject>,
+        max_keys: i32,
+        warn_as_error: bool,
+    ) -> Result<()> {
+        if let Some(source_version_map) = &self.source_version_map {
+            return self
+                .list_objects_from_source_version_map(source_version_map, sender, warn_as_error)
+                .await;
+        }
+
+        // with --non-recursive, the common prefixes(sub directories) are ignored.
+        let delimiter = self.config.non_recursive.then_some(NON_RECURSIVE_DELIMITER);
+        self.list_objects_with_prefix(sender, max_keys, &self.prefix, delimiter)
+            .await?;
+        Ok(())
+    }
+
+    async fn list_objects_with_parallel_prefixes(
+        &self,
+        sender: &Sender<S3syncObject>,
+        max_keys: i32,
+        prefixes: &[String],
+    ) -> Result<()> {
+        let seeded_prefixes: Vec<String> = prefixes
+            .iter()
+            .map(|prefix| format!("{}{}", self.prefix, prefix))
+            .collect();
+
+        let semaphore = Arc::new(Semaphore::new(self.config.worker_size as usize));
+
+        let mut join_handles = Vec::new();
+        for seeded_prefix in &seeded_prefixes {
+            join_handles.push(self.spawn_prefix_lister(
+                sender,
+                max_keys,
+                seeded_prefix,
+                semaphore.clone(),
+            ));
+        }
+
+        // Objects directly under the prefix and first-level prefixes that are not seeded are
+        // still listed, so that the seeded prefixes only change the order of the listing.
+        let common_prefixes = match self
+            .list_objects_with_prefix(sender, max_keys, &self.prefix, Some("/"))
+            .await
+        {
+            Ok(common_prefixes) => common_prefixes,
+            Err(e) => {
+                abort_prefix_listers(&join_handles);
+                return Err(e);
+            }
+        };
+        for common_prefix in common_prefixes {
+            if seeded_prefixes.contains(&common_prefix) {
+                continue;
+            }
+            join_handles.push(self.spawn_prefix_lister(
+                sender,
+                max_keys,
+                &common_prefix,
+                semaphore.clone(),
+            ));
+        }
+
+        // once a prefix fails, the listing fails, so the remaining listers are not needed.
+        let mut join_handles = join_handles.into_iter();
+        while let Some(join_handle) = join_handles.next() {
+            let result = join_handle
+                .await
+                .context("tokio::task::JoinHandle::await failed.")
+                .and_then(|result| result);
+            if let Err(e) = result {
+                abort_prefix_listers(join_handles.as_slice());
+                return Err(e);
+            }
+        }
+
+        Ok(())
+    }
+
+    // the sub prefix is relative to the source prefix(--include-only-prefixes-from-stdin).
+    async fn list_objects_under_sub_prefix(
+        &self,
+        sender: &Sender<S3syncObject>,
+        max_keys: i32,
+        sub_prefix: &str,
+        _warn_as_error: bool,
+    ) -> Result<()> {
+        self.list_objects_with_prefix(
+            sender,
+            max_keys,
+            &format!("{}{}", self.prefix, sub_prefix),
+            None,
+        )
+        .await?;
         Ok(())
     }
 
@@ -386,8 +1192,18 @@ impl StorageTrait for S3Storage {
                 .unwrap()
                 .list_object_versions()
                 .bucket(&self.bucket)
+                .set_expected_bucket_owner(self.expected_bucket_owner.clone())
                 .prefix(&self.prefix)
+                .set_delimiter(
+                    self.config
+                        .non_recursive
+                        .then(|| NON_RECURSIVE_DELIMITER.to_string()),
+                )
                 .max_keys(max_keys);
+            if self.include_restore_status {
+                list_object_versions = list_object_versions
+                    .optional_object_attributes(OptionalObjectAttributes::RestoreStatus)
+            }
             if !key_marker.is_empty() {
                 list_object_versions = list_object_versions.key_marker(key_marker)
             }
@@ -400,8 +1216,14 @@ impl StorageTrait for S3Storage {
                 break;
             }
 
-            let list_object_versions_output = list_object_versions
-                .send()
+            let list_object_versions_output = self
+                .send_list_request(|config_override| {
+                    list_object_versions
+                        .clone()
+                        .customize()
+                        .config_override(config_override)
+                        .send()
+                })
                 .await
                 .context("aws_sdk_s3::client::list_object_versions() failed.")?;
 
@@ -434,7 +1256,8 @@ impl StorageTrait for S3Storage {
 
         // send remaining versioning objects
         for versioning_objects in s3sync_versioning_map.values_mut() {
-            Self::send_object_versions_with_sort(sender, versioning_objects).await?;
+            self.send_object_versions_with_sort(sender, versioning_objects)
+                .await?;
         }
 
         Ok(())
@@ -449,29 +1272,50 @@ impl StorageTrait for S3Storage {
         sse_c_key: SseCustomerKey,
         sse_c_key_md5: Option<String>,
     ) -> Result<GetObjectOutput> {
-        if self.config.dry_run {
-            return self
-                .get_object_first_byte(key, version_id, sse_c, sse_c_key, sse_c_key_md5)
-                .await;
-        }
-
-        let result = self
-            .client
-            .as_ref()
-            .unwrap()
-            .get_object()
-            .bucket(&self.bucket)
-            .key(generate_full_key(&self.prefix, key))
-            .set_version_id(version_id)
-            .set_checksum_mode(checksum_mode)
-            .set_sse_customer_algorithm(sse_c)
-            .set_sse_customer_key(sse_c_key.key.clone())
-            .set_sse_customer_key_md5(sse_c_key_md5)
-            .send()
-            .await
-            .context("aws_sdk_s3::client::get_object() failed.")?;
+        self.probe_sse_c(
+            sse_c,
+            sse_c_key,
+            sse_c_key_md5,
+            |sse_c, sse_c_key, sse_c_key_md5| {
+                self.send_get_object(
+                    key,
+                    version_id.clone(),
+                    checksum_mode.clone(),
+                    sse_c,
+                    sse_c_key,
+                    sse_c_key_md5,
+                )
+            },
+        )
+        .await
+    }
 
-        Ok(result)
+    // S3 responds with 304 Not Modified if the object has not been modified since the given time.
+    async fn get_object_if_modified_since(
+        &self,
+        key: &str,
+        if_modified_since: DateTime,
+        checksum_mode: Option<ChecksumMode>,
+        sse_c: Option<String>,
+        sse_c_key: SseCustomerKey,
+        sse_c_key_md5: Option<String>,
+    ) -> Result<GetObjectOutput> {
+        self.probe_sse_c(
+            sse_c,
+            sse_c_key,
+            sse_c_key_md5,
+            |sse_c, sse_c_key, sse_c_key_md5| {
+                self.send_get_object_if_modified_since(
+                    key,
+                    if_modified_since,
+                    checksum_mode.clone(),
+                    sse_c,
+                    sse_c_key,
+                    sse_c_key_md5,
+                )
+            },
+        )
+        .await
     }
 
     async fn get_object_versions(&self, key: &str, max_keys: i32) -> Result<Vec<ObjectVersion>> {
@@ -490,6 +1334,7 @@ impl StorageTrait for S3Storage {
                 .unwrap()
                 .list_object_versions()
                 .bucket(&self.bucket)
+                .set_expected_bucket_owner(self.expected_bucket_owner.clone())
                 .prefix(&key)
                 .max_keys(max_keys);
             if !key_marker.is_empty() {
@@ -505,8 +1350,14 @@ impl StorageTrait for S3Storage {
                 break;
             }
 
-            let list_object_versions_output = list_object_versions
-                .send()
+            let list_object_versions_output = self
+                .send_list_request(|config_override| {
+                    list_object_versions
+                        .clone()
+                        .customize()
+                        .config_override(config_override)
+                        .send()
+                })
                 .await
                 .context("aws_sdk_s3::client::list_object_versions() failed.")?;
 
@@ -542,12 +1393,15 @@ impl StorageTrait for S3Storage {
         key: &str,
         version_id: Option<String>,
     ) -> Result<GetObjectTaggingOutput> {
+        self.exec_request_rate_limit().await;
+
         let result = self
             .client
             .as_ref()
             .unwrap()
             .get_object_tagging()
             .bucket(&self.bucket)
+            .set_expected_bucket_owner(self.expected_bucket_owner.clone())
             .key(generate_full_key(&self.prefix, key))
             .set_version_id(version_id)
             .send()
@@ -566,87 +1420,63 @@ impl StorageTrait for S3Storage {
         sse_c_key: SseCustomerKey,
         sse_c_key_md5: Option<String>,
     ) -> Result<HeadObjectOutput> {
-        let result = self
-            .client
-            .as_ref()
-            .unwrap()
-            .head_object()
-            .bucket(&self.bucket)
-            .key(generate_full_key(&self.prefix, key))
-            .set_version_id(version_id)
-            .set_checksum_mode(checksum_mode)
-            .set_sse_customer_algorithm(sse_c)
-            .set_sse_customer_key(sse_c_key.key.clone())
-            .set_sse_customer_key_md5(sse_c_key_md5)
-            .send()
-            .await
-            .context("aws_sdk_s3::client::head_object() failed.")?;
-
-        Ok(result)
-    }
-
-    async fn get_object_parts(
-        &self,
-        key: &str,
-        version_id: Option<String>,
-        sse_c: Option<String>,
-        sse_c_key: SseCustomerKey,
-        sse_c_key_md5: Option<String>,
-    ) -> Result<Vec<ObjectPart>> {
-        let object = self
-            .client
-            .as_ref()
-            .unwrap()
-            .head_object()
-            .bucket(&self.bucket)
-            .key(generate_full_key(&self.prefix, key))
-            .set_version_id(version_id.clone())
-            .part_number(1)
-            .set_sse_customer_algorithm(sse_c.clone())
-            .set_sse_customer_key(sse_c_key.key.clone())
-            .set_sse_customer_key_md5(sse_c_key_md5.clone())
-            .send()
-            .await
-            .context("aws_sdk_s3::client::head_object() failed.")?;
-
-        let mut object_parts = vec![];
-
-        let parts_count = object.parts_count().unwrap_or_default();
-        if parts_count == 0 {
-            return Ok(vec![]);
-        }
-
-        object_parts.push(
-            ObjectPartBuilder::default()
-                .size(object.content_length().unwrap())
-                .build(),
-        );
-
-        for part_number in 2..=parts_count {
-            let object = self
-                .client
-                .as_ref()
-                .unwrap()
-                .head_object()
-                .bucket(&self.bucket)
-                .key(generate_full_key(&self.prefix, key))
-                .set_version_id(version_id.clone())
-                .part_number(part_number)
-                .set_sse_customer_algorithm(sse_c.clone())
-                .set_sse_customer_key(sse_c_key.key.clone())
-                .set_sse_customer_key_md5(sse_c_key_md5.clone())
-                .send()
-                .await
-                .context("aws_sdk_s3::client::head_object() failed.")?;
-
-            object_parts.push(
-                ObjectPartBuilder::default()
-                    .size(object.content_length().unwrap())
-                    .build(),
-            );
+        self.probe_sse_c(
+            sse_c,
+            sse_c_key,
+            sse_c_key_md5,
+            |sse_c, sse_c_key, sse_c_key_md5| {
+                self.send_head_object(
+                    key,
+                    version_id.clone(),
+                    checksum_mode.clone(),
+                    sse_c,
+                    sse_c_key,
+                    sse_c_key_md5,
+                )
+            },
+        )
+        .await
+    }
+
+    async fn get_object_parts(
+        &self,
+        key: &str,
+        version_id: Option<String>,
+        sse_c: Option<String>,
+        sse_c_key: SseCustomerKey,
+        sse_c_key_md5: Option<String>,
+    ) -> Result<Vec<ObjectPart>> {
+        if self
+            .config
+            .transfer_config
+            .verify_multipart_parts_attributes
+        {
+            let result = self
+                .get_object_parts_attributes(
+                    key,
+                    version_id.clone(),
+                    self.config.max_keys,
+                    sse_c.clone(),
+                    sse_c_key.clone(),
+                    sse_c_key_md5.clone(),
+                )
+                .await;
+            match result {
+                Ok(object_parts) if !object_parts.is_empty() => return Ok(object_parts),
+                // GetObjectAttributes returns the parts only for the objects with an additional checksum.
+                Ok(_) => {}
+                Err(e) => {
+                    debug!(
+                        key = key,
+                        error = e.to_string(),
+                        "get_object_attributes() failed. falling back to HeadObject per part."
+                    );
+                }
+            }
         }
 
-        Ok(object_parts)
+        self.get_object_parts_by_head_object(key, version_id, sse_c, sse_c_key, sse_c_key_md5)
+            .await
     }
 
     async fn get_object_parts_attributes(
@@ -661,12 +1491,15 @@ impl StorageTrait for S3Storage {
         let mut object_parts = vec![];
         let mut part_number_marker = None;
         loop {
+            self.exec_request_rate_limit().await;
+
             let object = self
                 .client
                 .as_ref()
                 .unwrap()
                 .get_object_attributes()
                 .bucket(&self.bucket)
+                .set_expected_bucket_owner(self.expected_bucket_owner.clone())
                 .key(generate_full_key(&self.prefix, key))
                 .set_version_id(version_id.clone())
                 .object_attributes(ObjectAttributes::ObjectParts)
@@ -784,14 +1617,24 @@ impl StorageTrait for S3Storage {
 
         let content_length = get_object_output.content_length();
 
+        let object_parts = if self.config.transfer_config.chunk_size_from_target {
+            self.get_target_part_layout(key, content_length.unwrap_or_default())
+                .await
+                .context("get_target_part_layout() failed.")?
+        } else {
+            object_checksum.unwrap_or_default().object_parts
+        };
+
         let mut upload_manager = UploadManager::new(
             self.client.clone().unwrap(),
             self.config.clone(),
             self.cancellation_token.clone(),
             self.get_stats_sender(),
             tagging,
-            object_checksum.unwrap_or_default().object_parts,
+            object_parts,
             self.is_express_onezone_storage(),
+            self.expected_bucket_owner.clone(),
+            self.request_rate_limiter.clone(),
         );
 
         self.exec_rate_limit_objects_per_sec().await;
@@ -833,6 +1676,7 @@ impl StorageTrait for S3Storage {
         }
 
         self.exec_rate_limit_objects_per_sec().await;
+        self.exec_request_rate_limit().await;
 
         let result = self
             .client
@@ -840,6 +1684,7 @@ impl StorageTrait for S3Storage {
             .unwrap()
             .put_object_tagging()
             .bucket(&self.bucket)
+            .set_expected_bucket_owner(self.expected_bucket_owner.clone())
             .key(&target_key)
             .set_version_id(version_id.clone())
             .tagging(tagging)
@@ -877,6 +1722,7 @@ impl StorageTrait for S3Storage {
         }
 
         self.exec_rate_limit_objects_per_sec().await;
+        self.exec_request_rate_limit().await;
 
         let result = self
             .client
@@ -884,6 +1730,7 @@ impl StorageTrait for S3Storage {
             .unwrap()
             .delete_object()
             .bucket(&self.bucket)
+            .set_expected_bucket_owner(self.expected_bucket_owner.clone())
             .key(&target_key)
             .set_version_id(version_id.clone())
             .send()
@@ -920,6 +1767,7 @@ impl StorageTrait for S3Storage {
         }
 
         self.exec_rate_limit_objects_per_sec().await;
+        self.exec_request_rate_limit().await;
 
         let result = self
             .client
@@ -927,6 +1775,7 @@ impl StorageTrait for S3Storage {
             .unwrap()
             .delete_object_tagging()
             .bucket(&self.bucket)
+            .set_expected_bucket_owner(self.expected_bucket_owner.clone())
             .key(&target_key)
             .set_version_id(version_id.clone())
             .send()
@@ -944,12 +1793,15 @@ impl StorageTrait for S3Storage {
     }
 
     async fn is_versioning_enabled(&self) -> Result<bool> {
+        self.exec_request_rate_limit().await;
+
         let result = self
             .client
             .as_ref()
             .unwrap()
             .get_bucket_versioning()
             .bucket(&self.bucket)
+            .set_expected_bucket_owner(self.expected_bucket_owner.clone())
             .send()
             .await
             .context("aws_sdk_s3::client::get_bucket_versioning() failed.")?;
@@ -961,6 +1813,125 @@ impl StorageTrait for S3Storage {
         Ok(*result.status().unwrap() == BucketVersioningStatus::Enabled)
     }
 
+    async fn list_multipart_uploads(&self) -> Result<Vec<MultipartUpload>> {
+        let mut uploads = Vec::new();
+        let mut key_marker = None;
+        let mut upload_id_marker = None;
+        loop {
+            if self.cancellation_token.is_cancelled() {
+                trace!("list_multipart_uploads() canceled.");
+                break;
+            }
+
+            self.exec_request_rate_limit().await;
+
+            let list_multipart_uploads_output = self
+                .client
+                .as_ref()
+                .unwrap()
+                .list_multipart_uploads()
+                .bucket(&self.bucket)
+                .set_expected_bucket_owner(self.expected_bucket_owner.clone())
+                .prefix(&self.prefix)
+                .set_key_marker(key_marker)
+                .set_upload_id_marker(upload_id_marker)
+                .send()
+                .await
+                .context("aws_sdk_s3::client::list_multipart_uploads() failed.")?;
+
+            uploads.extend_from_slice(list_multipart_uploads_output.uploads());
+
+            if !list_multipart_uploads_output
+                .is_truncated()
+                .unwrap_or(false)
+            {
+                break;
+            }
+
+            key_marker = list_multipart_uploads_output
+                .next_key_marker()
+                .map(|marker| marker.to_string());
+            upload_id_marker = list_multipart_uploads_output
+                .next_upload_id_marker()
+                .map(|marker| marker.to_string());
+        }
+
+        Ok(uploads)
+    }
+
+    async fn get_multipart_upload_size(&self, key: &str, upload_id: &str) -> Result<u64> {
+        let mut size = 0;
+        let mut part_number_marker = None;
+        loop {
+            self.exec_request_rate_limit().await;
+
+            let list_parts_output = self
+                .client
+                .as_ref()
+                .unwrap()
+                .list_parts()
+                .bucket(&self.bucket)
+                .set_expected_bucket_owner(self.expected_bucket_owner.clone())
+                .key(key)
+                .upload_id(upload_id)
+                .set_part_number_marker(part_number_marker)
+                .send()
+                .await
+                .context("aws_sdk_s3::client::list_parts() failed.")?;
+
+            size += list_parts_output
+                .parts()
+                .iter()
+                .map(|part| part.size().unwrap_or_default() as u64)
+                .sum::<u64>();
+
+            if !list_parts_output.is_truncated().unwrap_or(false) {
+                break;
+            }
+
+            part_number_marker = list_parts_output
+                .next_part_number_marker()
+                .map(|marker| marker.to_string());
+        }
+
+        Ok(size)
+    }
+
+    async fn abort_multipart_upload(&self, key: &str, upload_id: &str) -> Result<()> {
+        if self.config.dry_run {
+            info!(
+                key = key,
+                upload_id = upload_id,
+                "[dry-run] abort multipart upload completed.",
+            );
+
+            return Ok(());
+        }
+
+        self.exec_rate_limit_objects_per_sec().await;
+        self.exec_request_rate_limit().await;
+
+        self.client
+            .as_ref()
+            .unwrap()
+            .abort_multipart_upload()
+            .bucket(&self.bucket)
+            .set_expected_bucket_owner(self.expected_bucket_owner.clone())
+            .key(key)
+            .upload_id(upload_id)
+            .send()
+            .await
+            .context("aws_sdk_s3::client::abort_multipart_upload() failed.")?;
+
+        info!(
+            key = key,
+            upload_id = upload_id,
+            "abort multipart upload completed.",
+        );
+
+        Ok(())
+    }
+
     fn get_client(&self) -> Option<Arc<Client>> {
         self.client.clone()
     }
@@ -979,6 +1950,38 @@ impl StorageTrait for S3Storage {
     }
 }
 
+// object_versions must be sorted from oldest to latest.
+// Only the latest delete marker is aggregated, so it is always at the end and never trimmed.
+fn trim_object_versions(
+    object_versions: &mut ObjectVersions,
+    versions_limit: Option<u32>,
+) -> ObjectVersions {
+    if versions_limit.is_none() {
+        return ObjectVersions::new();
+    }
+
+    let object_version_count = object_versions
+        .iter()
+        .filter(|object| !object.is_delete_marker())
+        .count();
+    let trim_count = object_version_count.saturating_sub(versions_limit.unwrap() as usize);
+
+    object_versions.drain(..trim_count).collect()
+}
+
+// object_versions must be sorted from oldest to latest.
+// Only the versions are reversed, and the delete marker is kept at the end. If the delete marker were
+// written first, the versions written after it would become the latest, and the deleted object would be
+// resurrected in the target.
+fn reverse_object_versions(object_versions: &mut ObjectVersions) {
+    let version_count = object_versions
+        .iter()
+        .position(|object| object.is_delete_marker())
+        .unwrap_or(object_versions.len());
+
+    object_versions[..version_count].reverse();
+}
+
 pub fn remove_s3_prefix(key: &str, prefix: &str) -> String {
     key.to_string().replacen(prefix, "", 1)
 }
@@ -987,12 +1990,135 @@ pub fn generate_full_key(prefix: &str, key: &str) -> String {
     format!("{}{}", prefix, key)
 }
 
+// S3 lists keys in UTF-8 binary order, so the listing can be stopped at the first key after --stop-at.
+fn is_after_stop_at(key: &str, prefix: &str, stop_at: &Option<String>) -> bool {
+    stop_at
+        .as_ref()
+        .is_some_and(|stop_at| generate_full_key(prefix, stop_at).as_str() < key)
+}
+
+fn abort_prefix_listers(join_handles: &[JoinHandle<Result<()>>]) {
+    for join_handle in join_handles {
+        join_handle.abort();
+    }
+}
+
 fn is_express_onezone_storage(bucket: &str) -> bool {
     bucket.ends_with(EXPRESS_ONEZONE_STORAGE_SUFFIX)
 }
 
+// S3 responds with 400 Bad Request(InvalidRequest) if the SSE-C parameters are required but not specified,
+// or specified for the object that is not encrypted with SSE-C.
+fn is_sse_c_mismatch_error(e: &anyhow::Error) -> bool {
+    if let Some(SdkError::ServiceError(e)) =
+        e.downcast_ref::<SdkError<GetObjectError, Response<SdkBody>>>()
+    {
+        return e.raw().status().as_u16() == 400;
+    }
+    // get_object_first_byte() returns the service error.
+    if let Some(e) = e.downcast_ref::<GetObjectError>() {
+        return e.code() == Some("InvalidRequest");
+    }
+    if let Some(SdkError::ServiceError(e)) =
+        e.downcast_ref::<SdkError<HeadObjectError, Response<SdkBody>>>()
+    {
+        return e.raw().status().as_u16() == 400;
+    }
+
+    false
+}
+
+fn is_object_attributes_not_found_error(e: &anyhow::Error) -> bool {
+    if let Some(SdkError::ServiceError(e)) =
+        e.downcast_ref::<SdkError<GetObjectAttributesError, Response<SdkBody>>>()
+    {
+        return e.raw().status().as_u16() == 404;
+    }
+
+    false
+}
+
+// 404: no such version, 405: the version is a delete marker.
+fn is_version_not_found_error(e: &anyhow::Error) -> bool {
+    if let Some(SdkError::ServiceError(e)) =
+        e.downcast_ref::<SdkError<HeadObjectError, Response<SdkBody>>>()
+    {
+        return matches!(e.raw().status().as_u16(), 404 | 405);
+    }
+
+    false
+}
+
+fn is_bad_request_error(e: &anyhow::Error) -> bool {
+    if let Some(SdkError::ServiceError(e)) =
+        e.downcast_ref::<SdkError<HeadObjectError, Response<SdkBody>>>()
+    {
+        return e.raw().status().as_u16() == 400;
+    }
+
+    false
+}
+
+// 400 means a missing version only when versionId itself is rejected. other 400 errors(e.g. SSE-C) must propagate.
+fn is_invalid_version_id_error(e: &anyhow::Error) -> bool {
+    // get_object_first_byte() returns the service error.
+    let Some(e) = e.downcast_ref::<GetObjectError>() else {
+        return false;
+    };
+
+    e.code() == Some("InvalidArgument")
+        && e.message()
+            .is_some_and(|message| message.to_lowercase().contains("version"))
+}
+
+// HeadObject does not return the checksum algorithm, so it is taken from the returned checksum.
+fn build_object_version_from_head_object(
+    key: &str,
+    version_id: &str,
+    head_object_output: &HeadObjectOutput,
+) -> ObjectVersion {
+    let checksum_algorithm = [
+        (
+            head_object_output.checksum_crc64_nvme(),
+            ChecksumAlgorithm::Crc64Nvme,
+        ),
+        (
+            head_object_output.checksum_crc32(),
+            ChecksumAlgorithm::Crc32,
+        ),
+        (
+            head_object_output.checksum_crc32_c(),
+            ChecksumAlgorithm::Crc32C,
+        ),
+        (head_object_output.checksum_sha1(), ChecksumAlgorithm::Sha1),
+        (
+            head_object_output.checksum_sha256(),
+            ChecksumAlgorithm::Sha256,
+        ),
+    ]
+    .into_iter()
+    .find(|(checksum, _)| checksum.is_some())
+    .map(|(_, algorithm)| vec![algorithm]);
+
+    ObjectVersion::builder()
+        .key(key)
+        .version_id(version_id)
+        .size(head_object_output.content_length().unwrap_or_default())
+        .set_last_modified(head_object_output.last_modified().cloned())
+        .set_e_tag(head_object_output.e_tag().map(|e_tag| e_tag.to_string()))
+        .set_storage_class(
+            head_object_output
+                .storage_class()
+                .map(|storage_class| ObjectVersionStorageClass::from(storage_class.as_str())),
+        )
+        .set_checksum_algorithm(checksum_algorithm)
+        .build()
+}
+
 #[cfg(test)]
 mod tests {
+    use aws_smithy_runtime_api::http::StatusCode;
+
     use crate::config::args::parse_from_args;
     use crate::types::token;
 
@@ -1010,6 +2136,93 @@ mod tests {
         assert_eq!(remove_s3_prefix("/dir1/data1", "/dir1/"), "data1");
     }
 
+    #[test]
+    fn is_after_stop_at_test() {
+        init_dummy_tracing_subscriber();
+
+        assert!(!is_after_stop_at("dir1/data2", "dir1/", &None));
+
+        let stop_at = Some("data2".to_string());
+        assert!(!is_after_stop_at("dir1/data1", "dir1/", &stop_at));
+        assert!(!is_after_stop_at("dir1/data2", "dir1/", &stop_at));
+        assert!(is_after_stop_at("dir1/data2/data", "dir1/", &stop_at));
+        assert!(is_after_stop_at("dir1/data3", "dir1/", &stop_at));
+        assert!(is_after_stop_at("dir1/data3/", "dir1/", &stop_at));
+    }
+
+    #[test]
+    fn trim_object_versions_test() {
+        init_dummy_tracing_subscriber();
+
+        let mut object_versions = vec![
+            S3syncObject::Versioning(ObjectVersion::builder().version_id("v1").build()),
+            S3syncObject::Versioning(ObjectVersion::builder().version_id("v2").build()),
+            S3syncObject::Versioning(ObjectVersion::builder().version_id("v3").build()),
+            S3syncObject::DeleteMarker(DeleteMarkerEntry::builder().version_id("d1").build()),
+        ];
+
+        let trimmed = trim_object_versions(&mut object_versions, Some(2));
+        assert_eq!(trimmed.len(), 1);
+        assert_eq!(trimmed[0].version_id().unwrap(), "v1");
+        assert_eq!(object_versions.len(), 3);
+        assert_eq!(object_versions[0].version_id().unwrap(), "v2");
+        assert!(object_versions[2].is_delete_marker());
+    }
+
+    #[test]
+    fn trim_object_versions_limit_exceeds_versions() {
+        init_dummy_tracing_subscriber();
+
+        let mut object_versions = vec![
+            S3syncObject::Versioning(ObjectVersion::builder().version_id("v1").build()),
+            S3syncObject::DeleteMarker(DeleteMarkerEntry::builder().version_id("d1").build()),
+        ];
+
+        assert!(trim_object_versions(&mut object_versions, Some(5)).is_empty());
+        assert_eq!(object_versions.len(), 2);
+
+        assert!(trim_object_versions(&mut object_versions, None).is_empty());
+        assert_eq!(object_versions.len(), 2);
+    }
+
+    #[test]
+    fn reverse_object_versions_test() {
+        init_dummy_tracing_subscriber();
+
+        let mut object_versions = vec![
+            S3syncObject::Versioning(ObjectVersion::builder().version_id("v1").build()),
+            S3syncObject::Versioning(ObjectVersion::builder().version_id("v2").build()),
+            S3syncObject::Versioning(ObjectVersion::builder().version_id("v3").build()),
+            S3syncObject::DeleteMarker(DeleteMarkerEntry::builder().version_id("d1").build()),
+        ];
+
+        reverse_object_versions(&mut object_versions);
+
+        assert_eq!(object_versions.len(), 4);
+        assert_eq!(object_versions[0].version_id().unwrap(), "v3");
+        assert_eq!(object_versions[1].version_id().unwrap(), "v2");
+        assert_eq!(object_versions[2].version_id().unwrap(), "v1");
+        assert!(object_versions[3].is_delete_marker());
+
+        let mut object_versions = vec![
+            S3syncObject::Versioning(ObjectVersion::builder().version_id("v1").build()),
+            S3syncObject::Versioning(ObjectVersion::builder().version_id("v2").build()),
+        ];
+
+        reverse_object_versions(&mut object_versions);
+
+        assert_eq!(object_versions[0].version_id().unwrap(), "v2");
+        assert_eq!(object_versions[1].version_id().unwrap(), "v1");
+
+        let mut object_versions = vec![S3syncObject::DeleteMarker(
+            DeleteMarkerEntry::builder().version_id("d1").build(),
+        )];
+
+        reverse_object_versions(&mut object_versions);
+
+        assert!(object_versions[0].is_delete_marker());
+    }
+
     #[test]
     fn is_express_onezone_storage_test() {
         init_dummy_tracing_subscriber();
@@ -1021,6 +2234,271 @@ mod tests {
         assert!(!is_express_onezone_storage("bucket"));
     }
 
+    #[test]
+    fn is_version_not_found_error_test() {
+        init_dummy_tracing_subscriber();
+
+        for (status, expected) in [(400, false), (404, true), (405, true), (403, false)] {
+            let not_found = aws_sdk_s3::types::error::NotFound::builder().build();
+            let response = Response::new(StatusCode::try_from(status).unwrap(), SdkBody::empty());
+            let e = anyhow!(SdkError::service_error(
+                HeadObjectError::NotFound(not_found),
+                response
+            ));
+
+            assert_eq!(is_version_not_found_error(&e), expected);
+        }
+
+        for (status, expected) in [(400, true), (404, false), (403, false)] {
+            let not_found = aws_sdk_s3::types::error::NotFound::builder().build();
+            let response = Response::new(StatusCode::try_from(status).unwrap(), SdkBody::empty());
+            let e = anyhow!(SdkError::service_error(
+                HeadObjectError::NotFound(not_found),
+                response
+            ));
+
+            assert_eq!(is_bad_request_error(&e), expected);
+        }
+
+        let timeout_error: SdkError<HeadObjectError, Response<SdkBody>> =
+            SdkError::timeout_error("timeout_error");
+        assert!(!is_version_not_found_error(&anyhow!(timeout_error)));
+    }
+
+    #[test]
+    fn is_invalid_version_id_error_test() {
+        init_dummy_tracing_subscriber();
+
+        for (code, message, expected) in [
+            ("InvalidArgument", "Invalid version id specified", true),
+            ("InvalidArgument", "Invalid Argument", false),
+            ("InvalidRequest", "Invalid version id specified", false),
+        ] {
+            let e = anyhow!(GetObjectError::generic(
+                aws_sdk_s3::error::ErrorMetadata::builder()
+                    .code(code)
+                    .message(message)
+                    .build()
+            ));
+
+            assert_eq!(is_invalid_version_id_error(&e), expected);
+        }
+
+        assert!(!is_invalid_version_id_error(&anyhow!("error")));
+    }
+
+    #[test]
+    fn is_object_attributes_not_found_error_test() {
+        init_dummy_tracing_subscriber();
+
+        for (status, expected) in [(404, true), (403, false)] {
+            let no_such_key = aws_sdk_s3::types::error::NoSuchKey::builder().build();
+            let response = Response::new(StatusCode::try_from(status).unwrap(), SdkBody::empty());
+            let e = anyhow!(SdkError::service_error(
+                GetObjectAttributesError::NoSuchKey(no_such_key),
+                response
+            ))
+            .context("aws_sdk_s3::client::get_object_attributes() failed.");
+
+            assert_eq!(is_object_attributes_not_found_error(&e), expected);
+        }
+
+        let timeout_error: SdkError<GetObjectAttributesError, Response<SdkBody>> =
+            SdkError::timeout_error("timeout_error");
+        assert!(!is_object_attributes_not_found_error(&anyhow!(
+            timeout_error
+        )));
+    }
+
+    #[test]
+    fn is_sse_c_mismatch_error_test() {
+        init_dummy_tracing_subscriber();
+
+        for (status, expected) in [(400, true), (403, false), (404, false)] {
+            let response = Response::new(StatusCode::try_from(status).unwrap(), SdkBody::empty());
+            let e = anyhow!(SdkError::service_error(
+                HeadObjectError::unhandled("head_object error"),
+                response
+            ));
+            assert_eq!(is_sse_c_mismatch_error(&e), expected);
+
+            let response = Response::new(StatusCode::try_from(status).unwrap(), SdkBody::empty());
+            let e = anyhow!(SdkError::service_error(
+                GetObjectError::generic(
+                    aws_sdk_s3::error::ErrorMetadata::builder()
+                        .code("InvalidRequest")
+                        .build()
+                ),
+                response
+            ))
+            .context("aws_sdk_s3::client::get_object() failed.");
+            assert_eq!(is_sse_c_mismatch_error(&e), expected);
+        }
+
+        let e = anyhow!(GetObjectError::generic(
+            aws_sdk_s3::error::ErrorMetadata::builder()
+                .code("InvalidRequest")
+                .build()
+        ));
+        assert!(is_sse_c_mismatch_error(&e));
+        let e = anyhow!(GetObjectError::generic(
+            aws_sdk_s3::error::ErrorMetadata::builder()
+                .code("AccessDenied")
+                .build()
+        ));
+        assert!(!is_sse_c_mismatch_error(&e));
+
+        let timeout_error: SdkError<HeadObjectError, Response<SdkBody>> =
+            SdkError::timeout_error("timeout_error");
+        assert!(!is_sse_c_mismatch_error(&anyhow!(timeout_error)));
+    }
+
+    #[tokio::test]
+    async fn probe_sse_c() {
+        init_dummy_tracing_subscriber();
+
+        let args = vec![
+            "s3sync",
+            "--source-access-key",
+            "source_access_key",
+            "--source-secret-access-key",
+            "source_secret_access_key",
+            "--target-access-key",
+            "target_access_key",
+            "--target-secret-access-key",
+            "target_secret_access_key",
+            "--source-sse-c",
+            "AES256",
+            "--source-sse-c-key",
+            "MDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDA=",
+            "--source-sse-c-probe",
+            "s3://source-bucket",
+            "s3://target-bucket",
+        ];
+        let config = Config::try_from(parse_from_args(args).unwrap()).unwrap();
+        let (stats_sender, _) = async_channel::unbounded();
+
+        let storage = S3Storage {
+            config: config.clone(),
+            bucket: "source-bucket".to_string(),
+            prefix: "".to_string(),
+            cancellation_token: token::create_pipeline_cancellation_token(),
+            client: None,
+            expected_bucket_owner: None,
+            include_restore_status: false,
+            include_owner: false,
+            source_version_map: None,
+            #[cfg(feature = "s3_select")]
+            source_select: None,
+            stats_sender,
+            rate_limit_objects_per_sec: None,
+            rate_limit_bandwidth: None,
+            sse_c_required: Some(Arc::new(AtomicBool::new(false))),
+            request_rate_limiter: None,
+            list_throttle: None,
+        };
+
+        // S3 accepts the request only with the SSE-C parameters.
+        let encrypted_object_request = |sse_c: Option<String>,
+                                        _: SseCustomerKey,
+                                        _: Option<String>| async move {
+            if sse_c.is_some() {
+                Ok(())
+            } else {
+                let response = Response::new(StatusCode::try_from(400).unwrap(), SdkBody::empty());
+                Err(anyhow!(SdkError::service_error(
+                    HeadObjectError::unhandled("head_object error"),
+                    response
+                )))
+            }
+        };
+
+        storage
+            .probe_sse_c(
+                config.source_sse_c.clone(),
+                config.source_sse_c_key.clone(),
+                config.source_sse_c_key_md5.clone(),
+                encrypted_object_request,
+            )
+            .await
+            .unwrap();
+        assert!(storage
+            .sse_c_required
+            .as_ref()
+            .unwrap()
+            .load(AtomicOrdering::SeqCst));
+
+        // S3 accepts the request only without the SSE-C parameters.
+        let plain_object_request = |sse_c: Option<String>,
+                                    sse_c_key: SseCustomerKey,
+                                    _: Option<String>| async move {
+            if sse_c.is_none() && sse_c_key.key.is_none() {
+                Ok(())
+            } else {
+                let response = Response::new(StatusCode::try_from(400).unwrap(), SdkBody::empty());
+                Err(anyhow!(SdkError::service_error(
+                    HeadObjectError::unhandled("head_object error"),
+                    response
+                )))
+            }
+        };
+
+        storage
+            .probe_sse_c(
+                config.source_sse_c.clone(),
+                config.source_sse_c_key.clone(),
+                config.source_sse_c_key_md5.clone(),
+                plain_object_request,
+            )
+            .await
+            .unwrap();
+        assert!(!storage
+            .sse_c_required
+            .as_ref()
+            .unwrap()
+            .load(AtomicOrdering::SeqCst));
+    }
+
+    #[test]
+    fn build_object_version_from_head_object_test() {
+        init_dummy_tracing_subscriber();
+
+        let head_object_output = HeadObjectOutput::builder()
+            .content_length(6)
+            .e_tag("\"my-etag\"")
+            .last_modified(DateTime::from_secs(777))
+            .storage_class(aws_sdk_s3::types::StorageClass::Glacier)
+            .checksum_sha256("sha256")
+            .build();
+
+        let object = S3syncObject::Versioning(build_object_version_from_head_object(
+            "data1",
+            "version1",
+            &head_object_output,
+        ));
+        assert_eq!(object.key(), "data1");
+        assert_eq!(object.version_id(), Some("version1"));
+        assert_eq!(object.size(), 6);
+        assert_eq!(object.e_tag(), Some("\"my-etag\""));
+        assert_eq!(object.last_modified(), &DateTime::from_secs(777));
+        assert_eq!(object.storage_class(), Some("GLACIER"));
+        assert_eq!(
+            object.checksum_algorithm(),
+            Some([ChecksumAlgorithm::Sha256].as_slice())
+        );
+
+        let object = S3syncObject::Versioning(build_object_version_from_head_object(
+            "data1",
+            "version1",
+            &HeadObjectOutput::builder()
+                .last_modified(DateTime::from_secs(777))
+                .build(),
+        ));
+        assert_eq!(object.size(), 0);
+        assert!(object.storage_class().is_none());
+        assert!(object.checksum_algorithm().is_none());
+    }
+
     #[tokio::test]
     async fn create_storage() {
         init_dummy_tracing_subscriber();
@@ -1047,8 +2525,7 @@ mod tests {
             token::create_pipeline_cancellation_token(),
             stats_sender,
             config.source_client_config.clone(),
-            None,
-            None,
+            StorageLimiters::default(),
         )
         .await;
 
@@ -1081,8 +2558,7 @@ mod tests {
             token::create_pipeline_cancellation_token(),
             stats_sender,
             config.source_client_config.clone(),
-            None,
-            None,
+            StorageLimiters::default(),
         )
         .await;
 
@@ -1117,8 +2593,7 @@ mod tests {
             token::create_pipeline_cancellation_token(),
             stats_sender,
             config.source_client_config.clone(),
-            None,
-            None,
+            StorageLimiters::default(),
         )
         .await;
     }
@@ -1149,8 +2624,7 @@ mod tests {
             token::create_pipeline_cancellation_token(),
             stats_sender,
             config.source_client_config.clone(),
-            None,
-            None,
+            StorageLimiters::default(),
         )
         .await;
 
@@ -0,0 +1,226 @@
+use std::path::Path;
+
+use anyhow::{anyhow, Context, Result};
+use aws_sdk_s3::operation::get_object::GetObjectOutput;
+use aws_sdk_s3::operation::select_object_content::SelectObjectContentOutput;
+use aws_sdk_s3::primitives::{ByteStream, Length};
+use aws_sdk_s3::types::{
+    CsvInput, CsvOutput, FileHeaderInfo, InputSerialization, JsonInput, JsonOutput, JsonType,
+    OutputSerialization, ParquetInput, SelectObjectContentEventStream,
+};
+
+use tokio::fs::File;
+use tokio::io::{AsyncSeekExt, AsyncWriteExt};
+
+use crate::types::{SelectInputFormat, SelectOutputFormat};
+
+const CSV_CONTENT_TYPE: &str = "text/csv";
+const JSON_CONTENT_TYPE: &str = "application/json";
+
+pub fn build_input_serialization(format: SelectInputFormat) -> InputSerialization {
+    let builder = InputSerialization::builder();
+    match format {
+        // the header line is used for the column names in the expression.
+        SelectInputFormat::Csv => builder.csv(
+            CsvInput::builder()
+                .file_header_info(FileHeaderInfo::Use)
+                .build(),
+        ),
+        SelectInputFormat::Json => {
+            builder.json(JsonInput::builder().r#type(JsonType::Lines).build())
+        }
+        SelectInputFormat::Parquet => builder.parquet(ParquetInput::builder().build()),
+    }
+    .build()
+}
+
+pub fn build_output_serialization(format: SelectOutputFormat) -> OutputSerialization {
+    let builder = OutputSerialization::builder();
+    match format {
+        SelectOutputFormat::Csv => builder.csv(CsvOutput::builder().build()),
+        SelectOutputFormat::Json => builder.json(JsonOutput::builder().build()),
+    }
+    .build()
+}
+
+// S3 Select sends the End event only after the whole object has been scanned.
+// Without it, the records may be truncated.
+// The records are written to an anonymous temp file as they arrive, so that a large result is not held
+// in memory. The upload needs the size of the result, so the body is read from the file after the End event.
+// Returns the body and the size of the records.
+pub async fn spool_records(
+    mut select_output: SelectObjectContentOutput,
+    local_tmp_dir: Option<&Path>,
+) -> Result<(ByteStream, u64)> {
+    let records_file = if let Some(local_tmp_dir) = local_tmp_dir {
+        tempfile::tempfile_in(local_tmp_dir)
+    } else {
+        tempfile::tempfile()
+    }
+    .context("tempfile::tempfile() failed.")?;
+    let mut records_file = File::from_std(records_file);
+
+    let mut records_size = 0;
+    let mut completed = false;
+
+    while let Some(event) = select_output
+        .payload
+        .recv()
+        .await
+        .context("aws_sdk_s3::client::select_object_content() failed.")?
+    {
+        match event {
+            SelectObjectContentEventStream::Records(records_event) => {
+                if let Some(payload) = records_event.payload() {
+                    records_file
+                        .write_all(payload.as_ref())
+                        .await
+                        .context("tokio::fs::File::write_all() failed.")?;
+                    records_size += payload.as_ref().len() as u64;
+                }
+            }
+            SelectObjectContentEventStream::End(_) => completed = true,
+            _ => {}
+        }
+    }
+
+    if !completed {
+        return Err(anyhow!("S3 Select result ended without End event."));
+    }
+
+    records_file
+        .flush()
+        .await
+        .context("tokio::fs::File::flush() failed.")?;
+    records_file
+        .rewind()
+        .await
+        .context("tokio::fs::File::rewind() failed.")?;
+
+    let records = ByteStream::read_from()
+        .file(records_file)
+        .length(Length::Exact(records_size))
+        .build()
+        .await
+        .context("aws_sdk_s3::primitives::ByteStream::read_from() failed.")?;
+
+    Ok((records, records_size))
+}
+
+// The object attributes are taken from the source object, but the content is replaced with the records.
+// ETag and checksums are removed, so that the target is not verified against the source.
+pub fn replace_body_with_records(
+    mut get_object_output: GetObjectOutput,
+    records: ByteStream,
+    records_size: u64,
+    output_format: SelectOutputFormat,
+) -> GetObjectOutput {
+    get_object_output.content_length = Some(records_size as i64);
+    get_object_output.content_range = None;
+    get_object_output.content_encoding = None;
+    get_object_output.content_type = Some(
+        match output_format {
+            SelectOutputFormat::Csv => CSV_CONTENT_TYPE,
+            SelectOutputFormat::Json => JSON_CONTENT_TYPE,
+        }
+        .to_string(),
+    );
+    get_object_output.e_tag = None;
+    get_object_output.parts_count = None;
+    get_object_output.checksum_crc32 = None;
+    get_object_output.checksum_crc32_c = None;
+    get_object_output.checksum_crc64_nvme = None;
+    get_object_output.checksum_sha1 = None;
+    get_object_output.checksum_sha256 = None;
+    get_object_output.checksum_type = None;
+    get_object_output.body = records;
+
+    get_object_output
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_s3::primitives::DateTime;
+
+    use super::*;
+
+    #[test]
+    fn build_input_serialization_test() {
+        init_dummy_tracing_subscriber();
+
+        let csv = build_input_serialization(SelectInputFormat::Csv);
+        assert_eq!(
+            csv.csv().unwrap().file_header_info(),
+            Some(&FileHeaderInfo::Use)
+        );
+        assert!(csv.json().is_none());
+
+        let json = build_input_serialization(SelectInputFormat::Json);
+        assert_eq!(json.json().unwrap().r#type(), Some(&JsonType::Lines));
+        assert!(json.csv().is_none());
+
+        let parquet = build_input_serialization(SelectInputFormat::Parquet);
+        assert!(parquet.parquet().is_some());
+    }
+
+    #[test]
+    fn build_output_serialization_test() {
+        init_dummy_tracing_subscriber();
+
+        let csv = build_output_serialization(SelectOutputFormat::Csv);
+        assert!(csv.csv().is_some());
+        assert!(csv.json().is_none());
+
+        let json = build_output_serialization(SelectOutputFormat::Json);
+        assert!(json.json().is_some());
+        assert!(json.csv().is_none());
+    }
+
+    #[tokio::test]
+    async fn replace_body_with_records_test() {
+        init_dummy_tracing_subscriber();
+
+        let get_object_output = GetObjectOutput::builder()
+            .content_length(1)
+            .content_range("bytes 0-0/100")
+            .content_type("application/octet-stream")
+            .e_tag("\"d41d8cd98f00b204e9800998ecf8427e\"")
+            .checksum_sha256("47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=")
+            .last_modified(DateTime::from_secs(1))
+            .metadata("key1", "value1")
+            .tag_count(2)
+            .body(ByteStream::from_static(b"a"))
+            .build();
+
+        let get_object_output = replace_body_with_records(
+            get_object_output,
+            ByteStream::from_static(b"{\"id\":1}\n"),
+            9,
+            SelectOutputFormat::Json,
+        );
+
+        assert_eq!(get_object_output.content_length(), Some(9));
+        assert_eq!(get_object_output.content_type(), Some("application/json"));
+        assert!(get_object_output.content_range().is_none());
+        assert!(get_object_output.e_tag().is_none());
+        assert!(get_object_output.checksum_sha256().is_none());
+        assert_eq!(
+            get_object_output.last_modified(),
+            Some(&DateTime::from_secs(1))
+        );
+        assert_eq!(
+            get_object_output.metadata().unwrap().get("key1").unwrap(),
+            "value1"
+        );
+        assert_eq!(get_object_output.tag_count(), Some(2));
+
+        let body = get_object_output.body.collect().await.unwrap();
+        assert_eq!(body.into_bytes().as_ref(), b"{\"id\":1}\n");
+    }
+
+    fn init_dummy_tracing_subscriber() {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter("dummy=trace")
+            .try_init();
+    }
+}
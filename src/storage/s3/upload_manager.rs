@@ -3,6 +3,7 @@ use std::sync::Arc;
 
 use anyhow::{anyhow, Context, Result};
 use async_channel::Sender;
+use aws_sdk_s3::config::timeout::TimeoutConfig;
 use aws_sdk_s3::operation::complete_multipart_upload::CompleteMultipartUploadOutput;
 use aws_sdk_s3::operation::get_object::GetObjectOutput;
 use aws_sdk_s3::operation::put_object::PutObjectOutput;
@@ -16,17 +17,24 @@ use aws_sdk_s3::Client;
 use aws_smithy_types_convert::date_time::DateTimeExt;
 use base64::{engine::general_purpose, Engine as _};
 use chrono::SecondsFormat;
-use tokio::io::AsyncReadExt;
-use tracing::{trace, warn};
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tracing::{error, trace, warn};
 
 use crate::config::Config;
 use crate::storage;
+use crate::storage::additional_checksum_verify::is_checksum_verify_sampled;
 use crate::storage::e_tag_verify::{generate_e_tag_hash, is_multipart_upload_e_tag};
+use crate::storage::encryption_verify::verify_encryption;
+use crate::storage::request_rate_limiter::{acquire_request_permit, RequestRateLimiter};
 use crate::types::error::S3syncError;
 use crate::types::token::PipelineCancellationToken;
-use crate::types::SyncStatistics::{ChecksumVerified, ETagVerified, SyncWarning};
+use crate::types::SyncStatistics::{
+    BucketKeyEnabled, ChecksumVerified, ContentLengthVerified, ETagVerified, EncryptionVerified,
+    SyncWarning,
+};
 use crate::types::{
-    SyncStatistics, S3SYNC_ORIGIN_LAST_MODIFIED_METADATA_KEY, S3SYNC_ORIGIN_VERSION_ID_METADATA_KEY,
+    expand_content_disposition_template, SyncStatistics, S3SYNC_FILE_MODE_METADATA_KEY,
+    S3SYNC_ORIGIN_LAST_MODIFIED_METADATA_KEY, S3SYNC_ORIGIN_VERSION_ID_METADATA_KEY,
 };
 
 const MISMATCH_WARNING_WITH_HELP: &str = "mismatch. object in the target storage may be corrupted. \
@@ -43,9 +51,12 @@ pub struct UploadManager {
     object_parts: Option<Vec<ObjectPart>>,
     concatnated_md5_hash: Vec<u8>,
     express_onezone_storage: bool,
+    expected_bucket_owner: Option<String>,
+    request_rate_limiter: Option<Arc<RequestRateLimiter>>,
 }
 
 impl UploadManager {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         client: Arc<Client>,
         config: Config,
@@ -54,6 +65,8 @@ impl UploadManager {
         tagging: Option<String>,
         object_parts: Option<Vec<ObjectPart>>,
         express_onezone_storage: bool,
+        expected_bucket_owner: Option<String>,
+        request_rate_limiter: Option<Arc<RequestRateLimiter>>,
     ) -> Self {
         UploadManager {
             client,
@@ -64,6 +77,8 @@ impl UploadManager {
             object_parts,
             concatnated_md5_hash: vec![],
             express_onezone_storage,
+            expected_bucket_owner,
+            request_rate_limiter,
         }
     }
 
@@ -73,15 +88,22 @@ impl UploadManager {
         key: &str,
         mut get_object_output: GetObjectOutput,
     ) -> Result<PutObjectOutput> {
-        get_object_output = self.modify_metadata(get_object_output);
+        get_object_output = self.modify_metadata(key, get_object_output);
 
-        if self.is_auto_chunksize_enabled() {
-            return self
-                .upload_with_auto_chunksize(bucket, key, get_object_output)
-                .await;
+        if self.is_fast_empty_file(&get_object_output) {
+            let put_object_output = self
+                .singlepart_upload(bucket, key, get_object_output)
+                .await?;
+            self.send_bucket_key_stats(key, &put_object_output).await;
+
+            trace!(key = key, "{put_object_output:?}");
+            return Ok(put_object_output);
         }
 
-        let put_object_output = if self
+        let put_object_output = if self.is_part_layout_specified() {
+            self.upload_with_auto_chunksize(bucket, key, get_object_output)
+                .await?
+        } else if self
             .config
             .transfer_config
             .is_multipart_upload_required(get_object_output.content_length().unwrap() as u64)
@@ -93,19 +115,68 @@ impl UploadManager {
                 .await?
         };
 
+        if self.config.verify_target_encryption {
+            self.verify_target_encryption(bucket, key).await;
+        }
+
+        self.send_bucket_key_stats(key, &put_object_output).await;
+
         trace!(key = key, "{put_object_output:?}");
         Ok(put_object_output)
     }
 
-    fn modify_metadata(&self, mut get_object_output: GetObjectOutput) -> GetObjectOutput {
-        if self.config.metadata.is_some() {
-            get_object_output.metadata = Some(self.config.metadata.as_ref().unwrap().clone());
+    // with --fast-empty-files, an empty object is uploaded by a minimal PutObject without hashing and verification.
+    fn is_fast_empty_file(&self, get_object_output: &GetObjectOutput) -> bool {
+        self.config.fast_empty_files && get_object_output.content_length() == Some(0)
+    }
+
+    // with --per-object-timeout-base, the operation timeout is scaled with the size of the request body.
+    fn build_operation_timeout_override(&self, size: u64) -> Option<aws_sdk_s3::config::Builder> {
+        let per_object_timeout_config = self.config.per_object_timeout_config.as_ref()?;
+
+        Some(
+            aws_sdk_s3::config::Builder::default().timeout_config(
+                TimeoutConfig::builder()
+                    .operation_timeout(per_object_timeout_config.timeout_for(size))
+                    .build(),
+            ),
+        )
+    }
+
+    fn modify_metadata(
+        &self,
+        key: &str,
+        mut get_object_output: GetObjectOutput,
+    ) -> GetObjectOutput {
+        // the file mode put by the local storage(--put-file-mode-metadata) is kept even if the
+        // source metadata is replaced with --metadata.
+        let file_mode = if self.config.put_file_mode_metadata {
+            get_object_output
+                .metadata()
+                .and_then(|metadata| metadata.get(S3SYNC_FILE_MODE_METADATA_KEY))
+                .cloned()
+        } else {
+            None
+        };
+
+        if let Some(metadata) = &self.config.metadata {
+            get_object_output =
+                Self::apply_metadata(get_object_output, metadata, self.config.metadata_merge);
+        }
+
+        if let Some(template) = &self.config.content_disposition_template {
+            get_object_output.content_disposition =
+                Some(expand_content_disposition_template(template, key));
         }
 
         if self.config.put_last_modified_metadata {
             get_object_output = Self::modify_last_modified_metadata(get_object_output);
         }
 
+        if let Some(file_mode) = file_mode {
+            get_object_output = Self::modify_file_mode_metadata(get_object_output, file_mode);
+        }
+
         if self.config.enable_versioning {
             get_object_output = Self::update_versioning_metadata(get_object_output);
         }
@@ -113,6 +184,35 @@ impl UploadManager {
         get_object_output
     }
 
+    // without --metadata-merge, the metadata of the source object is replaced with --metadata.
+    // with it, --metadata takes precedence over the source metadata. S3 returns the keys in lowercase,
+    // so the keys are compared case-insensitively.
+    fn apply_metadata(
+        mut get_object_output: GetObjectOutput,
+        metadata: &HashMap<String, String>,
+        merge: bool,
+    ) -> GetObjectOutput {
+        let mut merged_metadata = if merge {
+            // skipcq: RS-W1031
+            get_object_output
+                .metadata()
+                .unwrap_or(&HashMap::new())
+                .clone()
+        } else {
+            HashMap::new()
+        };
+
+        merged_metadata.retain(|source_key, _| {
+            !metadata
+                .keys()
+                .any(|key| key.eq_ignore_ascii_case(source_key))
+        });
+        merged_metadata.extend(metadata.clone());
+        get_object_output.metadata = Some(merged_metadata);
+
+        get_object_output
+    }
+
     fn modify_last_modified_metadata(mut get_object_output: GetObjectOutput) -> GetObjectOutput {
         // skipcq: RS-W1031
         let mut metadata = get_object_output
@@ -139,6 +239,21 @@ impl UploadManager {
         get_object_output
     }
 
+    fn modify_file_mode_metadata(
+        mut get_object_output: GetObjectOutput,
+        file_mode: String,
+    ) -> GetObjectOutput {
+        // skipcq: RS-W1031
+        let mut metadata = get_object_output
+            .metadata()
+            .unwrap_or(&HashMap::new())
+            .clone();
+        metadata.insert(S3SYNC_FILE_MODE_METADATA_KEY.to_string(), file_mode);
+        get_object_output.metadata = Some(metadata);
+
+        get_object_output
+    }
+
     fn update_versioning_metadata(mut get_object_output: GetObjectOutput) -> GetObjectOutput {
         if get_object_output.version_id().is_none() {
             return get_object_output;
@@ -202,17 +317,20 @@ impl UploadManager {
         key: &str,
         get_object_output: GetObjectOutput,
     ) -> Result<PutObjectOutput> {
-        let storage_class = if self.config.storage_class.is_none() {
-            get_object_output.storage_class().cloned()
-        } else {
-            Some(self.config.storage_class.as_ref().unwrap().clone())
-        };
+        let storage_class = select_storage_class(
+            self.config.storage_class.as_ref(),
+            get_object_output.storage_class(),
+            bucket,
+        );
+
+        acquire_request_permit(&self.request_rate_limiter).await;
 
         let create_multipart_upload_output = self
             .client
             .create_multipart_upload()
             .set_storage_class(storage_class)
             .bucket(bucket)
+            .set_expected_bucket_owner(self.expected_bucket_owner.clone())
             .key(key)
             .set_metadata(get_object_output.metadata().cloned())
             .set_tagging(self.tagging.clone())
@@ -266,6 +384,7 @@ impl UploadManager {
             })
             .set_server_side_encryption(self.config.sse.clone())
             .set_ssekms_key_id(self.config.sse_kms_key_id.clone().id.clone())
+            .set_bucket_key_enabled(self.get_bucket_key_enabled(&get_object_output))
             .set_sse_customer_algorithm(self.config.target_sse_c.clone())
             .set_sse_customer_key(self.config.target_sse_c_key.clone().key.clone())
             .set_sse_customer_key_md5(self.config.target_sse_c_key_md5.clone())
@@ -281,9 +400,12 @@ impl UploadManager {
             .await
             .context("upload_parts() failed.");
         if upload_result.is_err() {
+            acquire_request_permit(&self.request_rate_limiter).await;
+
             self.client
                 .abort_multipart_upload()
                 .bucket(bucket)
+                .set_expected_bucket_owner(self.expected_bucket_owner.clone())
                 .key(key)
                 .upload_id(upload_id)
                 .send()
@@ -313,7 +435,7 @@ impl UploadManager {
         );
         let source_storage_class = get_object_output.storage_class().cloned();
 
-        let upload_parts = if self.is_auto_chunksize_enabled() {
+        let upload_parts = if self.is_part_layout_specified() {
             self.upload_parts_with_auto_chunksize(bucket, key, upload_id, get_object_output)
                 .await
                 .context("upload_parts_with_auto_chunksize() failed.")?
@@ -327,10 +449,13 @@ impl UploadManager {
             .set_parts(Some(upload_parts))
             .build();
 
+        acquire_request_permit(&self.request_rate_limiter).await;
+
         let complete_multipart_upload_output = self
             .client
             .complete_multipart_upload()
             .bucket(bucket)
+            .set_expected_bucket_owner(self.expected_bucket_owner.clone())
             .key(key)
             .upload_id(upload_id)
             .multipart_upload(completed_multipart_upload)
@@ -347,7 +472,11 @@ impl UploadManager {
             "{complete_multipart_upload_output:?}"
         );
 
-        let source_e_tag = if source_local_storage {
+        // with --chunk-size-from-target, the parts differ from the source and the chunksize options.
+        // the ETag is computed from the uploaded parts instead of comparing with the source ETag.
+        let source_e_tag = if self.is_target_part_layout_reused() {
+            Some(self.generate_e_tag_hash(self.object_parts.as_ref().unwrap().len() as i64))
+        } else if source_local_storage {
             Some(self.generate_e_tag_hash(self.calculate_parts_count(source_content_length)))
         } else {
             source_e_tag
@@ -357,22 +486,40 @@ impl UploadManager {
             && !self.express_onezone_storage
             && source_storage_class != Some(StorageClass::ExpressOnezone)
         {
-            let target_sse = complete_multipart_upload_output
-                .server_side_encryption()
-                .cloned();
-            let target_e_tag = complete_multipart_upload_output
-                .e_tag()
-                .map(|e| e.to_string());
-
-            self.verify_e_tag(
-                key,
-                &source_sse,
-                source_remote_storage,
-                &source_e_tag,
-                &target_sse,
-                &target_e_tag,
-            )
-            .await;
+            let target = if self.config.verify_via_head {
+                self.head_target_e_tag(
+                    bucket,
+                    key,
+                    complete_multipart_upload_output
+                        .version_id()
+                        .map(|version_id| version_id.to_string()),
+                )
+                .await?
+            } else {
+                Some((
+                    complete_multipart_upload_output
+                        .server_side_encryption()
+                        .cloned(),
+                    complete_multipart_upload_output
+                        .e_tag()
+                        .map(|e| e.to_string()),
+                ))
+            };
+
+            if let Some((target_sse, target_e_tag)) = target {
+                self.verify_e_tag(
+                    key,
+                    &source_sse,
+                    source_remote_storage,
+                    &source_e_tag,
+                    &target_sse,
+                    &target_e_tag,
+                )
+                .await?;
+            }
+        } else if self.config.verify_content_length_only {
+            self.verify_content_length(bucket, key, source_content_length)
+                .await?;
         }
 
         let target_checksum = get_additional_checksum_from_multipart_upload_result(
@@ -383,14 +530,23 @@ impl UploadManager {
         self.validate_checksum(
             key,
             source_checksum,
-            target_checksum,
+            target_checksum.clone(),
             &source_e_tag,
             source_remote_storage,
         )
-        .await;
+        .await?;
+
+        if self.config.checksum_display {
+            self.display_checksum(
+                key,
+                complete_multipart_upload_output.e_tag(),
+                target_checksum.as_deref(),
+            );
+        }
 
         Ok(PutObjectOutput::builder()
             .e_tag(complete_multipart_upload_output.e_tag().unwrap())
+            .set_bucket_key_enabled(complete_multipart_upload_output.bucket_key_enabled())
             .build())
     }
 
@@ -402,7 +558,7 @@ impl UploadManager {
         source_e_tag: &Option<String>,
         target_sse: &Option<ServerSideEncryption>,
         target_e_tag: &Option<String>,
-    ) {
+    ) -> Result<()> {
         let verify_result = storage::e_tag_verify::verify_e_tag(
             !self.config.disable_multipart_verify,
             &self.config.source_sse_c,
@@ -449,6 +605,8 @@ impl UploadManager {
                         target_e_tag = target_e_tag,
                         message
                     );
+
+                    return self.verification_mismatch_result();
                 }
             } else {
                 self.send_stats(ETagVerified {
@@ -464,6 +622,203 @@ impl UploadManager {
                 );
             }
         }
+
+        Ok(())
+    }
+
+    // with --verify-via-head, the Content-MD5 header is not sent, because some S3-compatible storages reject it.
+    fn content_md5_header(&self, md5_digest_base64: Option<String>) -> Option<String> {
+        if self.config.verify_via_head {
+            None
+        } else {
+            md5_digest_base64
+        }
+    }
+
+    // with --verify-via-head, the ETag of the uploaded object is taken from HeadObject instead of the upload response.
+    // the uploaded version is specified, so that an object written by another writer is not verified.
+    // a failure of HeadObject is a verification failure, and returns None after the mismatch has been reported.
+    async fn head_target_e_tag(
+        &mut self,
+        bucket: &str,
+        key: &str,
+        version_id: Option<String>,
+    ) -> Result<Option<(Option<ServerSideEncryption>, Option<String>)>> {
+        acquire_request_permit(&self.request_rate_limiter).await;
+
+        let head_object_result = self
+            .client
+            .head_object()
+            .bucket(bucket)
+            .set_expected_bucket_owner(self.expected_bucket_owner.clone())
+            .key(key)
+            .set_version_id(version_id)
+            .set_sse_customer_algorithm(self.config.target_sse_c.clone())
+            .set_sse_customer_key(self.config.target_sse_c_key.clone().key.clone())
+            .set_sse_customer_key_md5(self.config.target_sse_c_key_md5.clone())
+            .send()
+            .await;
+
+        match head_object_result {
+            Ok(head_object_output) => Ok(Some((
+                head_object_output.server_side_encryption().cloned(),
+                head_object_output.e_tag().map(|e| e.to_string()),
+            ))),
+            Err(e) => {
+                self.send_stats(SyncWarning {
+                    key: key.to_string(),
+                })
+                .await;
+
+                let error = e.into_service_error().to_string();
+                warn!(
+                    key = &key,
+                    error = error,
+                    "head_object() failed. the e_tag of the uploaded object cannot be verified."
+                );
+
+                self.verification_mismatch_result()?;
+                Ok(None)
+            }
+        }
+    }
+
+    async fn verify_content_length(
+        &mut self,
+        bucket: &str,
+        key: &str,
+        source_content_length: i64,
+    ) -> Result<()> {
+        acquire_request_permit(&self.request_rate_limiter).await;
+
+        let head_object_result = self
+            .client
+            .head_object()
+            .bucket(bucket)
+            .set_expected_bucket_owner(self.expected_bucket_owner.clone())
+            .key(key)
+            .set_sse_customer_algorithm(self.config.target_sse_c.clone())
+            .set_sse_customer_key(self.config.target_sse_c_key.clone().key.clone())
+            .set_sse_customer_key_md5(self.config.target_sse_c_key_md5.clone())
+            .send()
+            .await;
+
+        let target_content_length = match head_object_result {
+            Ok(head_object_output) => head_object_output.content_length(),
+            Err(e) => {
+                self.send_stats(SyncWarning {
+                    key: key.to_string(),
+                })
+                .await;
+
+                let error = e.into_service_error().to_string();
+                warn!(
+                    key = &key,
+                    error = error,
+                    "head_object() failed. skip content length verification."
+                );
+                return Ok(());
+            }
+        };
+
+        if target_content_length == Some(source_content_length) {
+            self.send_stats(ContentLengthVerified {
+                key: key.to_string(),
+            })
+            .await;
+
+            trace!(
+                key = &key,
+                source_content_length = source_content_length,
+                target_content_length = target_content_length,
+                "content length verified."
+            );
+        } else {
+            self.send_stats(SyncWarning {
+                key: key.to_string(),
+            })
+            .await;
+
+            warn!(
+                key = &key,
+                source_content_length = source_content_length,
+                target_content_length = target_content_length,
+                "content length mismatch. file in the target storage may be corrupted."
+            );
+
+            return self.verification_mismatch_result();
+        }
+
+        Ok(())
+    }
+
+    // some buckets silently apply the default encryption instead of the requested one.
+    // HeadObject is used, so that the stored object is verified regardless of the upload path.
+    async fn verify_target_encryption(&mut self, bucket: &str, key: &str) {
+        acquire_request_permit(&self.request_rate_limiter).await;
+
+        let head_object_result = self
+            .client
+            .head_object()
+            .bucket(bucket)
+            .set_expected_bucket_owner(self.expected_bucket_owner.clone())
+            .key(key)
+            .set_sse_customer_algorithm(self.config.target_sse_c.clone())
+            .set_sse_customer_key(self.config.target_sse_c_key.clone().key.clone())
+            .set_sse_customer_key_md5(self.config.target_sse_c_key_md5.clone())
+            .send()
+            .await;
+
+        let head_object_output = match head_object_result {
+            Ok(head_object_output) => head_object_output,
+            Err(e) => {
+                self.send_stats(SyncWarning {
+                    key: key.to_string(),
+                })
+                .await;
+
+                let error = e.into_service_error().to_string();
+                warn!(
+                    key = &key,
+                    error = error,
+                    "head_object() failed. skip encryption verification."
+                );
+                return;
+            }
+        };
+
+        let expected_sse = self.config.sse.as_ref().unwrap();
+        let target_sse = head_object_output.server_side_encryption();
+        if verify_encryption(
+            expected_sse,
+            self.config.sse_kms_key_id.id.as_deref(),
+            target_sse,
+            head_object_output.ssekms_key_id(),
+        ) {
+            self.send_stats(EncryptionVerified {
+                key: key.to_string(),
+            })
+            .await;
+
+            trace!(
+                key = &key,
+                target_sse = target_sse.map(|sse| sse.as_str()),
+                "encryption verified."
+            );
+        } else {
+            self.send_stats(SyncWarning {
+                key: key.to_string(),
+            })
+            .await;
+
+            // the KMS key id is not logged, as it is regarded as a secret.
+            warn!(
+                key = &key,
+                expected_sse = expected_sse.as_str(),
+                target_sse = target_sse.map(|sse| sse.as_str()),
+                "encryption mismatch. the target object may not be encrypted as requested."
+            );
+        }
     }
 
     async fn upload_parts(
@@ -477,6 +832,7 @@ impl UploadManager {
 
         let mut part_number = 1;
         let mut remaining_bytes = get_object_output.content_length().unwrap() as u64;
+        let mut offset = 0;
 
         let mut body = get_object_output.body.into_async_read();
         while 0 < remaining_bytes {
@@ -493,9 +849,11 @@ impl UploadManager {
             let mut buffer =
                 Vec::<u8>::with_capacity(self.config.transfer_config.multipart_chunksize as usize);
             buffer.resize_with(chunksize as usize, Default::default);
-            body.read_exact(buffer.as_mut_slice())
-                .await
-                .context("async_read_ext::AsyncReadExt read_exact() failed.")?;
+            let result = body.read_exact(buffer.as_mut_slice()).await;
+            if result.is_err() {
+                self.report_short_part(key, part_number as usize, offset, chunksize);
+            }
+            result.context("async_read_ext::AsyncReadExt read_exact() failed.")?;
 
             let md5_digest_base64 = if !self.express_onezone_storage {
                 let md5_digest = md5::compute(&buffer);
@@ -511,10 +869,11 @@ impl UploadManager {
                 .client
                 .upload_part()
                 .bucket(bucket)
+                .set_expected_bucket_owner(self.expected_bucket_owner.clone())
                 .key(key)
                 .upload_id(upload_id)
                 .part_number(part_number)
-                .set_content_md5(md5_digest_base64)
+                .set_content_md5(self.content_md5_header(md5_digest_base64))
                 .content_length(chunksize as i64)
                 .set_checksum_algorithm(self.config.additional_checksum_algorithm.clone())
                 .set_sse_customer_algorithm(self.config.target_sse_c.clone())
@@ -522,19 +881,20 @@ impl UploadManager {
                 .set_sse_customer_key_md5(self.config.target_sse_c_key_md5.clone())
                 .body(ByteStream::from(buffer));
 
-            let upload_part_output = if self.config.disable_payload_signing {
-                builder
-                    .customize()
-                    .disable_payload_signing()
-                    .send()
-                    .await
-                    .context("aws_sdk_s3::client::Client upload_part() failed.")?
-            } else {
-                builder
-                    .send()
-                    .await
-                    .context("aws_sdk_s3::client::Client upload_part() failed.")?
-            };
+            let mut operation = builder.customize();
+            if self.config.disable_payload_signing {
+                operation = operation.disable_payload_signing();
+            }
+            if let Some(config_override) = self.build_operation_timeout_override(chunksize) {
+                operation = operation.config_override(config_override);
+            }
+
+            acquire_request_permit(&self.request_rate_limiter).await;
+
+            let upload_part_output = operation
+                .send()
+                .await
+                .context("aws_sdk_s3::client::Client upload_part() failed.")?;
 
             trace!(key = key, "{upload_part_output:?}");
 
@@ -571,8 +931,10 @@ impl UploadManager {
             );
 
             remaining_bytes -= chunksize;
+            offset += chunksize;
             part_number += 1;
         }
+        self.verify_body_exhausted(key, &mut body).await?;
         trace!(key = key, upload_id = upload_id, "{upload_parts:?}");
 
         Ok(upload_parts)
@@ -588,6 +950,7 @@ impl UploadManager {
         let mut upload_parts: Vec<CompletedPart> = Vec::new();
 
         let mut part_number = 1;
+        let mut offset = 0;
 
         let mut body = get_object_output.body.into_async_read();
         while part_number <= self.object_parts.as_ref().unwrap().len() {
@@ -605,9 +968,11 @@ impl UploadManager {
 
             let mut buffer = Vec::<u8>::with_capacity(chunksize as usize);
             buffer.resize_with(chunksize as usize, Default::default);
-            body.read_exact(buffer.as_mut_slice())
-                .await
-                .context("async_read_ext::AsyncReadExt read_exact() failed.")?;
+            let result = body.read_exact(buffer.as_mut_slice()).await;
+            if result.is_err() {
+                self.report_short_part(key, part_number, offset, chunksize as u64);
+            }
+            result.context("async_read_ext::AsyncReadExt read_exact() failed.")?;
 
             let md5_digest_base64 = if !self.express_onezone_storage {
                 let md5_digest = md5::compute(&buffer);
@@ -623,10 +988,11 @@ impl UploadManager {
                 .client
                 .upload_part()
                 .bucket(bucket)
+                .set_expected_bucket_owner(self.expected_bucket_owner.clone())
                 .key(key)
                 .upload_id(upload_id)
                 .part_number(part_number as i32)
-                .set_content_md5(md5_digest_base64)
+                .set_content_md5(self.content_md5_header(md5_digest_base64))
                 .content_length(chunksize)
                 .set_checksum_algorithm(self.config.additional_checksum_algorithm.clone())
                 .set_sse_customer_algorithm(self.config.target_sse_c.clone())
@@ -634,19 +1000,20 @@ impl UploadManager {
                 .set_sse_customer_key_md5(self.config.target_sse_c_key_md5.clone())
                 .body(ByteStream::from(buffer));
 
-            let upload_part_output = if self.config.disable_payload_signing {
-                builder
-                    .customize()
-                    .disable_payload_signing()
-                    .send()
-                    .await
-                    .context("aws_sdk_s3::client::Client upload_part() failed.")?
-            } else {
-                builder
-                    .send()
-                    .await
-                    .context("aws_sdk_s3::client::Client upload_part() failed.")?
-            };
+            let mut operation = builder.customize();
+            if self.config.disable_payload_signing {
+                operation = operation.disable_payload_signing();
+            }
+            if let Some(config_override) = self.build_operation_timeout_override(chunksize as u64) {
+                operation = operation.config_override(config_override);
+            }
+
+            acquire_request_permit(&self.request_rate_limiter).await;
+
+            let upload_part_output = operation
+                .send()
+                .await
+                .context("aws_sdk_s3::client::Client upload_part() failed.")?;
 
             trace!(key = key, "{upload_part_output:?}");
 
@@ -682,13 +1049,61 @@ impl UploadManager {
                     .build(),
             );
 
+            offset += chunksize as u64;
             part_number += 1;
         }
+        self.verify_body_exhausted(key, &mut body).await?;
         trace!(key = key, upload_id = upload_id, "{upload_parts:?}");
 
         Ok(upload_parts)
     }
 
+    // --verify-range-reads: the part that the source returned fewer bytes for is reported.
+    fn report_short_part(&self, key: &str, part_number: usize, offset: u64, chunksize: u64) {
+        if !self.config.verify_range_reads {
+            return;
+        }
+
+        error!(
+            key = key,
+            part_number = part_number,
+            first_byte = offset,
+            last_byte = offset + chunksize - 1,
+            "the source returned fewer bytes than the part."
+        );
+    }
+
+    // --verify-range-reads: the source must not return more bytes than Content-Length.
+    async fn verify_body_exhausted<R: AsyncRead + Unpin>(
+        &self,
+        key: &str,
+        body: &mut R,
+    ) -> Result<()> {
+        if !self.config.verify_range_reads {
+            return Ok(());
+        }
+
+        let mut extra_byte = [0u8; 1];
+        if body
+            .read(&mut extra_byte)
+            .await
+            .context("async_read_ext::AsyncReadExt read() failed.")?
+            == 0
+        {
+            return Ok(());
+        }
+
+        error!(
+            key = key,
+            "the source returned more bytes than Content-Length after the last part."
+        );
+
+        Err(anyhow!(
+            "range read verification of {} failed. the source returned more bytes than Content-Length.",
+            key
+        ))
+    }
+
     async fn singlepart_upload(
         &mut self,
         bucket: &str,
@@ -704,6 +1119,7 @@ impl UploadManager {
             self.config.additional_checksum_algorithm.as_ref().cloned(),
         );
         let source_storage_class = get_object_output.storage_class().cloned();
+        let source_content_length = get_object_output.content_length().unwrap();
 
         let mut body = get_object_output.body.into_async_read();
         get_object_output.body = ByteStream::from_static(b"");
@@ -720,7 +1136,9 @@ impl UploadManager {
             .await
             .context("async_read_ext::AsyncReadExt read_exact() failed.")?;
 
-        let md5_digest_base64 = if !self.express_onezone_storage {
+        let fast_empty_file = self.is_fast_empty_file(&get_object_output);
+
+        let md5_digest_base64 = if !self.express_onezone_storage && !fast_empty_file {
             let md5_digest = md5::compute(&buffer);
             self.concatnated_md5_hash
                 .append(&mut md5_digest.as_slice().to_vec());
@@ -732,23 +1150,24 @@ impl UploadManager {
 
         let buffer_stream = ByteStream::from(buffer);
 
-        let storage_class = if self.config.storage_class.is_none() {
-            get_object_output.storage_class().cloned()
-        } else {
-            Some(self.config.storage_class.as_ref().unwrap().clone())
-        };
+        let storage_class = select_storage_class(
+            self.config.storage_class.as_ref(),
+            get_object_output.storage_class(),
+            bucket,
+        );
 
         let builder = self
             .client
             .put_object()
             .set_storage_class(storage_class)
             .bucket(bucket)
+            .set_expected_bucket_owner(self.expected_bucket_owner.clone())
             .key(key)
             .content_length(get_object_output.content_length().unwrap())
             .body(buffer_stream)
             .set_metadata(get_object_output.metadata().cloned())
             .set_tagging(self.tagging.clone())
-            .set_content_md5(md5_digest_base64)
+            .set_content_md5(self.content_md5_header(md5_digest_base64))
             .set_content_type(if self.config.content_type.is_none() {
                 get_object_output
                     .content_type()
@@ -799,25 +1218,33 @@ impl UploadManager {
             })
             .set_server_side_encryption(self.config.sse.clone())
             .set_ssekms_key_id(self.config.sse_kms_key_id.clone().id.clone())
+            .set_bucket_key_enabled(self.get_bucket_key_enabled(&get_object_output))
             .set_sse_customer_algorithm(self.config.target_sse_c.clone())
             .set_sse_customer_key(self.config.target_sse_c_key.clone().key.clone())
             .set_sse_customer_key_md5(self.config.target_sse_c_key_md5.clone())
             .set_acl(self.config.canned_acl.clone())
             .set_checksum_algorithm(self.config.additional_checksum_algorithm.as_ref().cloned());
 
-        let put_object_output = if self.config.disable_payload_signing {
-            builder
-                .customize()
-                .disable_payload_signing()
-                .send()
-                .await
-                .context("aws_sdk_s3::client::Client put_object() failed.")?
-        } else {
-            builder
-                .send()
-                .await
-                .context("aws_sdk_s3::client::Client put_object() failed.")?
-        };
+        let mut operation = builder.customize();
+        if self.config.disable_payload_signing {
+            operation = operation.disable_payload_signing();
+        }
+        if let Some(config_override) =
+            self.build_operation_timeout_override(source_content_length as u64)
+        {
+            operation = operation.config_override(config_override);
+        }
+
+        acquire_request_permit(&self.request_rate_limiter).await;
+
+        let put_object_output = operation
+            .send()
+            .await
+            .context("aws_sdk_s3::client::Client put_object() failed.")?;
+
+        if fast_empty_file {
+            return Ok(put_object_output);
+        }
 
         let source_e_tag = if source_local_storage {
             Some(self.generate_e_tag_hash(0))
@@ -829,18 +1256,36 @@ impl UploadManager {
             && !self.express_onezone_storage
             && source_storage_class != Some(StorageClass::ExpressOnezone)
         {
-            let target_sse = put_object_output.server_side_encryption().cloned();
-            let target_e_tag = put_object_output.e_tag().map(|e| e.to_string());
+            let target = if self.config.verify_via_head {
+                self.head_target_e_tag(
+                    bucket,
+                    key,
+                    put_object_output
+                        .version_id()
+                        .map(|version_id| version_id.to_string()),
+                )
+                .await?
+            } else {
+                Some((
+                    put_object_output.server_side_encryption().cloned(),
+                    put_object_output.e_tag().map(|e| e.to_string()),
+                ))
+            };
 
-            self.verify_e_tag(
-                key,
-                &source_sse,
-                source_remote_storage,
-                &source_e_tag,
-                &target_sse,
-                &target_e_tag,
-            )
-            .await;
+            if let Some((target_sse, target_e_tag)) = target {
+                self.verify_e_tag(
+                    key,
+                    &source_sse,
+                    source_remote_storage,
+                    &source_e_tag,
+                    &target_sse,
+                    &target_e_tag,
+                )
+                .await?;
+            }
+        } else if self.config.verify_content_length_only {
+            self.verify_content_length(bucket, key, source_content_length)
+                .await?;
         }
 
         let target_checksum = get_additional_checksum_from_put_object_result(
@@ -851,11 +1296,15 @@ impl UploadManager {
         self.validate_checksum(
             key,
             source_checksum,
-            target_checksum,
+            target_checksum.clone(),
             &source_e_tag,
             source_remote_storage,
         )
-        .await;
+        .await?;
+
+        if self.config.checksum_display {
+            self.display_checksum(key, put_object_output.e_tag(), target_checksum.as_deref());
+        }
 
         Ok(put_object_output)
     }
@@ -867,7 +1316,7 @@ impl UploadManager {
         target_checksum: Option<String>,
         source_e_tag: &Option<String>,
         source_remote_storage: bool,
-    ) {
+    ) -> Result<()> {
         if self.config.additional_checksum_mode.is_some() && source_checksum.is_none() {
             self.send_stats(SyncWarning {
                 key: key.to_string(),
@@ -880,6 +1329,17 @@ impl UploadManager {
             );
         }
 
+        if target_checksum.is_some()
+            && source_checksum.is_some()
+            && !is_checksum_verify_sampled(key, self.config.checksum_verify_sampling)
+        {
+            trace!(
+                key = &key,
+                "additional checksum verification is not sampled."
+            );
+            return Ok(());
+        }
+
         if target_checksum.is_some() && source_checksum.is_some() {
             let target_checksum = target_checksum.unwrap();
             let source_checksum = source_checksum.unwrap();
@@ -926,6 +1386,8 @@ impl UploadManager {
                         source_checksum = source_checksum,
                         message
                     );
+
+                    return self.verification_mismatch_result();
                 }
             } else {
                 self.send_stats(ChecksumVerified {
@@ -942,6 +1404,30 @@ impl UploadManager {
                 );
             }
         }
+
+        Ok(())
+    }
+
+    // with --strict-verify, a mismatch fails the object instead of being a warning.
+    // the skipped verifications(e.g. --disable-multipart-verify) are not regarded as mismatches.
+    fn verification_mismatch_result(&self) -> Result<()> {
+        if self.config.strict_verify {
+            return Err(anyhow!(S3syncError::VerificationMismatch));
+        }
+
+        Ok(())
+    }
+
+    fn display_checksum(&self, key: &str, e_tag: Option<&str>, checksum: Option<&str>) {
+        storage::display_checksum(
+            key,
+            e_tag,
+            self.config
+                .additional_checksum_algorithm
+                .as_ref()
+                .map(|algorithm| algorithm.as_str()),
+            checksum,
+        );
     }
 
     fn generate_e_tag_hash(&self, parts_count: i64) -> String {
@@ -956,6 +1442,25 @@ impl UploadManager {
         )
     }
 
+    // with --target-bucket-key-reuse, the S3 Bucket Key setting of the source object is propagated.
+    // otherwise, the setting of the target bucket is applied.
+    fn get_bucket_key_enabled(&self, get_object_output: &GetObjectOutput) -> Option<bool> {
+        if !self.config.target_bucket_key_reuse {
+            return None;
+        }
+
+        get_object_output.bucket_key_enabled()
+    }
+
+    async fn send_bucket_key_stats(&self, key: &str, put_object_output: &PutObjectOutput) {
+        if put_object_output.bucket_key_enabled() == Some(true) {
+            self.send_stats(BucketKeyEnabled {
+                key: key.to_string(),
+            })
+            .await;
+        }
+    }
+
     async fn send_stats(&self, stats: SyncStatistics) {
         let _ = self.stats_sender.send(stats).await;
     }
@@ -963,6 +1468,43 @@ impl UploadManager {
     fn is_auto_chunksize_enabled(&self) -> bool {
         self.config.transfer_config.auto_chunksize && self.object_parts.is_some()
     }
+
+    fn is_target_part_layout_reused(&self) -> bool {
+        self.config.transfer_config.chunk_size_from_target && self.object_parts.is_some()
+    }
+
+    // the part sizes are taken from the source(--auto-chunksize) or the target(--chunk-size-from-target).
+    fn is_part_layout_specified(&self) -> bool {
+        (self.config.transfer_config.auto_chunksize
+            || self.config.transfer_config.chunk_size_from_target)
+            && self.object_parts.is_some()
+    }
+}
+
+// without --storage-class, the storage class of the source object is preserved.
+// but directory buckets(Express One Zone) accept only EXPRESS_ONEZONE, and general purpose buckets do not accept it.
+// in such cases, the default storage class of the target bucket is used.
+fn select_storage_class(
+    config_storage_class: Option<&StorageClass>,
+    source_storage_class: Option<&StorageClass>,
+    bucket: &str,
+) -> Option<StorageClass> {
+    if let Some(storage_class) = config_storage_class {
+        return Some(storage_class.clone());
+    }
+
+    let source_storage_class = source_storage_class?;
+    let is_express_onezone_class = source_storage_class == &StorageClass::ExpressOnezone;
+    if is_express_onezone_class != super::is_express_onezone_storage(bucket) {
+        trace!(
+            bucket = bucket,
+            storage_class = source_storage_class.as_str(),
+            "the source storage class is not supported by the target bucket. the default is used."
+        );
+        return None;
+    }
+
+    Some(source_storage_class.clone())
 }
 
 fn calculate_parts_count(
@@ -1201,6 +1743,78 @@ mod tests {
         );
     }
 
+    #[test]
+    fn modify_file_mode_metadata_after_apply_metadata() {
+        init_dummy_tracing_subscriber();
+
+        let get_object_output = GetObjectOutput::builder()
+            .metadata(S3SYNC_FILE_MODE_METADATA_KEY, "644")
+            .metadata("key1", "value1")
+            .build();
+        let metadata = HashMap::from([("key2".to_string(), "value2".to_string())]);
+        let mut get_object_output =
+            UploadManager::apply_metadata(get_object_output, &metadata, false);
+        get_object_output =
+            UploadManager::modify_file_mode_metadata(get_object_output, "644".to_string());
+
+        let metadata = get_object_output.metadata().unwrap();
+        assert_eq!(metadata.len(), 2);
+        assert_eq!(metadata.get(S3SYNC_FILE_MODE_METADATA_KEY).unwrap(), "644");
+        assert_eq!(metadata.get("key2").unwrap(), "value2");
+    }
+
+    #[test]
+    fn apply_metadata_without_merge() {
+        init_dummy_tracing_subscriber();
+
+        let get_object_output = GetObjectOutput::builder()
+            .metadata("key1", "source_value1")
+            .metadata("key2", "source_value2")
+            .build();
+        let metadata = HashMap::from([("key1".to_string(), "value1".to_string())]);
+
+        let get_object_output = UploadManager::apply_metadata(get_object_output, &metadata, false);
+        assert_eq!(get_object_output.metadata().unwrap(), &metadata);
+    }
+
+    #[test]
+    fn apply_metadata_with_merge() {
+        init_dummy_tracing_subscriber();
+
+        let get_object_output = GetObjectOutput::builder()
+            .metadata("key1", "source_value1")
+            .metadata("key2", "source_value2")
+            .metadata("key3", "source_value3")
+            .build();
+        let metadata = HashMap::from([
+            ("key1".to_string(), "value1".to_string()),
+            ("Key3".to_string(), "value3".to_string()),
+            ("key4".to_string(), "value4".to_string()),
+        ]);
+
+        let get_object_output = UploadManager::apply_metadata(get_object_output, &metadata, true);
+        assert_eq!(
+            get_object_output.metadata().unwrap(),
+            &HashMap::from([
+                ("key1".to_string(), "value1".to_string()),
+                ("key2".to_string(), "source_value2".to_string()),
+                ("Key3".to_string(), "value3".to_string()),
+                ("key4".to_string(), "value4".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn apply_metadata_with_merge_without_source_metadata() {
+        init_dummy_tracing_subscriber();
+
+        let get_object_output = GetObjectOutput::builder().build();
+        let metadata = HashMap::from([("key1".to_string(), "value1".to_string())]);
+
+        let get_object_output = UploadManager::apply_metadata(get_object_output, &metadata, true);
+        assert_eq!(get_object_output.metadata().unwrap(), &metadata);
+    }
+
     #[test]
     fn calculate_parts_count_test() {
         init_dummy_tracing_subscriber();
@@ -1226,6 +1840,68 @@ mod tests {
         );
     }
 
+    #[test]
+    fn select_storage_class_preserves_source() {
+        init_dummy_tracing_subscriber();
+
+        for storage_class in [
+            StorageClass::StandardIa,
+            StorageClass::OnezoneIa,
+            StorageClass::IntelligentTiering,
+            StorageClass::GlacierIr,
+            StorageClass::Glacier,
+            StorageClass::DeepArchive,
+        ] {
+            assert_eq!(
+                select_storage_class(None, Some(&storage_class), "bucket"),
+                Some(storage_class)
+            );
+        }
+
+        assert!(select_storage_class(None, None, "bucket").is_none());
+        assert_eq!(
+            select_storage_class(
+                None,
+                Some(&StorageClass::ExpressOnezone),
+                "bucket--usw2-az1--x-s3"
+            ),
+            Some(StorageClass::ExpressOnezone)
+        );
+    }
+
+    #[test]
+    fn select_storage_class_not_supported_by_target() {
+        init_dummy_tracing_subscriber();
+
+        assert!(
+            select_storage_class(None, Some(&StorageClass::ExpressOnezone), "bucket").is_none()
+        );
+        assert!(select_storage_class(
+            None,
+            Some(&StorageClass::StandardIa),
+            "bucket--usw2-az1--x-s3"
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn select_storage_class_with_config() {
+        init_dummy_tracing_subscriber();
+
+        assert_eq!(
+            select_storage_class(
+                Some(&StorageClass::Standard),
+                Some(&StorageClass::Glacier),
+                "bucket"
+            ),
+            Some(StorageClass::Standard)
+        );
+        assert_eq!(
+            select_storage_class(Some(&StorageClass::StandardIa), None, "bucket"),
+            Some(StorageClass::StandardIa)
+        );
+    }
+
     fn init_dummy_tracing_subscriber() {
         let _ = tracing_subscriber::fmt()
             .with_env_filter("dummy=trace")
@@ -4,6 +4,12 @@ use thiserror::Error;
 pub enum S3syncError {
     #[error("a object references a parent directory.")]
     DirectoryTraversalError,
+    #[error("a object path conflicts with an existing path.")]
+    PathConflictError,
+    #[error("the downloaded file does not match the source object.")]
+    LocalVerificationMismatch,
+    #[error("the target object does not match the source object.")]
+    VerificationMismatch,
     #[error("cancelled")]
     Cancelled,
 }
@@ -10,15 +10,24 @@ use aws_sdk_s3::primitives::DateTime;
 use aws_sdk_s3::types::{
     ChecksumAlgorithm, ChecksumType, DeleteMarkerEntry, Object, ObjectPart, ObjectVersion,
 };
+use aws_smithy_types_convert::date_time::DateTimeExt;
+use chrono::FixedOffset;
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
 use sha1::{Digest, Sha1};
+use unicode_normalization::UnicodeNormalization as _;
 use zeroize_derive::{Zeroize, ZeroizeOnDrop};
 
+use crate::config::FilterConfig;
+
 pub mod async_callback;
 pub mod error;
 pub mod token;
 
 pub const S3SYNC_ORIGIN_VERSION_ID_METADATA_KEY: &str = "s3sync_origin_version_id";
 pub const S3SYNC_ORIGIN_LAST_MODIFIED_METADATA_KEY: &str = "s3sync_origin_last_modified";
+pub const S3SYNC_HARDLINK_TARGET_METADATA_KEY: &str = "s3sync_hardlink_target";
+pub const S3SYNC_FILE_MODE_METADATA_KEY: &str = "s3sync-mode";
 
 pub type Sha1Digest = [u8; 20];
 
@@ -123,6 +132,24 @@ impl S3syncObject {
         }
     }
 
+    pub fn storage_class(&self) -> Option<&str> {
+        match &self {
+            Self::Versioning(object) => object.storage_class().map(|class| class.as_str()),
+            Self::NotVersioning(object) => object.storage_class().map(|class| class.as_str()),
+            _ => None,
+        }
+    }
+
+    // the owner of ListObjectsV2 is returned only with fetch-owner(--source-list-include-owner).
+    pub fn owner_id(&self) -> Option<&str> {
+        match &self {
+            Self::Versioning(object) => object.owner().and_then(|owner| owner.id()),
+            Self::NotVersioning(object) => object.owner().and_then(|owner| owner.id()),
+            Self::DeleteMarker(maker) => maker.owner().and_then(|owner| owner.id()),
+            Self::PackedVersions(_) => None,
+        }
+    }
+
     pub fn version_id(&self) -> Option<&str> {
         match &self {
             Self::Versioning(object) => object.version_id(),
@@ -202,6 +229,127 @@ pub fn sha1_digest_from_key(key: &str) -> Sha1Digest {
     TryInto::<Sha1Digest>::try_into(digest.as_slice()).unwrap()
 }
 
+// normalizes the key to the unicode normalization form. e.g. macOS file names are NFD, Linux file names are usually NFC.
+pub fn normalize_key(key: &str, normalization: Option<UnicodeNormalization>) -> String {
+    match normalization {
+        Some(UnicodeNormalization::Nfc) => key.nfc().collect(),
+        Some(UnicodeNormalization::Nfd) => key.nfd().collect(),
+        None => key.to_string(),
+    }
+}
+
+// converts the key of the source object to the key of the target object with --target-object-key-case.
+// the prefix of the target is not converted.
+pub fn convert_key_case(key: &str, key_case: Option<KeyCase>) -> String {
+    match key_case {
+        Some(KeyCase::Lower) => key.to_lowercase(),
+        Some(KeyCase::Upper) => key.to_uppercase(),
+        None => key.to_string(),
+    }
+}
+
+// converts the key of the source object to the key of the target object with --target-object-key-case
+// and --date-partition. last_modified is required only with --date-partition.
+pub fn convert_to_target_key(
+    key: &str,
+    last_modified: Option<&DateTime>,
+    filter_config: &FilterConfig,
+) -> String {
+    let key = convert_key_case(key, filter_config.target_key_case);
+    match &filter_config.date_partition {
+        Some(date_partition) => date_partition.prepend_to_key(
+            &key,
+            last_modified.expect("date partition requires LastModified."),
+        ),
+        None => key,
+    }
+}
+
+pub fn convert_object_to_target_key(object: &S3syncObject, filter_config: &FilterConfig) -> String {
+    let last_modified = filter_config
+        .date_partition
+        .as_ref()
+        .map(|_| object.last_modified());
+    convert_to_target_key(object.key(), last_modified, filter_config)
+}
+
+// whether the target key of the source object exists in the target listing.
+// the key is looked up in the same way as the modified filter.
+pub fn is_object_in_target_key_map(
+    object: &S3syncObject,
+    filter_config: &FilterConfig,
+    target_key_map: &ObjectKeyMap,
+) -> bool {
+    let key = normalize_key(
+        &convert_object_to_target_key(object, filter_config),
+        filter_config.normalize_unicode,
+    );
+
+    let target_key_map = target_key_map.lock().unwrap();
+    target_key_map.contains_key(&ObjectKey::KeySHA1Digest(sha1_digest_from_key(&key)))
+        || target_key_map.contains_key(&ObjectKey::KeyString(key))
+}
+
+pub const CONTENT_DISPOSITION_TEMPLATE_BASENAME: &str = "basename";
+pub const CONTENT_DISPOSITION_TEMPLATE_KEY: &str = "key";
+
+// expands `{basename}` and `{key}` of a validated template to RFC 6266 quoted-strings.
+// if the value is the `filename` parameter and it is not printable ASCII,
+// `filename*` with the UTF-8 percent-encoded value is appended to the ASCII fallback.
+pub fn expand_content_disposition_template(template: &str, key: &str) -> String {
+    let basename = key.rsplit('/').next().unwrap_or(key);
+
+    let mut expanded = String::with_capacity(template.len() + key.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let end = start + rest[start..].find('}').unwrap();
+        expanded.push_str(&rest[..start]);
+
+        let value = match &rest[start + 1..end] {
+            CONTENT_DISPOSITION_TEMPLATE_BASENAME => basename,
+            CONTENT_DISPOSITION_TEMPLATE_KEY => key,
+            _ => panic!("unknown content disposition template placeholder"),
+        };
+
+        let is_filename_parameter = expanded.to_ascii_lowercase().ends_with("filename=");
+        let is_printable_ascii = value.chars().all(|c| (' '..='~').contains(&c));
+
+        let fallback = value
+            .chars()
+            .map(|c| match c {
+                '"' | '\\' => format!("\\{c}"),
+                ' '..='~' => c.to_string(),
+                _ => "_".to_string(),
+            })
+            .collect::<String>();
+        expanded.push_str(&format!("\"{fallback}\""));
+
+        if is_filename_parameter && !is_printable_ascii {
+            expanded.push_str("; filename*=UTF-8''");
+            expanded.push_str(&percent_encode_attr_chars(value));
+        }
+
+        rest = &rest[end + 1..];
+    }
+    expanded.push_str(rest);
+
+    expanded
+}
+
+// percent-encodes the value except attr-char of RFC 8187.
+fn percent_encode_attr_chars(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| {
+            if b.is_ascii_alphanumeric() || b"!#$&+-.^_`|~".contains(&b) {
+                (b as char).to_string()
+            } else {
+                format!("%{b:02X}")
+            }
+        })
+        .collect()
+}
+
 pub fn clone_object_with_key(object: &Object, key: &str) -> Object {
     let checksum_algorithm = if object.checksum_algorithm().is_empty() {
         None
@@ -318,25 +466,210 @@ pub enum SyncStatistics {
     SyncWarning { key: String },
     ETagVerified { key: String },
     ChecksumVerified { key: String },
+    // the additional checksum verification has been skipped by --checksum-verify-sampling.
+    ChecksumVerifyNotSampled { key: String },
+    ContentLengthVerified { key: String },
+    EncryptionVerified { key: String },
+    RestoreStatus(ObjectRestoreStatus),
+    ObjectListed,
+    // the number of the active workers chosen by --concurrency-auto.
+    AutoConcurrency(u16),
+    // the object has been given up after --max-retries-per-object. SyncError is also sent.
+    RetryExhausted { key: String },
+    // the size of the transferred object. only with --object-size-histogram.
+    TransferredObjectSize(u64),
+    // the target object has been stored with an S3 Bucket Key.
+    BucketKeyEnabled { key: String },
+    // the object has been skipped because it is archived(--skip-glacier). SyncSkip is also sent.
+    ArchivedSkip { key: String },
+    // the object has been healed by re-download(--verify-only-on-mismatch-redownload).
+    RedownloadHealed { key: String },
+    // the object is still corrupted after re-download. SyncError is also sent.
+    RedownloadStillCorrupted { key: String },
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ObjectRestoreStatus {
+    pub key: String,
+    pub in_progress: bool,
+    // RFC 3339. only for the restored objects.
+    pub expiry_date: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum TransferOrder {
+    SizeAsc,
+    SizeDesc,
+    Key,
+    Random,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct UploadManifest {
+    // target keys in the order of the manifest.
+    pub keys: Vec<String>,
+    // target key -> local file path. relative paths are relative to the source directory.
+    pub local_paths: HashMap<String, PathBuf>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SourceVersionMap {
+    // (key, version_id) in the order of the file. keys are relative to the source prefix.
+    pub entries: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum SelectInputFormat {
+    Csv,
+    Json,
+    Parquet,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum SelectOutputFormat {
+    Csv,
+    Json,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SourceSelectConfig {
+    pub expression: String,
+    pub input_format: SelectInputFormat,
+    pub output_format: SelectOutputFormat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum HardlinkMode {
+    Copy,
+    DedupMetadata,
+    Skip,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum UnicodeNormalization {
+    Nfc,
+    Nfd,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum KeyCase {
+    Lower,
+    Upper,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum RetryJitter {
+    Full,
+    Equal,
+    Disabled,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum TagGuardMode {
+    Warn,
+    Error,
+}
+
+// time-based layout of the target keys(--date-partition). the partition is formatted from
+// the LastModified of the source object in the timezone of --date-partition-timezone.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DatePartition {
+    pub pattern: String,
+    #[serde(serialize_with = "crate::config::serialize::fixed_offset")]
+    pub offset: FixedOffset,
+}
+
+impl DatePartition {
+    pub fn format(&self, last_modified: &DateTime) -> String {
+        last_modified
+            .to_chrono_utc()
+            .unwrap()
+            .with_timezone(&self.offset)
+            .format(&self.pattern)
+            .to_string()
+    }
+
+    pub fn prepend_to_key(&self, key: &str, last_modified: &DateTime) -> String {
+        format!("{}/{}", self.format(last_modified), key)
+    }
+}
+
+// the limits of S3 for the tags of an object(--tagging, --object-tag-count-guard).
+pub const MAX_TAGS_PER_OBJECT: usize = 10;
+pub const MAX_TAG_KEY_LENGTH: usize = 128;
+pub const MAX_TAG_VALUE_LENGTH: usize = 256;
+
+// the lengths are counted in Unicode characters, as S3 does.
+pub fn check_tag_limits<'a>(
+    tags: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> std::result::Result<(), String> {
+    let mut count = 0;
+    for (key, value) in tags {
+        count += 1;
+
+        if MAX_TAG_KEY_LENGTH < key.chars().count() {
+            return Err(format!(
+                "the tag key is longer than {} characters: {}",
+                MAX_TAG_KEY_LENGTH, key
+            ));
+        }
+        if MAX_TAG_VALUE_LENGTH < value.chars().count() {
+            return Err(format!(
+                "the value of the tag {} is longer than {} characters",
+                key, MAX_TAG_VALUE_LENGTH
+            ));
+        }
+    }
+
+    if MAX_TAGS_PER_OBJECT < count {
+        return Err(format!(
+            "{} tags exceed the limit of {} tags per object",
+            count, MAX_TAGS_PER_OBJECT
+        ));
+    }
+
+    Ok(())
+}
+
+// the permission bits of a file in octal(--download-file-mode, --preserve-file-mode).
+// the leading zero is optional, e.g. "644" and "0644" are the same.
+pub fn parse_file_mode(mode: &str) -> Option<u32> {
+    if mode.is_empty() || !mode.chars().all(|c| ('0'..='7').contains(&c)) {
+        return None;
+    }
+
+    let mode = u32::from_str_radix(mode, 8).ok()?;
+    if 0o7777 < mode {
+        return None;
+    }
+
+    Some(mode)
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub enum StoragePath {
     S3 { bucket: String, prefix: String },
     Local(PathBuf),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ClientConfigLocation {
     pub aws_config_file: Option<PathBuf>,
     pub aws_shared_credentials_file: Option<PathBuf>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub enum S3Credentials {
     Profile(String),
-    Credentials { access_keys: AccessKeys },
+    Credentials {
+        access_keys: AccessKeys,
+    },
     FromEnvironment,
+    Anonymous,
+    WebIdentity {
+        web_identity_token_file: PathBuf,
+        role_arn: String,
+    },
 }
 
 #[derive(Clone, Zeroize, ZeroizeOnDrop)]
@@ -360,6 +693,20 @@ impl Debug for AccessKeys {
     }
 }
 
+// the access key is also redacted, because the serialized Config is printed(--dump-config).
+impl Serialize for AccessKeys {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut keys = serializer.serialize_struct("AccessKeys", 3)?;
+        keys.serialize_field("access_key", "** redacted **")?;
+        keys.serialize_field("secret_access_key", "** redacted **")?;
+        keys.serialize_field(
+            "session_token",
+            &self.session_token.as_ref().map(|_| "** redacted **"),
+        )?;
+        keys.end()
+    }
+}
+
 #[derive(Clone, Zeroize, ZeroizeOnDrop)]
 pub struct SseKmsKeyId {
     pub id: Option<String>,
@@ -374,6 +721,15 @@ impl Debug for SseKmsKeyId {
     }
 }
 
+impl Serialize for SseKmsKeyId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.id
+            .as_ref()
+            .map(|_| "** redacted **")
+            .serialize(serializer)
+    }
+}
+
 #[derive(Clone, Zeroize, ZeroizeOnDrop)]
 pub struct SseCustomerKey {
     pub key: Option<String>,
@@ -388,6 +744,15 @@ impl Debug for SseCustomerKey {
     }
 }
 
+impl Serialize for SseCustomerKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.key
+            .as_ref()
+            .map(|_| "** redacted **")
+            .serialize(serializer)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use aws_sdk_s3::types::{
@@ -396,6 +761,235 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn normalize_key_test() {
+        // "é" composed(U+00E9) and decomposed(U+0065 U+0301)
+        let nfc_key = "dir/caf\u{00e9}.txt";
+        let nfd_key = "dir/cafe\u{0301}.txt";
+
+        assert_eq!(
+            normalize_key(nfd_key, Some(UnicodeNormalization::Nfc)),
+            nfc_key
+        );
+        assert_eq!(
+            normalize_key(nfc_key, Some(UnicodeNormalization::Nfc)),
+            nfc_key
+        );
+        assert_eq!(
+            normalize_key(nfc_key, Some(UnicodeNormalization::Nfd)),
+            nfd_key
+        );
+        assert_eq!(
+            normalize_key(nfd_key, Some(UnicodeNormalization::Nfd)),
+            nfd_key
+        );
+        assert_eq!(normalize_key(nfd_key, None), nfd_key);
+
+        // Hangul syllable "가" and conjoining jamo
+        assert_eq!(
+            normalize_key("\u{1100}\u{1161}", Some(UnicodeNormalization::Nfc)),
+            "\u{ac00}"
+        );
+    }
+
+    #[test]
+    fn convert_key_case_test() {
+        assert_eq!(
+            convert_key_case("Dir1/Data1.CSV", Some(KeyCase::Lower)),
+            "dir1/data1.csv"
+        );
+        assert_eq!(
+            convert_key_case("Dir1/Data1.csv", Some(KeyCase::Upper)),
+            "DIR1/DATA1.CSV"
+        );
+        assert_eq!(convert_key_case("Dir1/Data1.csv", None), "Dir1/Data1.csv");
+
+        // not only ASCII
+        assert_eq!(
+            convert_key_case("\u{00c9}t\u{00e9}.txt", Some(KeyCase::Lower)),
+            "\u{00e9}t\u{00e9}.txt"
+        );
+    }
+
+    #[test]
+    fn check_tag_limits_test() {
+        let tags = (1..=10)
+            .map(|i| (format!("key{i}"), format!("value{i}")))
+            .collect::<Vec<(String, String)>>();
+        check_tag_limits(tags.iter().map(|(k, v)| (k.as_str(), v.as_str()))).unwrap();
+
+        let mut too_many_tags = tags.clone();
+        too_many_tags.push(("key11".to_string(), "value11".to_string()));
+        assert!(
+            check_tag_limits(too_many_tags.iter().map(|(k, v)| (k.as_str(), v.as_str()))).is_err()
+        );
+
+        check_tag_limits([("k".repeat(128).as_str(), "")]).unwrap();
+        assert!(check_tag_limits([("k".repeat(129).as_str(), "")]).is_err());
+        check_tag_limits([("key", "\u{30d0}".repeat(256).as_str())]).unwrap();
+        assert!(check_tag_limits([("key", "v".repeat(257).as_str())]).is_err());
+    }
+
+    #[test]
+    fn parse_file_mode_test() {
+        assert_eq!(parse_file_mode("644"), Some(0o644));
+        assert_eq!(parse_file_mode("0644"), Some(0o644));
+        assert_eq!(parse_file_mode("4755"), Some(0o4755));
+        assert_eq!(parse_file_mode("7777"), Some(0o7777));
+        assert!(parse_file_mode("10000").is_none());
+        assert!(parse_file_mode("0648").is_none());
+        assert!(parse_file_mode("rw-r--r--").is_none());
+        assert!(parse_file_mode("+644").is_none());
+        assert!(parse_file_mode("").is_none());
+    }
+
+    #[test]
+    fn date_partition_test() {
+        // 2023-11-14T22:13:20Z
+        let last_modified = DateTime::from_secs(1_700_000_000);
+
+        let date_partition = DatePartition {
+            pattern: "%Y/%m/%d".to_string(),
+            offset: FixedOffset::east_opt(0).unwrap(),
+        };
+        assert_eq!(date_partition.format(&last_modified), "2023/11/14");
+        assert_eq!(
+            date_partition.prepend_to_key("dir1/data1", &last_modified),
+            "2023/11/14/dir1/data1"
+        );
+
+        let date_partition = DatePartition {
+            pattern: "year=%Y/month=%m/day=%d/hour=%H".to_string(),
+            offset: FixedOffset::east_opt(9 * 3600).unwrap(),
+        };
+        assert_eq!(
+            date_partition.format(&last_modified),
+            "year=2023/month=11/day=15/hour=07"
+        );
+    }
+
+    #[test]
+    fn convert_to_target_key_test() {
+        let last_modified = DateTime::from_secs(1_700_000_000);
+
+        let filter_config = FilterConfig::default();
+        assert_eq!(
+            convert_to_target_key("Dir1/Data1", None, &filter_config),
+            "Dir1/Data1"
+        );
+
+        let filter_config = FilterConfig {
+            target_key_case: Some(KeyCase::Lower),
+            date_partition: Some(DatePartition {
+                pattern: "%Y-%m".to_string(),
+                offset: FixedOffset::east_opt(0).unwrap(),
+            }),
+            ..Default::default()
+        };
+        assert_eq!(
+            convert_to_target_key("Dir1/Data1", Some(&last_modified), &filter_config),
+            "2023-11/dir1/data1"
+        );
+
+        let object = S3syncObject::NotVersioning(
+            Object::builder()
+                .key("Dir1/Data1")
+                .size(1)
+                .last_modified(last_modified)
+                .build(),
+        );
+        assert_eq!(
+            convert_object_to_target_key(&object, &filter_config),
+            "2023-11/dir1/data1"
+        );
+
+        // LastModified is not required without --date-partition.
+        let object = S3syncObject::NotVersioning(Object::builder().key("Dir1/Data1").build());
+        assert_eq!(
+            convert_object_to_target_key(&object, &FilterConfig::default()),
+            "Dir1/Data1"
+        );
+    }
+
+    #[test]
+    fn is_object_in_target_key_map_test() {
+        let entry = ObjectEntry {
+            last_modified: DateTime::from_secs(1),
+            content_length: 1,
+            e_tag: None,
+        };
+        let target_key_map = ObjectKeyMap::new(Mutex::new(HashMap::new()));
+        target_key_map.lock().unwrap().insert(
+            ObjectKey::KeySHA1Digest(sha1_digest_from_key("dir1/data1")),
+            entry.clone(),
+        );
+        target_key_map
+            .lock()
+            .unwrap()
+            .insert(ObjectKey::KeyString("data2".to_string()), entry);
+
+        let filter_config = FilterConfig::default();
+        let object = S3syncObject::NotVersioning(Object::builder().key("dir1/data1").build());
+        assert!(is_object_in_target_key_map(
+            &object,
+            &filter_config,
+            &target_key_map
+        ));
+        let object = S3syncObject::NotVersioning(Object::builder().key("data2").build());
+        assert!(is_object_in_target_key_map(
+            &object,
+            &filter_config,
+            &target_key_map
+        ));
+        let object = S3syncObject::NotVersioning(Object::builder().key("Dir1/Data1").build());
+        assert!(!is_object_in_target_key_map(
+            &object,
+            &filter_config,
+            &target_key_map
+        ));
+
+        let filter_config = FilterConfig {
+            target_key_case: Some(KeyCase::Lower),
+            ..Default::default()
+        };
+        assert!(is_object_in_target_key_map(
+            &object,
+            &filter_config,
+            &target_key_map
+        ));
+    }
+
+    #[test]
+    fn expand_content_disposition_template_test() {
+        assert_eq!(
+            expand_content_disposition_template("attachment; filename={basename}", "dir/a b.txt"),
+            "attachment; filename=\"a b.txt\""
+        );
+        assert_eq!(
+            expand_content_disposition_template("inline; name={key}", "dir/a.txt"),
+            "inline; name=\"dir/a.txt\""
+        );
+        assert_eq!(
+            expand_content_disposition_template("attachment; filename={basename}", "a\"b\\c"),
+            "attachment; filename=\"a\\\"b\\\\c\""
+        );
+        assert_eq!(
+            expand_content_disposition_template(
+                "attachment; filename={basename}",
+                "dir/€ rates.txt"
+            ),
+            "attachment; filename=\"_ rates.txt\"; filename*=UTF-8''%E2%82%AC%20rates.txt"
+        );
+        assert_eq!(
+            expand_content_disposition_template("inline; name={basename}", "€.txt"),
+            "inline; name=\"_.txt\""
+        );
+        assert_eq!(
+            expand_content_disposition_template("attachment", "dir/a.txt"),
+            "attachment"
+        );
+    }
+
     #[test]
     fn is_checksum_type_full_object_return_true() {
         let crc64nvme_checksum = ObjectChecksum {